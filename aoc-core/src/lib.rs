@@ -0,0 +1,9 @@
+//! Puzzle types, input handling, utilities, and virtual machines shared by
+//! the Advent of Code solutions.
+
+pub mod log;
+pub mod puzzle;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod util;
+pub mod vm;
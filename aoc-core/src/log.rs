@@ -0,0 +1,171 @@
+//! Diagnostic messages from the solutions and the runner.
+//!
+//! Messages are written to stderr, so they never mix with the answers
+//! printed on stdout, and only if their [`Level`] is shown at the current
+//! [`Verbosity`]. By default only warnings are shown; the runner raises the
+//! verbosity with `-v` or `-vv`, and lowers it with `-q`.
+//!
+//! Solutions emit messages through the `warn!`, `info!`, and `debug!` macros
+//! of the solutions crate, which skip formatting messages that would not be
+//! shown. Tests may record the messages emitted on a thread with
+//! [`capture`].
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The importance of a message.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Level {
+    /// Something that may have gone wrong, such as a skipped input line.
+    Warn,
+    /// Progress through a long computation.
+    Info,
+    /// Intermediate states that are useful when debugging a solution.
+    Debug,
+}
+
+/// The levels of messages that are shown.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Verbosity {
+    /// No messages are shown.
+    Quiet,
+    /// Warnings are shown.
+    #[default]
+    Normal,
+    /// Warnings and informational messages are shown.
+    Verbose,
+    /// All messages are shown.
+    Debug,
+}
+
+/// The current verbosity, stored as its discriminant.
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+thread_local! {
+    /// The messages recorded on this thread by [`capture`], while it runs.
+    static CAPTURED: RefCell<Option<Vec<(Level, String)>>> = const { RefCell::new(None) };
+}
+
+impl Verbosity {
+    /// Returns the verbosity selected by passing `-v` the given number of
+    /// times.
+    pub fn from_count(count: usize) -> Self {
+        match count {
+            0 => Verbosity::Normal,
+            1 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+
+    /// Returns `true` if messages of the given level are shown at this
+    /// verbosity.
+    pub fn shows(self, level: Level) -> bool {
+        let needed = match level {
+            Level::Warn => Verbosity::Normal,
+            Level::Info => Verbosity::Verbose,
+            Level::Debug => Verbosity::Debug,
+        };
+        self >= needed
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Level::Warn => "warning",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        })
+    }
+}
+
+/// Sets the verbosity of every thread.
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Returns the current verbosity.
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Debug,
+    }
+}
+
+/// Returns `true` if a message of the given level would be shown or
+/// captured.
+pub fn enabled(level: Level) -> bool {
+    verbosity().shows(level) || CAPTURED.with(|captured| captured.borrow().is_some())
+}
+
+/// Emits a message of the given level.
+///
+/// The message is recorded if this thread is inside [`capture`], and is
+/// otherwise written to stderr if its level is shown.
+pub fn log(level: Level, args: fmt::Arguments) {
+    let recorded = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(messages) => {
+            messages.push((level, args.to_string()));
+            true
+        }
+        None => false,
+    });
+    if !recorded && verbosity().shows(level) {
+        eprintln!("{}: {}", level, args);
+    }
+}
+
+/// Calls `f`, returning what it returns along with every message emitted on
+/// this thread while it ran, whatever the verbosity.
+pub fn capture<F, R>(f: F) -> (R, Vec<(Level, String)>)
+where
+    F: FnOnce() -> R,
+{
+    let outer = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let result = f();
+    let messages = CAPTURED.with(|captured| captured.replace(outer));
+    (result, messages.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_shown() {
+        assert!(!Verbosity::Quiet.shows(Level::Warn));
+        assert!(Verbosity::Normal.shows(Level::Warn));
+        assert!(!Verbosity::Normal.shows(Level::Info));
+        assert!(Verbosity::Verbose.shows(Level::Info));
+        assert!(!Verbosity::Verbose.shows(Level::Debug));
+        assert!(Verbosity::Debug.shows(Level::Debug));
+
+        assert_eq!(Verbosity::Normal, Verbosity::from_count(0));
+        assert_eq!(Verbosity::Verbose, Verbosity::from_count(1));
+        assert_eq!(Verbosity::Debug, Verbosity::from_count(3));
+    }
+
+    #[test]
+    fn capture_messages() {
+        let (answer, messages) = capture(|| {
+            assert!(enabled(Level::Debug));
+            log(Level::Info, format_args!("step {}", 1));
+            let (_, inner) = capture(|| log(Level::Debug, format_args!("inner")));
+            assert_eq!(vec![(Level::Debug, "inner".to_owned())], inner);
+            log(Level::Warn, format_args!("step {}", 2));
+            42
+        });
+        assert_eq!(42, answer);
+        assert_eq!(
+            vec![
+                (Level::Info, "step 1".to_owned()),
+                (Level::Warn, "step 2".to_owned())
+            ],
+            messages
+        );
+        assert_eq!(verbosity().shows(Level::Debug), enabled(Level::Debug));
+    }
+}
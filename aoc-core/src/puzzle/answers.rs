@@ -0,0 +1,268 @@
+//! Known-correct answers for the puzzle inputs.
+//!
+//! The answers are recorded in a TOML manifest with one table per puzzle,
+//! keyed by year and then day:
+//!
+//! ```toml
+//! [2016.15]
+//! part1 = "122318"
+//! part2 = "3208583"
+//! slow = false
+//! ```
+
+use super::solution::{Answer, Solution};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Default location of the answer manifest.
+pub const MANIFEST_PATH: &str = "./resources/answers.toml";
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+/// The known answers for a single puzzle.
+pub struct Expected {
+    parts: [Option<String>; 2],
+    slow: bool,
+}
+
+#[derive(Debug, Default)]
+/// A collection of known answers, indexed by puzzle year and day.
+pub struct AnswerKey {
+    puzzles: BTreeMap<(u16, u8), Expected>,
+}
+
+impl Expected {
+    /// Builds the known answers for a puzzle from the answers of a solution
+    /// that are believed to be correct.
+    pub fn from_solution(solution: &Solution) -> Self {
+        let ans = |part: &Option<Answer>| part.as_ref().map(|ans| ans.ans().to_owned());
+        Expected {
+            parts: [ans(&solution.0), ans(&solution.1)],
+            slow: false,
+        }
+    }
+
+    /// Returns the known answer for the given part (`1` or `2`), if any.
+    pub fn part(&self, part: usize) -> Option<&str> {
+        self.parts.get(part.checked_sub(1)?)?.as_deref()
+    }
+
+    /// Returns `true` if this puzzle's solution takes too long to be run
+    /// as part of the regular test suite.
+    pub fn is_slow(&self) -> bool {
+        self.slow
+    }
+
+    /// Checks the answers of a solution against the known answers.
+    ///
+    /// Parts without a known answer are not checked. On failure, returns a
+    /// description of each part that did not match.
+    pub fn check(&self, solution: &Solution) -> Result<(), String> {
+        let actual = [&solution.0, &solution.1];
+        let mismatches: Vec<String> = self
+            .parts
+            .iter()
+            .zip(actual.iter())
+            .enumerate()
+            .filter_map(|(i, (expected, actual))| {
+                let expected = expected.as_deref()?;
+                match actual.as_ref().map(Answer::ans) {
+                    Some(ans) if ans == expected => None,
+                    Some(ans) => Some(format!(
+                        "part {}: expected `{}`, got `{}`",
+                        i + 1,
+                        expected,
+                        ans
+                    )),
+                    None => Some(format!(
+                        "part {}: expected `{}`, got nothing",
+                        i + 1,
+                        expected
+                    )),
+                }
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches.join("; "))
+        }
+    }
+
+    /// Renders these answers as the manifest table of the given puzzle.
+    pub fn to_entry(&self, year: u16, day: u8) -> String {
+        let mut entry = format!("[{}.{}]\n", year, day);
+        for (i, part) in self.parts.iter().enumerate() {
+            if let Some(ans) = part {
+                let ans = toml::Value::String(ans.clone());
+                entry.push_str(&format!("part{} = {}\n", i + 1, ans));
+            }
+        }
+        if self.slow {
+            entry.push_str("slow = true\n");
+        }
+        entry
+    }
+}
+
+impl AnswerKey {
+    /// Loads the answer manifest from its default location.
+    pub fn load() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Self::from_file(MANIFEST_PATH)
+    }
+
+    /// Loads an answer manifest from the given file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(fs::read_to_string(path)?.parse()?)
+    }
+
+    /// Returns the known answers for the given puzzle, if any.
+    pub fn get(&self, year: u16, day: u8) -> Option<&Expected> {
+        self.puzzles.get(&(year, day))
+    }
+
+    /// Returns an iterator over the year, day, and known answers of each
+    /// puzzle in this key, in chronological order.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, u8, &Expected)> {
+        self.puzzles
+            .iter()
+            .map(|(&(year, day), expected)| (year, day, expected))
+    }
+}
+
+impl FromStr for AnswerKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s
+            .parse()
+            .map_err(|e| format!("malformed answer manifest: {}", e))?;
+
+        let mut puzzles = BTreeMap::new();
+        for (year_key, days) in table.iter() {
+            let year: u16 = year_key
+                .parse()
+                .map_err(|_| format!("invalid year `{}` in answer manifest", year_key))?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| format!("year {} in answer manifest is not a table", year))?;
+
+            for (day_key, entry) in days.iter() {
+                let day: u8 = day_key.parse().map_err(|_| {
+                    format!("invalid day `{}` for {} in answer manifest", day_key, year)
+                })?;
+                let expected = parse_expected(entry)
+                    .map_err(|e| format!("{} day {} in answer manifest: {}", year, day, e))?;
+                puzzles.insert((year, day), expected);
+            }
+        }
+        Ok(AnswerKey { puzzles })
+    }
+}
+
+/// Parses the known answers for a single puzzle from its manifest entry.
+fn parse_expected(entry: &toml::Value) -> Result<Expected, &'static str> {
+    let entry = entry.as_table().ok_or("entry is not a table")?;
+    let mut expected = Expected::default();
+
+    for (key, value) in entry.iter() {
+        match key.as_str() {
+            "part1" | "part2" => {
+                let ans = value.as_str().ok_or("answers must be strings")?;
+                let index = if key == "part1" { 0 } else { 1 };
+                expected.parts[index] = Some(ans.to_owned());
+            }
+            "slow" => expected.slow = value.as_bool().ok_or("`slow` must be a boolean")?,
+            _ => return Err("unknown key"),
+        }
+    }
+    Ok(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[2016.5]
+part1 = "801b56a7"
+part2 = "424a0197"
+slow = true
+
+[2018.12]
+part1 = "3915"
+"#;
+
+    #[test]
+    fn parse_manifest() {
+        let key: AnswerKey = MANIFEST.parse().unwrap();
+
+        let day5 = key.get(2016, 5).unwrap();
+        assert_eq!(Some("801b56a7"), day5.part(1));
+        assert_eq!(Some("424a0197"), day5.part(2));
+        assert!(day5.is_slow());
+
+        let day12 = key.get(2018, 12).unwrap();
+        assert_eq!(Some("3915"), day12.part(1));
+        assert_eq!(None, day12.part(2));
+        assert!(!day12.is_slow());
+
+        assert!(key.get(2018, 13).is_none());
+        assert_eq!(2, key.iter().count());
+    }
+
+    #[test]
+    fn reject_malformed_manifest() {
+        assert!("[2016.x]\npart1 = \"1\"".parse::<AnswerKey>().is_err());
+        assert!("[2016.1]\npart1 = 1".parse::<AnswerKey>().is_err());
+        assert!("[2016.1]\npart3 = \"1\"".parse::<AnswerKey>().is_err());
+        assert!("2016 = 1".parse::<AnswerKey>().is_err());
+    }
+
+    #[test]
+    fn render_entry() {
+        let solution = Solution::new(Some(Answer::new(3915)), None);
+        let entry = Expected::from_solution(&solution).to_entry(2018, 12);
+        assert_eq!("[2018.12]\npart1 = \"3915\"\n", entry);
+
+        // Answers rendered as letters span several lines.
+        let solution = Solution::new(Some(Answer::new(1)), Some(Answer::new("#.\"\n.#")));
+        let entry = Expected::from_solution(&solution).to_entry(2016, 8);
+        let key: AnswerKey = entry.parse().unwrap();
+        assert!(key.get(2016, 8).unwrap().check(&solution).is_ok());
+
+        let key: AnswerKey = MANIFEST.parse().unwrap();
+        let day5 = key.get(2016, 5).unwrap();
+        assert_eq!(
+            *day5,
+            day5.to_entry(2016, 5).parse::<AnswerKey>().unwrap().puzzles[&(2016, 5)]
+        );
+    }
+
+    #[test]
+    fn check_solution() {
+        let key: AnswerKey = MANIFEST.parse().unwrap();
+        let day12 = key.get(2018, 12).unwrap();
+
+        assert!(day12
+            .check(&Solution::new(Some(Answer::new(3915)), None))
+            .is_ok());
+        assert!(day12
+            .check(&Solution::new(
+                Some(Answer::new(3915)),
+                Some(Answer::new(1))
+            ))
+            .is_ok());
+        assert_eq!(
+            Err("part 1: expected `3915`, got `42`".to_owned()),
+            day12.check(&Solution::new(Some(Answer::new(42)), None))
+        );
+        assert_eq!(
+            Err("part 1: expected `3915`, got nothing".to_owned()),
+            day12.check(&Solution::empty())
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Cooperative cancellation of puzzle solutions.
+//!
+//! Threads cannot be stopped from the outside, so a solution timed with
+//! [`Summary::measure_within`](super::Summary::measure_within) that runs out
+//! of time is only asked to stop. Solutions that may run for long poll
+//! [`requested`] as they go, and give up with [`Cancelled`] once it returns
+//! `true`.
+//!
+//! Until an abandoned solution stops, it competes for the processor with
+//! the runs that follow it, so their summaries are marked as contended (see
+//! [`abandoned`]).
+
+use super::error::Cancelled;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// State of a run that has neither finished nor been cancelled.
+const RUNNING: u8 = 0;
+
+/// State of a run that finished before it was cancelled.
+const FINISHED: u8 = 1;
+
+/// State of a run that was cancelled before it finished.
+const CANCELLED: u8 = 2;
+
+/// Number of cancelled runs that have not finished yet.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The cancellable run on this thread, if any.
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// A handle on a cancellable run, shared by the thread doing the run and the
+/// thread waiting on it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Token(Arc<AtomicU8>);
+
+impl Token {
+    /// Runs `f` as the run of this token on the current thread.
+    ///
+    /// The run is finished once `f` returns, even if it panics.
+    pub(crate) fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        /// Finishes the run when dropped.
+        struct Finish<'a>(&'a Token);
+
+        impl Drop for Finish<'_> {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.borrow_mut().take());
+                if !self.0.transition(FINISHED) {
+                    ABANDONED.fetch_sub(1, Ordering::SeqCst);
+                }
+            }
+        }
+
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+        let _finish = Finish(self);
+        f()
+    }
+
+    /// Asks the run to stop, counting it as abandoned until it does unless
+    /// it has already finished.
+    pub(crate) fn cancel(&self) {
+        // Counted first so that the run cannot finish and uncount itself
+        // before it has been counted.
+        ABANDONED.fetch_add(1, Ordering::SeqCst);
+        if !self.transition(CANCELLED) {
+            ABANDONED.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Moves a running run to the given state, returning `false` if it had
+    /// already left the running state.
+    fn transition(&self, state: u8) -> bool {
+        self.0
+            .compare_exchange(RUNNING, state, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+}
+
+/// Returns `true` if the run on the current thread has been asked to stop.
+pub fn requested() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|token| token.0.load(Ordering::SeqCst) == CANCELLED)
+    })
+}
+
+/// Returns [`Cancelled`] if the run on the current thread has been asked to
+/// stop.
+pub fn check() -> Result<(), Cancelled> {
+    if requested() {
+        Err(Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the number of runs that were asked to stop and are still running.
+pub fn abandoned() -> usize {
+    ABANDONED.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_cancellation() {
+        assert!(!requested());
+
+        let token = Token::default();
+        token.run(|| {
+            assert_eq!(Ok(()), check());
+            token.cancel();
+            assert!(requested());
+            assert_eq!(Err(Cancelled), check());
+        });
+        assert!(!requested());
+
+        // Runs that have finished are not abandoned by cancelling them.
+        let token = Token::default();
+        token.run(|| {});
+        token.cancel();
+        assert!(!token.transition(CANCELLED));
+    }
+}
@@ -0,0 +1,158 @@
+//! Time measurement for puzzle solutions.
+//!
+//! Every timing reported for a solution is read through the current
+//! thread's [`Clock`]. By default this is the system's monotonic clock, but
+//! a different clock may be installed with [`with_clock`] so that tests can
+//! make assertions about timings deterministically.
+//!
+//! WebAssembly targets without a system clock default to a clock that always
+//! reads zero, until the host installs one of its own.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A source of monotonic timestamps.
+pub trait Clock {
+    /// Returns the time elapsed since this clock's epoch.
+    fn now(&self) -> Duration;
+}
+
+/// A clock backed by the system's monotonic clock.
+///
+/// Its epoch is the moment that it was created.
+#[derive(Debug, Copy, Clone)]
+pub struct SystemClock {
+    epoch: Instant,
+}
+
+/// A clock that advances by a fixed step each time it is read.
+///
+/// The first reading is always zero.
+#[derive(Debug, Default)]
+pub struct StepClock {
+    step: Duration,
+    ticks: Cell<u32>,
+}
+
+thread_local! {
+    /// The clock used to time solutions on this thread.
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(default_clock());
+}
+
+/// Returns the clock installed on each thread before any other.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn default_clock() -> Rc<dyn Clock> {
+    Rc::new(SystemClock::new())
+}
+
+/// Returns the clock installed on each thread before any other.
+///
+/// `Instant` is not available on this target, so the default clock is
+/// stopped at zero.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn default_clock() -> Rc<dyn Clock> {
+    Rc::new(StepClock::default())
+}
+
+impl SystemClock {
+    /// Builds a new system clock whose epoch is the present moment.
+    pub fn new() -> Self {
+        SystemClock {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+}
+
+impl StepClock {
+    /// Builds a new clock that advances by `step` each time it is read.
+    pub fn new(step: Duration) -> Self {
+        StepClock {
+            step,
+            ticks: Cell::new(0),
+        }
+    }
+}
+
+impl Clock for StepClock {
+    fn now(&self) -> Duration {
+        let ticks = self.ticks.get();
+        self.ticks.set(ticks + 1);
+        self.step * ticks
+    }
+}
+
+/// Reads the current thread's clock.
+pub fn now() -> Duration {
+    CLOCK.with(|clock| clock.borrow().now())
+}
+
+/// Returns the time elapsed on the current thread's clock since the given
+/// reading.
+pub fn elapsed(start: Duration) -> Duration {
+    now().saturating_sub(start)
+}
+
+/// Runs `f` with `clock` installed as the current thread's clock.
+///
+/// The previous clock is restored once `f` returns, even if it panics.
+pub fn with_clock<C, F, R>(clock: C, f: F) -> R
+where
+    C: Clock + 'static,
+    F: FnOnce() -> R,
+{
+    /// Restores the previous clock when dropped.
+    struct Restore(Option<Rc<dyn Clock>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                CLOCK.with(|clock| *clock.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let previous = CLOCK.with(|current| current.replace(Rc::new(clock)));
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_clock_advances() {
+        let clock = StepClock::new(Duration::from_millis(3));
+        assert_eq!(Duration::from_millis(0), clock.now());
+        assert_eq!(Duration::from_millis(3), clock.now());
+        assert_eq!(Duration::from_millis(6), clock.now());
+    }
+
+    #[test]
+    fn with_clock_restores_previous() {
+        with_clock(StepClock::new(Duration::from_secs(1)), || {
+            let start = now();
+            assert_eq!(Duration::from_secs(1), elapsed(start));
+
+            with_clock(StepClock::new(Duration::from_secs(5)), || {
+                assert_eq!(Duration::from_secs(0), now());
+                assert_eq!(Duration::from_secs(5), now());
+            });
+
+            assert_eq!(Duration::from_secs(2), now());
+        });
+    }
+}
@@ -0,0 +1,305 @@
+//! Error types for handling puzzles on the naughty list.
+
+use std::num::{ParseFloatError, ParseIntError};
+use std::time::Duration;
+use std::{error, fmt, io};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// Errors that may occur while selecting a puzzle solution.
+///
+/// Represents errors that occur during puzzle selection/setup/routing
+/// /divining/guessing.
+pub enum SelectionError {
+    /// Insufficient arguments to select a puzzle.
+    NoSelection,
+    /// No solutions exists of the provided year.
+    UnimplementedYear,
+    /// No solution exists for the provided day.
+    UnimplementedDay,
+    /// The puzzle's year could not be parsed
+    BadYear,
+    /// The puzzle's day could not be parsed
+    BadDay,
+    /// An unrecognized command-line flag was provided
+    BadFlag,
+    /// A puzzle parameter was malformed or could not be parsed
+    BadParam,
+    /// Today's puzzle was selected outside of an event
+    NotDecember,
+}
+
+impl SelectionError {
+    /// Returns a brief description of the setup error for display.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SelectionError::NoSelection => "insufficient arguments to select a puzzle",
+            SelectionError::UnimplementedYear => "no solutions exist for the provided year",
+            SelectionError::UnimplementedDay => "no solution exists for the provided day",
+            SelectionError::BadYear => "puzzle year could not be parsed",
+            SelectionError::BadDay => "puzzle day could not be parsed",
+            SelectionError::BadFlag => "unrecognized flag",
+            SelectionError::BadParam => "puzzle parameter could not be parsed",
+            SelectionError::NotDecember => {
+                "no puzzle is released today; `today` only selects puzzles from December 1 to 25 (EST), try `latest`"
+            }
+        }
+    }
+}
+
+impl error::Error for SelectionError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// Error reported for a solution that did not finish within its time budget.
+pub struct TimedOut(pub Duration);
+
+impl error::Error for TimedOut {}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:.3}s", self.0.as_secs_f64())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// Error returned by a solution that stopped early because it was asked to
+/// (see [`cancel`](super::cancel)).
+pub struct Cancelled;
+
+impl error::Error for Cancelled {}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled before finishing")
+    }
+}
+
+#[derive(Debug)]
+/// Errors that may occur while solving a puzzle.
+///
+/// Solutions may use `?` on I/O errors, selection errors, integer and float
+/// parsing errors, string messages, and boxed errors, which are converted
+/// to the matching variant. The runner inspects the variant to tell, for
+/// instance, a missing input from a solution that failed.
+pub enum PuzzleError {
+    /// The puzzle input could not be read.
+    Io(io::Error),
+    /// The puzzle input could not be parsed.
+    Parse {
+        /// The line of the input that could not be parsed, counting from
+        /// one, if known.
+        line: Option<usize>,
+        /// Why the input could not be parsed.
+        reason: String,
+    },
+    /// No solution exists for the selected year or day.
+    Unimplemented(SelectionError),
+    /// The puzzle was selected with invalid arguments or parameters.
+    BadSelection(SelectionError),
+    /// The solution did not finish within its time budget.
+    TimedOut(TimedOut),
+    /// The solution failed for another reason.
+    SolverFailure(Box<dyn error::Error + Send + Sync + 'static>),
+}
+
+impl PuzzleError {
+    /// Builds an error for input that could not be parsed at an unknown
+    /// line.
+    pub fn parse<S: ToString>(reason: S) -> Self {
+        PuzzleError::Parse {
+            line: None,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Builds an error for the given line of input, counting from one,
+    /// that could not be parsed.
+    pub fn parse_at<S: ToString>(line: usize, reason: S) -> Self {
+        PuzzleError::Parse {
+            line: Some(line),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Returns `true` if the puzzle input could not be read because it does
+    /// not exist.
+    ///
+    /// Any I/O error in the chain of causes of an I/O error is considered,
+    /// since a missing input may be reported after trying to download it.
+    pub fn is_missing_input(&self) -> bool {
+        let mut cause: Option<&(dyn error::Error + 'static)> = match self {
+            PuzzleError::Io(e) => Some(e),
+            _ => return false,
+        };
+        while let Some(e) = cause {
+            if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::NotFound)
+            {
+                return true;
+            }
+            cause = e.source();
+        }
+        false
+    }
+}
+
+impl error::Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        // Wrapped errors are displayed in place of this one, so their
+        // causes are this error's causes.
+        match self {
+            PuzzleError::Io(e) => e.source(),
+            PuzzleError::Parse { .. } => None,
+            PuzzleError::Unimplemented(e) | PuzzleError::BadSelection(e) => e.source(),
+            PuzzleError::TimedOut(e) => e.source(),
+            PuzzleError::SolverFailure(e) => e.source(),
+        }
+    }
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::Io(e) => write!(f, "{}", e),
+            PuzzleError::Parse {
+                line: Some(line),
+                reason,
+            } => write!(f, "line {}: {}", line, reason),
+            PuzzleError::Parse { line: None, reason } => write!(f, "{}", reason),
+            PuzzleError::Unimplemented(e) | PuzzleError::BadSelection(e) => write!(f, "{}", e),
+            PuzzleError::TimedOut(e) => write!(f, "{}", e),
+            PuzzleError::SolverFailure(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for PuzzleError {
+    fn from(e: io::Error) -> Self {
+        PuzzleError::Io(e)
+    }
+}
+
+impl From<SelectionError> for PuzzleError {
+    fn from(e: SelectionError) -> Self {
+        match e {
+            SelectionError::UnimplementedYear | SelectionError::UnimplementedDay => {
+                PuzzleError::Unimplemented(e)
+            }
+            _ => PuzzleError::BadSelection(e),
+        }
+    }
+}
+
+impl From<TimedOut> for PuzzleError {
+    fn from(e: TimedOut) -> Self {
+        PuzzleError::TimedOut(e)
+    }
+}
+
+impl From<Cancelled> for PuzzleError {
+    fn from(e: Cancelled) -> Self {
+        PuzzleError::SolverFailure(Box::new(e))
+    }
+}
+
+impl From<ParseIntError> for PuzzleError {
+    fn from(e: ParseIntError) -> Self {
+        PuzzleError::parse(e)
+    }
+}
+
+impl From<ParseFloatError> for PuzzleError {
+    fn from(e: ParseFloatError) -> Self {
+        PuzzleError::parse(e)
+    }
+}
+
+impl From<&str> for PuzzleError {
+    fn from(e: &str) -> Self {
+        PuzzleError::SolverFailure(e.into())
+    }
+}
+
+impl From<String> for PuzzleError {
+    fn from(e: String) -> Self {
+        PuzzleError::SolverFailure(e.into())
+    }
+}
+
+impl From<Box<dyn error::Error + Send + Sync + 'static>> for PuzzleError {
+    /// Converts a boxed error, recovering the variant of errors that have
+    /// one.
+    fn from(e: Box<dyn error::Error + Send + Sync + 'static>) -> Self {
+        let e = match e.downcast::<PuzzleError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<io::Error>() {
+            Ok(e) => return PuzzleError::Io(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<SelectionError>() {
+            Ok(e) => return PuzzleError::from(*e),
+            Err(e) => e,
+        };
+        match e.downcast::<TimedOut>() {
+            Ok(e) => PuzzleError::TimedOut(*e),
+            Err(e) => PuzzleError::SolverFailure(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_errors() {
+        assert!(matches!(
+            PuzzleError::from(SelectionError::UnimplementedDay),
+            PuzzleError::Unimplemented(_)
+        ));
+        assert!(matches!(
+            PuzzleError::from(SelectionError::BadDay),
+            PuzzleError::BadSelection(_)
+        ));
+        assert!(matches!(
+            PuzzleError::from("12x".parse::<u32>().unwrap_err()),
+            PuzzleError::Parse { line: None, .. }
+        ));
+        assert!(matches!(
+            PuzzleError::from("bad input"),
+            PuzzleError::SolverFailure(_)
+        ));
+    }
+
+    #[test]
+    fn recover_boxed_errors() {
+        let boxed: Box<dyn error::Error + Send + Sync> = Box::new(TimedOut(Duration::from_secs(1)));
+        assert!(matches!(PuzzleError::from(boxed), PuzzleError::TimedOut(_)));
+
+        let boxed: Box<dyn error::Error + Send + Sync> = Box::new(PuzzleError::parse_at(3, "bad"));
+        let err = PuzzleError::from(boxed);
+        assert!(matches!(err, PuzzleError::Parse { line: Some(3), .. }));
+        assert_eq!("line 3: bad", err.to_string());
+    }
+
+    #[test]
+    fn detect_missing_input() {
+        let missing = io::Error::new(io::ErrorKind::NotFound, "no input");
+        assert!(PuzzleError::from(missing).is_missing_input());
+
+        let denied = io::Error::new(io::ErrorKind::PermissionDenied, "no access");
+        assert!(!PuzzleError::from(denied).is_missing_input());
+        assert!(!PuzzleError::from("not found").is_missing_input());
+    }
+}
@@ -0,0 +1,296 @@
+//! Functions for collecting puzzle input.
+//!
+//! Inputs are read through the current thread's [`InputSource`]. By default
+//! this reads each puzzle's input file from disk, but a different source may
+//! be installed with [`with_source`] so that solutions can be run against
+//! inputs that are not present on disk. Selections that hold their input in
+//! memory bypass the source entirely.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Result as IOResult};
+use std::ops::{Index, Range};
+use std::rc::Rc;
+use std::str::FromStr;
+
+use super::error::PuzzleError;
+use super::selector::Selection as Pz;
+
+/// A provider of puzzle inputs.
+pub trait InputSource {
+    /// Returns the full input for the given puzzle.
+    fn read(&self, puzzle: &Pz) -> IOResult<String>;
+
+    /// Returns the lines of the input for the given puzzle.
+    fn read_lines(&self, puzzle: &Pz) -> IOResult<Vec<String>> {
+        Ok(self.read(puzzle)?.lines().map(str::to_owned).collect())
+    }
+}
+
+/// An input source that reads each puzzle's input file from disk.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct FileSource;
+
+thread_local! {
+    /// The source from which puzzle inputs are read on this thread.
+    static SOURCE: RefCell<Rc<dyn InputSource>> = RefCell::new(Rc::new(FileSource));
+}
+
+impl InputSource for FileSource {
+    fn read(&self, puzzle: &Pz) -> IOResult<String> {
+        let f = File::open(puzzle.path())?;
+        let mut buf = BufReader::new(f);
+
+        let mut input = String::new();
+        buf.read_to_string(&mut input)?;
+
+        Ok(input)
+    }
+
+    fn read_lines(&self, puzzle: &Pz) -> IOResult<Vec<String>> {
+        let f = File::open(puzzle.path())?;
+        let buf = BufReader::new(f);
+
+        let lines: Result<Vec<_>, _> = buf.lines().collect();
+
+        Ok(lines?)
+    }
+}
+
+/// Runs `f` with `source` installed as the current thread's input source.
+///
+/// The previous source is restored once `f` returns, even if it panics.
+pub fn with_source<S, F, R>(source: S, f: F) -> R
+where
+    S: InputSource + 'static,
+    F: FnOnce() -> R,
+{
+    /// Restores the previous source when dropped.
+    struct Restore(Option<Rc<dyn InputSource>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                SOURCE.with(|source| *source.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let previous = SOURCE.with(|current| current.replace(Rc::new(source)));
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+/// Returns the current thread's input source.
+fn source() -> Rc<dyn InputSource> {
+    SOURCE.with(|source| Rc::clone(&source.borrow()))
+}
+
+/// Enters the span covering the fetch of a puzzle's input.
+fn fetch_span(puzzle: &Pz) -> tracing::span::EnteredSpan {
+    tracing::info_span!("fetch_input", input = puzzle.path_str()).entered()
+}
+
+pub fn fetch_string(puzzle: &Pz) -> IOResult<String> {
+    let _span = fetch_span(puzzle);
+    if let Some(text) = puzzle.text() {
+        return Ok(text.to_owned());
+    }
+    source().read(puzzle)
+}
+
+#[deprecated]
+// Deprecated in favor of reading directly to a string. Input files may
+// include trailing whitespace, which may be handled succinctly with
+// str methods.
+pub fn fetch_bytes(puzzle: &Pz) -> IOResult<Vec<u8>> {
+    fetch_string(puzzle).map(String::into_bytes)
+}
+
+pub fn fetch_lines(puzzle: &Pz) -> IOResult<Vec<String>> {
+    let _span = fetch_span(puzzle);
+    if let Some(text) = puzzle.text() {
+        return Ok(text.lines().map(str::to_owned).collect());
+    }
+    source().read_lines(puzzle)
+}
+
+/// Reads the puzzle input into a single buffer and returns an index of its
+/// lines.
+///
+/// Unlike `fetch_lines`, this performs one allocation for the input rather
+/// than one per line. Lines are split as by `str::lines`.
+pub fn fetch_line_index(puzzle: &Pz) -> IOResult<Lines> {
+    Ok(Lines::from_string(fetch_string(puzzle)?))
+}
+
+/// The lines of a puzzle input, stored in a single shared buffer.
+#[derive(Clone, Debug)]
+pub struct Lines {
+    buf: String,
+    spans: Vec<Range<usize>>,
+}
+
+/// Iterator over the lines stored in a `Lines` buffer.
+pub struct LinesIter<'a> {
+    lines: &'a Lines,
+    spans: ::std::slice::Iter<'a, Range<usize>>,
+}
+
+impl Lines {
+    /// Indexes the lines of the given string.
+    pub fn from_string(buf: String) -> Self {
+        let base = buf.as_ptr() as usize;
+        let spans = buf
+            .lines()
+            .map(|line| {
+                let start = line.as_ptr() as usize - base;
+                start..start + line.len()
+            })
+            .collect();
+        Self { buf, spans }
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns true if there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the line at the given index, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.spans.get(index).map(|span| &self.buf[span.clone()])
+    }
+
+    /// Returns an iterator over the lines.
+    pub fn iter(&self) -> LinesIter<'_> {
+        LinesIter {
+            lines: self,
+            spans: self.spans.iter(),
+        }
+    }
+
+    /// Returns a vector of slices for functions that expect `&[&str]`.
+    pub fn to_vec(&self) -> Vec<&str> {
+        self.iter().collect()
+    }
+
+    /// Parses every line as a `T`.
+    ///
+    /// Fails with a [`PuzzleError::Parse`] naming the first line that could
+    /// not be parsed.
+    pub fn parse_each<T>(&self) -> Result<Vec<T>, PuzzleError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.iter()
+            .enumerate()
+            .map(|(i, line)| line.parse().map_err(|e| PuzzleError::parse_at(i + 1, e)))
+            .collect()
+    }
+}
+
+impl Index<usize> for Lines {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &str {
+        &self.buf[self.spans[index].clone()]
+    }
+}
+
+impl<'a> IntoIterator for &'a Lines {
+    type Item = &'a str;
+    type IntoIter = LinesIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> Iterator for LinesIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.spans.next().map(|span| &self.lines.buf[span.clone()])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for LinesIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.spans.next_back().map(|span| &self.lines.buf[span.clone()])
+    }
+}
+
+impl ExactSizeIterator for LinesIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_lines() {
+        let lines = Lines::from_string("abc\r\n\nde\nf\n".to_owned());
+
+        assert_eq!(4, lines.len());
+        assert_eq!(vec!["abc", "", "de", "f"], lines.to_vec());
+        assert_eq!("de", &lines[2]);
+        assert_eq!(Some("f"), lines.iter().next_back());
+        assert_eq!(None, lines.get(4));
+    }
+
+    #[test]
+    fn fetch_in_memory() {
+        let puzzle = Pz::with_text(2016, 1, "ab\ncd\n");
+
+        assert_eq!("ab\ncd\n", fetch_string(&puzzle).unwrap());
+        assert_eq!(vec!["ab", "cd"], fetch_lines(&puzzle).unwrap());
+        assert_eq!(vec!["ab", "cd"], fetch_line_index(&puzzle).unwrap().to_vec());
+    }
+
+    #[test]
+    fn fetch_from_source() {
+        use crate::testing::FakeInputs;
+
+        let puzzle = Pz::new(2016, 1);
+        let inputs = FakeInputs::new().with(&puzzle, "ab\ncd\n");
+
+        with_source(inputs, || {
+            assert_eq!("ab\ncd\n", fetch_string(&puzzle).unwrap());
+            assert_eq!(vec!["ab", "cd"], fetch_lines(&puzzle).unwrap());
+
+            let err = fetch_string(&Pz::example(2016, 1, 1)).unwrap_err();
+            assert_eq!(std::io::ErrorKind::NotFound, err.kind());
+
+            // In-memory input takes precedence over the source
+            assert_eq!("ef", fetch_string(&Pz::with_text(2016, 1, "ef")).unwrap());
+        });
+    }
+
+    #[test]
+    fn parse_each_line() {
+        let lines = Lines::from_string("12\n-3\n".to_owned());
+        assert_eq!(vec![12, -3], lines.parse_each::<i32>().unwrap());
+
+        let lines = Lines::from_string("12\nx\n".to_owned());
+        let err = lines.parse_each::<i32>().unwrap_err();
+        assert_eq!("line 2: invalid digit found in string", err.to_string());
+    }
+
+    #[test]
+    fn index_empty() {
+        let lines = Lines::from_string(String::new());
+
+        assert!(lines.is_empty());
+        assert_eq!(None, lines.iter().next());
+    }
+}
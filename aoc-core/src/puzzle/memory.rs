@@ -0,0 +1,188 @@
+//! Approximate heap usage of puzzle solutions.
+//!
+//! Heap usage is counted by [`CountingAlloc`], which must be installed as
+//! the global allocator by the binary, as the runner does when built with
+//! its `memory` feature:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: CountingAlloc = CountingAlloc;
+//! ```
+//!
+//! Allocations are counted against the thread that made them, so puzzles
+//! solved on different threads at once are measured separately. Memory
+//! allocated on one thread and freed on another, or allocated by threads
+//! that a solution spawns, is not attributed to the solution, which is why
+//! the figures are approximate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether [`CountingAlloc`] has served any allocation, and so is installed.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Bytes allocated and not yet freed by this thread.
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    /// The most bytes that have been live at once on this thread since
+    /// tracking began.
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+/// A global allocator that defers to the system allocator while counting
+/// the bytes allocated by each thread.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CountingAlloc;
+
+/// A number of bytes, displayed in the largest binary unit that keeps it at
+/// or above one.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Bytes(pub usize);
+
+/// Adds `delta` bytes to the live bytes of this thread, raising the peak if
+/// needed.
+///
+/// Counting is skipped while the thread is being torn down, when its
+/// counters may no longer be accessed.
+fn count(delta: isize) {
+    let _ = LIVE.try_with(|live| {
+        let now = live.get() + delta;
+        live.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            INSTALLED.store(true, Ordering::Relaxed);
+            count(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            INSTALLED.store(true, Ordering::Relaxed);
+            count(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            count(new_size as isize - layout.size() as isize);
+        }
+        new
+    }
+}
+
+/// Returns `true` if [`CountingAlloc`] is the global allocator.
+///
+/// This is only known once it has served an allocation, which the runtime
+/// makes before `main` is called.
+pub fn is_tracking() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Calls `f`, returning its result along with the most bytes that it had
+/// allocated on this thread at once, or `None` if allocations are not being
+/// counted.
+///
+/// Memory that was already allocated when `f` was called is not included.
+pub fn track<F, R>(f: F) -> (R, Option<usize>)
+where
+    F: FnOnce() -> R,
+{
+    if !is_tracking() {
+        return (f(), None);
+    }
+    let (result, peak) = peak_of(f);
+    (result, Some(peak))
+}
+
+/// Calls `f`, returning its result along with the most bytes that it had
+/// allocated on this thread at once, according to the counters.
+fn peak_of<F, R>(f: F) -> (R, usize)
+where
+    F: FnOnce() -> R,
+{
+    let start = LIVE.with(Cell::get);
+    let outer = PEAK.with(|peak| peak.replace(start));
+    let result = f();
+    let peak = PEAK.with(|peak| {
+        let inner = peak.get();
+        // Leave the peak as it would have been without the reset, for any
+        // enclosing call.
+        peak.set(outer.max(inner));
+        inner
+    });
+    (result, (peak - start).max(0) as usize)
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_bytes() {
+        assert_eq!("0 B", Bytes(0).to_string());
+        assert_eq!("1023 B", Bytes(1023).to_string());
+        assert_eq!("1.0 KiB", Bytes(1024).to_string());
+        assert_eq!("1.5 KiB", Bytes(1536).to_string());
+        assert_eq!("33.4 MiB", Bytes(35_000_000).to_string());
+        assert_eq!("2.0 GiB", Bytes(2 << 30).to_string());
+    }
+
+    #[test]
+    fn count_peak_of_thread() {
+        // The allocator is not installed in the tests, so its bookkeeping
+        // is driven by hand.
+        count(100);
+
+        let ((), peak) = peak_of(|| {
+            count(1_000);
+            count(-600);
+            let ((), inner) = peak_of(|| {
+                count(50);
+                count(-50);
+            });
+            assert_eq!(50, inner);
+            count(-400);
+        });
+        assert_eq!(1_000, peak);
+        assert_eq!(100, LIVE.with(Cell::get));
+
+        let ((), peak) = peak_of(|| count(-100));
+        assert_eq!(0, peak);
+
+        assert!(!is_tracking());
+        assert_eq!(((), None), track(|| ()));
+    }
+}
@@ -0,0 +1,25 @@
+//! Modules for handling puzzle selection and input.
+
+use std::result;
+
+// Module reexports
+pub use self::answers::AnswerKey;
+pub use self::error::{Cancelled, PuzzleError, SelectionError, TimedOut};
+pub use self::input::{fetch_line_index, fetch_lines, fetch_string, Lines};
+pub use self::selector::{set_input_dir, Selection, DEFAULT_INPUT_DIR, INPUT_DIR_VAR};
+pub use self::solution::{Answer, Solution};
+pub use self::summary::{Stats, Status, Style, Summary};
+
+/// Specialized result type for puzzle processing.
+pub type Result = result::Result<Solution, PuzzleError>;
+
+pub mod answers;
+pub mod cancel;
+pub mod clock;
+mod error;
+pub mod input;
+pub mod memory;
+mod selector;
+mod solution;
+mod summary;
+pub mod timings;
@@ -0,0 +1,562 @@
+//! Puzzle solution selection.
+
+use super::error::SelectionError;
+use crate::util::date::{self, EVENT_DAYS};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Underlying type representing a puzzle's year.
+type Year = u16;
+
+/// Underlying type representing a puzzle's day.
+type Day = u8;
+
+/// Placeholder path reported for selections with in-memory input.
+const IN_MEMORY_PATH: &str = "<in-memory input>";
+
+/// Directory holding the default input files, unless changed with
+/// [`set_input_dir`] or the [`INPUT_DIR_VAR`] environment variable.
+pub const DEFAULT_INPUT_DIR: &str = "./resources";
+
+/// Environment variable naming the directory holding the default input
+/// files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory holding the default input files, if it has been changed
+/// from [`DEFAULT_INPUT_DIR`].
+static INPUT_DIR: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Debug, Clone)]
+/// Structure identifying a distinct puzzle solution by year and day.
+pub struct Selection {
+    year: Year,
+    day: Day,
+    input: String, // the path, not the puzzle input
+    /// The puzzle input itself, if it is held in memory rather than read
+    /// from `input`.
+    text: Option<String>,
+    /// Whether the solution should display visualizations of its progress.
+    visualize: bool,
+    /// Whether the solution's execution should be profiled.
+    profile: bool,
+    /// Whether the solve pipeline's spans should be printed.
+    trace: bool,
+    /// Named values overriding the constants used by a solution.
+    params: BTreeMap<String, String>,
+}
+
+impl Selection {
+    /// Builds a `PuzzleSelection` for the specified year and day.
+    pub fn new(year: Year, day: Day) -> Self {
+        let input = default_input_for(year, day);
+        Selection::with_input(year, day, input)
+    }
+
+    /// Builds a `PuzzleSelection` for the specified year and day with
+    /// the specified input file.
+    pub fn with_input<P>(year: Year, day: Day, input_file: P) -> Self
+    where
+        P: ToString,
+    {
+        Selection {
+            year,
+            day,
+            input: input_file.to_string(),
+            text: None,
+            visualize: false,
+            profile: false,
+            trace: false,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Builds a `PuzzleSelection` for the specified year and day whose
+    /// input is the given string rather than the contents of a file.
+    pub fn with_text<S>(year: Year, day: Day, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut selection = Selection::with_input(year, day, IN_MEMORY_PATH);
+        selection.text = Some(text.into());
+        selection
+    }
+
+    /// Builds a `PuzzleSelection` for the specified year and day whose
+    /// input is the numbered example from the examples fixture tree.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn example(year: Year, day: Day, example: u8) -> Self {
+        let input = format!("./examples/y{:4}/day{:02}/ex{}.txt", year, day, example);
+        Selection::with_input(year, day, input)
+    }
+
+    /// Sets whether the solution should display visualizations of its
+    /// progress.
+    pub fn with_visualize(mut self, visualize: bool) -> Self {
+        self.visualize = visualize;
+        self
+    }
+
+    /// Sets whether the solution's execution should be profiled.
+    pub fn with_profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets whether the solve pipeline's spans should be printed.
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Sets the input file to this puzzle's file in the given directory,
+    /// at `<dir>/yYYYY/dayDD.txt`.
+    pub fn with_input_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.input = input_in(&dir.as_ref().to_string_lossy(), self.year, self.day);
+        self
+    }
+
+    /// Sets a named parameter for the solution.
+    pub fn with_param<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.params.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Attempts to parse a puzzle selection from the specified command-line
+    /// arguments.
+    ///
+    /// The `--visualize`, `--profile`, `--trace`, and `--param <key>=<value>`
+    /// flags may appear anywhere after the executable path.
+    ///
+    /// In place of a year and day, `today` selects the puzzle released today
+    /// and `latest` the most recently released puzzle, as of the system
+    /// clock in EST. `today` is only valid from December 1 to 25.
+    pub fn from_args<I>(args: I) -> Result<Selection, SelectionError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Selection::parse_args(args)
+    }
+
+    fn parse_args<I>(args: I) -> Result<Selection, SelectionError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Selection::parse_args_at(args, date::unix_now)
+    }
+
+    /// Parses a puzzle selection, reading the current Unix time from `now`
+    /// if the selection is relative to it.
+    fn parse_args_at<I, F>(args: I, now: F) -> Result<Selection, SelectionError>
+    where
+        I: IntoIterator<Item = String>,
+        F: FnOnce() -> i64,
+    {
+        let mut args = args.into_iter().skip(1); // Ignore executable path
+        let mut positional = Vec::new();
+        let mut visualize = false;
+        let mut profile = false;
+        let mut trace = false;
+        let mut params = BTreeMap::new();
+
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "--visualize" => visualize = true,
+                "--profile" => profile = true,
+                "--trace" => trace = true,
+                "--param" => {
+                    let param = args.next().ok_or(SelectionError::BadParam)?;
+                    let mut parts = param.splitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(key), Some(value)) if !key.is_empty() => {
+                            params.insert(key.to_owned(), value.to_owned());
+                        }
+                        _ => return Err(SelectionError::BadParam),
+                    }
+                }
+                flag if flag.starts_with("--") => return Err(SelectionError::BadFlag),
+                _ => positional.push(arg),
+            }
+        }
+        let mut args = positional.into_iter().peekable();
+
+        let (year, day) = match args.peek().map(String::as_str) {
+            Some("today") => {
+                args.next();
+                date::released_today(now()).ok_or(SelectionError::NotDecember)?
+            }
+            Some("latest") => {
+                args.next();
+                date::latest_release(now())
+            }
+            _ => {
+                let year: Year = args
+                    .next()
+                    .ok_or(SelectionError::NoSelection)?
+                    .parse()
+                    .map_err(|_| SelectionError::BadYear)?;
+
+                let day: Day = args
+                    .next()
+                    .ok_or(SelectionError::NoSelection)?
+                    .parse()
+                    .map_err(|_| SelectionError::BadDay)?;
+                (year, day)
+            }
+        };
+
+        let input = args.next().unwrap_or_else(|| default_input_for(year, day));
+
+        let mut selection = Selection::with_input(year, day, input)
+            .with_visualize(visualize)
+            .with_profile(profile)
+            .with_trace(trace);
+        selection.params = params;
+        Ok(selection)
+    }
+
+    /// Parses a specification of several puzzles, returning a selection of
+    /// each in chronological order.
+    ///
+    /// A specification is a year or inclusive range of years, then a colon,
+    /// then the days to select in each year:
+    ///
+    /// ```text
+    /// spec  := years ':' days
+    /// years := year | year '-' year
+    /// days  := '*' | item (',' item)*
+    /// item  := day | day '-' day
+    /// ```
+    ///
+    /// For example, `2016:1-10`, `2016:1,3,9`, and `2015-2018:*`, where `*`
+    /// selects every day of the event. Days selected more than once are only
+    /// included once.
+    pub fn parse_many(spec: &str) -> Result<Vec<Selection>, SelectionError> {
+        let mut parts = spec.splitn(2, ':');
+        let (years, days) = match (parts.next(), parts.next()) {
+            (Some(years), Some(days)) => (years, days),
+            _ => return Err(SelectionError::NoSelection),
+        };
+        let years: (Year, Year) = parse_span(years, SelectionError::BadYear)?;
+
+        let mut selected = BTreeSet::new();
+        if days == "*" {
+            selected.extend(1..=EVENT_DAYS);
+        } else {
+            for item in days.split(',') {
+                let (first, last): (Day, Day) = parse_span(item, SelectionError::BadDay)?;
+                if first == 0 || last > EVENT_DAYS {
+                    return Err(SelectionError::BadDay);
+                }
+                selected.extend(first..=last);
+            }
+        }
+
+        Ok((years.0..=years.1)
+            .flat_map(|year| selected.iter().map(move |&day| Selection::new(year, day)))
+            .collect())
+    }
+
+    /// Returns the year associated with this puzzle selection.
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// Returns the day associated with this puzzle selection.
+    pub fn day(&self) -> Day {
+        self.day
+    }
+
+    /// Returns the path to the input file associated with this puzzle
+    /// selection.
+    pub fn path(&self) -> &Path {
+        Path::new(&self.input)
+    }
+
+    /// Returns the input file path as a string slice.
+    pub fn path_str(&self) -> &str {
+        &self.input[..]
+    }
+
+    /// Returns the puzzle input held in memory by this selection, if it
+    /// was built with one.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Returns true if the solution should display visualizations of its
+    /// progress.
+    pub fn visualize(&self) -> bool {
+        self.visualize
+    }
+
+    /// Returns true if the solution's execution should be profiled.
+    pub fn profile(&self) -> bool {
+        self.profile
+    }
+
+    /// Returns true if the solve pipeline's spans should be printed.
+    pub fn trace(&self) -> bool {
+        self.trace
+    }
+
+    /// Returns the value of the named parameter, if it was provided.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// Returns the value of the named parameter parsed as a `T`, or
+    /// `default` if the parameter was not provided.
+    ///
+    /// An error is returned if the parameter could not be parsed.
+    pub fn param_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, SelectionError> {
+        match self.param(key) {
+            Some(value) => value.parse().map_err(|_| SelectionError::BadParam),
+            None => Ok(default),
+        }
+    }
+}
+
+/// Changes the directory holding the default input files of the selections
+/// built from now on, taking precedence over the [`INPUT_DIR_VAR`]
+/// environment variable.
+///
+/// The input of each puzzle is expected at `<dir>/yYYYY/dayDD.txt`.
+pub fn set_input_dir<S: Into<String>>(dir: S) {
+    *INPUT_DIR.write().unwrap() = Some(dir.into());
+}
+
+/// Parses a number or an inclusive range of numbers, such as `3` or `1-10`,
+/// failing with `err` if it is malformed or empty.
+fn parse_span<T>(s: &str, err: SelectionError) -> Result<(T, T), SelectionError>
+where
+    T: FromStr + PartialOrd + Copy,
+{
+    let parse = |n: &str| n.parse().map_err(|_| err);
+    let (first, last) = match s.find('-') {
+        Some(i) => (parse(&s[..i])?, parse(&s[i + 1..])?),
+        None => {
+            let n = parse(s)?;
+            (n, n)
+        }
+    };
+    if first > last {
+        return Err(err);
+    }
+    Ok((first, last))
+}
+
+/// Returns the default path for a puzzle's input file.
+fn default_input_for(year: Year, day: Day) -> String {
+    if let Some(dir) = INPUT_DIR.read().unwrap().as_deref() {
+        return input_in(dir, year, day);
+    }
+    match env::var(INPUT_DIR_VAR) {
+        Ok(dir) if !dir.is_empty() => input_in(&dir, year, day),
+        _ => input_in(DEFAULT_INPUT_DIR, year, day),
+    }
+}
+
+/// Returns the path of a puzzle's input file in the given directory.
+fn input_in(dir: &str, year: Year, day: Day) -> String {
+    format!(
+        "{}/y{:4}/day{:02}.txt",
+        dir.trim_end_matches('/'),
+        year,
+        day
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::date::{Timestamp, RELEASE_OFFSET};
+
+    #[test]
+    fn make_selection() {
+        let pz = Selection::new(2017, 5);
+        assert_eq!(5, pz.day());
+        assert_eq!(2017, pz.year());
+    }
+
+    #[test]
+    fn path_builds_correctly() {
+        assert_eq!(
+            "./resources/y2016/day11.txt",
+            Selection::new(2016, 11).path_str()
+        )
+    }
+
+    #[test]
+    fn path_padded_with_zeros() {
+        assert_eq!(
+            "./resources/y2016/day01.txt",
+            Selection::new(2016, 1).path_str()
+        )
+    }
+
+    #[test]
+    fn path_in_input_dir() {
+        assert_eq!(
+            "/srv/inputs/y2016/day04.txt",
+            Selection::new(2016, 4)
+                .with_input_dir("/srv/inputs/")
+                .path_str()
+        );
+        assert_eq!(
+            "inputs/y2018/day12.txt",
+            Selection::new(2018, 12).with_input_dir("inputs").path_str()
+        );
+    }
+
+    #[test]
+    fn parse_visualize_flag() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        let pz = Selection::parse_args(args("aoc 2018 10")).unwrap();
+        assert!(!pz.visualize());
+
+        let pz = Selection::parse_args(args("aoc 2018 --visualize 10 in.txt")).unwrap();
+        assert!(pz.visualize());
+        assert_eq!(10, pz.day());
+        assert_eq!("in.txt", pz.path_str());
+
+        assert!(Selection::parse_args(args("aoc 2018 10 --bogus")).is_err());
+    }
+
+    #[test]
+    fn parse_today() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let at = |year, month, day, hour| {
+            let est = Timestamp::new(year, month, day, hour, 0).unwrap();
+            move || est.unix_seconds() - RELEASE_OFFSET
+        };
+
+        let pz = Selection::parse_args_at(args("aoc today"), at(2019, 12, 7, 0)).unwrap();
+        assert_eq!((2019, 7), (pz.year(), pz.day()));
+        let pz = Selection::parse_args_at(args("aoc today in.txt"), at(2019, 12, 25, 23)).unwrap();
+        assert_eq!((2019, 25), (pz.year(), pz.day()));
+        assert_eq!("in.txt", pz.path_str());
+
+        let err = Selection::parse_args_at(args("aoc today"), at(2019, 12, 26, 0));
+        assert_eq!(Some(SelectionError::NotDecember), err.err());
+        let err = Selection::parse_args_at(args("aoc today"), at(2019, 11, 30, 23));
+        assert_eq!(Some(SelectionError::NotDecember), err.err());
+    }
+
+    #[test]
+    fn parse_latest() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        let at = |year, month, day| {
+            let est = Timestamp::new(year, month, day, 12, 0).unwrap();
+            move || est.unix_seconds() - RELEASE_OFFSET
+        };
+
+        let latest = |now| {
+            let pz = Selection::parse_args_at(args("aoc latest --trace"), now).unwrap();
+            (pz.year(), pz.day())
+        };
+        assert_eq!((2019, 3), latest(at(2019, 12, 3)));
+        assert_eq!((2019, 25), latest(at(2019, 12, 31)));
+        assert_eq!((2018, 25), latest(at(2019, 11, 30)));
+    }
+
+    #[test]
+    fn parse_profile_flag() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        let pz = Selection::parse_args(args("aoc 2016 14")).unwrap();
+        assert!(!pz.profile());
+
+        let pz = Selection::parse_args(args("aoc 2016 14 --profile")).unwrap();
+        assert!(pz.profile());
+        assert!(!pz.visualize());
+        assert!(!pz.trace());
+
+        let pz = Selection::parse_args(args("aoc --trace 2016 14")).unwrap();
+        assert!(pz.trace());
+        assert_eq!(14, pz.day());
+    }
+
+    #[test]
+    fn parse_params() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        let pz = Selection::parse_args(args("aoc --param a=1 2016 10 --param b=x=y")).unwrap();
+        assert_eq!(10, pz.day());
+        assert_eq!(Some("1"), pz.param("a"));
+        assert_eq!(Some("x=y"), pz.param("b"));
+        assert_eq!(None, pz.param("c"));
+        assert_eq!(Ok(1_u8), pz.param_or("a", 5));
+        assert_eq!(Ok(5_u8), pz.param_or("c", 5));
+        assert_eq!(Err(SelectionError::BadParam), pz.param_or("b", 5_u8));
+
+        assert!(Selection::parse_args(args("aoc 2016 10 --param")).is_err());
+        assert!(Selection::parse_args(args("aoc 2016 10 --param novalue")).is_err());
+        assert!(Selection::parse_args(args("aoc 2016 10 --param =1")).is_err());
+    }
+
+    #[test]
+    fn parse_many() {
+        let days = |spec| -> Result<Vec<(Year, Day)>, SelectionError> {
+            let selections = Selection::parse_many(spec)?;
+            Ok(selections.iter().map(|pz| (pz.year(), pz.day())).collect())
+        };
+
+        assert_eq!(Ok(vec![(2016, 4)]), days("2016:4"));
+        assert_eq!(
+            Ok((1..=10).map(|day| (2016, day)).collect()),
+            days("2016:1-10")
+        );
+        assert_eq!(
+            Ok(vec![(2016, 1), (2016, 3), (2016, 9)]),
+            days("2016:9,1,3")
+        );
+        assert_eq!(
+            Ok(vec![(2018, 1), (2018, 2), (2018, 3), (2018, 5)]),
+            days("2018:1-3,5,2")
+        );
+
+        let all = days("2015-2018:*").unwrap();
+        assert_eq!(100, all.len());
+        assert_eq!(Some(&(2015, 1)), all.first());
+        assert_eq!(Some(&(2016, 1)), all.get(25));
+        assert_eq!(Some(&(2018, 25)), all.last());
+        assert_eq!(
+            "./resources/y2017/day06.txt",
+            Selection::parse_many("2017:6").unwrap()[0].path_str()
+        );
+
+        assert_eq!(Err(SelectionError::NoSelection), days("2016"));
+        assert_eq!(Err(SelectionError::BadYear), days("2018-2015:*"));
+        assert_eq!(Err(SelectionError::BadYear), days("20x6:1"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:0-3"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:20-26"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:5-3"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:1,,2"));
+        assert_eq!(Err(SelectionError::BadDay), days("2016:1-*"));
+    }
+
+    #[test]
+    fn in_memory_input() {
+        let pz = Selection::with_text(2016, 1, "R2, L3");
+        assert_eq!(Some("R2, L3"), pz.text());
+        assert_eq!(IN_MEMORY_PATH, pz.path_str());
+        assert_eq!(None, Selection::new(2016, 1).text());
+    }
+
+    #[test]
+    fn override_input_path() {
+        assert_eq!(
+            "./resources/y2016/day02.txt",
+            Selection::with_input(2016, 1, "./resources/y2016/day02.txt").path_str()
+        )
+    }
+}
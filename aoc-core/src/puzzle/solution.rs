@@ -1,5 +1,6 @@
 //! Puzzle solution representation
 
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use std::string::ToString;
 use std::time::Duration;
 use std::fmt;
@@ -9,6 +10,9 @@ use std::fmt;
 pub struct Answer {
     ans: String,
     bench: Option<Duration>,
+    /// A rendering of how the answer was found, such as the final state of
+    /// a simulation, if the solution produced one.
+    artifact: Option<String>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -20,14 +24,22 @@ impl Answer {
     pub fn new<S>(other: S) -> Self
         where S: ToString
     {
-        Answer { ans: other.to_string(), bench: None }
+        Answer { ans: other.to_string(), bench: None, artifact: None }
     }
 
     /// Builds an answer with the specified value and optional benchmark.
     pub fn with_bench<S>(ans: S, bench: Option<Duration>) -> Self
         where S: ToString
     {
-        Answer { ans: ans.to_string(), bench }
+        Answer { ans: ans.to_string(), bench, artifact: None }
+    }
+
+    /// Attaches a rendering of how the answer was found.
+    pub fn with_artifact<S>(mut self, artifact: S) -> Self
+        where S: Into<String>
+    {
+        self.artifact = Some(artifact.into());
+        self
     }
 
     /// Returns a slice to this puzzle part's answer string.
@@ -39,6 +51,11 @@ impl Answer {
     pub fn bench(&self) -> Option<&Duration> {
         self.bench.as_ref()
     }
+
+    /// Returns the rendering attached to this answer, if any.
+    pub fn artifact(&self) -> Option<&str> {
+        self.artifact.as_deref()
+    }
 }
 
 impl PartialEq for Answer {
@@ -61,6 +78,16 @@ impl fmt::Display for Answer {
     }
 }
 
+impl Serialize for Answer {
+    /// Serializes this answer as its value and its benchmark in seconds.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Answer", 2)?;
+        state.serialize_field("answer", &self.ans)?;
+        state.serialize_field("seconds", &self.bench.map(|b| b.as_secs_f64()))?;
+        state.end()
+    }
+}
+
 impl Solution {
     /// Builds a solution from the specified optional answers.
     pub fn new(one: Option<Answer>, two: Option<Answer>) -> Solution {
@@ -72,6 +99,20 @@ impl Solution {
         Solution(None, None)
     }
 
+    /// Attaches a rendering to the answer of the given part, if the part
+    /// was answered.
+    pub fn with_artifact<S>(mut self, part: u8, artifact: S) -> Self
+        where S: Into<String>
+    {
+        let answer = match part {
+            1 => &mut self.0,
+            2 => &mut self.1,
+            _ => return self,
+        };
+        *answer = answer.take().map(|answer| answer.with_artifact(artifact));
+        self
+    }
+
     /// Returns the total duration of time elapsed between the two
     /// answers.
     ///
@@ -111,6 +152,15 @@ impl fmt::Display for Solution {
     }
 }
 
+impl Serialize for Solution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Solution", 2)?;
+        state.serialize_field("part1", &self.0)?;
+        state.serialize_field("part2", &self.1)?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +189,30 @@ mod tests {
 
         assert_eq!(Duration::new(16, 217), solution.duration());
     }
+
+    #[test]
+    fn serialize_solution() {
+        let solution = Solution::new(
+            Some(Answer::with_bench(99332, Some(Duration::from_millis(250)))),
+            None,
+        );
+
+        assert_eq!(
+            r#"{"part1":{"answer":"99332","seconds":0.25},"part2":null}"#,
+            serde_json::to_string(&solution).unwrap()
+        );
+    }
+
+    #[test]
+    fn attach_artifacts() {
+        let solution = Solution::new(Some(Answer::new(7)), None)
+            .with_artifact(1, "#.#\n.#.\n")
+            .with_artifact(2, "unused");
+
+        let one = solution.0.as_ref().unwrap();
+        assert_eq!(Some("#.#\n.#.\n"), one.artifact());
+        assert_eq!(&Answer::new(7), one);
+        assert_eq!(None, solution.1);
+        assert_eq!(None, Answer::new(7).artifact());
+    }
 }
\ No newline at end of file
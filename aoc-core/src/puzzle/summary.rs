@@ -0,0 +1,840 @@
+//! Capture the results from running a puzzle solution.
+
+use super::cancel::{self, Token};
+use super::clock;
+use super::error::{PuzzleError, TimedOut};
+use super::memory::{self, Bytes};
+use super::selector::Selection;
+use super::solution::Answer;
+use super::Result as PuzzleResult;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::iter::FromIterator;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Text preceding selection error message.
+const SELECTION_ERROR_START: &str = "Error selecting puzzle";
+
+/// Text preceding solution error message.
+const SOLUTION_ERROR_START: &str = "Failed to execute solution";
+
+/// Returns the text preceding the message of the given error: whether the
+/// puzzle could not be selected or its solution failed.
+fn error_start(e: &PuzzleError) -> &'static str {
+    match e {
+        PuzzleError::Unimplemented(_) | PuzzleError::BadSelection(_) => SELECTION_ERROR_START,
+        _ => SOLUTION_ERROR_START,
+    }
+}
+
+/// ANSI escape codes used by [`Style::Ansi`].
+mod ansi {
+    pub const RESET: &str = "\x1b[0m";
+    pub const BOLD: &str = "\x1b[1m";
+    pub const DIM: &str = "\x1b[2m";
+    pub const RED: &str = "\x1b[31m";
+    pub const GREEN: &str = "\x1b[32m";
+    pub const YELLOW: &str = "\x1b[33m";
+}
+
+#[derive(Debug)]
+/// A summary of the execution of a puzzle's solution.
+pub struct Summary {
+    puzzle: Selection,
+    result: PuzzleResult,
+    duration: Duration,
+    peak_memory: Option<usize>,
+    contended: bool,
+}
+
+impl Summary {
+    /// Builds a new `Summary` with the specified puzzle selection,
+    /// solution result, and execution duration.
+    pub fn new(puzzle: Selection, result: PuzzleResult, duration: Duration) -> Self {
+        Summary {
+            puzzle,
+            result,
+            duration,
+            peak_memory: None,
+            contended: false,
+        }
+    }
+
+    /// Sets the peak heap usage of the run, in bytes.
+    pub fn with_peak_memory(mut self, bytes: Option<usize>) -> Self {
+        self.peak_memory = bytes;
+        self
+    }
+
+    /// Sets whether the run was timed alongside abandoned runs.
+    pub fn with_contended(mut self, contended: bool) -> Self {
+        self.contended = contended;
+        self
+    }
+
+    /// Runs `solve` on the specified puzzle selection, returning a summary
+    /// of its result and the total time that it took according to the
+    /// current thread's [`clock`].
+    ///
+    /// The peak heap usage of the run is also recorded if allocations are
+    /// being counted (see [`memory`]), and the run is marked as contended
+    /// if any abandoned run was still going at its start or end (see
+    /// [`cancel`]).
+    pub fn measure<F>(puzzle: Selection, solve: F) -> Self
+    where
+        F: FnOnce(&Selection) -> PuzzleResult,
+    {
+        let contended = cancel::abandoned() > 0;
+        let start = clock::now();
+        let (result, peak) = memory::track(|| solve(&puzzle));
+        Summary::new(puzzle, result, clock::elapsed(start))
+            .with_peak_memory(peak)
+            .with_contended(contended || cancel::abandoned() > 0)
+    }
+
+    /// Like [`Summary::measure`], but solves the puzzle on a new thread,
+    /// giving up once `timeout` has passed.
+    ///
+    /// Threads cannot be stopped from the outside, so a solution that runs
+    /// out of time is asked to stop (see [`cancel`]) and left running in the
+    /// background until it does. The solution is timed on its own thread,
+    /// which starts with its own clock and input source. A solution that
+    /// panics is reported as a failure.
+    pub fn measure_within<F>(puzzle: Selection, timeout: Duration, solve: F) -> Self
+    where
+        F: FnOnce(&Selection) -> PuzzleResult + Send + 'static,
+    {
+        let contended = cancel::abandoned() > 0;
+        let (tx, rx) = mpsc::channel();
+        let worker = puzzle.clone();
+        let token = Token::default();
+        let run = token.clone();
+        thread::spawn(move || {
+            run.run(|| {
+                let start = clock::now();
+                let (result, peak) = memory::track(|| solve(&worker));
+                let _ = tx.send((result, clock::elapsed(start), peak));
+            })
+        });
+
+        let received = rx.recv_timeout(timeout);
+        let contended = contended || cancel::abandoned() > 0;
+        match received {
+            Ok((result, duration, peak)) => Summary::new(puzzle, result, duration)
+                .with_peak_memory(peak)
+                .with_contended(contended),
+            Err(RecvTimeoutError::Timeout) => {
+                token.cancel();
+                Summary::new(puzzle, Err(TimedOut(timeout).into()), timeout)
+                    .with_contended(contended)
+            }
+            Err(RecvTimeoutError::Disconnected) => Summary::new(
+                puzzle,
+                Err(From::from("solution panicked")),
+                Duration::default(),
+            ),
+        }
+    }
+
+    /// Returns the puzzle selection that was run.
+    pub fn puzzle(&self) -> &Selection {
+        &self.puzzle
+    }
+
+    /// Returns the result of running the puzzle's solution.
+    pub fn result(&self) -> &PuzzleResult {
+        &self.result
+    }
+
+    /// Returns the total time taken to run the puzzle's solution.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the most heap memory that the run had allocated at once, in
+    /// bytes, if it was measured.
+    pub fn peak_memory(&self) -> Option<usize> {
+        self.peak_memory
+    }
+
+    /// Returns `true` if the run was timed while solutions abandoned by
+    /// earlier runs were still going, and so may have been slowed down.
+    pub fn contended(&self) -> bool {
+        self.contended
+    }
+
+    /// Returns the time spent during puzzle setup.
+    ///
+    /// The time spent routing to the desired puzzle solution and
+    /// reading/parsing the puzzle's input.
+    ///
+    /// Determined by subtracting each parts execution time from the
+    /// total duration.
+    pub fn setup_time(&self) -> Duration {
+        self.duration
+            - match self.result {
+                Ok(ref solution) => solution.duration(),
+                Err(..) => Duration::default(),
+            }
+    }
+
+    /// Returns how the run ended.
+    ///
+    /// A run failed for missing input if it could not read its input because
+    /// it does not exist (see [`PuzzleError::is_missing_input`]).
+    pub fn status(&self) -> Status {
+        match self.result {
+            Ok(_) => Status::Solved,
+            Err(PuzzleError::TimedOut(_)) => Status::TimedOut,
+            Err(PuzzleError::Unimplemented(_)) => Status::Unimplemented,
+            Err(ref e) if e.is_missing_input() => Status::MissingInput,
+            Err(_) => Status::Failed,
+        }
+    }
+
+    /// Returns a value that renders this summary in the given style.
+    pub fn styled(&self, style: Style) -> Styled<'_> {
+        Styled {
+            summary: self,
+            style,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// How a puzzle run ended.
+///
+/// Statuses are ordered from best to worst, so the status of a batch of runs
+/// is the greatest of its runs' statuses.
+pub enum Status {
+    /// The solution ran to completion.
+    Solved,
+    /// There is no solution for the puzzle's year or day.
+    Unimplemented,
+    /// The puzzle's input could not be found.
+    MissingInput,
+    /// The solution failed for any other reason, such as malformed input.
+    Failed,
+    /// The solution did not finish within its time budget.
+    TimedOut,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The styles in which a summary may be rendered as text.
+pub enum Style {
+    /// Plain text, as written by the summary's `Display` impl.
+    Plain,
+    /// Text colored with ANSI escape codes, marking each part with a status
+    /// icon and aligning the timings.
+    Ansi,
+}
+
+/// A summary rendered in a particular [`Style`], as returned by
+/// [`Summary::styled`].
+pub struct Styled<'a> {
+    summary: &'a Summary,
+    style: Style,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+/// Aggregate statistics over the summaries of many puzzle runs.
+///
+/// Statistics are collected from an iterator of summaries, or accumulated
+/// one summary at a time with [`Stats::add`]. Only the runs that produced a
+/// solution count towards the mean and slowest times.
+pub struct Stats {
+    runs: usize,
+    failed: usize,
+    total: Duration,
+    solved_total: Duration,
+    slowest: Option<(u16, u8, Duration)>,
+    largest: Option<(u16, u8, usize)>,
+}
+
+impl Stats {
+    /// Accounts for another puzzle run in these statistics.
+    pub fn add(&mut self, summary: &Summary) {
+        self.runs += 1;
+        self.total += summary.duration;
+        if summary.result.is_err() {
+            self.failed += 1;
+            return;
+        }
+        self.solved_total += summary.duration;
+        if self
+            .slowest
+            .is_none_or(|(_, _, slowest)| summary.duration > slowest)
+        {
+            let puzzle = &summary.puzzle;
+            self.slowest = Some((puzzle.year(), puzzle.day(), summary.duration));
+        }
+        if let Some(peak) = summary.peak_memory {
+            if self.largest.is_none_or(|(_, _, largest)| peak > largest) {
+                let puzzle = &summary.puzzle;
+                self.largest = Some((puzzle.year(), puzzle.day(), peak));
+            }
+        }
+    }
+
+    /// Returns the number of puzzles that were run.
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    /// Returns the number of puzzles whose solutions failed.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Returns the total time taken by every run.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the mean time taken by the solved puzzles, or `None` if no
+    /// puzzle was solved.
+    pub fn mean(&self) -> Option<Duration> {
+        let solved = self.runs - self.failed;
+        if solved == 0 {
+            return None;
+        }
+        Some(self.solved_total / solved as u32)
+    }
+
+    /// Returns the year, day, and time of the slowest solved puzzle, or
+    /// `None` if no puzzle was solved.
+    pub fn slowest(&self) -> Option<(u16, u8, Duration)> {
+        self.slowest
+    }
+
+    /// Returns the year, day, and peak heap usage in bytes of the solved
+    /// puzzle that used the most memory, or `None` if none was measured.
+    pub fn largest(&self) -> Option<(u16, u8, usize)> {
+        self.largest
+    }
+}
+
+impl<'a> FromIterator<&'a Summary> for Stats {
+    fn from_iter<I: IntoIterator<Item = &'a Summary>>(iter: I) -> Self {
+        let mut stats = Stats::default();
+        iter.into_iter().for_each(|summary| stats.add(summary));
+        stats
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} puzzles ({} failed) in {:.6}s",
+            self.runs,
+            self.failed,
+            self.total.as_secs_f64()
+        )?;
+        if let Some(mean) = self.mean() {
+            writeln!(f, "Mean: {:.6}s", mean.as_secs_f64())?;
+        }
+        if let Some((year, day, slowest)) = self.slowest {
+            writeln!(
+                f,
+                "Slowest: {:4} day {:02} ({:.6}s)",
+                year,
+                day,
+                slowest.as_secs_f64()
+            )?;
+        }
+        if let Some((year, day, largest)) = self.largest {
+            writeln!(
+                f,
+                "Most memory: {:4} day {:02} ({})",
+                year,
+                day,
+                Bytes(largest)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.styled(Style::Plain).fmt(f)
+    }
+}
+
+impl fmt::Display for Styled<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Style::Plain => self.fmt_plain(f),
+            Style::Ansi => self.fmt_ansi(f),
+        }
+    }
+}
+
+impl Styled<'_> {
+    /// Renders the summary as plain text.
+    fn fmt_plain(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = self.summary;
+        match summary.result {
+            Err(ref e) => {
+                writeln!(f, "{}: {}", error_start(e), e)?;
+                let mut cause = e.source();
+                while let Some(e) = cause {
+                    writeln!(f, "  caused by: {}", e)?;
+                    cause = e.source();
+                }
+                Ok(())
+            }
+            Ok(ref solution) => {
+                let setup_bench = summary.setup_time();
+                writeln!(f, "Input: {}\n", summary.puzzle.path_str())?;
+                writeln!(f, "{}", solution)?;
+                writeln!(
+                    f,
+                    "Setup, Parsing: {}.{:09}s",
+                    setup_bench.as_secs(),
+                    setup_bench.subsec_nanos()
+                )?;
+                writeln!(
+                    f,
+                    "Total Elapsed: {}.{:09}s",
+                    summary.duration.as_secs(),
+                    summary.duration.subsec_nanos()
+                )?;
+                if let Some(peak) = summary.peak_memory {
+                    writeln!(f, "Peak Memory: {}", Bytes(peak))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Renders the summary with ANSI colors: a green check for each solved
+    /// part, a red cross followed by the chain of causes for a failure, and
+    /// dimmed timings.
+    fn fmt_ansi(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ansi::*;
+
+        let summary = self.summary;
+        let solution = match summary.result {
+            Ok(ref solution) => solution,
+            Err(ref e) => {
+                writeln!(f, "{}\u{2718} {}:{} {}", RED, error_start(e), RESET, e)?;
+                let mut cause = e.source();
+                while let Some(e) = cause {
+                    writeln!(f, "  {}caused by:{} {}", DIM, RESET, e)?;
+                    cause = e.source();
+                }
+                return Ok(());
+            }
+        };
+
+        writeln!(f, "{}Input:{} {}\n", DIM, RESET, summary.puzzle.path_str())?;
+        let parts = [&solution.0, &solution.1];
+        // Timings line up after the widest single-line answer.
+        let width = parts
+            .iter()
+            .filter_map(|part| part.as_ref().map(Answer::ans))
+            .filter(|ans| !ans.contains('\n'))
+            .map(|ans| ans.chars().count() + 2)
+            .max()
+            .unwrap_or(0);
+        for (i, part) in parts.iter().enumerate() {
+            match part {
+                Some(answer) => {
+                    let ans = format!("`{}`", answer.ans());
+                    write!(
+                        f,
+                        "{}\u{2714}{} Part {}: {}{:<width$}{}",
+                        GREEN,
+                        RESET,
+                        i + 1,
+                        BOLD,
+                        ans,
+                        RESET,
+                        width = width
+                    )?;
+                    match answer.bench() {
+                        Some(bench) => {
+                            writeln!(f, " {}[{:.9}s]{}", DIM, bench.as_secs_f64(), RESET)?
+                        }
+                        None => writeln!(f, " {}[x]{}", DIM, RESET)?,
+                    }
+                }
+                None => writeln!(
+                    f,
+                    "{}-{} Part {}: {}not implemented{}",
+                    YELLOW,
+                    RESET,
+                    i + 1,
+                    DIM,
+                    RESET
+                )?,
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Setup, Parsing: {}{:.9}s{}",
+            DIM,
+            summary.setup_time().as_secs_f64(),
+            RESET
+        )?;
+        writeln!(
+            f,
+            "Total Elapsed:  {}{:.9}s{}",
+            DIM,
+            summary.duration.as_secs_f64(),
+            RESET
+        )?;
+        if let Some(peak) = summary.peak_memory {
+            writeln!(f, "Peak Memory:    {}{}{}", DIM, Bytes(peak), RESET)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Summary {
+    /// Serializes this summary as the puzzle's year, day, and input path,
+    /// followed by either its solution or the error that it failed with, its
+    /// timings in seconds, its peak heap usage in bytes if measured, and
+    /// whether it was contended if so.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Summary", 9)?;
+        state.serialize_field("year", &self.puzzle.year())?;
+        state.serialize_field("day", &self.puzzle.day())?;
+        state.serialize_field("input", self.puzzle.path_str())?;
+        state.serialize_field("solution", &self.result.as_ref().ok())?;
+        state.serialize_field("error", &self.result.as_ref().err().map(|e| e.to_string()))?;
+        state.serialize_field("setup_seconds", &self.setup_time().as_secs_f64())?;
+        state.serialize_field("total_seconds", &self.duration.as_secs_f64())?;
+        match self.peak_memory {
+            Some(peak) => state.serialize_field("peak_memory_bytes", &peak)?,
+            None => state.skip_field("peak_memory_bytes")?,
+        }
+        if self.contended {
+            state.serialize_field("contended", &true)?;
+        } else {
+            state.skip_field("contended")?;
+        }
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::SelectionError;
+    use super::super::solution::{Answer, Solution};
+    use super::*;
+    use std::error::Error;
+    use std::io;
+
+    #[test]
+    fn displays_error_on_selection_failure() {
+        let error = SelectionError::UnimplementedDay;
+
+        let summary = Summary::new(
+            Selection::new(2016, 1),
+            Result::Err(error.into()),
+            Duration::default(),
+        );
+
+        let out = format!("{}", &summary);
+        assert!(out.starts_with(SELECTION_ERROR_START));
+        assert!(out.trim_end().ends_with(&error.to_string()));
+    }
+
+    #[test]
+    fn displays_error_on_solution_failure() {
+        let err_msg = "some error";
+        let error: Box<dyn Error + Send + Sync> = From::from(err_msg);
+
+        let summary = Summary::new(
+            Selection::new(2016, 1),
+            Result::Err(error.into()),
+            Duration::default(),
+        );
+
+        let out = format!("{}", &summary);
+        assert!(out.starts_with(SOLUTION_ERROR_START));
+        assert!(out.trim_end().ends_with(err_msg));
+    }
+
+    #[test]
+    fn determine_setup_time() {
+        let summary = Summary::new(
+            Selection::new(2016, 1),
+            Ok(Solution::new(
+                Some(Answer::with_bench("one", Some(Duration::new(10, 100)))),
+                Some(Answer::with_bench("two", Some(Duration::new(5, 20)))),
+            )),
+            Duration::new(100, 1000),
+        );
+
+        assert_eq!(Duration::new(85, 880), summary.setup_time())
+    }
+
+    #[test]
+    fn measure_with_injected_clock() {
+        let step = Duration::from_millis(250);
+        let summary = clock::with_clock(clock::StepClock::new(step), || {
+            Summary::measure(Selection::new(2016, 2), |_| {
+                let part_one = clock::now();
+                let part_one = Answer::with_bench(99332, Some(clock::elapsed(part_one)));
+                Ok(Solution::new(Some(part_one), None))
+            })
+        });
+
+        assert_eq!(step * 3, summary.duration);
+        assert_eq!(step * 2, summary.setup_time());
+        insta::assert_snapshot!(summary);
+    }
+
+    #[test]
+    fn serialize_summary() {
+        let summary = Summary::new(
+            Selection::new(2018, 12),
+            Ok(Solution::new(
+                Some(Answer::with_bench(3915, Some(Duration::new(1, 0)))),
+                None,
+            )),
+            Duration::new(2, 0),
+        );
+        insta::assert_snapshot!(serde_json::to_string_pretty(&summary).unwrap());
+
+        let failed = Summary::new(
+            Selection::new(2016, 1),
+            Err(From::from("some error")),
+            Duration::default(),
+        );
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(serde_json::Value::Null, json["solution"]);
+        assert_eq!("some error", json["error"]);
+    }
+
+    #[test]
+    fn snapshot_solved() {
+        let summary = Summary::new(
+            Selection::new(2016, 2),
+            Ok(Solution::new(
+                Some(Answer::with_bench(99332, Some(Duration::new(0, 457_333)))),
+                Some(Answer::with_bench("DD483", Some(Duration::new(0, 369_839)))),
+            )),
+            Duration::new(0, 1_574_792),
+        );
+        insta::assert_snapshot!(summary);
+    }
+
+    #[test]
+    fn snapshot_partially_solved() {
+        let summary = Summary::new(
+            Selection::new(2018, 12),
+            Ok(Solution::new(
+                Some(Answer::with_bench(3915, Some(Duration::new(1, 5)))),
+                None,
+            )),
+            Duration::new(2, 0),
+        );
+        insta::assert_snapshot!(summary);
+    }
+
+    #[test]
+    fn snapshot_errors() {
+        let selection_error = Summary::new(
+            Selection::new(2016, 1),
+            Err(SelectionError::UnimplementedDay.into()),
+            Duration::default(),
+        );
+        insta::assert_snapshot!("selection_error", selection_error);
+
+        let solution_error = Summary::new(
+            Selection::new(2016, 1),
+            Err(From::from("some error")),
+            Duration::default(),
+        );
+        insta::assert_snapshot!("solution_error", solution_error);
+    }
+
+    #[test]
+    fn snapshot_ansi() {
+        let summary = Summary::new(
+            Selection::new(2016, 2),
+            Ok(Solution::new(
+                Some(Answer::with_bench(99332, Some(Duration::new(0, 457_333)))),
+                Some(Answer::with_bench("DD483x", None)),
+            )),
+            Duration::new(0, 1_574_792),
+        );
+        insta::assert_snapshot!(summary.styled(Style::Ansi));
+
+        let partial = Summary::new(
+            Selection::new(2018, 12),
+            Ok(Solution::new(Some(Answer::with_bench(3915, None)), None)),
+            Duration::new(2, 0),
+        );
+        insta::assert_snapshot!("ansi_partially_solved", partial.styled(Style::Ansi));
+    }
+
+    #[test]
+    fn ansi_error_chain() {
+        #[derive(Debug)]
+        struct Wrapped(Box<dyn Error + Send + Sync>);
+
+        impl fmt::Display for Wrapped {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "failed to parse input")
+            }
+        }
+
+        impl Error for Wrapped {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&*self.0)
+            }
+        }
+
+        let summary = Summary::new(
+            Selection::new(2016, 1),
+            Err(PuzzleError::SolverFailure(Box::new(Wrapped(From::from(
+                "bad turn `X3`",
+            ))))),
+            Duration::default(),
+        );
+        let out = summary.styled(Style::Ansi).to_string();
+        assert_eq!(
+            "\x1b[31m\u{2718} Failed to execute solution:\x1b[0m failed to parse input\n  \
+             \x1b[2mcaused by:\x1b[0m bad turn `X3`\n",
+            out
+        );
+        assert_eq!(
+            summary.to_string(),
+            summary.styled(Style::Plain).to_string()
+        );
+    }
+
+    #[test]
+    fn classify_status() {
+        let status = |result: PuzzleResult| {
+            Summary::new(Selection::new(2016, 1), result, Duration::default()).status()
+        };
+        let missing = io::Error::new(io::ErrorKind::NotFound, "no such file");
+
+        assert_eq!(Status::Solved, status(Ok(Solution::empty())));
+        assert_eq!(
+            Status::Unimplemented,
+            status(Err(SelectionError::UnimplementedDay.into()))
+        );
+        assert_eq!(Status::Failed, status(Err(SelectionError::BadParam.into())));
+        assert_eq!(Status::MissingInput, status(Err(missing.into())));
+        assert_eq!(Status::Failed, status(Err(From::from("bad input"))));
+        assert_eq!(Status::Failed, status(Err(PuzzleError::parse_at(3, "bad"))));
+        assert_eq!(
+            Status::TimedOut,
+            status(Err(TimedOut(Duration::from_secs(1)).into()))
+        );
+        assert!(Status::Solved < Status::Unimplemented && Status::MissingInput < Status::Failed);
+    }
+
+    #[test]
+    fn measure_with_timeout() {
+        let timeout = Duration::from_millis(50);
+
+        let solved =
+            Summary::measure_within(Selection::new(2016, 2), timeout, |_| Ok(Solution::empty()));
+        assert_eq!(Status::Solved, solved.status());
+
+        let hung = Summary::measure_within(Selection::new(2016, 23), timeout, |_| loop {
+            cancel::check()?;
+            thread::sleep(Duration::from_millis(1));
+        });
+        assert_eq!(Status::TimedOut, hung.status());
+        assert_eq!(timeout, hung.duration());
+        assert_eq!(2016, hung.puzzle().year());
+        assert!(hung.to_string().contains("timed out after 0.050s"));
+    }
+
+    #[test]
+    fn mark_contended_runs() {
+        let (release, blocked) = mpsc::channel::<()>();
+        let stuck = Summary::measure_within(
+            Selection::new(2016, 5),
+            Duration::from_millis(10),
+            move |_| {
+                // Ignores the request to stop until released.
+                let _ = blocked.recv();
+                Ok(Solution::empty())
+            },
+        );
+        assert_eq!(Status::TimedOut, stuck.status());
+
+        let next = Summary::measure(Selection::new(2016, 1), |_| Ok(Solution::empty()));
+        assert!(next.contended());
+        let json = serde_json::to_value(&next).unwrap();
+        assert_eq!(serde_json::Value::Bool(true), json["contended"]);
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn collect_stats() {
+        let run = |day, result: PuzzleResult, millis| {
+            Summary::new(
+                Selection::new(2016, day),
+                result,
+                Duration::from_millis(millis),
+            )
+        };
+        let summaries = [
+            run(1, Ok(Solution::empty()), 100),
+            run(2, Err(From::from("some error")), 1_000),
+            run(3, Ok(Solution::empty()), 300),
+            run(4, Ok(Solution::empty()), 200),
+        ];
+
+        let stats: Stats = summaries.iter().collect();
+        assert_eq!(4, stats.runs());
+        assert_eq!(1, stats.failed());
+        assert_eq!(Duration::from_millis(1_600), stats.total());
+        assert_eq!(Some(Duration::from_millis(200)), stats.mean());
+        assert_eq!(Some((2016, 3, Duration::from_millis(300))), stats.slowest());
+        assert_eq!(
+            "4 puzzles (1 failed) in 1.600000s\n\
+             Mean: 0.200000s\n\
+             Slowest: 2016 day 03 (0.300000s)\n",
+            stats.to_string()
+        );
+
+        let empty: Stats = summaries[1..2].iter().collect();
+        assert_eq!(None, empty.mean());
+        assert_eq!("1 puzzles (1 failed) in 1.000000s\n", empty.to_string());
+    }
+
+    #[test]
+    fn report_peak_memory() {
+        let run = |day, peak| {
+            Summary::new(
+                Selection::new(2016, day),
+                Ok(Solution::empty()),
+                Duration::from_millis(100),
+            )
+            .with_peak_memory(peak)
+        };
+        let summaries = [run(15, Some(2_048)), run(16, Some(35 << 20)), run(17, None)];
+
+        assert!(summaries[1]
+            .to_string()
+            .ends_with("Total Elapsed: 0.100000000s\nPeak Memory: 35.0 MiB\n"));
+        let json = serde_json::to_value(&summaries[0]).unwrap();
+        assert_eq!(2_048, json["peak_memory_bytes"]);
+        let json = serde_json::to_value(&summaries[2]).unwrap();
+        assert!(json.get("peak_memory_bytes").is_none());
+
+        let stats: Stats = summaries.iter().collect();
+        assert_eq!(Some((2016, 16, 35 << 20)), stats.largest());
+        assert!(stats
+            .to_string()
+            .ends_with("Most memory: 2016 day 16 (35.0 MiB)\n"));
+    }
+}
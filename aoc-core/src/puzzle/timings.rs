@@ -0,0 +1,186 @@
+//! Reports of the answers and timings of many puzzle runs.
+//!
+//! A report has a row for each run, with columns for the puzzle's year and
+//! day, the answer and time of each part, and the setup and total times. Runs
+//! that failed have their error in the last column instead of answers.
+//! Reports are rendered as either a Markdown table or a CSV file.
+
+use super::solution::Answer;
+use super::summary::Summary;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// The columns of a report.
+const COLUMNS: [&str; 9] = [
+    "Year",
+    "Day",
+    "Part 1",
+    "Part 1 (s)",
+    "Part 2",
+    "Part 2 (s)",
+    "Setup (s)",
+    "Total (s)",
+    "Error",
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The file formats that a report may be rendered in.
+pub enum Format {
+    /// A Markdown table.
+    Markdown,
+    /// Comma-separated values, with a header row.
+    Csv,
+}
+
+impl Format {
+    /// Returns the format suggested by the extension of the given path:
+    /// CSV for `.csv` files, and Markdown otherwise.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+            _ => Format::Markdown,
+        }
+    }
+}
+
+/// Renders a report of the given runs in the given format.
+pub fn render<'a, I>(summaries: I, format: Format) -> String
+where
+    I: IntoIterator<Item = &'a Summary>,
+{
+    let rows = summaries.into_iter().map(row);
+    match format {
+        Format::Markdown => markdown_table(&COLUMNS, rows),
+        Format::Csv => to_csv(rows),
+    }
+}
+
+/// Returns the cells of the report row for a single run.
+fn row(summary: &Summary) -> Vec<String> {
+    let puzzle = summary.puzzle();
+    let mut cells = vec![puzzle.year().to_string(), puzzle.day().to_string()];
+    match summary.result() {
+        Ok(solution) => {
+            for part in &[&solution.0, &solution.1] {
+                cells.push(
+                    part.as_ref()
+                        .map_or_else(String::new, |a| a.ans().to_owned()),
+                );
+                cells.push(
+                    part.as_ref()
+                        .and_then(Answer::bench)
+                        .map_or_else(String::new, secs),
+                );
+            }
+            cells.push(secs(&summary.setup_time()));
+            cells.push(secs(&summary.duration()));
+            cells.push(String::new());
+        }
+        Err(e) => {
+            cells.extend((0..6).map(|_| String::new()));
+            cells.push(e.to_string());
+        }
+    }
+    cells
+}
+
+/// Formats a duration as a number of seconds.
+fn secs(duration: &Duration) -> String {
+    format!("{:.9}", duration.as_secs_f64())
+}
+
+/// Renders rows as a Markdown table with the given column headers.
+///
+/// Pipes are escaped, and cells spanning several lines, such as rendered
+/// letters, have their lines separated by `<br>`.
+pub fn markdown_table<H, I>(columns: &[H], rows: I) -> String
+where
+    H: AsRef<str>,
+    I: IntoIterator<Item = Vec<String>>,
+{
+    let columns: Vec<&str> = columns.iter().map(AsRef::as_ref).collect();
+    let mut out = String::new();
+    writeln!(out, "| {} |", columns.join(" | ")).unwrap();
+    writeln!(out, "|{}", " --- |".repeat(columns.len())).unwrap();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace('\n', "<br>"))
+            .collect();
+        writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+    }
+    out
+}
+
+/// Renders rows as CSV.
+///
+/// Cells containing commas, quotes, or line breaks are quoted.
+fn to_csv<I: Iterator<Item = Vec<String>>>(rows: I) -> String {
+    let mut out = String::new();
+    writeln!(out, "{}", COLUMNS.join(",")).unwrap();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                if cell.contains(&[',', '"', '\n', '\r'][..]) {
+                    format!("\"{}\"", cell.replace('"', "\"\""))
+                } else {
+                    cell.clone()
+                }
+            })
+            .collect();
+        writeln!(out, "{}", cells.join(",")).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{Selection, Solution};
+
+    fn summaries() -> Vec<Summary> {
+        vec![
+            Summary::new(
+                Selection::new(2016, 8),
+                Ok(Solution::new(
+                    Some(Answer::with_bench(106, Some(Duration::from_micros(1_500)))),
+                    Some(Answer::with_bench(
+                        "#..#\n#..#",
+                        Some(Duration::from_millis(2)),
+                    )),
+                )),
+                Duration::from_millis(5),
+            ),
+            Summary::new(
+                Selection::new(2016, 25),
+                Ok(Solution::new(Some(Answer::with_bench("a|b", None)), None)),
+                Duration::from_millis(1),
+            ),
+            Summary::new(
+                Selection::new(2016, 16),
+                Err(From::from("bad input, \"really\"")),
+                Duration::default(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(Format::Csv, Format::from_path("bench.csv"));
+        assert_eq!(Format::Csv, Format::from_path("out/BENCH.CSV"));
+        assert_eq!(Format::Markdown, Format::from_path("bench.md"));
+        assert_eq!(Format::Markdown, Format::from_path("bench"));
+    }
+
+    #[test]
+    fn snapshot_markdown() {
+        insta::assert_snapshot!(render(&summaries(), Format::Markdown));
+    }
+
+    #[test]
+    fn snapshot_csv() {
+        insta::assert_snapshot!(render(&summaries(), Format::Csv));
+    }
+}
@@ -0,0 +1,107 @@
+//! Helpers shared by the solutions' unit tests.
+
+use crate::puzzle::input::InputSource;
+use crate::puzzle::{fetch_string, Answer, AnswerKey, Selection, Solution};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io;
+use std::str::FromStr;
+
+/// An input source that serves fixed strings in place of puzzle input files.
+///
+/// Inputs are keyed by the puzzle's year, day, and input path, so a fixture
+/// registered for `Selection::new(2016, 15)` is not served for one of that
+/// day's examples. Reading any other puzzle fails with `NotFound`.
+#[derive(Debug, Default, Clone)]
+pub struct FakeInputs {
+    inputs: HashMap<(u16, u8, String), String>,
+}
+
+impl FakeInputs {
+    /// Builds a new source without any inputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `input` as the input for the given puzzle.
+    pub fn with<S: Into<String>>(mut self, puzzle: &Selection, input: S) -> Self {
+        self.inputs.insert(Self::key(puzzle), input.into());
+        self
+    }
+
+    fn key(puzzle: &Selection) -> (u16, u8, String) {
+        (puzzle.year(), puzzle.day(), puzzle.path_str().to_owned())
+    }
+}
+
+impl InputSource for FakeInputs {
+    fn read(&self, puzzle: &Selection) -> io::Result<String> {
+        self.inputs.get(&Self::key(puzzle)).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no fake input for {}", puzzle.path_str()),
+            )
+        })
+    }
+}
+
+/// Returns the contents of the numbered example input for a puzzle.
+///
+/// Example inputs are loaded from `examples/yYYYY/dayDD/exN.txt`.
+pub fn example(year: u16, day: u8, example: u8) -> String {
+    let puzzle = Selection::example(year, day, example);
+    fetch_string(&puzzle)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", puzzle.path_str(), err))
+}
+
+/// Builds a puzzle selection whose input is the given string.
+pub fn selection(year: u16, day: u8, input: &str) -> Selection {
+    Selection::with_text(year, day, input)
+}
+
+/// Builds the expected solution for a puzzle with answers for both parts.
+pub fn solution<A: ToString, B: ToString>(part_one: A, part_two: B) -> Solution {
+    Solution::new(Some(Answer::new(part_one)), Some(Answer::new(part_two)))
+}
+
+/// Builds the expected solution for a puzzle with an answer for only the
+/// first part.
+pub fn part_one<A: ToString>(part_one: A) -> Solution {
+    Solution::new(Some(Answer::new(part_one)), None)
+}
+
+/// Asserts that a solution's answers match the known answers recorded for
+/// its puzzle in the answer manifest.
+pub fn assert_known_answers(puzzle: &Selection, solution: &Solution) {
+    let key =
+        AnswerKey::load().unwrap_or_else(|err| panic!("failed to load answer manifest: {}", err));
+    let expected = key.get(puzzle.year(), puzzle.day()).unwrap_or_else(|| {
+        panic!(
+            "no known answers for {} day {}",
+            puzzle.year(),
+            puzzle.day()
+        )
+    });
+    if let Err(mismatch) = expected.check(solution) {
+        panic!("{} day {}: {}", puzzle.year(), puzzle.day(), mismatch);
+    }
+}
+
+/// Parses each of the given lines, panicking with the offending line if any
+/// fail to parse.
+pub fn parse_lines<T, I>(lines: I) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = line.as_ref();
+            line.parse()
+                .unwrap_or_else(|err| panic!("failed to parse {:?}: {:?}", line, err))
+        })
+        .collect()
+}
@@ -0,0 +1,361 @@
+//! Minimal calendar timestamps, as they appear in puzzle input, and the
+//! times at which puzzles are released.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Offset of the time zone in which puzzles are released at midnight (EST,
+/// UTC-5) from UTC, in seconds.
+pub const RELEASE_OFFSET: i64 = -5 * 60 * 60;
+
+/// The number of puzzles in each event.
+pub const EVENT_DAYS: u8 = 25;
+
+/// A timestamp with minute resolution in the proleptic Gregorian calendar.
+///
+/// Timestamps are ordered chronologically.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Timestamp {
+    // Field order matters: the derived `Ord` compares fields in sequence.
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+}
+
+impl Timestamp {
+    /// Creates a timestamp from its components, or returns `None` if the
+    /// components do not describe a valid date and time.
+    pub fn new(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> Option<Self> {
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour >= 24
+            || minute >= 60
+        {
+            return None;
+        }
+        Some(Timestamp {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        })
+    }
+
+    /// Returns the year of this timestamp.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month of this timestamp, starting from 1.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month of this timestamp, starting from 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour of this timestamp, from 0 to 23.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute of this timestamp, from 0 to 59.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the timestamp of the minute containing the given number of
+    /// seconds since 1970-01-01 00:00.
+    pub fn from_unix(secs: i64) -> Self {
+        let minutes = secs.div_euclid(60);
+        let (year, month, day) = civil_from_days(minutes.div_euclid(24 * 60));
+        let minute_of_day = minutes.rem_euclid(24 * 60);
+        Timestamp {
+            year,
+            month,
+            day,
+            hour: (minute_of_day / 60) as u8,
+            minute: (minute_of_day % 60) as u8,
+        }
+    }
+
+    /// Returns the number of seconds elapsed since 1970-01-01 00:00.
+    pub fn unix_seconds(&self) -> i64 {
+        self.minutes_since_epoch() * 60
+    }
+
+    /// Returns the number of minutes elapsed from `earlier` to this
+    /// timestamp. The result is negative if `earlier` is later than this
+    /// timestamp.
+    pub fn minutes_since(&self, earlier: Timestamp) -> i64 {
+        self.minutes_since_epoch() - earlier.minutes_since_epoch()
+    }
+
+    /// Returns the number of minutes elapsed since 1970-01-01 00:00.
+    fn minutes_since_epoch(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        (days * 24 + i64::from(self.hour)) * 60 + i64::from(self.minute)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = &'static str;
+
+    /// Parses a timestamp of the form `YYYY-MM-DD HH:MM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn field<T: FromStr>(part: Option<&str>, width: usize) -> Result<T, &'static str> {
+            part.filter(|p| p.len() == width && p.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|p| p.parse().ok())
+                .ok_or("malformed timestamp")
+        }
+
+        let (date, time) = s.split_once(' ').ok_or("malformed timestamp")?;
+        let mut date = date.split('-');
+        let mut time = time.split(':');
+
+        let timestamp = Timestamp::new(
+            field(date.next(), 4)?,
+            field(date.next(), 2)?,
+            field(date.next(), 2)?,
+            field(time.next(), 2)?,
+            field(time.next(), 2)?,
+        )
+        .ok_or("timestamp out of range")?;
+
+        if date.next().is_some() || time.next().is_some() {
+            return Err("malformed timestamp");
+        }
+        Ok(timestamp)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+/// Returns the current Unix timestamp.
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Returns the date and time in EST at the given Unix timestamp.
+pub fn release_date(now: i64) -> Timestamp {
+    Timestamp::from_unix(now + RELEASE_OFFSET)
+}
+
+/// Returns the Unix timestamp at which the puzzle for the given day was
+/// released, or `None` if the day is not a puzzle day.
+pub fn release_time(year: u16, day: u8) -> Option<i64> {
+    if !(1..=EVENT_DAYS).contains(&day) {
+        return None;
+    }
+    let midnight = Timestamp::new(i32::from(year), 12, day, 0, 0)?;
+    Some(midnight.unix_seconds() - RELEASE_OFFSET)
+}
+
+/// Returns the year and day of the puzzle released on the day of the given
+/// Unix timestamp, if one was.
+pub fn released_today(now: i64) -> Option<(u16, u8)> {
+    let today = release_date(now);
+    match (today.month(), today.day()) {
+        (12, day) if day <= EVENT_DAYS => Some((today.year() as u16, day)),
+        _ => None,
+    }
+}
+
+/// Returns the year and day of the last puzzle released at or before the
+/// given Unix timestamp.
+///
+/// Before December, this is the last puzzle of the previous year's event.
+pub fn latest_release(now: i64) -> (u16, u8) {
+    let today = release_date(now);
+    let year = today.year() as u16;
+    match (today.month(), today.day()) {
+        (12, day) => (year, day.min(EVENT_DAYS)),
+        _ => (year - 1, EVENT_DAYS),
+    }
+}
+
+/// Returns the year and day of the first puzzle released after the given
+/// Unix timestamp.
+pub fn next_release(now: i64) -> (u16, u8) {
+    let today = release_date(now);
+    let year = today.year() as u16;
+    match (today.month(), today.day()) {
+        (12, day) if day < EVENT_DAYS => (year, day + 1),
+        (12, _) => (year + 1, 1),
+        _ => (year, 1),
+    }
+}
+
+/// Returns `true` if `year` is a leap year.
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days in the given month of the given year.
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days from 1970-01-01 to the given date.
+///
+/// Based on Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+        + i64::from(day)
+        - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the date that is the given number of days from 1970-01-01, as
+/// `(year, month, day)`.
+///
+/// Based on Howard Hinnant's `civil_from_days` algorithm, the inverse of
+/// `days_from_civil`.
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month as u8, day as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp() {
+        let ts: Timestamp = "1518-11-01 23:58".parse().unwrap();
+        assert_eq!(Timestamp::new(1518, 11, 1, 23, 58), Some(ts));
+        assert_eq!(58, ts.minute());
+        assert_eq!(23, ts.hour());
+        assert_eq!("1518-11-01 23:58", ts.to_string());
+    }
+
+    #[test]
+    fn reject_bad_timestamps() {
+        for s in &[
+            "",
+            "1518-11-01",
+            "1518-11-01 23:5",
+            "1518-11-01T23:58",
+            "1518-13-01 00:00",
+            "1518-02-29 00:00",
+            "1518-11-01 24:00",
+            "1518-11-01 00:60",
+            "1518-11-01 00:00:00",
+            "1518-11-+1 00:00",
+        ] {
+            assert!(s.parse::<Timestamp>().is_err(), "{}", s);
+        }
+        assert!("1600-02-29 00:00".parse::<Timestamp>().is_ok());
+    }
+
+    #[test]
+    fn chronological_order() {
+        let mut stamps: Vec<Timestamp> = [
+            "1518-11-02 00:00",
+            "1518-11-01 23:58",
+            "1518-10-31 00:30",
+            "1517-12-31 23:59",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+        stamps.sort();
+        assert_eq!("1517-12-31 23:59", stamps[0].to_string());
+        assert_eq!("1518-11-02 00:00", stamps[3].to_string());
+    }
+
+    #[test]
+    fn minutes_between() {
+        let start: Timestamp = "1518-02-28 23:58".parse().unwrap();
+        let end: Timestamp = "1518-03-01 00:05".parse().unwrap();
+        assert_eq!(7, end.minutes_since(start));
+        assert_eq!(-7, start.minutes_since(end));
+
+        let start: Timestamp = "1970-01-01 00:00".parse().unwrap();
+        assert_eq!(0, start.minutes_since_epoch());
+        let end: Timestamp = "2000-03-01 00:00".parse().unwrap();
+        assert_eq!(11_017 * 24 * 60, end.minutes_since(start));
+    }
+
+    #[test]
+    fn unix_round_trip() {
+        let stamp = Timestamp::from_unix(951_782_399);
+        assert_eq!("2000-02-28 23:59", stamp.to_string());
+        assert_eq!(951_782_340, stamp.unix_seconds());
+        assert_eq!("1969-12-31 23:59", Timestamp::from_unix(-1).to_string());
+
+        for &days in &[-1, 0, 59, 11_016, 18_321, 20_000] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days, days_from_civil(year, month, day));
+        }
+    }
+
+    #[test]
+    fn release_times() {
+        // 2015-12-01T05:00:00Z and 2020-12-25T05:00:00Z
+        assert_eq!(Some(1_448_946_000), release_time(2015, 1));
+        assert_eq!(Some(1_608_872_400), release_time(2020, 25));
+        assert_eq!(None, release_time(2020, 0));
+        assert_eq!(None, release_time(2020, 26));
+    }
+
+    #[test]
+    fn next_releases() {
+        let day_one = release_time(2019, 1).unwrap();
+        let day_last = release_time(2019, 25).unwrap();
+        assert_eq!((2019, 1), next_release(day_one - 1));
+        assert_eq!((2019, 2), next_release(day_one));
+        assert_eq!((2019, 1), next_release(day_one - 90 * 24 * 60 * 60));
+        assert_eq!((2020, 1), next_release(day_last));
+        assert_eq!((2020, 1), next_release(day_last + 7 * 24 * 60 * 60));
+    }
+
+    #[test]
+    fn latest_releases() {
+        let day_one = release_time(2019, 1).unwrap();
+        assert_eq!((2018, 25), latest_release(day_one - 1));
+        assert_eq!((2019, 1), latest_release(day_one));
+        assert_eq!(Some((2019, 1)), released_today(day_one));
+        assert_eq!(None, released_today(day_one - 1));
+    }
+}
@@ -0,0 +1,131 @@
+//! A disjoint-set (union-find) structure for grouping connected elements.
+
+/// A collection of disjoint sets over the elements `0..len`.
+///
+/// Each element begins in its own set. Sets are merged with
+/// [`DisjointSet::union`].
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::DisjointSet;
+/// let mut sets = DisjointSet::new(4);
+/// sets.union(0, 1);
+/// sets.union(2, 1);
+/// assert_eq!(sets.find(0), sets.find(2));
+/// assert_ne!(sets.find(0), sets.find(3));
+/// assert_eq!(2, sets.set_count());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    /// The parent of each element. Root elements are their own parent.
+    parents: Vec<usize>,
+    /// The number of elements in the set rooted at each element. Only
+    /// meaningful for root elements.
+    sizes: Vec<usize>,
+    /// The number of distinct sets.
+    set_count: usize,
+}
+
+impl DisjointSet {
+    /// Creates a collection of `len` singleton sets.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            set_count: len,
+        }
+    }
+
+    /// Returns the number of elements in this collection.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns `true` if this collection contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the number of distinct sets in this collection.
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Returns the representative element of the set containing `elem`.
+    ///
+    /// Panics if `elem` is out of bounds.
+    pub fn find(&mut self, elem: usize) -> usize {
+        let mut root = elem;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        // Compress the path from `elem` to its root
+        let mut elem = elem;
+        while self.parents[elem] != root {
+            elem = std::mem::replace(&mut self.parents[elem], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they were
+    /// already in the same set.
+    ///
+    /// Panics if either element is out of bounds.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // Attach the smaller set beneath the larger one
+        let (small, large) = if self.sizes[a] < self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.set_count -= 1;
+        true
+    }
+
+    /// Returns the number of elements in the set containing `elem`.
+    pub fn set_size(&mut self, elem: usize) -> usize {
+        let root = self.find(elem);
+        self.sizes[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_find() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(6, sets.set_count());
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(1, 2));
+        assert!(sets.union(4, 3));
+        assert!(!sets.union(2, 0));
+
+        assert_eq!(3, sets.set_count());
+        assert_eq!(sets.find(0), sets.find(2));
+        assert_eq!(sets.find(3), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(3));
+        assert_eq!(3, sets.set_size(1));
+        assert_eq!(1, sets.set_size(5));
+        assert_eq!(6, sets.len());
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut sets = DisjointSet::new(1000);
+        for i in 1..1000 {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(1, sets.set_count());
+        assert_eq!(1000, sets.set_size(0));
+        assert_eq!(sets.find(0), sets.find(999));
+    }
+}
@@ -0,0 +1,96 @@
+//! Basic geometric shapes.
+
+/// A triangle described by the lengths of its three sides.
+///
+/// The sides may be given in any order. A `Triangle` is not guaranteed to be
+/// valid; see [`Triangle::is_valid`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Triangle(pub u32, pub u32, pub u32);
+
+impl Triangle {
+    /// Returns `true` if the sides of this triangle satisfy the triangle
+    /// inequality, i.e. the sum of any two sides is strictly greater than the
+    /// remaining side.
+    ///
+    /// Degenerate triangles, whose vertices are collinear, are not valid.
+    ///
+    /// # Example
+    /// ```
+    /// # use aoc_core::util::geom::Triangle;
+    /// assert!(Triangle(3, 4, 5).is_valid());
+    /// assert!(!Triangle(5, 10, 25).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let [a, b, c] = self.sorted_sides();
+        a + b > c
+    }
+
+    /// Returns the sum of the lengths of this triangle's sides.
+    pub fn perimeter(&self) -> u64 {
+        self.sorted_sides().iter().sum()
+    }
+
+    /// Returns the area of this triangle, or `None` if it is not valid.
+    ///
+    /// # Example
+    /// ```
+    /// # use aoc_core::util::geom::Triangle;
+    /// assert_eq!(Some(6.0), Triangle(3, 4, 5).area());
+    /// assert_eq!(None, Triangle(1, 2, 3).area());
+    /// ```
+    pub fn area(&self) -> Option<f64> {
+        if !self.is_valid() {
+            return None;
+        }
+        // Heron's formula, in the form 16 * area^2 = (a+b+c)(-a+b+c)(a-b+c)(a+b-c).
+        // Every factor is positive for a valid triangle.
+        let [a, b, c] = self.sorted_sides();
+        let product = [a + b + c, b + c - a, a + c - b, a + b - c]
+            .iter()
+            .map(|&factor| u128::from(factor))
+            .product::<u128>();
+        Some((product as f64).sqrt() / 4.0)
+    }
+
+    /// Returns the side lengths of this triangle in ascending order.
+    fn sorted_sides(&self) -> [u64; 3] {
+        let mut sides = [u64::from(self.0), u64::from(self.1), u64::from(self.2)];
+        sides.sort_unstable();
+        sides
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validity() {
+        assert!(Triangle(3, 4, 5).is_valid());
+        assert!(Triangle(5, 3, 4).is_valid());
+        assert!(Triangle(1, 1, 1).is_valid());
+        assert!(!Triangle(1, 2, 3).is_valid());
+        assert!(!Triangle(3, 1, 2).is_valid());
+        assert!(!Triangle(0, 0, 0).is_valid());
+        assert!(Triangle(u32::MAX, u32::MAX, u32::MAX).is_valid());
+    }
+
+    #[test]
+    fn perimeter() {
+        assert_eq!(12, Triangle(3, 4, 5).perimeter());
+        assert_eq!(
+            3 * u64::from(u32::MAX),
+            Triangle(u32::MAX, u32::MAX, u32::MAX).perimeter()
+        );
+    }
+
+    #[test]
+    fn area() {
+        assert_eq!(Some(6.0), Triangle(5, 3, 4).area());
+        assert_eq!(Some(12.0), Triangle(5, 5, 6).area());
+        assert_eq!(None, Triangle(5, 10, 25).area());
+
+        let equilateral = Triangle(2, 2, 2).area().unwrap();
+        assert!((equilateral - 3_f64.sqrt()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,113 @@
+//! The knot hash, as introduced in 2017 Day 10 and reused in 2017 Day 14.
+//!
+//! A knot hash is computed by repeatedly reversing spans of a circular list
+//! of the values `0..=255`. The lengths of the spans are derived from the
+//! message being hashed.
+
+use super::Digest;
+
+/// The number of elements in the circular list.
+const LIST_SIZE: usize = 256;
+
+/// The number of rounds performed when computing a full knot hash.
+const ROUNDS: usize = 64;
+
+/// Lengths appended to every message before computing a full knot hash.
+const LENGTH_SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+/// Computes the knot hash of the given message.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::hash::{knot, to_hex};
+/// assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", to_hex(&knot(b"")));
+/// ```
+pub fn knot(msg: &[u8]) -> Digest {
+    let lengths: Vec<usize> = msg
+        .iter()
+        .chain(LENGTH_SUFFIX.iter())
+        .map(|&len| usize::from(len))
+        .collect();
+    dense_hash(&sparse_hash(LIST_SIZE, &lengths, ROUNDS))
+}
+
+/// Returns the circular list of the values `0..size` after applying the
+/// specified rounds of reversals.
+///
+/// The current position and skip size are preserved between rounds.
+///
+/// Panics if `size` is zero or greater than 256, or if any length is greater
+/// than `size`.
+pub fn sparse_hash(size: usize, lengths: &[usize], rounds: usize) -> Vec<u8> {
+    assert!(
+        size > 0 && size <= LIST_SIZE,
+        "knot hash list must have between 1 and 256 elements"
+    );
+    let mut list: Vec<u8> = (0..size).map(|n| n as u8).collect();
+    let mut pos = 0;
+    let mut skip = 0;
+
+    for _ in 0..rounds {
+        for &len in lengths {
+            assert!(len <= size, "knot length exceeds list size");
+            // Rotate the span to the front of the list to avoid reversing
+            // across the wrap-around point
+            list.rotate_left(pos);
+            list[..len].reverse();
+            list.rotate_right(pos);
+
+            pos = (pos + len + skip) % size;
+            skip += 1;
+        }
+    }
+    list
+}
+
+/// Condenses a full-sized sparse hash into a digest by XORing each block of
+/// sixteen values.
+///
+/// Panics if the sparse hash does not contain exactly 256 values.
+pub fn dense_hash(sparse: &[u8]) -> Digest {
+    assert_eq!(
+        LIST_SIZE,
+        sparse.len(),
+        "sparse hash must contain 256 values"
+    );
+    let mut digest = Digest::default();
+    for (byte, block) in digest.iter_mut().zip(sparse.chunks(16)) {
+        *byte = block.iter().fold(0, |acc, &value| acc ^ value);
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::to_hex;
+    use super::*;
+
+    #[test]
+    fn single_round() {
+        assert_eq!(vec![3, 4, 2, 1, 0], sparse_hash(5, &[3, 4, 1, 5], 1));
+    }
+
+    #[test]
+    fn full_hash() {
+        let test_cases: [(&[u8], &str); 4] = [
+            (b"", "a2582a3a0e66e6e86e3812dcb672a272"),
+            (b"AoC 2017", "33efeb34ea91902bb2f59c9920caa6cd"),
+            (b"1,2,3", "3efbe78a8d82f29979031a4aa0b16a9d"),
+            (b"1,2,4", "63960835bcdc130f0b66d7ff4f6a5a8e"),
+        ];
+
+        for &(msg, expected) in test_cases.iter() {
+            assert_eq!(expected, to_hex(&knot(msg)));
+        }
+    }
+
+    #[test]
+    fn dense() {
+        let mut sparse = vec![0; LIST_SIZE];
+        sparse[..16].copy_from_slice(&[65, 27, 9, 1, 4, 3, 40, 50, 91, 7, 6, 0, 2, 5, 68, 22]);
+        assert_eq!(64, dense_hash(&sparse)[0]);
+    }
+}
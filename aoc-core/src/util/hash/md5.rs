@@ -0,0 +1,395 @@
+//! A multi-lane implementation of the MD5 message-digest algorithm.
+//!
+//! Puzzles such as 2015 Day 4 and 2016 Days 5 and 14 hash millions of short
+//! messages of the form "seed + incrementing counter". Rather than hashing
+//! each message one at a time, the hasher in this module runs the MD5
+//! compression function over [`LANES`] single-block messages at once, with
+//! the state of each message stored in its own lane. The lane-wise loops are
+//! simple enough for the compiler to vectorize.
+
+/// The number of messages hashed simultaneously.
+pub const LANES: usize = 8;
+
+/// A 128-bit MD5 digest.
+pub type Digest = [u8; 16];
+
+/// The length of the longest message that fits in a single padded block.
+const MAX_SINGLE_BLOCK_LEN: usize = 55;
+
+/// The initial state of the MD5 hash: `a`, `b`, `c`, and `d`.
+const INIT_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+/// The per-step additive constants, `floor(abs(sin(i + 1)) * 2^32)`.
+const K: [u32; 64] = [
+    0xd76a_a478, 0xe8c7_b756, 0x2420_70db, 0xc1bd_ceee, 0xf57c_0faf, 0x4787_c62a, 0xa830_4613,
+    0xfd46_9501, 0x6980_98d8, 0x8b44_f7af, 0xffff_5bb1, 0x895c_d7be, 0x6b90_1122, 0xfd98_7193,
+    0xa679_438e, 0x49b4_0821, 0xf61e_2562, 0xc040_b340, 0x265e_5a51, 0xe9b6_c7aa, 0xd62f_105d,
+    0x0244_1453, 0xd8a1_e681, 0xe7d3_fbc8, 0x21e1_cde6, 0xc337_07d6, 0xf4d5_0d87, 0x455a_14ed,
+    0xa9e3_e905, 0xfcef_a3f8, 0x676f_02d9, 0x8d2a_4c8a, 0xfffa_3942, 0x8771_f681, 0x6d9d_6122,
+    0xfde5_380c, 0xa4be_ea44, 0x4bde_cfa9, 0xf6bb_4b60, 0xbebf_bc70, 0x289b_7ec6, 0xeaa1_27fa,
+    0xd4ef_3085, 0x0488_1d05, 0xd9d4_d039, 0xe6db_99e5, 0x1fa2_7cf8, 0xc4ac_5665, 0xf429_2244,
+    0x432a_ff97, 0xab94_23a7, 0xfc93_a039, 0x655b_59c3, 0x8f0c_cc92, 0xffef_f47d, 0x8584_5dd1,
+    0x6fa8_7e4f, 0xfe2c_e6e0, 0xa301_4314, 0x4e08_11a1, 0xf753_7e82, 0xbd3a_f235, 0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+/// The per-step left-rotation amounts.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// Lowercase hexadecimal digits.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Returns the index of the message word consumed during the given step.
+#[inline(always)]
+fn word_index(step: usize) -> usize {
+    match step / 16 {
+        0 => step,
+        1 => (5 * step + 1) % 16,
+        2 => (3 * step + 5) % 16,
+        _ => (7 * step) % 16,
+    }
+}
+
+/// Computes the MD5 digest of the given message.
+pub fn md5(msg: &[u8]) -> Digest {
+    let mut state = INIT_STATE;
+
+    let mut chunks = msg.chunks_exact(64);
+    for block in &mut chunks {
+        compress(&mut state, block);
+    }
+
+    // Pad the trailing bytes out to one or two final blocks
+    let rest = chunks.remainder();
+    let mut tail = [0_u8; 128];
+    tail[..rest.len()].copy_from_slice(rest);
+    tail[rest.len()] = 0x80;
+    let tail_len = if rest.len() <= MAX_SINGLE_BLOCK_LEN { 64 } else { 128 };
+    let bit_len = (msg.len() as u64).wrapping_mul(8);
+    tail[tail_len - 8..tail_len].copy_from_slice(&bit_len.to_le_bytes());
+
+    for block in tail[..tail_len].chunks_exact(64) {
+        compress(&mut state, block);
+    }
+
+    state_to_digest(&state)
+}
+
+/// Computes the MD5 digests of each of the given messages.
+///
+/// Messages no longer than 55 bytes fit in a single block and are hashed
+/// together; longer messages fall back to [`md5`].
+pub fn md5_lanes(msgs: [&[u8]; LANES]) -> [Digest; LANES] {
+    if msgs.iter().any(|msg| msg.len() > MAX_SINGLE_BLOCK_LEN) {
+        let mut out = [[0; 16]; LANES];
+        for (digest, msg) in out.iter_mut().zip(msgs.iter()) {
+            *digest = md5(msg);
+        }
+        return out;
+    }
+
+    let mut blocks = [[0_u8; 64]; LANES];
+    for (block, msg) in blocks.iter_mut().zip(msgs.iter()) {
+        pad_single_block(block, msg);
+    }
+    compress_padded_lanes(&blocks)
+}
+
+/// Returns the lowercase hexadecimal representation of a digest.
+pub fn to_hex(digest: &Digest) -> String {
+    let mut hex = [0_u8; 32];
+    write_hex(digest, &mut hex);
+    String::from_utf8(hex.to_vec()).unwrap() // only contains hex digits
+}
+
+/// Writes the lowercase hexadecimal representation of a digest into `out`.
+fn write_hex(digest: &Digest, out: &mut [u8; 32]) {
+    for (pair, &byte) in out.chunks_exact_mut(2).zip(digest.iter()) {
+        pair[0] = HEX_DIGITS[usize::from(byte >> 4)];
+        pair[1] = HEX_DIGITS[usize::from(byte & 0xf)];
+    }
+}
+
+/// Copies a message of at most 55 bytes into a block with MD5 padding.
+fn pad_single_block(block: &mut [u8; 64], msg: &[u8]) {
+    debug_assert!(msg.len() <= MAX_SINGLE_BLOCK_LEN);
+    *block = [0; 64];
+    block[..msg.len()].copy_from_slice(msg);
+    block[msg.len()] = 0x80;
+    block[56..].copy_from_slice(&(msg.len() as u64 * 8).to_le_bytes());
+}
+
+/// Runs the compression function over one block per lane, starting from the
+/// initial MD5 state, and returns each lane's digest.
+fn compress_padded_lanes(blocks: &[[u8; 64]; LANES]) -> [Digest; LANES] {
+    // Transpose the blocks so that each message word holds one value per lane
+    let mut words = [[0_u32; LANES]; 16];
+    for (lane, block) in blocks.iter().enumerate() {
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            word[lane] = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+    }
+
+    let mut a = [INIT_STATE[0]; LANES];
+    let mut b = [INIT_STATE[1]; LANES];
+    let mut c = [INIT_STATE[2]; LANES];
+    let mut d = [INIT_STATE[3]; LANES];
+
+    for step in 0..64 {
+        let m = &words[word_index(step)];
+        let (k, shift) = (K[step], SHIFTS[step]);
+        let round = step / 16;
+        for lane in 0..LANES {
+            let (x, y, z) = (b[lane], c[lane], d[lane]);
+            let f = match round {
+                0 => (x & y) | (!x & z),
+                1 => (x & z) | (y & !z),
+                2 => x ^ y ^ z,
+                _ => y ^ (x | !z),
+            };
+            let sum = f.wrapping_add(a[lane]).wrapping_add(k).wrapping_add(m[lane]);
+            a[lane] = z;
+            d[lane] = y;
+            c[lane] = x;
+            b[lane] = x.wrapping_add(sum.rotate_left(shift));
+        }
+    }
+
+    let mut out = [[0; 16]; LANES];
+    for (lane, digest) in out.iter_mut().enumerate() {
+        *digest = state_to_digest(&[
+            a[lane].wrapping_add(INIT_STATE[0]),
+            b[lane].wrapping_add(INIT_STATE[1]),
+            c[lane].wrapping_add(INIT_STATE[2]),
+            d[lane].wrapping_add(INIT_STATE[3]),
+        ]);
+    }
+    out
+}
+
+/// Runs the compression function over a single 64-byte block.
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut words = [0_u32; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    for step in 0..64 {
+        let f = match step / 16 {
+            0 => (b & c) | (!b & d),
+            1 => (b & d) | (c & !d),
+            2 => b ^ c ^ d,
+            _ => c ^ (b | !d),
+        };
+        let sum = f
+            .wrapping_add(a)
+            .wrapping_add(K[step])
+            .wrapping_add(words[word_index(step)]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(sum.rotate_left(SHIFTS[step]));
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d].iter()) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+/// Serializes an MD5 state into a digest.
+fn state_to_digest(state: &[u32; 4]) -> Digest {
+    let mut digest = [0; 16];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+/// Returns an iterator over the digests of the messages formed by appending
+/// the decimal representation of `0, 1, 2, ...` to `seed`.
+pub fn counter_hashes(seed: &[u8]) -> CounterHashes<'_> {
+    CounterHashes {
+        seed,
+        next_index: 0,
+        stretch: 0,
+        buf: [[0; 16]; LANES],
+        buf_start: 0,
+        buf_pos: LANES,
+    }
+}
+
+/// An iterator over the digests of a seed followed by an incrementing
+/// counter.
+///
+/// Digests are computed [`LANES`] at a time and yielded as a tuple
+/// containing 1) the counter value and 2) its digest.
+pub struct CounterHashes<'a> {
+    seed: &'a [u8],
+    /// The counter value of the next batch to be hashed.
+    next_index: u64,
+    /// The number of times each digest is re-hashed.
+    stretch: u32,
+    /// The digests from the most recently hashed batch.
+    buf: [Digest; LANES],
+    /// The counter value of the first digest in `buf`.
+    buf_start: u64,
+    /// The position of the next digest to yield from `buf`.
+    buf_pos: usize,
+}
+
+impl<'a> CounterHashes<'a> {
+    /// Skips ahead so that the first digest yielded is for the given counter.
+    pub fn starting_at(mut self, index: u64) -> Self {
+        self.next_index = index;
+        self.buf_pos = LANES;
+        self
+    }
+
+    /// Re-hashes each digest `rounds` additional times, where each round
+    /// hashes the lowercase hexadecimal representation of the prior digest.
+    pub fn stretched(mut self, rounds: u32) -> Self {
+        self.stretch = rounds;
+        self.buf_pos = LANES;
+        self
+    }
+
+    /// Hashes the next batch of counter values into this iterator's buffer.
+    fn fill_buf(&mut self) {
+        let mut blocks = [[0_u8; 64]; LANES];
+
+        if self.seed.len() + 20 <= MAX_SINGLE_BLOCK_LEN {
+            let mut msg = Vec::with_capacity(MAX_SINGLE_BLOCK_LEN);
+            for (offset, block) in blocks.iter_mut().enumerate() {
+                msg.clear();
+                msg.extend_from_slice(self.seed);
+                push_decimal(&mut msg, self.next_index + offset as u64);
+                pad_single_block(block, &msg);
+            }
+            self.buf = compress_padded_lanes(&blocks);
+        } else {
+            // The counter may not fit in the same block as the seed, so fall
+            // back to hashing each message on its own.
+            for (offset, digest) in self.buf.iter_mut().enumerate() {
+                let mut msg = self.seed.to_vec();
+                push_decimal(&mut msg, self.next_index + offset as u64);
+                *digest = md5(&msg);
+            }
+        }
+
+        for _ in 0..self.stretch {
+            let mut hex = [0_u8; 32];
+            for (block, digest) in blocks.iter_mut().zip(self.buf.iter()) {
+                write_hex(digest, &mut hex);
+                pad_single_block(block, &hex);
+            }
+            self.buf = compress_padded_lanes(&blocks);
+        }
+
+        self.buf_start = self.next_index;
+        self.buf_pos = 0;
+        self.next_index += LANES as u64;
+    }
+}
+
+impl Iterator for CounterHashes<'_> {
+    type Item = (u64, Digest);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf_pos == LANES {
+            self.fill_buf();
+        }
+        let item = (self.buf_start + self.buf_pos as u64, self.buf[self.buf_pos]);
+        self.buf_pos += 1;
+        Some(item)
+    }
+}
+
+/// Appends the decimal representation of `n` to `buf`.
+fn push_decimal(buf: &mut Vec<u8>, mut n: u64) {
+    let mut digits = [0_u8; 20];
+    let mut pos = digits.len();
+    loop {
+        pos -= 1;
+        digits[pos] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    buf.extend_from_slice(&digits[pos..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_digests() {
+        let test_cases: &[(&[u8], &str)] = &[
+            (b"", "d41d8cd98f00b204e9800998ecf8427e"),
+            (b"abc", "900150983cd24fb0d6963f7d28e17f72"),
+            (b"message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+
+        for &(msg, expected) in test_cases {
+            assert_eq!(expected, to_hex(&md5(msg)));
+        }
+    }
+
+    #[test]
+    fn padding_boundaries() {
+        // Messages around the single-block limit and the block size
+        // exercise each padding path.
+        let data = [b'x'; 130];
+        for len in &[54, 55, 56, 63, 64, 65, 119, 120, 128] {
+            let msg = &data[..*len];
+            let mut lanes = [&msg[..0]; LANES];
+            lanes[3] = msg;
+            assert_eq!(md5(msg), md5_lanes(lanes)[3], "length {}", len);
+        }
+    }
+
+    #[test]
+    fn lanes_match_scalar() {
+        let msgs: [&[u8]; LANES] = [b"", b"a", b"abc", b"abcdef609043", b"pqrstuv1048970", b"x", b"yz", b"0"];
+        let digests = md5_lanes(msgs);
+        for (msg, digest) in msgs.iter().zip(digests.iter()) {
+            assert_eq!(md5(msg), *digest);
+        }
+    }
+
+    #[test]
+    fn counter_hashes_match_scalar() {
+        // Counter lengths change within a batch at 10 and 100
+        for (index, digest) in counter_hashes(b"abc").starting_at(5).take(100) {
+            assert_eq!(md5(format!("abc{}", index).as_bytes()), digest);
+        }
+    }
+
+    #[test]
+    fn counter_hashes_long_seed() {
+        let seed = [b'q'; 50];
+        for (index, digest) in counter_hashes(&seed).starting_at(99_990).take(20) {
+            let mut msg = seed.to_vec();
+            msg.extend(index.to_string().bytes());
+            assert_eq!(md5(&msg), digest);
+        }
+    }
+
+    #[test]
+    fn stretched_hashes() {
+        let (index, digest) = counter_hashes(b"abc").stretched(2016).next().unwrap();
+        assert_eq!(0, index);
+        assert_eq!("a107ff634856bb300138cac6568c0f24", to_hex(&digest));
+    }
+}
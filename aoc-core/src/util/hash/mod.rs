@@ -0,0 +1,8 @@
+//! Hash functions used by puzzle solutions, most of which search for digests
+//! matching some criteria.
+
+pub use self::knot::{dense_hash, knot, sparse_hash};
+pub use self::md5::{counter_hashes, md5, md5_lanes, to_hex, CounterHashes, Digest, LANES};
+
+mod knot;
+mod md5;
@@ -0,0 +1,127 @@
+//! Keypads of arbitrary shape that can be navigated one key at a time.
+
+use super::Pt;
+use std::str::FromStr;
+
+/// Character marking a position in a keypad layout that has no key.
+const HOLE: char = 'x';
+
+/// A keypad with keys laid out on a grid.
+///
+/// Keys are addressed by points whose `x` component is the key's column
+/// and whose `y` component is the negation of the key's row, so that
+/// moving `Pt::n()` moves "up" the keypad.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Keypad {
+    rows: Vec<Vec<Option<char>>>,
+}
+
+impl Keypad {
+    /// Builds a keypad from a layout string.
+    ///
+    /// Each line of the layout describes one row of keys. Whitespace is
+    /// ignored, and `'x'` marks a position without a key. Rows need not
+    /// have the same length; positions past the end of a row are holes.
+    ///
+    /// An error is returned if the layout contains no keys.
+    ///
+    /// # Example
+    /// ```
+    /// # use aoc_core::util::{Keypad, Pt};
+    /// let keypad = Keypad::from_layout("x1x\n234").unwrap();
+    /// let two = keypad.position_of('2').unwrap();
+    /// assert_eq!(Some('1'), keypad.key_at(keypad.step(two, Pt { x: 1, y: 1 })));
+    /// // Moving into a hole leaves the position unchanged
+    /// assert_eq!(two, keypad.step(two, Pt::n()));
+    /// ```
+    pub fn from_layout(layout: &str) -> Result<Self, &'static str> {
+        let rows: Vec<Vec<Option<char>>> = layout
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(|c| if c == HOLE { None } else { Some(c) })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+
+        if rows.iter().flatten().all(Option::is_none) {
+            return Err("keypad layout contains no keys");
+        }
+        Ok(Keypad { rows })
+    }
+
+    /// Returns the key at the specified position, or `None` if there is
+    /// no key there.
+    pub fn key_at(&self, pos: Pt<i32>) -> Option<char> {
+        if pos.x < 0 || pos.y > 0 {
+            return None;
+        }
+        self.rows
+            .get(-pos.y as usize)
+            .and_then(|row| row.get(pos.x as usize))
+            .and_then(|&key| key)
+    }
+
+    /// Returns the position of the specified key, or `None` if the key is
+    /// not on this keypad.
+    pub fn position_of(&self, key: char) -> Option<Pt<i32>> {
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            keys.iter()
+                .position(|&k| k == Some(key))
+                .map(|col| Pt { x: col as i32, y: -(row as i32) })
+        })
+    }
+
+    /// Returns the position reached by moving from `pos` by `dir`.
+    ///
+    /// If there is no key at the destination, `pos` is returned.
+    pub fn step(&self, pos: Pt<i32>, dir: Pt<i32>) -> Pt<i32> {
+        let next = pos + dir;
+        if self.key_at(next).is_some() {
+            next
+        } else {
+            pos
+        }
+    }
+}
+
+impl FromStr for Keypad {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Keypad::from_layout(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_layout() {
+        let keypad = Keypad::from_layout("  1 2 3 4\n  5\n\n  x x 6").unwrap();
+
+        assert_eq!(Some('4'), keypad.key_at(Pt { x: 3, y: 0 }));
+        assert_eq!(Some('5'), keypad.key_at(Pt { x: 0, y: -1 }));
+        assert_eq!(Some('6'), keypad.key_at(Pt { x: 2, y: -2 }));
+        assert_eq!(None, keypad.key_at(Pt { x: 1, y: -1 }));
+        assert_eq!(None, keypad.key_at(Pt { x: 0, y: -2 }));
+        assert_eq!(None, keypad.key_at(Pt { x: 0, y: 1 }));
+        assert_eq!(None, keypad.key_at(Pt { x: -1, y: 0 }));
+
+        let five = keypad.position_of('5').unwrap();
+        assert_eq!(Pt { x: 0, y: -1 }, five);
+        assert_eq!(Pt { x: 0, y: 0 }, keypad.step(five, Pt::n()));
+        assert_eq!(five, keypad.step(five, Pt::e()));
+        assert_eq!(five, keypad.step(five, Pt::s()));
+        assert_eq!(None, keypad.position_of('7'));
+    }
+
+    #[test]
+    fn reject_empty_layout() {
+        assert!(Keypad::from_layout("").is_err());
+        assert!("x x\nx".parse::<Keypad>().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Number-theoretic helpers.
+
+use std::{error, fmt};
+
+/// Returns a tuple containing 1) the greatest common divisor of `a` and `b`
+/// and 2, 3) Bézout coefficients `x` and `y` such that `a*x + b*y == gcd`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (gcd, x, y) = ext_gcd(b, a % b);
+        (gcd, y, x - (a / b) * y)
+    }
+}
+
+/// Solves a system of congruences of the form `x ≡ residue (mod modulus)`
+/// using the Chinese Remainder Theorem.
+///
+/// Returns a tuple containing 1) the least non-negative solution and 2) the
+/// modulus of the combined congruence (i.e. the least common multiple of all
+/// of the moduli). The moduli need not be pairwise coprime.
+///
+/// # Errors
+/// Returns a [`CrtError`] if any modulus is zero, if the system has no
+/// solution, or if the combined modulus does not fit in a `u64`.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::crt;
+/// // x ≡ 2 (mod 3), x ≡ 3 (mod 5), x ≡ 2 (mod 7)
+/// assert_eq!(Ok((23, 105)), crt(vec![(2, 3), (3, 5), (2, 7)]));
+/// ```
+pub fn crt<I>(congruences: I) -> Result<(u64, u64), CrtError>
+where
+    I: IntoIterator<Item = (u64, u64)>,
+{
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for (r, m) in congruences {
+        if m == 0 {
+            return Err(CrtError::ZeroModulus);
+        }
+        let (r, m) = (i128::from(r % m), i128::from(m));
+
+        let (gcd, p, _) = ext_gcd(modulus, m);
+        if (r - residue) % gcd != 0 {
+            return Err(CrtError::NoSolution);
+        }
+
+        // Step from the current solution in increments of the current
+        // modulus until the new congruence is also satisfied.
+        let step_mod = m / gcd;
+        let steps = mul_mod((r - residue) / gcd, p, step_mod);
+
+        let combined = modulus
+            .checked_mul(step_mod)
+            .filter(|&c| c <= i128::from(u64::MAX))
+            .ok_or(CrtError::Overflow)?;
+        residue += modulus * steps;
+        modulus = combined;
+    }
+
+    Ok((residue as u64, modulus as u64))
+}
+
+/// Reasons that a system of congruences could not be solved by [`crt`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CrtError {
+    /// One of the congruences had a modulus of zero.
+    ZeroModulus,
+    /// The congruences are inconsistent, so no solution exists.
+    NoSolution,
+    /// A solution may exist, but the combined modulus does not fit in a
+    /// `u64`.
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CrtError::ZeroModulus => "congruence has a modulus of zero",
+            CrtError::NoSolution => "congruences have no common solution",
+            CrtError::Overflow => "combined modulus does not fit in a u64",
+        })
+    }
+}
+
+impl error::Error for CrtError {}
+
+/// Computes `a * b mod m` for a modulus that fits in a `u64` without
+/// overflowing the intermediate product.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let (a, b) = (a.rem_euclid(m) as u128, b.rem_euclid(m) as u128);
+    (a * b % m as u128) as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_coefficients() {
+        for &(a, b, expected_gcd) in &[(240, 46, 2), (17, 5, 1), (12, 18, 6), (7, 0, 7)] {
+            let (gcd, x, y) = ext_gcd(a, b);
+            assert_eq!(expected_gcd, gcd);
+            assert_eq!(gcd, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn solve_coprime_system() {
+        assert_eq!(Ok((23, 105)), crt(vec![(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Ok((0, 1)), crt(vec![]));
+        assert_eq!(Ok((4, 5)), crt(vec![(9, 5)]));
+    }
+
+    #[test]
+    fn solve_non_coprime_system() {
+        // x ≡ 2 (mod 4), x ≡ 4 (mod 6)
+        assert_eq!(Ok((10, 12)), crt(vec![(2, 4), (4, 6)]));
+        // x ≡ 1 (mod 4), x ≡ 2 (mod 6) has no solution
+        assert_eq!(Err(CrtError::NoSolution), crt(vec![(1, 4), (2, 6)]));
+    }
+
+    #[test]
+    fn reject_degenerate_systems() {
+        assert_eq!(Err(CrtError::ZeroModulus), crt(vec![(0, 0)]));
+        assert_eq!(
+            Err(CrtError::Overflow),
+            crt(vec![(0, u64::MAX), (0, u64::MAX - 1)])
+        );
+    }
+
+    #[test]
+    fn solve_large_moduli() {
+        let (residue, modulus) = crt(vec![
+            (123_456, 1_000_000_007),
+            (654_321, 998_244_353),
+        ])
+        .unwrap();
+        assert_eq!(1_000_000_007 * 998_244_353, modulus);
+        assert_eq!(123_456, residue % 1_000_000_007);
+        assert_eq!(654_321, residue % 998_244_353);
+    }
+}
@@ -0,0 +1,32 @@
+//! Functions and structures with common application in puzzle
+//! solutions.
+
+pub use self::disjoint_set::DisjointSet;
+pub use self::keypad::Keypad;
+pub use self::math::{crt, ext_gcd, CrtError};
+pub use self::parse::extract_ints;
+pub use self::permute::{permutations, Permutations};
+pub use self::point::{
+    grid::{Grid, GridIter},
+    IntoPoint, Pt, Pt3,
+};
+pub use self::range_set::RangeSet;
+pub use self::rotate::RotateSigned;
+
+pub type BTGrid<T, U> = ::std::collections::BTreeMap<Pt<T>, U>;
+pub type HGrid<T, U> = ::std::collections::HashMap<Pt<T>, U>;
+
+pub mod date;
+pub mod geom;
+pub mod hash;
+pub mod ocr;
+pub mod search;
+
+mod disjoint_set;
+mod keypad;
+mod math;
+mod parse;
+mod permute;
+mod point;
+mod range_set;
+mod rotate;
@@ -0,0 +1,166 @@
+//! Recognition of the block letters that some puzzles spell out with lit
+//! pixels.
+//!
+//! Renders are expected to be rows of `#` (lit) and any other character
+//! (unlit), one row per line, as produced by the puzzles' `Display`
+//! implementations.
+
+/// Height of the large glyphs, in pixels.
+const LARGE_HEIGHT: usize = 10;
+
+/// Width of the large glyphs, in pixels.
+const LARGE_WIDTH: usize = 6;
+
+/// Number of blank columns between adjacent large glyphs.
+const LARGE_SPACING: usize = 2;
+
+/// The known large glyphs, each encoded as the concatenation of its rows.
+const LARGE_GLYPHS: &[(char, &str)] = &[
+    (
+        'A',
+        "..##...#..#.#....##....##....########....##....##....##....#",
+    ),
+    (
+        'B',
+        "#####.#....##....##....######.#....##....##....##....######.",
+    ),
+    (
+        'C',
+        ".####.#....##.....#.....#.....#.....#.....#.....#....#.####.",
+    ),
+    (
+        'E',
+        "#######.....#.....#.....#####.#.....#.....#.....#.....######",
+    ),
+    (
+        'F',
+        "#######.....#.....#.....#####.#.....#.....#.....#.....#.....",
+    ),
+    (
+        'G',
+        ".####.#....##.....#.....#.....#..####....##....##...##.###.#",
+    ),
+    (
+        'H',
+        "#....##....##....##....########....##....##....##....##....#",
+    ),
+    (
+        'J',
+        "...###....#.....#.....#.....#.....#.....#.#...#.#...#..###..",
+    ),
+    (
+        'K',
+        "#....##...#.#..#..#.#...##....##....#.#...#..#..#...#.#....#",
+    ),
+    (
+        'L',
+        "#.....#.....#.....#.....#.....#.....#.....#.....#.....######",
+    ),
+    (
+        'N',
+        "#....###...###...##.#..##.#..##..#.##..#.##...###...###....#",
+    ),
+    (
+        'P',
+        "#####.#....##....##....######.#.....#.....#.....#.....#.....",
+    ),
+    (
+        'R',
+        "#####.#....##....##....######.#..#..#...#.#...#.#....##....#",
+    ),
+    (
+        'X',
+        "#....##....#.#..#..#..#...##....##...#..#..#..#.#....##....#",
+    ),
+    (
+        'Z',
+        "######.....#.....#....#....#....#....#....#.....#.....######",
+    ),
+];
+
+/// Decodes the message spelled out by a render of large (10 pixel tall)
+/// glyphs.
+///
+/// The render must be cropped to the lit pixels, as is the case when it is
+/// produced from a grid built around those pixels.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::ocr;
+/// let render = [
+///     "..##....######",
+///     ".#..#...#.....",
+///     "#....#..#.....",
+///     "#....#..#.....",
+///     "#....#..#####.",
+///     "######..#.....",
+///     "#....#..#.....",
+///     "#....#..#.....",
+///     "#....#..#.....",
+///     "#....#..######",
+/// ]
+/// .join("\n");
+/// assert_eq!(Ok("AE".to_owned()), ocr::decode_large(&render));
+/// ```
+pub fn decode_large(render: &str) -> Result<String, &'static str> {
+    let rows: Vec<&[u8]> = render
+        .lines()
+        .map(|line| line.trim().as_bytes())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if rows.len() != LARGE_HEIGHT {
+        return Err("render does not have the height of a large glyph");
+    }
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    if !(width + LARGE_SPACING).is_multiple_of(LARGE_WIDTH + LARGE_SPACING) {
+        return Err("render width is not a whole number of large glyphs");
+    }
+
+    (0..width)
+        .step_by(LARGE_WIDTH + LARGE_SPACING)
+        .map(|left| {
+            let glyph: String = rows
+                .iter()
+                .flat_map(|row| (left..left + LARGE_WIDTH).map(move |col| row.get(col)))
+                .map(|px| if px == Some(&b'#') { '#' } else { '.' })
+                .collect();
+            LARGE_GLYPHS
+                .iter()
+                .find(|&&(_, pixels)| pixels == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or("render contains an unrecognized glyph")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_well_formed() {
+        for &(letter, pixels) in LARGE_GLYPHS {
+            assert_eq!(LARGE_WIDTH * LARGE_HEIGHT, pixels.len(), "glyph {}", letter);
+        }
+    }
+
+    #[test]
+    fn decode_each_glyph() {
+        for &(letter, pixels) in LARGE_GLYPHS {
+            let render: String = pixels
+                .as_bytes()
+                .chunks(LARGE_WIDTH)
+                .map(|row| format!("{}\n", String::from_utf8_lossy(row)))
+                .collect();
+            assert_eq!(Ok(letter.to_string()), decode_large(&render));
+        }
+    }
+
+    #[test]
+    fn reject_bad_renders() {
+        assert!(decode_large("#####\n#...#\n").is_err());
+        assert!(decode_large(&"#######\n".repeat(LARGE_HEIGHT)).is_err());
+        assert!(decode_large(&"######\n".repeat(LARGE_HEIGHT)).is_err());
+    }
+}
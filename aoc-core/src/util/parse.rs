@@ -0,0 +1,71 @@
+//! Helpers for pulling values out of loosely-formatted puzzle input.
+
+use std::str::FromStr;
+
+/// Returns an iterator over the integers embedded in `s`, in order.
+///
+/// An integer is a maximal run of ASCII digits, optionally preceded by a
+/// `-` sign. The sign is only included when it does not directly follow an
+/// alphanumeric character, so that ranges such as `5-8` yield `5` and `8`.
+/// Integers that cannot be represented by `T` (e.g. negative integers when
+/// `T` is unsigned, or integers that overflow `T`) are skipped.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::extract_ints;
+/// let ints: Vec<i32> = extract_ints("pos=<-3, 11> range 5-8").collect();
+/// assert_eq!(vec![-3, 11, 5, 8], ints);
+/// ```
+pub fn extract_ints<'a, T>(s: &'a str) -> impl Iterator<Item = T> + 'a
+where
+    T: FromStr + 'a,
+{
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            let start = pos;
+            if !bytes[pos].is_ascii_digit() {
+                pos += 1;
+                continue;
+            }
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+
+            let signed = start > 0
+                && bytes[start - 1] == b'-'
+                && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+            let token = &s[if signed { start - 1 } else { start }..pos];
+
+            if let Ok(value) = token.parse() {
+                return Some(value);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_signed() {
+        let ints: Vec<i64> = extract_ints("-1 x=-20, y=+3 a-4 -").collect();
+        assert_eq!(vec![-1, -20, 3, 4], ints);
+    }
+
+    #[test]
+    fn extract_unsigned() {
+        let ints: Vec<u8> = extract_ints("12 -3 300 4").collect();
+        assert_eq!(vec![12, 4], ints);
+    }
+
+    #[test]
+    fn extract_none() {
+        assert_eq!(None, extract_ints::<u32>("no numbers here").next());
+        assert_eq!(None, extract_ints::<u32>("").next());
+    }
+}
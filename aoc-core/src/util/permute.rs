@@ -0,0 +1,100 @@
+//! Iteration over the orderings of a collection.
+
+/// Returns an iterator over every permutation of `items` in lexicographic
+/// order of their positions, beginning with `items` itself.
+///
+/// A collection of `n` items has `n!` permutations, so this is only
+/// practical for small collections.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::permutations;
+/// let perms: Vec<String> = permutations(vec!['a', 'b', 'c'])
+///     .map(|p| p.into_iter().collect())
+///     .collect();
+/// assert_eq!(vec!["abc", "acb", "bac", "bca", "cab", "cba"], perms);
+/// ```
+pub fn permutations<T: Clone>(items: Vec<T>) -> Permutations<T> {
+    Permutations {
+        indices: (0..items.len()).collect(),
+        items,
+        done: false,
+    }
+}
+
+/// Iterator over the permutations of a collection.
+///
+/// This struct is created by [`permutations`].
+#[derive(Clone, Debug)]
+pub struct Permutations<T> {
+    items: Vec<T>,
+    /// Positions in `items` of the elements in the next permutation.
+    indices: Vec<usize>,
+    /// Whether the last permutation has been yielded.
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let perm = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        self.done = !next_permutation(&mut self.indices);
+        Some(perm)
+    }
+}
+
+/// Rearranges `seq` into its lexicographically next permutation.
+///
+/// Returns `false` if `seq` was its last permutation, in which case it is
+/// left unchanged.
+fn next_permutation<T: Ord>(seq: &mut [T]) -> bool {
+    // Find the rightmost element that is smaller than its successor
+    let pivot = match seq.windows(2).rposition(|w| w[0] < w[1]) {
+        Some(pivot) => pivot,
+        None => return false,
+    };
+    // Swap it with the rightmost element greater than it, then reverse
+    // the (descending) suffix after it
+    let successor = seq.iter().rposition(|e| *e > seq[pivot]).unwrap();
+    seq.swap(pivot, successor);
+    seq[pivot + 1..].reverse();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_counts() {
+        assert_eq!(1, permutations(Vec::<u8>::new()).count());
+        assert_eq!(1, permutations(vec![1]).count());
+        assert_eq!(120, permutations(vec![1, 2, 3, 4, 5]).count());
+    }
+
+    #[test]
+    fn repeated_items() {
+        // Items are permuted by position, so equal items are not merged
+        let perms: Vec<Vec<u8>> = permutations(vec![1, 1]).collect();
+        assert_eq!(vec![vec![1, 1], vec![1, 1]], perms);
+    }
+
+    #[test]
+    fn next_permutation_order() {
+        let mut seq = [1, 3, 2];
+        assert!(next_permutation(&mut seq));
+        assert_eq!([2, 1, 3], seq);
+
+        let mut seq = [3, 2, 1];
+        assert!(!next_permutation(&mut seq));
+        assert_eq!([3, 2, 1], seq);
+    }
+}
@@ -7,6 +7,9 @@ use num_traits as nt;
 use num_traits::{NumCast, Signed};
 
 pub mod grid;
+mod pt3;
+
+pub use self::pt3::Pt3;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 /// A cartesian point on a two-dimensional plane.
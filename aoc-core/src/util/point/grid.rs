@@ -92,7 +92,7 @@ where
     ///
     /// # Example
     /// ```
-    /// # use advent_of_rust::common::util::{Pt, Grid};
+    /// # use aoc_core::util::{Pt, Grid};
     /// let grid = Grid::from_interior_points(&[Pt::origin(), Pt::ne()]);
     /// let contained_points: Vec<Pt<i8>> = grid.iter().collect();
     ///
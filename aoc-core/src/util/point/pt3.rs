@@ -0,0 +1,100 @@
+//! Handling of three-dimensional points
+
+use std::ops;
+
+use num_traits::Signed;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+/// A cartesian point in three-dimensional space.
+pub struct Pt3<T>
+where
+    T: Signed + Copy,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Signed + Copy> Pt3<T> {
+    /// Builds a point at the given coordinates.
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Pt3 { x, y, z }
+    }
+
+    /// Builds the origin point.
+    pub fn origin() -> Self {
+        Pt3 {
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
+        }
+    }
+
+    /// Returns this point's manhattan distance from the specified point.
+    pub fn dist_manh(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl<T> From<(T, T, T)> for Pt3<T>
+where
+    T: Signed + Copy,
+{
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Pt3 { x, y, z }
+    }
+}
+
+impl<T> ops::Add<Pt3<T>> for Pt3<T>
+where
+    T: Signed + Copy,
+{
+    type Output = Pt3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Pt3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T> ops::Sub<Pt3<T>> for Pt3<T>
+where
+    T: Signed + Copy,
+{
+    type Output = Pt3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Pt3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manhattan_distance() {
+        let a: Pt3<i32> = Pt3::new(1, -2, 3);
+        let b = Pt3::new(-4, 5, 3);
+
+        assert_eq!(12, a.dist_manh(b));
+        assert_eq!(12, b.dist_manh(a));
+        assert_eq!(6, a.dist_manh(Pt3::origin()));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: Pt3<i32> = (1, 2, 3).into();
+        let b = Pt3::new(4, 5, 6);
+
+        assert_eq!(Pt3::new(5, 7, 9), a + b);
+        assert_eq!(Pt3::new(3, 3, 3), b - a);
+    }
+}
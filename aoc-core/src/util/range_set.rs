@@ -0,0 +1,186 @@
+//! Sets of integers stored as disjoint inclusive ranges.
+
+use std::iter::FromIterator;
+use std::ops::RangeInclusive;
+
+/// A set of `u64` values, stored as a sorted list of disjoint inclusive
+/// ranges.
+///
+/// Overlapping and adjacent ranges are merged as they are inserted.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::RangeSet;
+/// let set: RangeSet = vec![5..=8, 0..=2, 3..=4, 10..=10].into_iter().collect();
+/// assert_eq!(vec![0..=8, 10..=10], set.ranges().to_vec());
+/// assert_eq!(10, set.len());
+/// assert_eq!(vec![9..=9, 11..=12], set.gaps(0..=12).collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RangeSet {
+    ranges: Vec<RangeInclusive<u64>>,
+}
+
+impl RangeSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value in `range` to this set. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = range.into_inner();
+
+        // Ranges that overlap or are adjacent to the new range
+        let first = self
+            .ranges
+            .partition_point(|r| r.end().saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= end.saturating_add(1));
+
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, Some(start..=end));
+    }
+
+    /// Returns `true` if this set contains `value`.
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(index).is_some_and(|r| r.contains(&value))
+    }
+
+    /// Returns the disjoint ranges that make up this set in ascending order.
+    pub fn ranges(&self) -> &[RangeInclusive<u64>] {
+        &self.ranges
+    }
+
+    /// Returns the number of values in this set.
+    ///
+    /// The count saturates at `u64::MAX`, which can only be reached if the
+    /// set contains every `u64`.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|r| r.end() - r.start())
+            .fold(0, |acc: u64, len| acc.saturating_add(len).saturating_add(1))
+    }
+
+    /// Returns `true` if this set contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns an iterator over the ranges of values in `within` that are
+    /// *not* in this set, in ascending order.
+    pub fn gaps(
+        &self,
+        within: RangeInclusive<u64>,
+    ) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        let (low, high) = within.into_inner();
+        // The next value that may begin a gap, or `None` once `high` is passed
+        let mut next = if low <= high { Some(low) } else { None };
+        let mut ranges = self.ranges.iter();
+
+        std::iter::from_fn(move || loop {
+            let start = next?;
+            match ranges.next() {
+                Some(r) if *r.end() < start => continue,
+                Some(r) if *r.start() <= start => {
+                    next = r.end().checked_add(1).filter(|&n| n <= high);
+                }
+                Some(r) if *r.start() <= high => {
+                    next = r.end().checked_add(1).filter(|&n| n <= high);
+                    return Some(start..=r.start() - 1);
+                }
+                _ => {
+                    next = None;
+                    return Some(start..=high);
+                }
+            }
+        })
+    }
+}
+
+impl Extend<RangeInclusive<u64>> for RangeSet {
+    fn extend<I: IntoIterator<Item = RangeInclusive<u64>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_ranges() {
+        let mut set = RangeSet::new();
+        set.insert(10..=20);
+        set.insert(30..=40);
+        set.insert(50..=60);
+        assert_eq!(3, set.ranges().len());
+
+        // Bridges the first two ranges
+        set.insert(15..=35);
+        assert_eq!(&[10..=40, 50..=60], set.ranges());
+
+        // Adjacent on both sides
+        set.insert(41..=49);
+        assert_eq!(&[10..=60], set.ranges());
+
+        // Contained ranges and empty ranges change nothing
+        set.insert(12..=13);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(5..=4);
+        assert_eq!(&[10..=60], set.ranges());
+
+        set.insert(0..=0);
+        assert_eq!(&[0..=0, 10..=60], set.ranges());
+        assert_eq!(52, set.len());
+    }
+
+    #[test]
+    fn membership() {
+        let set: RangeSet = vec![2..=4, 8..=8].into_iter().collect();
+        let members: Vec<u64> = (0..10).filter(|&n| set.contains(n)).collect();
+        assert_eq!(vec![2, 3, 4, 8], members);
+        assert!(RangeSet::new().is_empty());
+    }
+
+    #[test]
+    fn gaps() {
+        let set: RangeSet = vec![5..=8, 0..=2, 4..=7].into_iter().collect();
+        assert_eq!(vec![3..=3, 9..=20], set.gaps(0..=20).collect::<Vec<_>>());
+        assert_eq!(vec![3..=3], set.gaps(2..=6).collect::<Vec<_>>());
+        assert_eq!(0, set.gaps(4..=8).count());
+        assert_eq!(vec![10..=12], set.gaps(10..=12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn extreme_values() {
+        let set: RangeSet = vec![0..=u64::MAX].into_iter().collect();
+        assert_eq!(u64::MAX, set.len());
+        assert_eq!(0, set.gaps(0..=u64::MAX).count());
+
+        let set: RangeSet = vec![u64::MAX - 1..=u64::MAX].into_iter().collect();
+        assert_eq!(
+            vec![0..=u64::MAX - 2],
+            set.gaps(0..=u64::MAX).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Generic graph search over implicitly defined state spaces.
+
+use num_traits::Zero;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Returns an iterator that visits every state reachable from `start` in
+/// breadth-first order.
+///
+/// Each state is yielded once, along with the minimum number of steps
+/// required to reach it from `start`. Since states are visited in order of
+/// increasing distance, searches may be ended early with iterator adapters
+/// such as `find` or `take_while`.
+///
+/// Solutions may prune equivalent states by having `neighbors` return
+/// states in some canonical form.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::search::breadth_first;
+/// // Reach 10 from 1 by doubling or incrementing
+/// let (_, steps) = breadth_first(1, |&n| vec![n * 2, n + 1])
+///     .find(|&(n, _)| n == 10)
+///     .unwrap();
+/// assert_eq!(4, steps);
+/// ```
+pub fn breadth_first<S, F, I>(start: S, neighbors: F) -> BreadthFirst<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    seen.insert(start.clone());
+    let mut frontier = VecDeque::new();
+    frontier.push_back((start, 0));
+
+    BreadthFirst {
+        neighbors,
+        seen,
+        frontier,
+    }
+}
+
+/// Iterator over the states visited during a breadth-first search.
+///
+/// This struct is created by [`breadth_first`].
+pub struct BreadthFirst<S, F> {
+    neighbors: F,
+    /// States that have been discovered, including those in the frontier.
+    seen: HashSet<S>,
+    /// Discovered states that have not yet been visited, along with their
+    /// distances from the start.
+    frontier: VecDeque<(S, usize)>,
+}
+
+impl<S, F, I> Iterator for BreadthFirst<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (state, dist) = self.frontier.pop_front()?;
+        for next in (self.neighbors)(&state) {
+            if !self.seen.contains(&next) {
+                self.seen.insert(next.clone());
+                self.frontier.push_back((next, dist + 1));
+            }
+        }
+        Some((state, dist))
+    }
+}
+
+/// Returns the cheapest goal state reachable from `start`, along with the
+/// total cost of reaching it, using Dijkstra's algorithm.
+///
+/// `neighbors` returns the states reachable from a state in one step along
+/// with the cost of that step. Step costs must not be negative.
+///
+/// `None` is returned if no goal state is reachable.
+///
+/// # Example
+/// ```
+/// # use aoc_core::util::search::dijkstra;
+/// // Reach 10 from 1 by doubling for 3 or incrementing for 1
+/// let (_, cost) = dijkstra(1, |&n| vec![(n * 2, 3), (n + 1, 1)], |&n| n == 10).unwrap();
+/// assert_eq!(7, cost);
+/// ```
+pub fn dijkstra<S, C, F, I, G>(start: S, mut neighbors: F, mut is_goal: G) -> Option<(S, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    // States are stored separately from the queue so that they need not be
+    // ordered themselves
+    let mut states = vec![start.clone()];
+    let mut best: HashMap<S, C> = HashMap::new();
+    best.insert(start, C::zero());
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((C::zero(), 0)));
+
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        let state = &states[index];
+        if best.get(state).is_some_and(|&known| known < cost) {
+            // A cheaper path to this state has already been visited
+            continue;
+        }
+        if is_goal(state) {
+            return Some((states.swap_remove(index), cost));
+        }
+        for (next, step) in neighbors(state) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&known| next_cost < known) {
+                best.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost, states.len())));
+                states.push(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visits_in_distance_order() {
+        // Binary tree over the integers 1..16, with edges back to the parent
+        let visited: Vec<(u32, usize)> = breadth_first(1, |&n| {
+            let children = if n < 8 {
+                vec![2 * n, 2 * n + 1]
+            } else {
+                vec![]
+            };
+            children.into_iter().chain(Some(n / 2).filter(|&p| p > 0))
+        })
+        .collect();
+
+        assert_eq!(15, visited.len());
+        assert_eq!((1, 0), visited[0]);
+        assert!(visited.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(Some(&(13, 3)), visited.iter().find(|&&(n, _)| n == 13));
+    }
+
+    #[test]
+    fn cheapest_path() {
+        // Two routes from 'a' to 'd': a-b-d costs 2 + 5, a-c-d costs 4 + 1
+        let edges = |&node: &char| match node {
+            'a' => vec![('b', 2), ('c', 4)],
+            'b' => vec![('d', 5)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+        assert_eq!(Some(('d', 5)), dijkstra('a', edges, |&n| n == 'd'));
+        assert_eq!(Some(('a', 0)), dijkstra('a', edges, |&n| n == 'a'));
+        assert_eq!(None, dijkstra('a', edges, |&n| n == 'e'));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert!(
+            breadth_first(0, |&n: &u8| if n < 5 { vec![n + 1] } else { vec![] })
+                .find(|&(n, _)| n == 6)
+                .is_none()
+        );
+    }
+}
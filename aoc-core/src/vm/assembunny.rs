@@ -0,0 +1,491 @@
+//! An interpreter for assembunny, the assembly language introduced in 2016
+//! Day 12 and extended in 2016 Days 23 and 25.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::ops::{Index, IndexMut};
+
+pub use self::token::*;
+
+mod token {
+    use std::fmt;
+    use std::str::FromStr;
+    use std::num::ParseIntError;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    /// A register key from an instruction
+    pub struct RegisterKey(u8);
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    /// A integral literal from an instruction
+    pub struct Literal(i16);
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    /// A value token from an instruction; either a register key or a literal.
+    pub enum ValueToken {
+        Register(RegisterKey),
+        Literal(Literal),
+    }
+
+    impl RegisterKey {
+        #[inline]
+        pub fn key(self) -> u8 { self.0 }
+    }
+
+    impl From<u8> for RegisterKey {
+        fn from(key: u8) -> Self { RegisterKey(key) }
+    }
+
+    impl FromStr for RegisterKey {
+        type Err = &'static str;
+
+        fn from_str(token: &str) -> Result<Self, Self::Err> {
+            if token.len() != 1 {
+                Err("register key must be composed of a single character")
+            } else if let key @ b'a' ..= b'd' = *token.as_bytes().first().unwrap() {
+                Ok(RegisterKey(key))
+            } else { Err("register key must be (a | b | c | d)") }
+        }
+    }
+
+    impl Literal {
+        #[inline]
+        pub fn value(self) -> i16 { self.0 }
+    }
+
+    impl FromStr for Literal {
+        type Err = ParseIntError;
+
+        fn from_str(token: &str) -> Result<Self, Self::Err> {
+            Ok(Literal(token.parse()?))
+        }
+    }
+
+
+    impl fmt::Display for RegisterKey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0 as char)
+        }
+    }
+
+    impl fmt::Display for Literal {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl fmt::Display for ValueToken {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match *self {
+                ValueToken::Register(key) => key.fmt(f),
+                ValueToken::Literal(lit) => lit.fmt(f),
+            }
+        }
+    }
+
+    impl FromStr for ValueToken {
+        type Err = &'static str;
+
+        fn from_str(token: &str) -> Result<Self, Self::Err> {
+            match token.parse::<Literal>() {
+                Ok(lit) => Ok(ValueToken::Literal(lit)),
+                Err(_) => token.parse()
+                    .map(ValueToken::Register),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// An assembly-esk assembunny instruction
+pub enum Instr {
+    Copy(ValueToken, RegisterKey),
+    Jnz(ValueToken, ValueToken),
+    Inc(RegisterKey),
+    Dec(RegisterKey),
+}
+
+/// Value held by a register
+pub type Register = i32;
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+/// Set of registers used by an assembunny interpreter
+pub struct MiniRegisterTable {
+    a: Register,
+    b: Register,
+    c: Register,
+    d: Register,
+}
+
+#[derive(Debug)]
+/// Interpreter for assembunny instructions.
+pub struct Interpreter<'a> {
+    prog: &'a [Instr],
+    pos: usize,
+    reg: MiniRegisterTable,
+}
+
+impl FromStr for Instr {
+    type Err = &'static str;
+
+    fn from_str(instr: &str) -> Result<Self, Self::Err> {
+        let mut tokens = instr.split_whitespace();
+        let assem = tokens.next().unwrap_or_default();
+
+        let args: Vec<&str> = tokens.collect();
+
+        match args.len() {
+            1 => match assem {
+                "inc" => Ok(Instr::Inc(args[0].parse()?)),
+                "dec" => Ok(Instr::Dec(args[0].parse()?)),
+                _ => Err("unknown instruction; expected inc/dec from arg count")
+            },
+            2 => match assem {
+                "cpy" => Ok(Instr::Copy(
+                    args[0].parse()?,
+                    args[1].parse()?,
+                )),
+                "jnz" => Ok(Instr::Jnz(
+                    args[0].parse()?,
+                    args[1].parse()
+                        .map_err(|_| "could not parse integer")?,
+                )),
+                _ => Err("unknown instruction; expected cpy/jnz from arg count")
+            },
+            _ => Err("unknown instruction")
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Instr::Copy(src, dest) => write!(f, "cpy {} {}", src, dest),
+            Instr::Jnz(cond, offset) => write!(f, "jnz {} {}", cond, offset),
+            Instr::Inc(reg) => write!(f, "inc {}", reg),
+            Instr::Dec(reg) => write!(f, "dec {}", reg),
+        }
+    }
+}
+
+/// Parses the lines of an assembunny program into instructions.
+///
+/// In addition to plain instructions, program sources may contain:
+///
+/// - blank lines, which are skipped;
+/// - comments beginning with `;` or `#` and extending to the end of the line;
+/// - label definitions of the form `name:`, either on their own line or
+///   preceding an instruction. Labels may be used in place of the offset of
+///   a `jnz` instruction, and are resolved to the offset of the instruction
+///   following their definition.
+///
+/// Label names must begin with a letter or underscore, may only contain
+/// alphanumeric characters and underscores, and may not be register keys.
+pub fn parse_program<T, S>(lines: &[S]) -> Result<Vec<T>, String>
+    where T: FromStr<Err=&'static str>,
+          S: AsRef<str>
+{
+    // Line numbers and text of the program's instructions
+    let mut instrs: Vec<(usize, &str)> = Vec::with_capacity(lines.len());
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let line_num = line_index + 1;
+        let mut code = line.as_ref();
+        if let Some(comment) = code.find([';', '#']) {
+            code = &code[..comment];
+        }
+        let mut code = code.trim();
+
+        while let Some((label, rest)) = code.split_once(':') {
+            let label = label.trim();
+            if !is_label_name(label) {
+                return Err(format!("line {}: invalid label name `{}`", line_num, label));
+            }
+            if labels.insert(label, instrs.len()).is_some() {
+                return Err(format!("line {}: duplicate label `{}`", line_num, label));
+            }
+            code = rest.trim();
+        }
+
+        if !code.is_empty() {
+            instrs.push((line_num, code));
+        }
+    }
+
+    instrs.iter().enumerate().map(|(index, &(line_num, code))| {
+        let resolved;
+        let mut code = code;
+        if let ["jnz", cond, target] = *code.split_whitespace().collect::<Vec<_>>() {
+            if let Some(&dest) = labels.get(target) {
+                resolved = format!("jnz {} {}", cond, dest as isize - index as isize);
+                code = &resolved;
+            } else if target.parse::<ValueToken>().is_err() {
+                return Err(format!("line {}: undefined label `{}`", line_num, target));
+            }
+        }
+        code.parse().map_err(|e| format!("line {}: {}", line_num, e))
+    }).collect()
+}
+
+/// Returns true if the specified string is a valid label name.
+fn is_label_name(name: &str) -> bool {
+    match name.as_bytes().first() {
+        Some(&c) if c.is_ascii_alphabetic() || c == b'_' => {
+            name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_')
+                && name.parse::<RegisterKey>().is_err()
+        }
+        _ => false
+    }
+}
+
+impl<'a> Index<&'a RegisterKey> for MiniRegisterTable {
+    type Output = i32;
+
+    fn index(&self, index: &'a RegisterKey) -> &Self::Output {
+        match index.key() {
+            b'a' => &self.a,
+            b'b' => &self.b,
+            b'c' => &self.c,
+            b'd' => &self.d,
+            _ => panic!("illegal register index: {:?}", index)
+        }
+    }
+}
+
+impl<'a> IndexMut<&'a RegisterKey> for MiniRegisterTable {
+    fn index_mut(&mut self, index: &'a RegisterKey) -> &mut Self::Output {
+        match index.key() {
+            b'a' => &mut self.a,
+            b'b' => &mut self.b,
+            b'c' => &mut self.c,
+            b'd' => &mut self.d,
+            _ => panic!("illegal register index: {:?}", index)
+        }
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(prog: &'a [Instr]) -> Self {
+        Interpreter {
+            prog,
+            pos: 0,
+            reg: MiniRegisterTable::default(),
+        }
+    }
+
+    /// Execute the instruction at this interpreters read position and
+    /// increments/decrement the position accordingly.
+    pub fn execute_next(&mut self) {
+        let mut step = 1_isize;
+
+        match self.prog[self.pos] {
+            Instr::Inc(ref reg) => self.reg[reg] += 1,
+            Instr::Dec(ref reg) => self.reg[reg] -= 1,
+            Instr::Copy(val, ref reg) => self.reg[reg] = self.token_value(val),
+            Instr::Jnz(cond, mag) => if self.token_value(cond) != 0 {
+                step = self.token_value(mag) as isize
+            }
+        }
+
+        self.pos = (self.pos as isize + step) as usize;
+    }
+
+    /// Returns true if this interpreter has finished executing its
+    /// instructions.
+    pub fn done(&self) -> bool {
+        self.pos >= self.prog.len()
+    }
+
+    /// Returns a read-only reference to this interpreters set of registers.
+    pub fn registers(&self) -> &MiniRegisterTable {
+        &self.reg
+    }
+
+    /// Returns a *mutable* reference to this interpreters set of registers.
+    pub fn registers_mut(&mut self) -> &mut MiniRegisterTable {
+        &mut self.reg
+    }
+
+    /// Returns the value associated with the specified value token.
+    ///
+    /// - If the token is a literal, its value is returned.
+    /// - If the token is a register key, the value its respective
+    ///   register is returned
+    fn token_value(&self, token: ValueToken) -> Register {
+        match token {
+            ValueToken::Literal(lit) => i32::from(lit.value()),
+            ValueToken::Register(ref key) => self.reg[key],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_literal() {
+        assert_eq!(6, "6".parse::<Literal>().unwrap().value());
+        assert_eq!(-16, "-16".parse::<Literal>().unwrap().value());
+
+        assert!("a".parse::<Literal>().is_err());
+    }
+
+    #[test]
+    fn parse_register_key() {
+        assert_eq!(b'a', "a".parse::<RegisterKey>().unwrap().key());
+        assert_eq!(b'b', "b".parse::<RegisterKey>().unwrap().key());
+        assert_eq!(b'c', "c".parse::<RegisterKey>().unwrap().key());
+        assert_eq!(b'd', "d".parse::<RegisterKey>().unwrap().key());
+
+        assert!("".parse::<RegisterKey>().is_err());
+        assert!("e".parse::<RegisterKey>().is_err());
+    }
+
+    #[test]
+    fn parse_token() {
+        match "a".parse() {
+            Ok(ValueToken::Register(ref reg)) => assert_eq!(b'a', reg.key()),
+            _ => panic!("failed to parse `a` as value token"),
+        }
+
+        match "-7".parse() {
+            Ok(ValueToken::Literal(ref lit)) => assert_eq!(-7, lit.value()),
+            _ => panic!("failed to parse `a` as value token"),
+        }
+    }
+
+    #[test]
+    fn parse_instruction() {
+        match "jnz b -2".parse() {
+            Ok(Instr::Jnz(ValueToken::Register(cond), ValueToken::Literal(offset))) => {
+                assert_eq!(b'b', cond.key());
+                assert_eq!(-2, offset.value());
+            }
+            _ => panic!("failed to parse jnz instr")
+        }
+
+        match "jnz b a".parse() {
+            Ok(Instr::Jnz(ValueToken::Register(cond), ValueToken::Register(offset))) => {
+                assert_eq!(b'b', cond.key());
+                assert_eq!(b'a', offset.key());
+            }
+            _ => panic!("failed to parse jnz instr")
+        }
+
+        match "cpy 26 d".parse() {
+            Ok(Instr::Copy(ValueToken::Literal(val), ref reg)) => {
+                assert_eq!(26, val.value());
+                assert_eq!(b'd', reg.key());
+            }
+            _ => panic!("failed to parse cpy instr")
+        }
+
+        match "inc c".parse() {
+            Ok(Instr::Inc(ref reg)) => {
+                assert_eq!(b'c', reg.key());
+            }
+            _ => panic!("failed to parse inc instr")
+        }
+
+        match "dec c".parse() {
+            Ok(Instr::Dec(ref reg)) => {
+                assert_eq!(b'c', reg.key());
+            }
+            _ => panic!("failed to parse dec instr")
+        }
+    }
+
+    #[test]
+    fn parse_annotated_program() {
+        let lines = [
+            "# Count down from 3",
+            "",
+            "    cpy 3 a    ; initialize counter",
+            "top:",
+            "    dec a",
+            "    jnz a top",
+            "end: jnz 1 end",
+        ];
+        let prog: Vec<Instr> = parse_program(&lines).unwrap();
+        assert_eq!(4, prog.len());
+
+        match prog[2] {
+            Instr::Jnz(ValueToken::Register(cond), ValueToken::Literal(offset)) => {
+                assert_eq!(b'a', cond.key());
+                assert_eq!(-1, offset.value());
+            }
+            _ => panic!("failed to resolve backward label")
+        }
+        match prog[3] {
+            Instr::Jnz(_, ValueToken::Literal(offset)) => assert_eq!(0, offset.value()),
+            _ => panic!("failed to resolve label on same line")
+        }
+    }
+
+    #[test]
+    fn reject_bad_labels() {
+        let err = |lines: &[&str]| parse_program::<Instr, _>(lines).unwrap_err();
+
+        assert_eq!("line 2: undefined label `top`", err(&["inc a", "jnz a top"]));
+        assert_eq!("line 2: duplicate label `x`", err(&["x: inc a", "x:"]));
+        assert_eq!("line 1: invalid label name `a`", err(&["a: inc a"]));
+        assert_eq!("line 1: invalid label name `2x`", err(&["2x: inc a"]));
+        assert_eq!("line 1: invalid label name ``", err(&[": inc a"]));
+        assert!(err(&["inc e"]).starts_with("line 1: "));
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::collection::vec;
+        use proptest::prelude::*;
+
+        /// Strategy generating arbitrary register keys.
+        fn register_key() -> impl Strategy<Value=RegisterKey> {
+            (b'a'..=b'd').prop_map(RegisterKey::from)
+        }
+
+        /// Strategy generating arbitrary register or literal value tokens.
+        fn value_token() -> impl Strategy<Value=ValueToken> {
+            prop_oneof![
+                register_key().prop_map(ValueToken::Register),
+                any::<i16>().prop_map(|v| ValueToken::Literal(v.to_string().parse().unwrap())),
+            ]
+        }
+
+        /// Strategy generating arbitrary instructions.
+        fn instr() -> impl Strategy<Value=Instr> {
+            prop_oneof![
+                (value_token(), register_key()).prop_map(|(src, dest)| Instr::Copy(src, dest)),
+                (value_token(), value_token()).prop_map(|(cond, off)| Instr::Jnz(cond, off)),
+                register_key().prop_map(Instr::Inc),
+                register_key().prop_map(Instr::Dec),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn format_parse_round_trip(prog in vec(instr(), 0..32)) {
+                let lines: Vec<String> = prog.iter().map(Instr::to_string).collect();
+                prop_assert_eq!(prog, parse_program::<Instr, _>(&lines).unwrap());
+            }
+
+            #[test]
+            fn annotations_preserve_program(prog in vec((instr(), any::<bool>()), 0..32)) {
+                let mut lines = Vec::new();
+                for (instr, annotate) in &prog {
+                    if *annotate {
+                        lines.push(String::new());
+                        lines.push(format!("  {}  ; {}", instr, instr));
+                    } else {
+                        lines.push(instr.to_string());
+                    }
+                }
+                let expected: Vec<Instr> = prog.iter().map(|&(instr, _)| instr).collect();
+                prop_assert_eq!(expected, parse_program::<Instr, _>(&lines).unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! A virtual machine for running Intcode programs.
+//!
+//! Intcode programs are lists of integers that serve as both the code and
+//! the initial memory of the machine. The machine supports positional,
+//! immediate, and relative parameter modes, a relative base register, and
+//! memory that grows as addresses beyond the end of the program are
+//! written to.
+
+use crate::puzzle::PuzzleError;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Integral type used to represent the values stored in an Intcode
+/// machine's memory.
+pub type Int = i64;
+
+/// Parses a comma-separated Intcode program.
+pub fn parse_program(source: &str) -> Result<Vec<Int>, String> {
+    source
+        .trim()
+        .split(',')
+        .map(|token| {
+            token
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid Intcode value '{}'", token.trim()))
+        })
+        .collect()
+}
+
+/// An error encountered while running an Intcode program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// The instruction at the given address has an unknown opcode.
+    InvalidOpcode { address: usize, opcode: Int },
+    /// A parameter of the instruction at the given address has an unknown
+    /// mode, or an immediate mode parameter was written to.
+    InvalidMode { address: usize, mode: Int },
+    /// The instruction at the given address referred to a negative address.
+    NegativeAddress { address: usize, target: Int },
+    /// The program requested input when none remained.
+    InputExhausted,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidOpcode { address, opcode } => {
+                write!(f, "invalid opcode {} at address {}", opcode, address)
+            }
+            Error::InvalidMode { address, mode } => write!(
+                f,
+                "invalid parameter mode {} for instruction at address {}",
+                mode, address
+            ),
+            Error::NegativeAddress { address, target } => write!(
+                f,
+                "instruction at address {} accessed negative address {}",
+                address, target
+            ),
+            Error::InputExhausted => write!(f, "program requested input when none remained"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for PuzzleError {
+    fn from(e: Error) -> Self {
+        PuzzleError::SolverFailure(Box::new(e))
+    }
+}
+
+/// The reason that a machine stopped running.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    /// The machine produced an output value.
+    Output(Int),
+    /// The machine requires an input value before it can continue.
+    AwaitingInput,
+    /// The machine executed a halt instruction.
+    Halted,
+}
+
+/// An Intcode virtual machine.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    memory: Vec<Int>,
+    ip: usize,
+    relative_base: Int,
+    input: VecDeque<Int>,
+}
+
+impl Machine {
+    /// Creates a new machine with the given program loaded into memory.
+    pub fn new(program: Vec<Int>) -> Self {
+        Self {
+            memory: program,
+            ip: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+        }
+    }
+
+    /// Reads the value stored at the given address.
+    ///
+    /// Addresses beyond the end of memory contain `0`.
+    pub fn read(&self, address: usize) -> Int {
+        self.memory.get(address).copied().unwrap_or(0)
+    }
+
+    /// Stores a value at the given address, growing memory if needed.
+    pub fn write(&mut self, address: usize, value: Int) {
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, 0);
+        }
+        self.memory[address] = value;
+    }
+
+    /// Queues a value to be consumed by the program's next input
+    /// instruction.
+    pub fn push_input(&mut self, value: Int) {
+        self.input.push_back(value);
+    }
+
+    /// Runs the machine until it produces an output, requires an input that
+    /// has not been queued, or halts.
+    pub fn run(&mut self) -> Result<Status, Error> {
+        loop {
+            let address = self.ip;
+            let instr = self.read(address);
+            match instr % 100 {
+                1 | 2 | 7 | 8 => {
+                    let a = self.param(0)?;
+                    let b = self.param(1)?;
+                    let value = match instr % 100 {
+                        1 => a + b,
+                        2 => a * b,
+                        7 => (a < b) as Int,
+                        _ => (a == b) as Int,
+                    };
+                    let dest = self.param_address(2)?;
+                    self.write(dest, value);
+                    self.ip += 4;
+                }
+                3 => {
+                    if self.input.is_empty() {
+                        return Ok(Status::AwaitingInput);
+                    }
+                    let dest = self.param_address(0)?;
+                    let value = self.input.pop_front().unwrap();
+                    self.write(dest, value);
+                    self.ip += 2;
+                }
+                4 => {
+                    let value = self.param(0)?;
+                    self.ip += 2;
+                    return Ok(Status::Output(value));
+                }
+                5 | 6 => {
+                    let cond = self.param(0)?;
+                    if (cond != 0) == (instr % 100 == 5) {
+                        let target = self.param(1)?;
+                        self.ip = self.to_address(target)?;
+                    } else {
+                        self.ip += 3;
+                    }
+                }
+                9 => {
+                    self.relative_base += self.param(0)?;
+                    self.ip += 2;
+                }
+                99 => return Ok(Status::Halted),
+                opcode => return Err(Error::InvalidOpcode { address, opcode }),
+            }
+        }
+    }
+
+    /// Runs the machine to completion with the given input, returning every
+    /// value that it output.
+    pub fn run_with_input(&mut self, input: &[Int]) -> Result<Vec<Int>, Error> {
+        self.input.extend(input);
+        let mut output = Vec::new();
+        loop {
+            match self.run()? {
+                Status::Output(value) => output.push(value),
+                Status::AwaitingInput => return Err(Error::InputExhausted),
+                Status::Halted => return Ok(output),
+            }
+        }
+    }
+
+    /// Returns the mode of the parameter at the given index of the current
+    /// instruction.
+    fn mode(&self, index: u32) -> Int {
+        self.read(self.ip) / 10_i64.pow(index + 2) % 10
+    }
+
+    /// Returns the value of the parameter at the given index of the current
+    /// instruction.
+    fn param(&self, index: u32) -> Result<Int, Error> {
+        let raw = self.read(self.ip + 1 + index as usize);
+        match self.mode(index) {
+            1 => Ok(raw),
+            _ => Ok(self.read(self.param_address(index)?)),
+        }
+    }
+
+    /// Returns the address referred to by the parameter at the given index
+    /// of the current instruction.
+    fn param_address(&self, index: u32) -> Result<usize, Error> {
+        let raw = self.read(self.ip + 1 + index as usize);
+        match self.mode(index) {
+            0 => self.to_address(raw),
+            2 => self.to_address(self.relative_base + raw),
+            mode => Err(Error::InvalidMode {
+                address: self.ip,
+                mode,
+            }),
+        }
+    }
+
+    /// Converts a value into a memory address.
+    fn to_address(&self, target: Int) -> Result<usize, Error> {
+        if target < 0 {
+            Err(Error::NegativeAddress {
+                address: self.ip,
+                target,
+            })
+        } else {
+            Ok(target as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str, input: &[Int]) -> Vec<Int> {
+        Machine::new(parse_program(program).unwrap())
+            .run_with_input(input)
+            .unwrap()
+    }
+
+    #[test]
+    fn memory_grows_on_write() {
+        let mut machine = Machine::new(parse_program("1101,2,3,10,99").unwrap());
+        assert_eq!(Ok(Status::Halted), machine.run());
+        assert_eq!(5, machine.read(10));
+        assert_eq!(0, machine.read(11));
+    }
+
+    #[test]
+    fn pauses_for_input() {
+        let mut machine = Machine::new(parse_program("3,0,4,0,99").unwrap());
+        assert_eq!(Ok(Status::AwaitingInput), machine.run());
+        machine.push_input(42);
+        assert_eq!(Ok(Status::Output(42)), machine.run());
+        assert_eq!(Ok(Status::Halted), machine.run());
+    }
+
+    #[test]
+    fn relative_mode() {
+        assert_eq!(vec![7], run("109,5,204,1,99,6,7", &[]));
+        assert_eq!(vec![3], run("109,10,203,0,204,0,99", &[3]));
+    }
+
+    #[test]
+    fn reject_invalid_programs() {
+        assert_eq!(
+            Err(Error::InvalidOpcode {
+                address: 0,
+                opcode: 42
+            }),
+            Machine::new(vec![42]).run()
+        );
+        assert_eq!(
+            Err(Error::InvalidMode {
+                address: 0,
+                mode: 1
+            }),
+            Machine::new(vec![11101, 1, 1, 0, 99]).run()
+        );
+        assert_eq!(
+            Err(Error::InputExhausted),
+            Machine::new(vec![3, 0, 99]).run_with_input(&[])
+        );
+        assert!(parse_program("1,2,x").is_err());
+    }
+}
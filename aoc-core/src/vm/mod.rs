@@ -0,0 +1,4 @@
+//! Virtual machines for the puzzle languages that are reused across days.
+
+pub mod assembunny;
+pub mod intcode;
@@ -0,0 +1,101 @@
+//! Benchmarks for the hot loops of a few of the slower solutions.
+//!
+//! Each benchmark drives the same public library types that the solutions
+//! use, so that changes to those types can be compared before and after.
+//!
+//! Usage: `cargo bench [filter]`
+
+use aoc_solutions::common::util::hash;
+use aoc_solutions::y2016::day16::diskfiller::DiskFiller;
+use aoc_solutions::y2016::day18::floor::Floor;
+use aoc_solutions::y2018::day13::cart::TrackSimulator;
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+
+/// Door ID from the 2016 Day 5 puzzle input.
+const DOOR_ID: &str = include_str!("../resources/y2016/day05.txt");
+
+/// Initial state from the 2016 Day 16 puzzle input.
+const DISK_STATE: &str = include_str!("../resources/y2016/day16.txt");
+
+/// First row of tiles from the 2016 Day 18 puzzle input.
+const FIRST_ROW: &str = include_str!("../resources/y2016/day18.txt");
+
+/// Cart track from the 2018 Day 13 puzzle input.
+const CART_TRACK: &str = include_str!("../resources/y2018/day13.txt");
+
+/// The number of digests hashed per iteration of the MD5 benchmark.
+const HASHES_PER_ITER: u64 = 4096;
+
+/// MD5 mining step shared by 2016 Days 5 and 14.
+fn md5_mining(c: &mut Criterion) {
+    let door_id = DOOR_ID.trim_end().as_bytes();
+
+    let mut group = c.benchmark_group("md5_mining");
+    group.throughput(Throughput::Elements(HASHES_PER_ITER));
+    group.bench_function("counter_hashes", |b| {
+        b.iter(|| {
+            hash::counter_hashes(black_box(door_id))
+                .take(HASHES_PER_ITER as usize)
+                .filter(|(_, digest)| digest[0] == 0)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+/// Disk filling and checksum from 2016 Day 16.
+fn disk_checksum(c: &mut Criterion) {
+    let state: Vec<bool> = DISK_STATE.trim_end().bytes().map(|b| b == b'1').collect();
+
+    let mut group = c.benchmark_group("disk_checksum");
+    for &length in &[272, 1 << 20] {
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(length),
+            &length,
+            |b, &length| {
+                let mut filler = DiskFiller::new();
+                b.iter(|| {
+                    filler.input(&state);
+                    filler.generate_to_length(length);
+                    let checksum = filler.checksum();
+                    filler.reset();
+                    checksum
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Tile row generation from 2016 Day 18.
+fn tile_rows(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tile_rows");
+    for &rows in &[40, 10_000] {
+        group.throughput(Throughput::Elements(rows as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(rows), &rows, |b, &rows| {
+            b.iter(|| {
+                let mut floor: Floor = FIRST_ROW.trim_end().parse().unwrap();
+                floor.expand_to(rows);
+                floor.safe_count()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A single cart simulation tick from 2018 Day 13.
+fn cart_tick(c: &mut Criterion) {
+    c.bench_function("cart_tick", |b| {
+        b.iter_batched(
+            || TrackSimulator::from_puzzle_input(CART_TRACK).unwrap(),
+            |mut sim| sim.run_tick().unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, md5_mining, disk_checksum, tile_rows, cart_tick);
+criterion_main!(benches);
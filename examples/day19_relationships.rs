@@ -0,0 +1,28 @@
+//! Prints the table mapping elf group sizes to the winning elf for
+//! [2016 Day 19](https://adventofcode.com/2016/day/19), as computed by
+//! simulating both present-stealing strategies.
+//!
+//! Usage: `cargo run --example day19_relationships [first size] [last size]`
+//!
+//! The group sizes default to `1` through `254`, which reproduces
+//! `src/y2016/day19/relationship_data.txt`.
+
+use aoc_solutions::y2016::day19;
+use std::{env, process};
+
+/// The default range of group sizes to tabulate.
+const DEFAULT_SIZES: (usize, usize) = (1, 254);
+
+fn main() {
+    let mut args = env::args().skip(1).map(|arg| {
+        arg.parse().unwrap_or_else(|_| {
+            eprintln!("Invalid group size: {}", arg);
+            eprintln!("Usage: day19_relationships [first size] [last size]");
+            process::exit(1);
+        })
+    });
+    let first = args.next().unwrap_or(DEFAULT_SIZES.0);
+    let last = args.next().unwrap_or(DEFAULT_SIZES.1);
+
+    print!("{}", day19::relationship_table(first..=last));
+}
@@ -0,0 +1,496 @@
+//! Benchmarking a single puzzle over many runs.
+//!
+//! A single run of a solution is easily skewed by a cold cache or a busy
+//! machine. A [`Bench`] instead solves the puzzle repeatedly, discarding a
+//! few warm-up runs, and reports the distribution of the remaining timings
+//! of each part, along with the peak heap usage of the hungriest run if
+//! allocations are being counted.
+//!
+//! The median timings of benchmarks may be saved as a [`Baseline`], which
+//! later benchmarks are compared against to catch regressions:
+//!
+//! ```json
+//! {
+//!   "puzzles": [
+//!     {
+//!       "year": 2016,
+//!       "day": 2,
+//!       "part1_seconds": 0.000123,
+//!       "part2_seconds": 0.000101,
+//!       "setup_seconds": 0.000082,
+//!       "total_seconds": 0.000306
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::common::puzzle::memory::{self, Bytes};
+use crate::common::puzzle::{clock, Answer, Selection, Style};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Default number of measured runs.
+pub const DEFAULT_RUNS: usize = 10;
+
+/// Default number of warm-up runs, which are discarded.
+pub const DEFAULT_WARMUP: usize = 2;
+
+/// Default slowdown from a baseline, as a percentage, beyond which a timing
+/// is reported as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The distribution of the timings of many runs.
+pub struct Distribution {
+    /// The fastest time.
+    pub min: Duration,
+    /// The middle time, or the mean of the two middle times.
+    pub median: Duration,
+    /// The slowest time.
+    pub max: Duration,
+    /// The population standard deviation of the times.
+    pub stddev: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The median timings of a benchmarked puzzle, in seconds.
+pub struct Medians {
+    pub year: u16,
+    pub day: u8,
+    pub part1_seconds: Option<f64>,
+    pub part2_seconds: Option<f64>,
+    pub setup_seconds: f64,
+    pub total_seconds: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+/// The saved median timings of benchmarked puzzles, which later benchmarks
+/// are compared against.
+pub struct Baseline {
+    puzzles: Vec<Medians>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The change in a single median timing from a baseline.
+pub struct Change {
+    /// Name of the timing, as shown in benchmark tables.
+    pub name: &'static str,
+    /// The timing in the baseline, in seconds.
+    pub baseline: f64,
+    /// The timing in the new benchmark, in seconds.
+    pub current: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A comparison of a benchmark with the baseline of its puzzle.
+pub struct Comparison {
+    threshold: f64,
+    changes: Vec<Change>,
+}
+
+#[derive(Debug)]
+/// The timings of many runs of a single puzzle.
+pub struct Bench {
+    year: u16,
+    day: u8,
+    warmup: usize,
+    part_one: Vec<Duration>,
+    part_two: Vec<Duration>,
+    setup: Vec<Duration>,
+    total: Vec<Duration>,
+    peak_memory: Option<usize>,
+}
+
+impl Distribution {
+    /// Computes the distribution of the given timings, or returns `None` if
+    /// there are none.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Some(Distribution {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl Bench {
+    /// Solves the puzzle `warmup + runs` times, keeping the timings of the
+    /// last `runs` runs.
+    ///
+    /// Fails as soon as any run fails.
+    pub fn run(puzzle: &Selection, runs: usize, warmup: usize) -> Result<Self, Box<dyn Error>> {
+        let mut bench = Bench {
+            year: puzzle.year(),
+            day: puzzle.day(),
+            warmup,
+            part_one: Vec::with_capacity(runs),
+            part_two: Vec::with_capacity(runs),
+            setup: Vec::with_capacity(runs),
+            total: Vec::with_capacity(runs),
+            peak_memory: None,
+        };
+
+        for run in 0..warmup + runs {
+            let start = clock::now();
+            let (solution, peak) = memory::track(|| crate::route(puzzle));
+            let solution = solution.map_err(|e| e.to_string())?;
+            let total = clock::elapsed(start);
+            if run < warmup {
+                continue;
+            }
+
+            let bench_of = |part: &Option<Answer>| part.as_ref().and_then(Answer::bench).copied();
+            bench.part_one.extend(bench_of(&solution.0));
+            bench.part_two.extend(bench_of(&solution.1));
+            bench.setup.push(total - solution.duration());
+            bench.total.push(total);
+            bench.peak_memory = bench.peak_memory.max(peak);
+        }
+        Ok(bench)
+    }
+
+    /// Returns the number of measured runs.
+    pub fn runs(&self) -> usize {
+        self.total.len()
+    }
+
+    /// Returns the distribution of the timings of the given part, or `None`
+    /// if the part was not timed.
+    pub fn part(&self, part: u8) -> Option<Distribution> {
+        match part {
+            1 => Distribution::of(&self.part_one),
+            2 => Distribution::of(&self.part_two),
+            _ => None,
+        }
+    }
+
+    /// Returns the distribution of the setup times.
+    pub fn setup(&self) -> Option<Distribution> {
+        Distribution::of(&self.setup)
+    }
+
+    /// Returns the distribution of the total times.
+    pub fn total(&self) -> Option<Distribution> {
+        Distribution::of(&self.total)
+    }
+
+    /// Returns the most heap memory allocated at once by any measured run,
+    /// in bytes, if it was measured.
+    pub fn peak_memory(&self) -> Option<usize> {
+        self.peak_memory
+    }
+
+    /// Returns the median timings of this benchmark.
+    pub fn medians(&self) -> Medians {
+        let median = |dist: Option<Distribution>| dist.map(|d| d.median.as_secs_f64());
+        Medians {
+            year: self.year,
+            day: self.day,
+            part1_seconds: median(self.part(1)),
+            part2_seconds: median(self.part(2)),
+            setup_seconds: median(self.setup()).unwrap_or_default(),
+            total_seconds: median(self.total()).unwrap_or_default(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Loads a baseline saved with [`Baseline::to_json`].
+    ///
+    /// The puzzles are sorted once loaded, as [`Baseline::record`] expects,
+    /// in case the file was edited by hand.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let mut baseline: Baseline = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        baseline.puzzles.sort_by_key(|m| (m.year, m.day));
+        Ok(baseline)
+    }
+
+    /// Returns the median timings of the given puzzle, if recorded.
+    pub fn get(&self, year: u16, day: u8) -> Option<&Medians> {
+        self.puzzles
+            .iter()
+            .find(|medians| (medians.year, medians.day) == (year, day))
+    }
+
+    /// Records the median timings of a benchmark, replacing any earlier
+    /// timings of its puzzle.
+    pub fn record(&mut self, bench: &Bench) {
+        let medians = bench.medians();
+        let key = |m: &Medians| (m.year, m.day);
+        match self.puzzles.binary_search_by_key(&key(&medians), key) {
+            Ok(i) => self.puzzles[i] = medians,
+            Err(i) => self.puzzles.insert(i, medians),
+        }
+    }
+
+    /// Compares a benchmark with the timings recorded for its puzzle,
+    /// reporting slowdowns of more than `threshold` percent as regressions.
+    ///
+    /// Returns `None` if the puzzle has no recorded timings.
+    pub fn compare(&self, bench: &Bench, threshold: f64) -> Option<Comparison> {
+        let old = self.get(bench.year, bench.day)?;
+        let new = bench.medians();
+        let pairs = [
+            ("Part 1", old.part1_seconds, new.part1_seconds),
+            ("Part 2", old.part2_seconds, new.part2_seconds),
+            ("Setup", Some(old.setup_seconds), Some(new.setup_seconds)),
+            ("Total", Some(old.total_seconds), Some(new.total_seconds)),
+        ];
+        let changes = pairs
+            .iter()
+            .filter_map(|&(name, baseline, current)| {
+                Some(Change {
+                    name,
+                    baseline: baseline?,
+                    current: current?,
+                })
+            })
+            .collect();
+        Some(Comparison { threshold, changes })
+    }
+
+    /// Renders this baseline as a pretty-printed JSON document.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl Change {
+    /// Returns the change in the timing as a percentage of the baseline.
+    pub fn percent(&self) -> f64 {
+        if self.baseline > 0.0 {
+            (self.current - self.baseline) / self.baseline * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Comparison {
+    /// Returns the change in the total time of the puzzle.
+    pub fn total(&self) -> Option<&Change> {
+        self.changes.iter().find(|change| change.name == "Total")
+    }
+
+    /// Returns `true` if the total time of the puzzle regressed.
+    pub fn regressed(&self) -> bool {
+        self.total()
+            .is_some_and(|total| total.percent() > self.threshold)
+    }
+
+    /// Renders a table of the changes, highlighting regressions and
+    /// improvements beyond the threshold in the given style.
+    pub fn render(&self, style: Style) -> String {
+        let mut out = format!(
+            "Compared with baseline (medians, regressions over {}%)\n\n",
+            self.threshold
+        );
+        writeln!(
+            out,
+            "{:<8}{:>14}{:>14}{:>10}",
+            "", "baseline", "this run", "change"
+        )
+        .unwrap();
+        for change in &self.changes {
+            let percent = change.percent();
+            let (color, mark) = if percent > self.threshold {
+                ("\x1b[31m", "  regressed")
+            } else if percent < -self.threshold {
+                ("\x1b[32m", "  improved")
+            } else {
+                ("", "")
+            };
+            let (color, reset) = match style {
+                Style::Ansi if !color.is_empty() => (color, "\x1b[0m"),
+                _ => ("", ""),
+            };
+            writeln!(
+                out,
+                "{:<8}{:>13.9}s{:>13.9}s{}{:>+9.1}%{}{}",
+                change.name, change.baseline, change.current, color, percent, mark, reset
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Benchmarked {:4} day {:02} over {} runs ({} warm-up runs discarded)\n",
+            self.year,
+            self.day,
+            self.runs(),
+            self.warmup
+        )?;
+        writeln!(
+            f,
+            "{:<8}{:>14}{:>14}{:>14}{:>14}",
+            "", "min", "median", "max", "stddev"
+        )?;
+
+        let rows = [
+            ("Part 1", self.part(1)),
+            ("Part 2", self.part(2)),
+            ("Setup", self.setup()),
+            ("Total", self.total()),
+        ];
+        for (name, dist) in rows.iter() {
+            if let Some(dist) = dist {
+                writeln!(
+                    f,
+                    "{:<8}{:>13.9}s{:>13.9}s{:>13.9}s{:>13.9}s",
+                    name,
+                    dist.min.as_secs_f64(),
+                    dist.median.as_secs_f64(),
+                    dist.max.as_secs_f64(),
+                    dist.stddev.as_secs_f64()
+                )?;
+            }
+        }
+        if let Some(peak) = self.peak_memory {
+            writeln!(f, "\nPeak memory: {}", Bytes(peak))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::puzzle::clock::{with_clock, StepClock};
+
+    #[test]
+    fn distribution_of_samples() {
+        let ms = |ms: &[u64]| -> Vec<Duration> {
+            ms.iter().map(|&m| Duration::from_millis(m)).collect()
+        };
+
+        let dist = Distribution::of(&ms(&[4, 1, 3, 2])).unwrap();
+        assert_eq!(Duration::from_millis(1), dist.min);
+        assert_eq!(Duration::from_micros(2_500), dist.median);
+        assert_eq!(Duration::from_millis(4), dist.max);
+        assert_eq!(1_118, dist.stddev.as_micros());
+
+        let dist = Distribution::of(&ms(&[5, 9, 7])).unwrap();
+        assert_eq!(Duration::from_millis(7), dist.median);
+
+        assert_eq!(None, Distribution::of(&[]));
+    }
+
+    #[test]
+    fn bench_discards_warmup() {
+        let puzzle = Selection::with_text(
+            2016,
+            15,
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+             Disc #2 has 2 positions; at time=0, it is at position 1.\n",
+        );
+        let step = Duration::from_millis(10);
+        let bench = with_clock(StepClock::new(step), || Bench::run(&puzzle, 3, 2)).unwrap();
+
+        assert_eq!(3, bench.runs());
+        // Each run reads the clock at its start, before and after each
+        // part, and at its end, each read advancing the clock by a step.
+        let dist = bench.part(1).unwrap();
+        assert_eq!(
+            (step, step, Duration::default()),
+            (dist.min, dist.max, dist.stddev)
+        );
+        assert_eq!(step * 5, bench.total().unwrap().median);
+        assert_eq!(step * 3, bench.setup().unwrap().median);
+
+        let failed = Bench::run(&Selection::with_text(2016, 15, "Disc #1"), 3, 2);
+        assert!(failed.is_err());
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let puzzle = Selection::with_text(
+            2016,
+            15,
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n",
+        );
+        let bench = |ms| {
+            with_clock(StepClock::new(Duration::from_millis(ms)), || {
+                Bench::run(&puzzle, 3, 0)
+            })
+            .unwrap()
+        };
+
+        let mut baseline = Baseline::default();
+        assert_eq!(None, baseline.compare(&bench(10), DEFAULT_THRESHOLD));
+        baseline.record(&bench(10));
+        baseline.record(&bench(10));
+        assert_eq!(1, baseline.puzzles.len());
+
+        let json = baseline.to_json().unwrap();
+        assert_eq!(baseline, serde_json::from_str(&json).unwrap());
+
+        let same = baseline.compare(&bench(10), DEFAULT_THRESHOLD).unwrap();
+        assert!(!same.regressed());
+        assert_eq!(0.0, same.total().unwrap().percent());
+
+        let slower = baseline.compare(&bench(12), DEFAULT_THRESHOLD).unwrap();
+        assert!(slower.regressed());
+        assert_eq!(0.06, slower.total().unwrap().current);
+        assert!(!baseline.compare(&bench(12), 25.0).unwrap().regressed());
+        insta::assert_snapshot!(slower.render(Style::Plain));
+    }
+
+    #[test]
+    fn sort_loaded_baseline() {
+        let medians = |year, day| Medians {
+            year,
+            day,
+            part1_seconds: None,
+            part2_seconds: None,
+            setup_seconds: 0.0,
+            total_seconds: 0.0,
+        };
+        let unsorted = Baseline {
+            puzzles: vec![medians(2018, 1), medians(2016, 15), medians(2016, 2)],
+        };
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.json", std::process::id()));
+        fs::write(&path, unsorted.to_json().unwrap()).unwrap();
+        let mut baseline = Baseline::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let puzzle = Selection::with_text(
+            2016,
+            15,
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n",
+        );
+        baseline.record(&Bench::run(&puzzle, 1, 0).unwrap());
+        let keys: Vec<_> = baseline.puzzles.iter().map(|m| (m.year, m.day)).collect();
+        assert_eq!(vec![(2016, 2), (2016, 15), (2018, 1)], keys);
+    }
+}
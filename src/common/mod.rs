@@ -1,25 +1,61 @@
-pub mod puzzle;
-pub mod util;
+pub use aoc_core::{log, puzzle, util, vm};
+#[cfg(test)]
+pub use aoc_core::testing;
 
 macro_rules! route_days {
     ( $( $day:expr => $sol:ident ),+ $(,)?) => {
         use crate::common::puzzle::{Selection as Pz, Result as PuzzleResult, SelectionError};
+
+        /// The days that have solutions, in the order they are routed.
+        pub const DAYS: &[u8] = &[ $( $day ),+ ];
+
         pub fn route(puzzle: &Pz) -> PuzzleResult {
             match puzzle.day() {
                 $( $day => $sol::solve(puzzle), )*
-                _ => Err(Box::new(SelectionError::UnimplementedDay))
+                _ => Err(SelectionError::UnimplementedDay.into())
             }
         }
     };
 }
 
+/// Emits a message through `common::log` at the given level, skipping the
+/// formatting if the message would not be shown.
+macro_rules! log_at {
+    ( $level:ident, $($arg:tt)+ ) => {{
+        use crate::common::log::{self, Level};
+
+        if log::enabled(Level::$level) {
+            log::log(Level::$level, format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Emits a warning, shown unless the runner is quieted with `-q`.
+#[allow(unused_macros)]
+macro_rules! warn {
+    ( $($arg:tt)+ ) => { log_at!(Warn, $($arg)+) };
+}
+
+/// Emits an informational message, shown with `-v`.
+#[allow(unused_macros)]
+macro_rules! info {
+    ( $($arg:tt)+ ) => { log_at!(Info, $($arg)+) };
+}
+
+/// Emits a debugging message, shown with `-vv`.
+#[allow(unused_macros)]
+macro_rules! debug {
+    ( $($arg:tt)+ ) => { log_at!(Debug, $($arg)+) };
+}
+
 macro_rules! bench_ans {
-    ( $ans:expr ) => {{
-        use crate::common::puzzle::Answer;
-        use std::time::Instant;
+    ( $part:literal => $ans:expr ) => {{
+        use crate::common::puzzle::{clock, Answer};
 
-        let start = Instant::now();
-        Answer::with_bench($ans, Some(Instant::now().duration_since(start)))
+        crate::trace::end_setup();
+        let _span = tracing::info_span!("part", n = $part).entered();
+        let start = clock::now();
+        Answer::with_bench($ans, Some(clock::elapsed(start)))
     }};
 }
 
@@ -30,24 +66,25 @@ macro_rules! solve_parts {
     }};
     ( 1 => $part_one:expr $(,)?) => {{
         use crate::common::puzzle::Solution;
-        Ok(Solution(Some(bench_ans!($part_one)), None))
+        Ok(Solution(Some(bench_ans!(1 => $part_one)), None))
     }};
 
     ( 1 => $part_one:expr, 2 => $part_two:expr $(,)? ) => {{
         use crate::common::puzzle::Solution;
         Ok(Solution(
-            Some(bench_ans!($part_one)),
-            Some(bench_ans!($part_two)),
+            Some(bench_ans!(1 => $part_one)),
+            Some(bench_ans!(2 => $part_two)),
         ))
     }};
 
     ( both => $part_producer:expr ) => {{
-        use crate::common::puzzle::{Answer, Solution};
-        use std::time::Instant;
+        use crate::common::puzzle::{clock, Answer, Solution};
 
-        let start = Instant::now();
+        crate::trace::end_setup();
+        let _span = tracing::info_span!("parts").entered();
+        let start = clock::now();
         let (part_one, part_two) = $part_producer;
-        let bench = start.elapsed();
+        let bench = clock::elapsed(start);
 
         Ok(Solution(
             Some(Answer::with_bench(part_one, Some(bench))),
@@ -56,21 +93,203 @@ macro_rules! solve_parts {
     }};
 }
 
+/// Asserts that the module's `solve` produces the expected answers for a
+/// puzzle.
+///
+/// Given only the puzzle, the expected answers are read from the answer
+/// manifest (`resources/answers.toml`). Otherwise they are given before the
+/// puzzle.
+///
+/// By default the puzzle's input is read from disk. Appending
+/// `inputs = <source>` instead reads it from the given `InputSource`, such as
+/// `testing::FakeInputs`, so the solution can be checked without the real
+/// input file:
+///
+/// ```ignore
+/// assert_solution!(Pz::new(2016, 15));
+/// assert_solution!(122318, 3208583, Pz::new(2016, 15));
+/// assert_solution!(5, 85, Pz::new(2016, 15), inputs = FakeInputs::new().with(...));
+/// ```
 #[cfg(test)]
 macro_rules! assert_solution {
+    ( $puzzle:expr $(,)?) => {{
+        let puzzle = $puzzle;
+        crate::common::testing::assert_known_answers(&puzzle, &solve(&puzzle).unwrap())
+    }};
+
+    ( $part_one:expr, $puzzle:expr, inputs = $source:expr $(,)?) => {{
+        crate::common::puzzle::input::with_source($source, || assert_solution!($part_one, $puzzle))
+    }};
+
+    ( $part_one:expr, $part_two:expr, $puzzle:expr, inputs = $source:expr $(,)?) => {{
+        crate::common::puzzle::input::with_source($source, || {
+            assert_solution!($part_one, $part_two, $puzzle)
+        })
+    }};
+
     ( $part_one:expr, $puzzle:expr $(,)?) => {{
-        use crate::common::puzzle::{Answer, Solution};
         assert_eq! {
-            Solution::new(Some(Answer::new($part_one)), None),
+            crate::common::testing::part_one($part_one),
             solve(&$puzzle).unwrap()
         }
     }};
 
     ( $part_one:expr, $part_two:expr, $puzzle:expr $(,)?) => {{
-        use crate::common::puzzle::{Answer, Solution};
         assert_eq! {
-            Solution::new(Some(Answer::new($part_one)), Some(Answer::new($part_two))),
+            crate::common::testing::solution($part_one, $part_two),
             solve(&$puzzle).unwrap()
         }
     }};
 }
+
+/// Asserts that a day's solver, or a function of its input, produces the
+/// expected answers for a numbered example input.
+///
+/// Example inputs are loaded from `examples/yYYYY/dayDD/exN.txt`.
+///
+/// The solver form runs the module's `solve` on the example and compares its
+/// answers as `assert_solution!` does:
+///
+/// ```ignore
+/// assert_example!(2018, 8, 1, solve => (138, 66));
+/// ```
+///
+/// The function form passes the contents of the example to the given
+/// function as a `&str` and compares the value that it returns:
+///
+/// ```ignore
+/// assert_example!(2018, 12, 1, |input| checksum_after(input, 20) => 325);
+/// ```
+#[cfg(test)]
+macro_rules! assert_example {
+    ( $year:expr, $day:expr, $ex:expr, solve => ( $part_one:expr $(,)? ) ) => {{
+        assert_solution!($part_one, crate::common::puzzle::Selection::example($year, $day, $ex))
+    }};
+
+    ( $year:expr, $day:expr, $ex:expr, solve => ( $part_one:expr, $part_two:expr $(,)? ) ) => {{
+        assert_solution!(
+            $part_one,
+            $part_two,
+            crate::common::puzzle::Selection::example($year, $day, $ex),
+        )
+    }};
+
+    ( $year:expr, $day:expr, $ex:expr, $func:expr => $expected:expr $(,)? ) => {{
+        let input = crate::common::testing::example($year, $day, $ex);
+        assert_eq!(
+            $expected,
+            ($func)(input.as_str()),
+            "example {} of {} day {}", $ex, $year, $day,
+        );
+    }};
+}
+
+/// Generates a module of tests, one per named case, each of which asserts
+/// that a function produces the expected value for the case's input.
+///
+/// Each group of cases becomes a module named after the group, so a failing
+/// case is reported by name (e.g. `tests::ex1::nested_pairs`) along with its
+/// input:
+///
+/// ```ignore
+/// example_cases! {
+///     ex1: final_floor => {
+///         balanced: b"(())" => 0,
+///         nested_pairs: b"(((" => 3,
+///     }
+/// }
+/// ```
+#[cfg(test)]
+macro_rules! example_cases {
+    ( $(
+        $group:ident : $func:expr => {
+            $( $case:ident : $input:expr => $expected:expr ),+ $(,)?
+        }
+    )+ ) => {
+        $(
+            mod $group {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $case() {
+                        let input = $input;
+                        assert_eq!($expected, ($func)(input), "input: {:?}", input);
+                    }
+                )+
+            }
+        )+
+    };
+}
+
+/// Asserts that `route` dispatches each of the listed days to a solution
+/// and rejects all other days.
+///
+/// Solutions are not run to completion: each is given a nonexistent input
+/// file, so a routed day fails with an I/O error when it tries to read it.
+#[cfg(test)]
+macro_rules! assert_routes {
+    ( $year:expr, [ $( $day:expr ),+ $(,)? ] ) => {{
+        use crate::common::puzzle::{PuzzleError, Selection};
+
+        let expected: &[u8] = &[ $( $day ),+ ];
+        assert_eq!(expected, DAYS);
+
+        for day in 1..=25 {
+            let puzzle = Selection::with_input($year, day, "/nonexistent/input.txt");
+            let err = route(&puzzle).expect_err("solution ran without input");
+            if expected.contains(&day) {
+                assert!(err.is_missing_input(), "day {} not routed: {}", day, err);
+            } else {
+                assert!(
+                    matches!(err, PuzzleError::Unimplemented(_)),
+                    "day {} unexpectedly routed", day,
+                );
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::puzzle::clock::{with_clock, StepClock};
+    use crate::common::puzzle::{Answer, Result as PuzzleResult};
+    use std::time::Duration;
+
+    fn benches(result: PuzzleResult) -> (Option<Duration>, Option<Duration>) {
+        let solution = result.unwrap();
+        let bench = |part: Option<Answer>| part.and_then(|ans| ans.bench().copied());
+        (bench(solution.0), bench(solution.1))
+    }
+
+    #[test]
+    fn solve_parts_timed_by_clock() {
+        let step = Duration::from_millis(10);
+
+        let result = with_clock(StepClock::new(step), || solve_parts!(1 => 1, 2 => 2));
+        assert_eq!((Some(step), Some(step)), benches(result));
+
+        let result = with_clock(StepClock::new(step), || solve_parts!(both => (1, 2)));
+        assert_eq!((Some(step), None), benches(result));
+    }
+
+    #[test]
+    fn log_macros_capture() {
+        use crate::common::log::{capture, Level};
+
+        let ((), messages) = capture(|| {
+            warn!("skipped line {}", 3);
+            info!("tick");
+            debug!("state: {:?}", [1, 2]);
+        });
+        assert_eq!(
+            vec![
+                (Level::Warn, "skipped line 3".to_owned()),
+                (Level::Info, "tick".to_owned()),
+                (Level::Debug, "state: [1, 2]".to_owned()),
+            ],
+            messages
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Settings read from a configuration file.
+//!
+//! The runner looks for `aoc.toml` in the working directory, and then for
+//! `advent-of-rust.toml` in the user's configuration directory
+//! (`$XDG_CONFIG_HOME`, or `~/.config` if it is unset):
+//!
+//! ```toml
+//! input_dir = "/home/me/aoc-inputs"
+//! session = "53616c7465645f5f..."
+//! year = 2018
+//! iterations = 20
+//! warmup = 1
+//! ```
+//!
+//! Every setting is optional, and each is overridden by the matching
+//! command-line flag or environment variable.
+
+use serde::Deserialize;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Configuration file looked for in the working directory.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Configuration file looked for in the user's configuration directory.
+const USER_CONFIG_FILE: &str = "advent-of-rust.toml";
+
+/// The configuration used by the rest of the crate, once it has been
+/// installed or loaded.
+static CURRENT: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+/// Settings for the runner.
+pub struct Config {
+    /// Directory holding the puzzle inputs.
+    input_dir: Option<String>,
+    /// Session token for the Advent of Code website.
+    session: Option<String>,
+    /// Year of the puzzles selected by day alone.
+    year: Option<u16>,
+    /// Number of measured runs of the `bench` command.
+    iterations: Option<usize>,
+    /// Number of untimed warm-up runs before a puzzle is timed.
+    warmup: Option<usize>,
+}
+
+impl Config {
+    /// Loads the first configuration file that exists, or returns an empty
+    /// configuration if there is none.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        match search_paths().into_iter().find(|path| path.is_file()) {
+            Some(path) => Config::from_file(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Loads the configuration file at the given path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| text.parse())
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Makes this the configuration returned by [`Config::current`].
+    ///
+    /// Returns `false`, leaving the configuration unchanged, if one has
+    /// already been installed or used.
+    pub fn install(self) -> bool {
+        CURRENT.set(self).is_ok()
+    }
+
+    /// Returns the installed configuration, loading it on first use if none
+    /// was installed.
+    ///
+    /// A configuration file that cannot be loaded is ignored.
+    pub fn current() -> &'static Config {
+        CURRENT.get_or_init(|| Config::load().unwrap_or_default())
+    }
+
+    /// Returns the directory holding the puzzle inputs, if set.
+    pub fn input_dir(&self) -> Option<&str> {
+        self.input_dir.as_deref()
+    }
+
+    /// Returns the session token for the Advent of Code website, if set.
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    /// Returns the year of the puzzles selected by day alone, if set.
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// Returns the number of measured runs of the `bench` command, if set.
+    pub fn iterations(&self) -> Option<usize> {
+        self.iterations
+    }
+
+    /// Returns the number of untimed warm-up runs before a puzzle is timed,
+    /// if set.
+    pub fn warmup(&self) -> Option<usize> {
+        self.warmup
+    }
+
+    /// Overrides the directory holding the puzzle inputs.
+    pub fn with_input_dir<S: Into<String>>(mut self, dir: S) -> Self {
+        self.input_dir = Some(dir.into());
+        self
+    }
+}
+
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| e.message().to_owned())
+    }
+}
+
+/// Returns the paths of the configuration files, in the order they are
+/// looked for.
+fn search_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(CONFIG_FILE)];
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+    if let Some(dir) = config_dir {
+        paths.push(dir.join(USER_CONFIG_FILE));
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config: Config =
+            "input_dir = \"inputs\"\nsession = \"abc\"\nyear = 2018\niterations = 20\nwarmup = 1\n"
+                .parse()
+                .unwrap();
+        assert_eq!(Some("inputs"), config.input_dir());
+        assert_eq!(Some("abc"), config.session());
+        assert_eq!(Some(2018), config.year());
+        assert_eq!(Some(20), config.iterations());
+        assert_eq!(Some(1), config.warmup());
+
+        assert_eq!(Config::default(), "".parse().unwrap());
+        assert!("year = \"2018\"".parse::<Config>().is_err());
+        assert!("years = 2018".parse::<Config>().is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Exporting the answers and timings of many puzzles as a JSON document.
+//!
+//! The exported document lists a summary of each puzzle that was run in
+//! chronological order:
+//!
+//! ```json
+//! {
+//!   "puzzles": [
+//!     {
+//!       "year": 2016,
+//!       "day": 2,
+//!       "input": "./resources/y2016/day02.txt",
+//!       "solution": {
+//!         "part1": { "answer": "99332", "seconds": 0.000457333 },
+//!         "part2": { "answer": "DD483", "seconds": 0.000369839 }
+//!       },
+//!       "error": null,
+//!       "setup_seconds": 0.00074762,
+//!       "total_seconds": 0.001574792
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! While a batch is running, the summary of each puzzle may also be streamed
+//! as it finishes with [`JsonLines`], which benchmarks use to stream their
+//! timings as well.
+//!
+//! Batches may also be run on several threads with [`Export::run_parallel`],
+//! which still reports and collects the summaries in their original order.
+
+use crate::common::puzzle::memory::Bytes;
+use crate::common::puzzle::{Answer, Selection, SelectionError, Stats, Summary};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Default location of the exported document.
+pub const EXPORT_PATH: &str = "answers.json";
+
+/// Widest answer shown in a table before it is cut short.
+const MAX_ANSWER_WIDTH: usize = 24;
+
+#[derive(Debug, Serialize)]
+/// The summaries of a batch of puzzle runs.
+pub struct Export {
+    puzzles: Vec<Summary>,
+}
+
+#[derive(Debug, Copy, Clone)]
+/// The progress of a batch of puzzle runs.
+pub enum Progress<'a> {
+    /// The puzzle is about to be run.
+    Started(&'a Selection),
+    /// The puzzle has been run.
+    Finished(&'a Summary),
+}
+
+/// Writes puzzle summaries, or other records such as benchmark timings, as
+/// JSON lines: one compact JSON object per line.
+///
+/// The output is flushed after every summary, so that a long batch can be
+/// monitored, and whatever was written before an interruption can still be
+/// read.
+#[derive(Debug)]
+pub struct JsonLines<W: Write> {
+    out: W,
+}
+
+impl Export {
+    /// Runs each of the selected puzzles in order, collecting their summaries.
+    ///
+    /// `progress` is called before and after each puzzle is run.
+    pub fn run<I, F>(puzzles: I, mut progress: F) -> Self
+    where
+        I: IntoIterator<Item = Selection>,
+        F: FnMut(Progress<'_>),
+    {
+        let puzzles = puzzles
+            .into_iter()
+            .map(|puzzle| {
+                progress(Progress::Started(&puzzle));
+                let summary = Summary::measure(puzzle, crate::route);
+                progress(Progress::Finished(&summary));
+                summary
+            })
+            .collect();
+        Export { puzzles }
+    }
+
+    /// Runs the selected puzzles on `threads` worker threads with `solve`,
+    /// collecting their summaries in the order that the puzzles were given.
+    ///
+    /// Each worker starts with the default input source and clock, so
+    /// `solve` should install any others that it needs. Each puzzle is still
+    /// timed on the thread that solves it, but the workers compete for the
+    /// machine, so timings are best compared between runs with the same
+    /// number of threads.
+    ///
+    /// `progress` is called on the current thread before and after each
+    /// puzzle, in order, once that puzzle and every puzzle before it have
+    /// been run.
+    pub fn run_parallel<I, G, F>(puzzles: I, threads: usize, solve: G, mut progress: F) -> Self
+    where
+        I: IntoIterator<Item = Selection>,
+        G: Fn(Selection) -> Summary + Sync,
+        F: FnMut(Progress<'_>),
+    {
+        let queue: Vec<(usize, Selection)> = puzzles.into_iter().enumerate().collect();
+        let mut finished: Vec<Option<Summary>> = queue.iter().map(|_| None).collect();
+        let mut puzzles = Vec::with_capacity(queue.len());
+        let queue = Mutex::new(queue.into_iter());
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                let (tx, solve, queue) = (tx.clone(), &solve, &queue);
+                scope.spawn(move || loop {
+                    let next = queue.lock().unwrap().next();
+                    let (i, puzzle) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+                    if tx.send((i, solve(puzzle))).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(tx);
+
+            for (i, summary) in rx {
+                finished[i] = Some(summary);
+                while let Some(summary) = finished.get_mut(puzzles.len()).and_then(Option::take) {
+                    progress(Progress::Started(summary.puzzle()));
+                    progress(Progress::Finished(&summary));
+                    puzzles.push(summary);
+                }
+            }
+        });
+        Export { puzzles }
+    }
+
+    /// Returns the summaries of the puzzles that were run.
+    pub fn puzzles(&self) -> &[Summary] {
+        &self.puzzles
+    }
+
+    /// Renders this export as a pretty-printed JSON document.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders this export as a plain text table with a row for each
+    /// puzzle's answers and total time, followed by aggregate statistics.
+    /// Peak heap usage is shown too if it was measured.
+    ///
+    /// Long answers are cut short, and answers spanning several lines, such
+    /// as rendered letters, are left out.
+    pub fn to_table(&self) -> String {
+        let answers: Vec<(String, String)> = self
+            .puzzles
+            .iter()
+            .map(|summary| match summary.result() {
+                Ok(solution) => (cell(solution.0.as_ref()), cell(solution.1.as_ref())),
+                Err(e) => (format!("FAILED: {}", e), String::new()),
+            })
+            .collect();
+        let width = answers
+            .iter()
+            .filter(|(_, two)| !two.is_empty())
+            .flat_map(|(one, two)| vec![one.chars().count(), two.chars().count()])
+            .max()
+            .unwrap_or(0)
+            .max("Part 1".len());
+
+        let memory = self.puzzles.iter().any(|s| s.peak_memory().is_some());
+
+        let mut out = String::new();
+        write!(
+            out,
+            "Year Day  {:<w$}  {:<w$}  {:>12}",
+            "Part 1",
+            "Part 2",
+            "Time",
+            w = width
+        )
+        .unwrap();
+        if memory {
+            write!(out, "  {:>10}", "Memory").unwrap();
+        }
+        writeln!(out).unwrap();
+        for (summary, (one, two)) in self.puzzles.iter().zip(answers) {
+            let puzzle = summary.puzzle();
+            let row = if summary.result().is_ok() {
+                let mut row = format!(
+                    "{:4}  {:02}  {:<w$}  {:<w$}  {:>11.6}s",
+                    puzzle.year(),
+                    puzzle.day(),
+                    one,
+                    two,
+                    summary.duration().as_secs_f64(),
+                    w = width
+                );
+                if memory {
+                    let peak = summary.peak_memory().map(|b| Bytes(b).to_string());
+                    write!(row, "  {:>10}", peak.as_deref().unwrap_or("-")).unwrap();
+                }
+                row
+            } else {
+                format!("{:4}  {:02}  {}", puzzle.year(), puzzle.day(), one)
+            };
+            writeln!(out, "{}", row.trim_end()).unwrap();
+        }
+
+        let stats: Stats = self.puzzles.iter().collect();
+        write!(out, "\n{}", stats).unwrap();
+        out
+    }
+}
+
+/// Formats an answer as a table cell.
+fn cell(answer: Option<&Answer>) -> String {
+    let ans = match answer {
+        Some(answer) => answer.ans(),
+        None => return "-".to_owned(),
+    };
+    if ans.contains('\n') {
+        "(multi-line)".to_owned()
+    } else if ans.chars().count() > MAX_ANSWER_WIDTH {
+        let cut: String = ans.chars().take(MAX_ANSWER_WIDTH - 3).collect();
+        cut + "..."
+    } else {
+        ans.to_owned()
+    }
+}
+
+impl<W: Write> JsonLines<W> {
+    /// Builds a new writer of JSON lines to `out`.
+    pub fn new(out: W) -> Self {
+        JsonLines { out }
+    }
+
+    /// Writes the summary of a puzzle, or another record, as a single line.
+    pub fn write<T: Serialize>(&mut self, record: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+/// Returns the selections for every implemented puzzle, optionally limited
+/// to a single year or to a single day of that year.
+///
+/// Fails if the requested year or day has no solution.
+pub fn selections(year: Option<u16>, day: Option<u8>) -> Result<Vec<Selection>, SelectionError> {
+    let mut years = crate::YEARS
+        .iter()
+        .filter(|&&(y, _)| year.is_none_or(|year| y == year))
+        .peekable();
+    if years.peek().is_none() {
+        return Err(SelectionError::UnimplementedYear);
+    }
+
+    let selections: Vec<Selection> = years
+        .flat_map(|&(year, days)| {
+            days.iter()
+                .filter(|&&d| day.is_none_or(|day| d == day))
+                .map(move |&day| Selection::new(year, day))
+        })
+        .collect();
+    if selections.is_empty() {
+        return Err(SelectionError::UnimplementedDay);
+    }
+    Ok(selections)
+}
+
+/// Returns the selections for the implemented puzzles matching a range
+/// specification such as `2016:1-10` (see [`Selection::parse_many`]).
+///
+/// Puzzles without a solution are left out, and it is an error if none of
+/// the matching puzzles have one.
+pub fn selections_matching(spec: &str) -> Result<Vec<Selection>, SelectionError> {
+    let selections: Vec<Selection> = Selection::parse_many(spec)?
+        .into_iter()
+        .filter(|puzzle| crate::days(puzzle.year()).contains(&puzzle.day()))
+        .collect();
+    if selections.is_empty() {
+        return Err(SelectionError::UnimplementedDay);
+    }
+    Ok(selections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::puzzle::input::with_source;
+    use crate::common::testing::FakeInputs;
+
+    #[test]
+    fn select_puzzles() {
+        let all = selections(None, None).unwrap();
+        let total: usize = crate::YEARS.iter().map(|(_, days)| days.len()).sum();
+        assert_eq!(total, all.len());
+
+        let year = selections(Some(2016), None).unwrap();
+        assert!(year.iter().all(|puzzle| puzzle.year() == 2016));
+
+        let day = selections(Some(2016), Some(15)).unwrap();
+        assert_eq!(1, day.len());
+        assert_eq!((2016, 15), (day[0].year(), day[0].day()));
+
+        assert_eq!(
+            Some(SelectionError::UnimplementedYear),
+            selections(Some(2014), None).err()
+        );
+        assert_eq!(
+            Some(SelectionError::UnimplementedDay),
+            selections(Some(2016), Some(26)).err()
+        );
+
+        let range = selections_matching("2015-2016:*").unwrap();
+        let implemented = crate::days(2015).len() + crate::days(2016).len();
+        assert_eq!(implemented, range.len());
+        let list = selections_matching("2016:15,1-2").unwrap();
+        let days: Vec<_> = list.iter().map(|puzzle| puzzle.day()).collect();
+        assert_eq!(vec![1, 2, 15], days);
+        assert_eq!(
+            Some(SelectionError::UnimplementedDay),
+            selections_matching("2010-2011:*").err()
+        );
+        assert_eq!(
+            Some(SelectionError::BadDay),
+            selections_matching("2016:1-30").err()
+        );
+    }
+
+    #[test]
+    fn export_json() {
+        let puzzle = Selection::new(2016, 15);
+        let inputs = FakeInputs::new().with(
+            &puzzle,
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+             Disc #2 has 2 positions; at time=0, it is at position 1.\n",
+        );
+
+        let mut seen = Vec::new();
+        let export = with_source(inputs, || {
+            Export::run(
+                vec![puzzle, Selection::new(2016, 16)],
+                |progress| match progress {
+                    Progress::Started(puzzle) => seen.push(puzzle.day()),
+                    Progress::Finished(summary) => seen.push(summary.puzzle().day() + 100),
+                },
+            )
+        });
+        assert_eq!(vec![15, 115, 16, 116], seen);
+
+        let json: serde_json::Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+        let puzzles = json["puzzles"].as_array().unwrap();
+        assert_eq!(2, puzzles.len());
+        assert_eq!("5", puzzles[0]["solution"]["part1"]["answer"]);
+        assert_eq!("85", puzzles[0]["solution"]["part2"]["answer"]);
+        assert!(puzzles[1]["error"].is_string());
+    }
+
+    #[test]
+    fn run_in_parallel() {
+        let disc = Selection::new(2016, 15);
+        let inputs = FakeInputs::new()
+            .with(
+                &disc,
+                "Disc #1 has 5 positions; at time=0, it is at position 4.\n",
+            )
+            .with(&Selection::new(2016, 19), "5");
+        let puzzles = || {
+            vec![
+                Selection::new(2016, 15),
+                Selection::new(2016, 16),
+                Selection::new(2016, 19),
+            ]
+        };
+
+        let mut seen = Vec::new();
+        let solve = |puzzle| with_source(inputs.clone(), || Summary::measure(puzzle, crate::route));
+        let parallel = Export::run_parallel(puzzles(), 2, solve, |progress| {
+            if let Progress::Finished(summary) = progress {
+                seen.push(summary.puzzle().day());
+            }
+        });
+        assert_eq!(vec![15, 16, 19], seen);
+
+        let serial = with_source(inputs, || Export::run(puzzles(), |_| {}));
+        let answers = |export: &Export| -> Vec<Option<(String, String)>> {
+            export
+                .puzzles()
+                .iter()
+                .map(|summary| {
+                    let solution = summary.result().as_ref().ok()?;
+                    let ans = |part: &Option<Answer>| part.as_ref().unwrap().ans().to_owned();
+                    Some((ans(&solution.0), ans(&solution.1)))
+                })
+                .collect()
+        };
+        assert_eq!(answers(&serial), answers(&parallel));
+    }
+
+    #[test]
+    fn stream_json_lines() {
+        let mut lines = JsonLines::new(Vec::new());
+        for day in &[15, 16] {
+            let summary = Summary::new(
+                Selection::new(2016, *day),
+                Err(From::from("some error")),
+                Default::default(),
+            );
+            lines.write(&summary).unwrap();
+        }
+
+        let out = String::from_utf8(lines.out).unwrap();
+        let days: Vec<u64> = out
+            .lines()
+            .map(|line| {
+                let summary: serde_json::Value = serde_json::from_str(line).unwrap();
+                summary["day"].as_u64().unwrap()
+            })
+            .collect();
+        assert_eq!(vec![15, 16], days);
+        assert!(out.ends_with('\n'));
+    }
+
+    #[test]
+    fn snapshot_table() {
+        use crate::common::puzzle::Solution;
+        use std::time::Duration;
+
+        let solved = |day, one: &str, two: Option<&str>, millis| {
+            Summary::new(
+                Selection::new(2016, day),
+                Ok(Solution::new(Some(Answer::new(one)), two.map(Answer::new))),
+                Duration::from_millis(millis),
+            )
+        };
+        let export = Export {
+            puzzles: vec![
+                solved(2, "99332", Some("DD483"), 2),
+                solved(8, "106", Some("#..#\n#..#"), 35),
+                solved(14, "a very long answer that will not fit", Some("1"), 1_250),
+                solved(25, "192", None, 7),
+                Summary::new(
+                    Selection::new(2016, 16),
+                    Err(From::from("some error")),
+                    Duration::from_millis(1),
+                ),
+            ],
+        };
+
+        insta::assert_snapshot!(export.to_table());
+    }
+}
@@ -0,0 +1,79 @@
+//! Downloading puzzle inputs from the Advent of Code website.
+//!
+//! Each user is given their own inputs, so inputs are downloaded on behalf
+//! of the user whose session token is found by [`Session::load`]. Downloaded
+//! inputs are written to the selection's input path, where later runs read
+//! them from disk like any other input.
+
+use crate::common::puzzle::input::{FileSource, InputSource};
+use crate::common::puzzle::Selection;
+use crate::site::Session;
+use std::error::Error;
+use std::fs;
+use std::io;
+
+#[derive(Debug, Default, Copy, Clone)]
+/// An input source that reads each puzzle's input file from disk, first
+/// downloading any file that is missing.
+pub struct FetchSource;
+
+impl InputSource for FetchSource {
+    fn read(&self, puzzle: &Selection) -> io::Result<String> {
+        fetch_missing(puzzle)?;
+        FileSource.read(puzzle)
+    }
+
+    fn read_lines(&self, puzzle: &Selection) -> io::Result<Vec<String>> {
+        fetch_missing(puzzle)?;
+        FileSource.read_lines(puzzle)
+    }
+}
+
+/// Downloads the input of the given puzzle to its input path, replacing any
+/// existing file.
+pub fn download(session: &Session, puzzle: &Selection) -> Result<(), Box<dyn Error>> {
+    let path = format!("/{}/day/{}/input", puzzle.year(), puzzle.day());
+    let input = session.get(&path)?;
+    if let Some(dir) = puzzle.path().parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(puzzle.path(), input)?;
+    Ok(())
+}
+
+/// Downloads the input of the given puzzle if its input file does not exist.
+fn fetch_missing(puzzle: &Selection) -> io::Result<()> {
+    if puzzle.path().exists() {
+        return Ok(());
+    }
+    let _span = tracing::info_span!("download_input", input = puzzle.path_str()).entered();
+    info!(
+        "downloading input for {} day {:02}",
+        puzzle.year(),
+        puzzle.day()
+    );
+    Session::load()
+        .and_then(|session| download(&session, puzzle))
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "{} is missing and could not be downloaded: {}",
+                    puzzle.path_str(),
+                    e
+                ),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn present_inputs_are_read_from_disk() {
+        let puzzle = Selection::new(2016, 2);
+        let input = FetchSource.read(&puzzle).unwrap();
+        assert_eq!(fs::read_to_string(puzzle.path()).unwrap(), input);
+    }
+}
@@ -0,0 +1,51 @@
+//! Entry points for the fuzz targets in `fuzz/`.
+//!
+//! Each function parses a single line of puzzle input with one of the
+//! crate's line parsers. The parsers must reject malformed lines with an
+//! error rather than panicking. Where a line that parses can still be
+//! unusable, the function also runs the parsed value the way the solution
+//! does, which must likewise fail with an error rather than a panic.
+//!
+//! This module is only available with the `fuzzing` feature enabled.
+
+use crate::y2016::day08::screen::{MiniScreen, ScreenInstruction};
+use crate::common::vm::assembunny::Instr;
+use crate::y2016::day15::Disc;
+use crate::y2016::day21::scrambler::ScrambleRule;
+use crate::y2018::day04::guard::GuardLogEntry;
+use crate::y2018::day10::Light;
+
+/// Parses a 2016 Day 21 scramble rule.
+pub fn scramble_rule(line: &str) {
+    let _ = line.parse::<ScrambleRule>();
+}
+
+/// Parses a 2016 Day 8 screen instruction and runs it on the puzzle's
+/// screen, forwards and backwards.
+pub fn screen_instruction(line: &str) {
+    if let Ok(instr) = line.parse::<ScreenInstruction>() {
+        let mut screen = MiniScreen::new(50, 6);
+        let _ = screen.process_instr(&instr);
+        let _ = screen.unprocess_instr(&instr);
+    }
+}
+
+/// Parses a 2016 Day 15 disc description.
+pub fn disc(line: &str) {
+    let _ = line.parse::<Disc>();
+}
+
+/// Parses a 2016 assembunny instruction.
+pub fn assembunny_instr(line: &str) {
+    let _ = line.parse::<Instr>();
+}
+
+/// Parses a 2018 Day 4 guard log entry.
+pub fn guard_log_entry(line: &str) {
+    let _ = line.parse::<GuardLogEntry>();
+}
+
+/// Parses a 2018 Day 10 light.
+pub fn light(line: &str) {
+    let _ = line.parse::<Light>();
+}
@@ -0,0 +1,238 @@
+//! Viewing a private leaderboard.
+//!
+//! Private leaderboards are published by the site as JSON documents, which
+//! are only served to members of the leaderboard. Each member's entry holds
+//! the time at which every star was earned, from which the time taken to
+//! solve each day is derived.
+
+use crate::common::util::date;
+use crate::site::Session;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Debug, Deserialize)]
+/// A private leaderboard for a single event.
+pub struct Leaderboard {
+    /// The year of the event, as a string.
+    event: String,
+    /// The members of the leaderboard, keyed by their ID.
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+/// A member of a private leaderboard.
+pub struct Member {
+    id: u64,
+    /// The member's display name, if they have set one.
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    /// The stars earned by the member, indexed by day and part.
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+/// A single star earned by a member.
+struct Star {
+    /// The Unix timestamp at which the star was earned.
+    get_star_ts: u64,
+}
+
+impl Leaderboard {
+    /// Fetches the leaderboard with the given ID for the given year.
+    pub fn fetch(session: &Session, year: u16, id: u64) -> Result<Self, Box<dyn Error>> {
+        let path = format!("/{}/leaderboard/private/view/{}.json", year, id);
+        Leaderboard::from_json(&session.get(&path)?)
+    }
+
+    /// Parses a leaderboard from the site's JSON document.
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns the year of the leaderboard's event.
+    pub fn year(&self) -> Option<u16> {
+        self.event.parse().ok()
+    }
+
+    /// Returns the members of the leaderboard, from highest to lowest local
+    /// score.
+    pub fn members(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// Renders the leaderboard as a table with a row for each member.
+    ///
+    /// Each day with any stars has a column holding the time that each
+    /// member took to solve it, measured from the puzzle's release.
+    pub fn to_table(&self) -> String {
+        let members = self.members();
+        let days: BTreeSet<u8> = members
+            .iter()
+            .flat_map(|m| m.completion_day_level.keys().copied())
+            .collect();
+        let width = members
+            .iter()
+            .map(|m| m.display_name().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Name".len());
+
+        let mut out = String::new();
+        write!(
+            out,
+            "{:>4} {:>5} {:>5}  {:<width$}",
+            "#",
+            "Score",
+            "Stars",
+            "Name",
+            width = width
+        )
+        .unwrap();
+        for day in &days {
+            write!(out, " {:>9}", format!("Day {}", day)).unwrap();
+        }
+        out.push('\n');
+
+        for (rank, member) in members.iter().enumerate() {
+            write!(
+                out,
+                "{:>4} {:>5} {:>5}  {:<width$}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name(),
+                width = width
+            )
+            .unwrap();
+            for &day in &days {
+                let cell = match (self.year(), member.completion_day_level.get(&day)) {
+                    (Some(year), Some(parts)) => solve_time(year, day, parts),
+                    _ => String::new(),
+                };
+                write!(out, " {:>9}", cell).unwrap();
+            }
+            // Members without stars on the last days leave trailing blanks.
+            out.truncate(out.trim_end_matches(' ').len());
+            out.push('\n');
+        }
+        out.push_str(
+            "\nTimes are measured from each puzzle's release; * marks days with only one star.\n",
+        );
+        out
+    }
+}
+
+impl Member {
+    /// Returns the member's name, or their ID if they are anonymous.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+/// Formats the time taken to earn the last star of a day, marking days with
+/// only one star.
+fn solve_time(year: u16, day: u8, parts: &BTreeMap<u8, Star>) -> String {
+    let last = parts.values().map(|star| star.get_star_ts).max();
+    let elapsed = match (last, date::release_time(year, day)) {
+        (Some(ts), Some(release)) => ts.saturating_sub(release as u64),
+        _ => return String::new(),
+    };
+    let marker = if parts.len() < 2 { "*" } else { "" };
+    if elapsed >= 24 * 60 * 60 {
+        format!(">24h{}", marker)
+    } else {
+        format!(
+            "{:02}:{:02}:{:02}{}",
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60,
+            marker
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Release of 2018 day 1, 2018-12-01T05:00:00Z.
+    const DAY_ONE: u64 = 1_543_640_400;
+
+    fn leaderboard() -> Leaderboard {
+        let json = format!(
+            r#"{{
+                "owner_id": 1,
+                "event": "2018",
+                "members": {{
+                    "1": {{
+                        "id": 1, "name": "ada", "stars": 3, "local_score": 7,
+                        "global_score": 0, "last_star_ts": {a2},
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {a1}, "star_index": 0}},
+                                   "2": {{"get_star_ts": {a2}, "star_index": 1}}}},
+                            "2": {{"1": {{"get_star_ts": {b1}, "star_index": 2}}}}
+                        }}
+                    }},
+                    "2": {{
+                        "id": 2, "name": null, "stars": 2, "local_score": 4,
+                        "global_score": 0, "last_star_ts": {c2},
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {c1}, "star_index": 0}},
+                                   "2": {{"get_star_ts": {c2}, "star_index": 1}}}}
+                        }}
+                    }},
+                    "3": {{
+                        "id": 3, "name": "bob", "stars": 0, "local_score": 0,
+                        "global_score": 0, "last_star_ts": 0,
+                        "completion_day_level": {{}}
+                    }}
+                }}
+            }}"#,
+            a1 = DAY_ONE + 300,
+            a2 = DAY_ONE + 754,
+            b1 = DAY_ONE + 86_400 + 3_600 * 30,
+            c1 = DAY_ONE + 3_600,
+            c2 = DAY_ONE + 3_661,
+        );
+        Leaderboard::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn parse_leaderboard() {
+        let leaderboard = leaderboard();
+        assert_eq!(Some(2018), leaderboard.year());
+
+        let names: Vec<String> = leaderboard
+            .members()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(vec!["ada", "(anonymous user #2)", "bob"], names);
+    }
+
+    #[test]
+    fn skip_unknown_days() {
+        let leaderboard = leaderboard();
+        let parts = &leaderboard.members()[0].completion_day_level[&1];
+        assert_eq!("", solve_time(2018, 0, parts));
+        assert_eq!("", solve_time(2018, 26, parts));
+    }
+
+    #[test]
+    fn snapshot_table() {
+        insta::assert_snapshot!(leaderboard().to_table());
+    }
+}
@@ -2,4 +2,113 @@
 pub mod common;
 pub mod y2015;
 pub mod y2016;
+pub mod y2017;
 pub mod y2018;
+pub mod y2019;
+
+pub mod bench;
+pub mod config;
+pub mod export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fetch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod leaderboard;
+pub mod list;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod progress;
+pub mod report;
+pub mod serve;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod site;
+pub mod trace;
+pub mod verify;
+pub mod viz;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch;
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+#[cfg(all(feature = "profiling", unix))]
+pub mod profile;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use crate::common::puzzle::{Result as PuzzleResult, Selection, SelectionError};
+
+/// The years that have solutions, paired with the days solved in each.
+pub const YEARS: &[(u16, &[u8])] = &[
+    (2015, y2015::DAYS),
+    (2016, y2016::DAYS),
+    (2017, y2017::DAYS),
+    (2018, y2018::DAYS),
+    (2019, y2019::DAYS),
+];
+
+/// Returns the days of the given year that have solutions, in order.
+pub fn days(year: u16) -> &'static [u8] {
+    YEARS
+        .iter()
+        .find(|&&(y, _)| y == year)
+        .map_or(&[], |&(_, days)| days)
+}
+
+/// Routes the selected puzzle to the solution for its year and day.
+///
+/// The run is traced by a `solve` span, with a child `setup` span covering
+/// the time spent fetching and parsing the input before the first part is
+/// solved. See [`trace`] for the full set of spans.
+pub fn route(puzzle: &Selection) -> PuzzleResult {
+    let _solve = tracing::info_span!("solve", year = puzzle.year(), day = puzzle.day()).entered();
+    trace::begin_setup();
+    let result: PuzzleResult = match puzzle.year() {
+        2015 => y2015::route(puzzle),
+        2016 => y2016::route(puzzle),
+        2017 => y2017::route(puzzle),
+        2018 => y2018::route(puzzle),
+        2019 => y2019::route(puzzle),
+        _ => Err(SelectionError::UnimplementedYear.into()),
+    };
+    // Solutions that fail before reaching their first part never end setup.
+    trace::end_setup();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::puzzle::{AnswerKey, PuzzleError};
+
+    #[test]
+    fn years_match_routes() {
+        for &(year, days) in YEARS {
+            for &day in days {
+                let puzzle = Selection::with_input(year, day, "/nonexistent/input.txt");
+                let err = route(&puzzle).expect_err("solution ran without input");
+                assert!(
+                    !matches!(err, PuzzleError::Unimplemented(_)),
+                    "{} day {}",
+                    year,
+                    day
+                );
+            }
+        }
+
+        let err = route(&Selection::new(2014, 1)).unwrap_err();
+        assert!(matches!(
+            err,
+            PuzzleError::Unimplemented(SelectionError::UnimplementedYear)
+        ));
+    }
+
+    #[test]
+    fn load_manifest() {
+        let key = AnswerKey::load().unwrap();
+        assert!(key.iter().count() > 0);
+        for (year, day, _) in key.iter() {
+            let routed = YEARS.iter().any(|&(y, days)| y == year && days.contains(&day));
+            assert!(routed, "{} day {} has answers but no solution", year, day);
+        }
+    }
+}
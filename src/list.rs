@@ -0,0 +1,54 @@
+//! Listing the puzzles that have solutions.
+//!
+//! The implemented days of each year are laid out as a calendar grid, with a
+//! row for each year and a column for each day of December. Days marked as
+//! slow in the answer manifest, whose solution tests are ignored by default,
+//! are flagged separately.
+
+use crate::common::puzzle::AnswerKey;
+use std::fmt::Write;
+
+/// The number of days in each event.
+const EVENT_DAYS: u8 = 25;
+
+/// Renders the grid of implemented days, reading slow puzzles from `key`.
+pub fn to_grid(key: &AnswerKey) -> String {
+    let mut out = String::from("    ");
+    for day in 1..=EVENT_DAYS {
+        write!(out, " {:>2}", day).unwrap();
+    }
+    out.push_str("  Days\n");
+
+    for &(year, days) in crate::YEARS {
+        write!(out, "{}", year).unwrap();
+        for day in 1..=EVENT_DAYS {
+            let mark = if !days.contains(&day) {
+                '.'
+            } else if key.get(year, day).is_some_and(|e| e.is_slow()) {
+                's'
+            } else {
+                '*'
+            };
+            write!(out, "  {}", mark).unwrap();
+        }
+        writeln!(out, "  {:>4}", days.len()).unwrap();
+    }
+
+    let total: usize = crate::YEARS.iter().map(|(_, days)| days.len()).sum();
+    writeln!(out, "\n{} puzzles implemented.", total).unwrap();
+    out.push_str("* implemented, s implemented but slow (tests ignored), . not implemented\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_grid() {
+        let key: AnswerKey = "[2016.5]\npart1 = \"801b56a7\"\nslow = true\n"
+            .parse()
+            .unwrap();
+        insta::assert_snapshot!(to_grid(&key));
+    }
+}
@@ -1,37 +1,1476 @@
-use advent_of_rust;
+use aoc_solutions;
 
-use advent_of_rust::common::puzzle::{Result, Selection, SelectionError, Summary};
-use std::error::Error;
-use std::io::Write;
-use std::{env, io, process, time};
+use aoc_solutions::bench::{self, Baseline, Bench, Change};
+use aoc_solutions::common::log::{self, Verbosity};
+use aoc_solutions::common::puzzle::{
+    self, answers, input, timings, AnswerKey, Selection, SelectionError, Status, Style, Summary,
+};
+use aoc_solutions::common::util::date;
+use aoc_solutions::config::Config;
+use aoc_solutions::export::{self, Export, JsonLines, Progress};
+use aoc_solutions::fetch::{self, FetchSource};
+use aoc_solutions::leaderboard::Leaderboard;
+use aoc_solutions::list;
+#[cfg(all(feature = "profiling", unix))]
+use aoc_solutions::profile::{self, Profiler};
+use aoc_solutions::progress::Calendar;
+use aoc_solutions::report::{self, Report};
+use aoc_solutions::serve;
+use aoc_solutions::site::Session;
+use aoc_solutions::trace;
+use aoc_solutions::verify::{self, Outcome};
+use aoc_solutions::viz;
+use aoc_solutions::watch::{self, Watcher};
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+use std::{env, fs, io, panic, process, thread};
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOC: puzzle::memory::CountingAlloc = puzzle::memory::CountingAlloc;
+
+/// How the results of a run are printed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    /// Human-readable text.
+    Text,
+    /// A JSON document with the same layout as the `export` command's.
+    Json,
+}
+
+/// Where and how the results of a run are written.
+#[derive(Debug, Clone)]
+struct Output {
+    /// How the results are printed.
+    format: Format,
+    /// Whether text results are colored.
+    style: Style,
+    /// Path to write a report of the answers and timings to, as Markdown or
+    /// CSV depending on its extension.
+    report: Option<String>,
+    /// Whether the puzzles are visualized, with the visual answers written
+    /// to files rather than printed.
+    viz: bool,
+    /// Path to stream a line of JSON to as each puzzle of a batch finishes,
+    /// or `-` for stdout.
+    jsonl: Option<String>,
+}
+
+/// Number of attempts the `wait` command makes to download a newly released
+/// input.
+const WAIT_ATTEMPTS: usize = 5;
+
+/// Exit code for invalid command-line arguments.
+const EXIT_USAGE: i32 = 2;
+
+/// Cargo features of the runner that this build was made with, which are
+/// kept when it rebuilds itself.
+const FEATURES: &[&str] = &[
+    #[cfg(feature = "memory")]
+    "memory",
+    #[cfg(feature = "profiling")]
+    "profiling",
+];
+
+/// A subcommand of the runner.
+struct Command {
+    /// Name of the command, given as the first argument.
+    name: &'static str,
+    /// Arguments accepted by the command, as shown in the usage.
+    usage: &'static str,
+    /// Runs the command with the arguments following its name.
+    run: fn(Vec<String>, &Output) -> Result<(), SelectionError>,
+}
+
+/// The commands of the runner, in the order they are listed in the usage.
+///
+/// The first command is run when the first argument names no command.
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "solve",
+        usage: "(<year:4> [day:2] | today | latest) [input file] [--visualize] [--profile] [--trace] [--param <key>=<value>]... [--timeout <duration>] [--warmup <n>] [--watch | --watch-src] | (<year:4> | <years>:<days>) [--jobs <n>] [--timeout <duration>] [--warmup <n>]",
+        run: run_solve,
+    },
+    Command {
+        name: "all",
+        usage: "[--jobs <n>] [--timeout <duration>] [--warmup <n>]",
+        run: run_all,
+    },
+    Command {
+        name: "list",
+        usage: "",
+        run: run_list,
+    },
+    Command {
+        name: "fetch",
+        usage: "<year:4> <day:2>",
+        run: run_fetch,
+    },
+    Command {
+        name: "wait",
+        usage: "[year:4 day:2]",
+        run: run_wait,
+    },
+    Command {
+        name: "bench",
+        usage: "(<year:4> <day:2> [input file] | <year:4>) [--iterations <n>] [--warmup <n>] [--save <file>] [--baseline <file>] [--threshold <percent>]",
+        run: run_bench,
+    },
+    Command {
+        name: "export",
+        usage: "[year:4 [day:2]] [--out <file>]",
+        run: run_export,
+    },
+    Command {
+        name: "serve",
+        usage: "[address]",
+        run: run_server,
+    },
+    Command {
+        name: "report",
+        usage: "[--out <file>] [--include-slow]",
+        run: run_report,
+    },
+    Command {
+        name: "verify",
+        usage: "[year:4 [day:2]] [--include-slow] [--record]",
+        run: run_verify,
+    },
+    Command {
+        name: "progress",
+        usage: "",
+        run: run_progress,
+    },
+    Command {
+        name: "leaderboard",
+        usage: "<id> [--year <year:4>]",
+        run: run_leaderboard,
+    },
+];
 
 /// Application entry point.
 fn main() {
-    let puzzle = Selection::from_args(env::args()).unwrap_or_else(|err| {
-        eprintln!("Invalid arguments: {}", err.description());
-        eprintln!("Usage: <year:4> <day:2> [input file]");
+    let (args, config) =
+        take_config_flags(env::args().skip(1)).unwrap_or_else(|err| exit_usage(err));
+    if let Some(dir) = config.input_dir() {
+        puzzle::set_input_dir(dir);
+    }
+    config.install();
+
+    let (args, output) = take_output_flags(args).unwrap_or_else(|err| exit_usage(err));
+    let (mut args, verbosity) = take_verbosity(args).unwrap_or_else(|err| exit_usage(err));
+    log::set_verbosity(verbosity);
+
+    let command = take_command(&mut args);
+
+    // Inputs missing from disk are downloaded as the solutions ask for them.
+    let run = input::with_source(FetchSource, || (command.run)(args, &output));
+    if let Err(err) = run {
+        exit_usage(err);
+    }
+}
+
+/// Removes the name of the command from the front of the arguments, returning
+/// the named command, or the first command if no command is named.
+fn take_command(args: &mut Vec<String>) -> &'static Command {
+    let named = args
+        .first()
+        .and_then(|name| COMMANDS.iter().find(|command| command.name == name));
+    match named {
+        Some(command) => {
+            args.remove(0);
+            command
+        }
+        // A bare `<year> [day]` selects puzzles to solve.
+        None => &COMMANDS[0],
+    }
+}
+
+/// Prints the reason that the command-line arguments were rejected along
+/// with the program's usage, then exits.
+fn exit_usage(err: SelectionError) -> ! {
+    eprintln!("Invalid arguments: {}", err);
+    for (i, command) in COMMANDS.iter().enumerate() {
+        let prefix = if i == 0 { "Usage:" } else { "      " };
+        let line = format!("{} {} {}", prefix, command.name, command.usage);
+        eprintln!("{}", line.trim_end());
+    }
+    eprintln!("The command may be omitted to solve puzzles, as in `2016 2`.");
+    eprintln!("The results of a run are printed as text unless `--format json` is given,");
+    eprintln!("and may be written to a Markdown or CSV file with `--report <file>`.");
+    eprintln!("With `--viz`, visual answers are written to files under `out/` instead.");
+    eprintln!("Batch, `bench`, and `export` runs stream a line of JSON as each puzzle finishes");
+    eprintln!("with `--jsonl <file|->`.");
+    eprintln!(
+        "Messages logged by the solutions are shown with `-v` or `-vv`, and hidden with `-q`."
+    );
+    eprintln!("Text is colored on terminals unless `--color never` is given.");
+    eprintln!("Settings are read from `aoc.toml` or `--config <file>`, and inputs from");
+    eprintln!("`--input-dir <dir>` or `$AOC_INPUT_DIR` if given.");
+    eprintln!("Exits with 2 for invalid arguments, or when puzzles do not solve, with 3 if");
+    eprintln!("unimplemented, 4 if their input is missing, 5 if their solution failed, or 6");
+    eprintln!("if it ran longer than `--timeout <duration>`. `verify` also exits with 5 when an");
+    eprintln!("answer differs from the manifest, as does `bench` when a puzzle regressed.");
+    process::exit(EXIT_USAGE);
+}
+
+/// Exits with the code for the worst of the given statuses, unless every
+/// puzzle was solved.
+fn exit_with<I: IntoIterator<Item = Status>>(statuses: I) {
+    let code = match statuses.into_iter().max() {
+        None | Some(Status::Solved) => return,
+        Some(Status::Unimplemented) => 3,
+        Some(Status::MissingInput) => 4,
+        Some(Status::Failed) => 5,
+        Some(Status::TimedOut) => 6,
+    };
+    process::exit(code);
+}
+
+/// Rejects any arguments given to a command that takes none.
+fn no_args(args: &[String]) -> Result<(), SelectionError> {
+    if args.is_empty() {
+        Ok(())
+    } else {
+        Err(SelectionError::BadFlag)
+    }
+}
+
+/// Runs the `solve` command, which solves a single puzzle, or each puzzle of
+/// a year when only the year is given, or of a range such as `2016:1-10`.
+fn run_solve(mut args: Vec<String>, output: &Output) -> Result<(), SelectionError> {
+    let limits = take_limits(&mut args)?;
+    let watching = take_watch(&mut args);
+    // A lone day, which is never more than two digits, is of the default year.
+    if let (Some(year), [day]) = (Config::current().year(), &args[..]) {
+        if day.len() <= 2 {
+            args.insert(0, year.to_string());
+        }
+    }
+    if let [year] = &args[..] {
+        if let Ok(year) = year.parse() {
+            if watching.is_some() {
+                return Err(SelectionError::BadFlag);
+            }
+            return run_year(year, limits, output);
+        }
+        if year.contains(':') {
+            if watching.is_some() {
+                return Err(SelectionError::BadFlag);
+            }
+            run_batch(export::selections_matching(year)?, limits, output);
+            return Ok(());
+        }
+    }
+    if limits.jobs.is_some() {
+        return Err(SelectionError::BadFlag);
+    }
+
+    // The selection's parser expects the executable path to come first.
+    let args = std::iter::once(String::new()).chain(args);
+    let puzzle = Selection::from_args(args)?;
+    let puzzle = if output.viz {
+        puzzle.with_visualize(true)
+    } else {
+        puzzle
+    };
+
+    if puzzle.trace() {
+        trace::install();
+    }
+    if let Some(watching) = watching {
+        run_watched(puzzle, limits, watching, output);
+    }
+
+    let summary = if puzzle.profile() {
+        run_profiled(puzzle, limits, output)
+    } else {
+        run_solution(puzzle, limits, output)
+    };
+
+    write_report(output, Some(&summary));
+    match output.format {
+        Format::Text => print!("{}", summary.styled(output.style)),
+        Format::Json => println!("{}", to_json(&summary)),
+    }
+    write_visuals(output, Some(&summary));
+    exit_with(Some(summary.status()));
+    Ok(())
+}
+
+/// Removes the `--config <file>` and `--input-dir <dir>` flags from the
+/// command-line arguments, returning the remaining arguments and the
+/// configuration, which is read from the given file or the first one found.
+///
+/// The input directory is taken from the flag, then from `$AOC_INPUT_DIR`,
+/// and only then from the configuration.
+fn take_config_flags<I>(args: I) -> Result<(Vec<String>, Config), SelectionError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut rest = Vec::new();
+    let mut path = None;
+    let mut input_dir = None;
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--config" => path = Some(args.next().ok_or(SelectionError::BadFlag)?),
+            "--input-dir" => input_dir = Some(args.next().ok_or(SelectionError::BadFlag)?),
+            _ => rest.push(arg),
+        }
+    }
+
+    let config = match path {
+        Some(path) => Config::from_file(path),
+        None => Config::load(),
+    };
+    let mut config = config.unwrap_or_else(|err| {
+        eprintln!("Failed to load configuration: {}", err);
         process::exit(1);
     });
+    let env_dir = env::var(puzzle::INPUT_DIR_VAR)
+        .ok()
+        .filter(|dir| !dir.is_empty());
+    if let Some(dir) = input_dir.or(env_dir) {
+        config = config.with_input_dir(dir);
+    }
+    Ok((rest, config))
+}
 
-    let summary = run_solution(puzzle);
+/// Removes the `--format <text|json>`, `--report <file>`,
+/// `--color <auto|always|never>`, `--viz`, and `--jsonl <file|->` flags from
+/// the command-line arguments, returning the remaining arguments and the
+/// selected output.
+///
+/// Unless `--color` says otherwise, text is colored only when stdout is a
+/// terminal and `NO_COLOR` is unset.
+fn take_output_flags<I>(args: I) -> Result<(Vec<String>, Output), SelectionError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut rest = Vec::new();
+    let mut color = None;
+    let mut output = Output {
+        format: Format::Text,
+        style: Style::Plain,
+        report: None,
+        viz: false,
+        jsonl: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--viz" => output.viz = true,
+            "--format" => {
+                output.format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err(SelectionError::BadFlag),
+                };
+            }
+            "--report" => output.report = Some(args.next().ok_or(SelectionError::BadFlag)?),
+            "--jsonl" => output.jsonl = Some(args.next().ok_or(SelectionError::BadFlag)?),
+            "--color" => {
+                color = match args.next().as_deref() {
+                    Some("auto") => None,
+                    Some("always") => Some(true),
+                    Some("never") => Some(false),
+                    _ => return Err(SelectionError::BadFlag),
+                };
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    let color =
+        color.unwrap_or_else(|| io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none());
+    if color {
+        output.style = Style::Ansi;
+    }
+    Ok((rest, output))
+}
 
-    print!("{}", summary);
+/// Removes the `-v`, `-vv`, and `-q` flags from the given arguments,
+/// returning the remaining arguments and the verbosity of the messages that
+/// the solutions log.
+///
+/// `-v` may be repeated, and cannot be combined with `-q`.
+fn take_verbosity(args: Vec<String>) -> Result<(Vec<String>, Verbosity), SelectionError> {
+    let mut rest = Vec::new();
+    let mut count = 0;
+    let mut quiet = false;
+    for arg in args {
+        match &arg[..] {
+            "-q" | "--quiet" => quiet = true,
+            "-v" | "--verbose" => count += 1,
+            "-vv" => count += 2,
+            _ => rest.push(arg),
+        }
+    }
+    match (quiet, count) {
+        (false, count) => Ok((rest, Verbosity::from_count(count))),
+        (true, 0) => Ok((rest, Verbosity::Quiet)),
+        (true, _) => Err(SelectionError::BadFlag),
+    }
+}
+
+/// Writes a report of the given runs, if one was requested.
+fn write_report<'a, I>(output: &Output, summaries: I)
+where
+    I: IntoIterator<Item = &'a Summary>,
+{
+    let path = match &output.report {
+        Some(path) => path,
+        None => return,
+    };
+    let report = timings::render(summaries, timings::Format::from_path(path));
+    if let Err(err) = fs::write(path, report) {
+        eprintln!("Failed to write {}: {}", path, err);
+        process::exit(1);
+    }
+}
+
+/// Writes the visual answers of the given runs to files under
+/// [`viz::VIZ_DIR`] if `--viz` was given.
+///
+/// Otherwise, the renderings attached to their answers by `--visualize` are
+/// printed, on stderr if the results are printed as JSON.
+fn write_visuals<'a, I>(output: &Output, summaries: I)
+where
+    I: IntoIterator<Item = &'a Summary>,
+{
+    for summary in summaries {
+        if output.viz {
+            match viz::write(viz::VIZ_DIR, summary) {
+                Ok(paths) => {
+                    for path in paths {
+                        eprintln!("Wrote visualization to {}", path.display());
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Failed to write visualization: {}", err);
+                    process::exit(1);
+                }
+            }
+            continue;
+        }
+        let solution = match summary.result() {
+            Ok(solution) => solution,
+            Err(_) => continue,
+        };
+        for answer in [&solution.0, &solution.1].iter().filter_map(|a| a.as_ref()) {
+            if let Some(artifact) = answer.artifact() {
+                let end = if artifact.ends_with('\n') { "" } else { "\n" };
+                match output.format {
+                    Format::Text => print!("\n{}{}", artifact, end),
+                    Format::Json => eprint!("\n{}{}", artifact, end),
+                }
+            }
+        }
+    }
+}
+
+/// Opens the stream of JSON lines requested with `--jsonl`, if any.
+fn open_jsonl(output: &Output) -> Option<JsonLines<Box<dyn Write>>> {
+    output.jsonl.as_ref().map(|path| {
+        let out: Box<dyn Write> = if path == "-" {
+            Box::new(io::stdout())
+        } else {
+            Box::new(fs::File::create(path).unwrap_or_else(|err| {
+                eprintln!("Failed to create {}: {}", path, err);
+                process::exit(1);
+            }))
+        };
+        JsonLines::new(out)
+    })
+}
+
+/// Writes a line to the stream of JSON lines, if one was requested.
+fn stream_line<T: Serialize>(stream: &mut Option<JsonLines<Box<dyn Write>>>, record: &T) {
+    if let Some(stream) = stream.as_mut() {
+        if let Err(err) = stream.write(record) {
+            eprintln!("Failed to stream summary: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints the results of a run to stdout, or to stderr if stdout is taken
+/// by the stream of JSON lines.
+fn print_results(output: &Output, results: &str) {
+    if output.jsonl.as_deref() == Some("-") {
+        eprint!("{}", results);
+    } else {
+        print!("{}", results);
+    }
+}
+
+/// Renders the summary of a run as a pretty-printed JSON document.
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("failed to serialize summary")
+}
+
+/// The changes that a watched run is repeated after.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Watch {
+    /// Changes to the puzzle's input.
+    Input,
+    /// Changes to the puzzle's input or to the sources of the solutions.
+    Source,
+}
+
+/// Removes the `--watch` and `--watch-src` flags from the arguments of a
+/// run, returning what the run should be repeated after, if anything.
+fn take_watch(args: &mut Vec<String>) -> Option<Watch> {
+    let watch = if args.iter().any(|arg| arg == "--watch-src") {
+        Some(Watch::Source)
+    } else if args.iter().any(|arg| arg == "--watch") {
+        Some(Watch::Input)
+    } else {
+        None
+    };
+    args.retain(|arg| arg != "--watch" && arg != "--watch-src");
+    watch
+}
+
+/// Solves the specified puzzle, then solves it again whenever its input
+/// changes, printing a fresh summary each time.
+///
+/// When watching the sources, a change to them rebuilds the runner and
+/// restarts it with the same arguments.
+fn run_watched(puzzle: Selection, limits: Limits, watching: Watch, output: &Output) -> ! {
+    let mut input = Watcher::new(Some(puzzle.path()));
+    let mut source = Watcher::new(match watching {
+        Watch::Input => Vec::new(),
+        Watch::Source => watch::source_dirs(),
+    });
+
+    loop {
+        if output.style == Style::Ansi {
+            // Clear the terminal, so that only the latest run is shown.
+            print!("\x1b[2J\x1b[H");
+        }
+        let summary = run_solution(puzzle.clone(), limits, output);
+        write_report(output, Some(&summary));
+        match output.format {
+            Format::Text => print!("{}", summary.styled(output.style)),
+            Format::Json => println!("{}", to_json(&summary)),
+        }
+        write_visuals(output, Some(&summary));
+        eprintln!("Watching for changes, press Ctrl-C to stop ...");
+
+        if watch::wait_for_change(&mut [&mut input, &mut source]) == 1 {
+            rebuild_and_restart();
+        }
+    }
+}
+
+/// Rebuilds the runner, then replaces this process with the new build run
+/// with the same arguments.
+///
+/// The build keeps the features that this one was made with, and returns
+/// if it fails, so that watching can carry on.
+fn rebuild_and_restart() {
+    eprintln!("Sources changed, rebuilding ...");
+    let mut cargo = process::Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    cargo
+        .args(["build", "--bin", env!("CARGO_BIN_NAME")])
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    if !FEATURES.is_empty() {
+        cargo.args(["--features", &FEATURES.join(",")]);
+    }
+    match cargo.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            eprintln!("Build failed, watching for changes ...");
+            return;
+        }
+        Err(err) => {
+            eprintln!("Failed to run cargo: {}", err);
+            return;
+        }
+    }
+
+    // The build replaces the running executable, whose path as reported by
+    // `current_exe` then names the old, deleted file, so the new build is
+    // run by the name that this one was started with.
+    let mut args = env::args_os();
+    let exe = args.next().expect("missing executable path");
+    let mut runner = process::Command::new(exe);
+    runner.args(args);
+    restart(runner);
+}
+
+/// Replaces this process with the given command.
+#[cfg(unix)]
+fn restart(mut command: process::Command) -> ! {
+    use std::os::unix::process::CommandExt;
+
+    let err = command.exec();
+    eprintln!("Failed to restart: {}", err);
+    process::exit(1);
+}
+
+/// Runs the given command in place of this process, exiting with its status.
+#[cfg(not(unix))]
+fn restart(mut command: process::Command) -> ! {
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(err) => {
+            eprintln!("Failed to restart: {}", err);
+            process::exit(1);
+        }
+    }
 }
 
 /// Solves the specified puzzle, returning a summary of its solution.
-fn run_solution(puzzle: Selection) -> Summary {
-    let start = time::Instant::now();
-    print!("Solving {:4} day {:02} ... ", puzzle.year(), puzzle.day());
-    io::stdout().flush().expect("failed to write to stdout");
+///
+/// Progress is reported on stdout, unless stdout is reserved for a JSON
+/// document.
+fn run_solution(puzzle: Selection, limits: Limits, output: &Output) -> Summary {
+    let mut log: Box<dyn Write> = match output.format {
+        Format::Text => Box::new(io::stdout()),
+        Format::Json => Box::new(io::stderr()),
+    };
+    write!(
+        log,
+        "Solving {:4} day {:02} ... ",
+        puzzle.year(),
+        puzzle.day()
+    )
+    .and_then(|_| log.flush())
+    .expect("failed to write progress");
+
+    let summary = solve(puzzle, limits);
 
-    let solution: Result = match puzzle.year() {
-        2015 => advent_of_rust::y2015::route(&puzzle),
-        2016 => advent_of_rust::y2016::route(&puzzle),
-        2018 => advent_of_rust::y2018::route(&puzzle),
-        _ => Err(Box::new(SelectionError::UnimplementedYear)),
+    let status = summary.status();
+    let padding = if status == Status::Solved { "" } else { " " };
+    writeln!(
+        log,
+        "{}{}{}",
+        padding,
+        status_label(status, output.style),
+        contention_note(&summary)
+    )
+    .expect("failed to write progress");
+    summary
+}
+
+/// Solves a puzzle, downloading its input if it is missing, and gives up
+/// once the timeout of `limits` has passed, if given.
+///
+/// The puzzle is first solved the given number of warm-up times without
+/// reporting the results, so that its input has been read and the caches
+/// are warm by the time it is timed. The first warm-up run that does not
+/// solve the puzzle is reported instead.
+///
+/// A solution that panics is reported as a failure, as it is when timed on
+/// its own thread, so that batches and watched runs carry on past it.
+fn solve(puzzle: Selection, limits: Limits) -> Summary {
+    let route = |puzzle: &Selection| {
+        panic::catch_unwind(|| input::with_source(FetchSource, || aoc_solutions::route(puzzle)))
+            .unwrap_or_else(|_| Err(From::from("solution panicked")))
+    };
+    let run = |puzzle| match limits.timeout {
+        Some(timeout) => Summary::measure_within(puzzle, timeout, route),
+        None => Summary::measure(puzzle, route),
     };
+    for _ in 0..limits.warmup {
+        let summary = run(puzzle.clone());
+        if summary.status() != Status::Solved {
+            return summary;
+        }
+    }
+    run(puzzle)
+}
+
+/// Returns the label reported in the progress of a run that ended with the
+/// given status.
+fn status_label(status: Status, style: Style) -> &'static str {
+    match (status, style) {
+        (Status::Solved, Style::Plain) => "OK",
+        (Status::TimedOut, Style::Plain) => "TIMED OUT",
+        (_, Style::Plain) => "FAILED",
+        (Status::Solved, Style::Ansi) => "\x1b[32mOK\x1b[0m",
+        (Status::TimedOut, Style::Ansi) => "\x1b[33mTIMED OUT\x1b[0m",
+        (_, Style::Ansi) => "\x1b[31mFAILED\x1b[0m",
+    }
+}
+
+/// Solves the specified puzzle while sampling its execution, then writes a
+/// flamegraph of the samples.
+#[cfg(all(feature = "profiling", unix))]
+fn run_profiled(puzzle: Selection, limits: Limits, output: &Output) -> Summary {
+    let profiler = Profiler::start(&puzzle).unwrap_or_else(|err| {
+        eprintln!("Failed to start profiler: {}", err);
+        process::exit(1);
+    });
+
+    let summary = run_solution(puzzle, limits, output);
+
+    match profiler.finish(profile::PROFILE_DIR) {
+        Ok(path) => eprintln!("Flamegraph: {}", path.display()),
+        Err(err) => eprintln!("Failed to write flamegraph: {}", err),
+    }
+    summary
+}
+
+/// Exits with an error, since profiling support was not compiled in.
+#[cfg(not(all(feature = "profiling", unix)))]
+fn run_profiled(_puzzle: Selection, _limits: Limits, _output: &Output) -> Summary {
+    eprintln!("Profiling requires building with `--features profiling` on a Unix platform");
+    process::exit(1);
+}
+
+/// Runs the `all` command, which solves every implemented puzzle.
+fn run_all(mut args: Vec<String>, output: &Output) -> Result<(), SelectionError> {
+    let limits = take_limits(&mut args)?;
+    no_args(&args)?;
+    let puzzles = export::selections(None, None).expect("no puzzles are implemented");
+    run_batch(puzzles, limits, output);
+    Ok(())
+}
+
+/// Solves every implemented puzzle of the given year.
+fn run_year(year: u16, limits: Limits, output: &Output) -> Result<(), SelectionError> {
+    run_batch(export::selections(Some(year), None)?, limits, output);
+    Ok(())
+}
+
+/// Limits on the resources used to solve puzzles, and how they are timed.
+#[derive(Debug, Default, Copy, Clone)]
+struct Limits {
+    /// Number of puzzles to solve at once, if given.
+    jobs: Option<usize>,
+    /// Time after which a puzzle is given up on, if given.
+    timeout: Option<Duration>,
+    /// Number of untimed runs of each puzzle before the one that is timed.
+    warmup: usize,
+}
+
+/// Removes the `--jobs <n>`, `--timeout <duration>`, and `--warmup <n>`
+/// flags from the arguments of a run, returning the limits that they set.
+///
+/// Timeouts are a number of seconds, optionally suffixed by `ms`, `s`, or
+/// `m`, e.g. `30s`. Unless given, the number of warm-up runs is taken from
+/// the configuration, and is otherwise zero.
+fn take_limits(args: &mut Vec<String>) -> Result<Limits, SelectionError> {
+    let mut limits = Limits {
+        warmup: Config::current().warmup().unwrap_or(0),
+        ..Limits::default()
+    };
+    while let Some(flag) = args
+        .iter()
+        .position(|arg| arg == "--jobs" || arg == "--timeout" || arg == "--warmup")
+    {
+        let value = args.get(flag + 1).ok_or(SelectionError::BadFlag)?;
+        match &args[flag][..] {
+            "--jobs" => {
+                let jobs = value.parse().ok().filter(|&n| n > 0);
+                limits.jobs = Some(jobs.ok_or(SelectionError::BadFlag)?);
+            }
+            "--timeout" => {
+                limits.timeout = Some(parse_timeout(value).ok_or(SelectionError::BadFlag)?);
+            }
+            _ => limits.warmup = value.parse().map_err(|_| SelectionError::BadFlag)?,
+        }
+        args.drain(flag..flag + 2);
+    }
+    Ok(limits)
+}
+
+/// Parses a positive duration such as `500ms`, `30s`, `1.5m`, or `30`.
+fn parse_timeout(s: &str) -> Option<Duration> {
+    let (n, unit) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let n: f64 = n.parse().ok()?;
+    let secs = match unit {
+        "ms" => n / 1000.0,
+        "s" => n,
+        "m" => n * 60.0,
+        _ => return None,
+    };
+    if secs.is_finite() && secs > 0.0 {
+        Some(Duration::from_secs_f64(secs))
+    } else {
+        None
+    }
+}
+
+/// Returns the note added to the progress of a run that was timed while
+/// solutions that had run out of time were still going.
+fn contention_note(summary: &Summary) -> &'static str {
+    if summary.contended() {
+        " (contended)"
+    } else {
+        ""
+    }
+}
+
+/// Solves the given puzzles, several at a time (one per available core by
+/// default), then prints a table of their answers and timings along with
+/// aggregate statistics, or the same JSON document as the `export` command.
+fn run_batch(puzzles: Vec<Selection>, limits: Limits, output: &Output) {
+    let puzzles = if output.viz {
+        let visualize = |puzzle: Selection| puzzle.with_visualize(true);
+        puzzles.into_iter().map(visualize).collect()
+    } else {
+        puzzles
+    };
+    let jobs = limits
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from));
+    let solve = |puzzle| solve(puzzle, limits);
+    let mut stream = open_jsonl(output);
+    let results = Export::run_parallel(puzzles, jobs, solve, |progress| {
+        if let Progress::Finished(summary) = progress {
+            let puzzle = summary.puzzle();
+            eprintln!(
+                "Solved {:4} day {:02} ... {}{}",
+                puzzle.year(),
+                puzzle.day(),
+                status_label(summary.status(), Style::Plain),
+                contention_note(summary)
+            );
+            stream_line(&mut stream, summary);
+        }
+    });
+    write_report(output, results.puzzles());
+    match output.format {
+        Format::Text => print_results(output, &results.to_table()),
+        Format::Json => print_results(output, &(to_json(&results) + "\n")),
+    }
+    write_visuals(output, results.puzzles());
+    exit_with(results.puzzles().iter().map(Summary::status));
+}
+
+/// Runs the `list` command, which prints a calendar grid of the days with
+/// solutions in each year.
+fn run_list(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    no_args(&args)?;
+    let key = AnswerKey::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load answer manifest: {}", err);
+        process::exit(1);
+    });
+    print!("{}", list::to_grid(&key));
+    Ok(())
+}
+
+/// Runs the `fetch` command, which downloads the input of a single puzzle,
+/// replacing any existing input file.
+fn run_fetch(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    let (year, day) = match &args[..] {
+        [year, day] => (
+            year.parse().map_err(|_| SelectionError::BadYear)?,
+            day.parse().map_err(|_| SelectionError::BadDay)?,
+        ),
+        _ => return Err(SelectionError::NoSelection),
+    };
+    let puzzle = Selection::new(year, day);
+
+    let session = Session::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load session: {}", err);
+        process::exit(1);
+    });
+    if let Err(err) = fetch::download(&session, &puzzle) {
+        eprintln!("Failed to download {}: {}", puzzle.path_str(), err);
+        process::exit(1);
+    }
+    eprintln!("Wrote input to {}", puzzle.path_str());
+    Ok(())
+}
+
+/// Runs the `wait` command, which counts down to the release of the next
+/// puzzle (or the given one), then downloads its input and solves it if a
+/// solution exists.
+fn run_wait(args: Vec<String>, output: &Output) -> Result<(), SelectionError> {
+    let (year, day) = match &args[..] {
+        [] => date::next_release(date::unix_now()),
+        [year, day] => (
+            year.parse().map_err(|_| SelectionError::BadYear)?,
+            day.parse().map_err(|_| SelectionError::BadDay)?,
+        ),
+        _ => return Err(SelectionError::BadFlag),
+    };
+    let release = date::release_time(year, day).ok_or(SelectionError::BadDay)?;
+    let session = Session::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load session: {}", err);
+        process::exit(1);
+    });
+
+    loop {
+        let remaining = release - date::unix_now();
+        if remaining <= 0 {
+            break;
+        }
+        eprint!(
+            "\r{:4} day {:02} unlocks in {:02}:{:02}:{:02} ",
+            year,
+            day,
+            remaining / 3600,
+            remaining / 60 % 60,
+            remaining % 60
+        );
+        thread::sleep(Duration::from_secs(1));
+    }
+    eprintln!("\r{:4} day {:02} is unlocked{:10}", year, day, "");
+
+    // The site may take a moment to serve a puzzle's input after its release.
+    let puzzle = Selection::new(year, day);
+    let mut attempts = 0;
+    while !puzzle.path().exists() {
+        let err = match fetch::download(&session, &puzzle) {
+            Ok(()) => break,
+            Err(err) => err,
+        };
+        attempts += 1;
+        if attempts == WAIT_ATTEMPTS {
+            eprintln!("Failed to download {}: {}", puzzle.path_str(), err);
+            process::exit(1);
+        }
+        thread::sleep(Duration::from_secs(2));
+    }
+    eprintln!("Input is at {}", puzzle.path_str());
+
+    if aoc_solutions::days(year).contains(&day) {
+        run_solve(vec![year.to_string(), day.to_string()], output)
+    } else {
+        eprintln!("No solution for {:4} day {:02} yet", year, day);
+        Ok(())
+    }
+}
+
+/// Arguments of the `bench` command.
+struct BenchArgs {
+    /// The puzzles to benchmark.
+    puzzles: Vec<Selection>,
+    /// Number of measured runs of each puzzle.
+    runs: usize,
+    /// Number of warm-up runs of each puzzle, which are discarded.
+    warmup: usize,
+    /// Path to save the median timings to.
+    save: Option<String>,
+    /// Path of a saved baseline to compare the timings with.
+    baseline: Option<String>,
+    /// Slowdown from the baseline, as a percentage, beyond which a puzzle
+    /// is reported as a regression.
+    threshold: f64,
+}
+
+/// Runs the `bench` command, which solves a puzzle, or every puzzle of a
+/// year, many times and reports the distribution of the timings.
+///
+/// Exits with `5`, as for a failed solution, if any puzzle failed or
+/// regressed from the baseline.
+fn run_bench(args: Vec<String>, output: &Output) -> Result<(), SelectionError> {
+    let args = parse_bench_args(args.into_iter())?;
+
+    let load = |path: &String| {
+        Baseline::from_file(path).unwrap_or_else(|err| {
+            eprintln!("Failed to load baseline: {}", err);
+            process::exit(1);
+        })
+    };
+    let baseline = args.baseline.as_ref().map(load);
+    // Timings are saved alongside those of the puzzles benchmarked earlier.
+    let mut saved = args.save.as_ref().map(|path| {
+        if Path::new(path).exists() {
+            load(path)
+        } else {
+            Baseline::default()
+        }
+    });
+
+    let mut stream = open_jsonl(output);
+    let mut failed = false;
+    let mut regressions = Vec::new();
+    for (i, puzzle) in args.puzzles.iter().enumerate() {
+        if i > 0 {
+            print_results(output, "\n");
+        }
+        eprintln!(
+            "Benchmarking {:4} day {:02} ...",
+            puzzle.year(),
+            puzzle.day()
+        );
+        let bench = match Bench::run(puzzle, args.runs, args.warmup) {
+            Ok(bench) => bench,
+            Err(err) => {
+                eprintln!("Failed to execute solution: {}", err);
+                failed = true;
+                continue;
+            }
+        };
+        print_results(output, &bench.to_string());
+        stream_line(&mut stream, &bench.medians());
+
+        let comparison = baseline
+            .as_ref()
+            .and_then(|baseline| baseline.compare(&bench, args.threshold));
+        if let Some(comparison) = comparison {
+            print_results(output, &format!("\n{}", comparison.render(output.style)));
+            if comparison.regressed() {
+                let percent = comparison.total().map_or(0.0, Change::percent);
+                regressions.push((puzzle.year(), puzzle.day(), percent));
+            }
+        }
+        if let Some(saved) = saved.as_mut() {
+            saved.record(&bench);
+        }
+    }
+
+    if let (Some(path), Some(saved)) = (&args.save, &saved) {
+        let written = saved
+            .to_json()
+            .map_err(|err| err.to_string())
+            .and_then(|json| fs::write(path, json).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            eprintln!("Failed to write {}: {}", path, err);
+            process::exit(1);
+        }
+        eprintln!("Saved median timings to {}", path);
+    }
+
+    if !regressions.is_empty() {
+        let mut report = format!("\nRegressed by more than {}%:\n", args.threshold);
+        for (year, day, percent) in &regressions {
+            report += &format!("  {:4} day {:02} ({:+.1}%)\n", year, day, percent);
+        }
+        print_results(output, &report);
+    }
+    if failed || !regressions.is_empty() {
+        exit_with(Some(Status::Failed));
+    }
+    Ok(())
+}
+
+/// Parses the arguments of the `bench` command.
+///
+/// Given only a year, every puzzle of that year whose input is present is
+/// benchmarked, except for those marked as slow in the answer manifest.
+fn parse_bench_args<I>(mut args: I) -> Result<BenchArgs, SelectionError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut runs = Config::current()
+        .iterations()
+        .unwrap_or(bench::DEFAULT_RUNS);
+    let mut warmup = Config::current().warmup().unwrap_or(bench::DEFAULT_WARMUP);
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = bench::DEFAULT_THRESHOLD;
+    // The selection's parser expects the executable path to come first.
+    let mut rest = vec![String::new()];
+
+    while let Some(arg) = args.next() {
+        let count = match &arg[..] {
+            "--iterations" => &mut runs,
+            "--warmup" => &mut warmup,
+            "--save" => {
+                save = Some(args.next().ok_or(SelectionError::BadFlag)?);
+                continue;
+            }
+            "--baseline" => {
+                baseline = Some(args.next().ok_or(SelectionError::BadFlag)?);
+                continue;
+            }
+            "--threshold" => {
+                threshold = args
+                    .next()
+                    .and_then(|n| n.trim_end_matches('%').parse().ok())
+                    .filter(|&n: &f64| n >= 0.0)
+                    .ok_or(SelectionError::BadFlag)?;
+                continue;
+            }
+            _ => {
+                rest.push(arg);
+                continue;
+            }
+        };
+        *count = args
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or(SelectionError::BadFlag)?;
+    }
+    if runs == 0 {
+        return Err(SelectionError::BadFlag);
+    }
+
+    let puzzles = match rest.get(1).map(|year| year.parse()) {
+        Some(Ok(year)) if rest.len() == 2 => {
+            let key = AnswerKey::load().unwrap_or_default();
+            export::selections(Some(year), None)?
+                .into_iter()
+                .filter(|puzzle| puzzle.path().exists())
+                .filter(|puzzle| {
+                    !key.get(puzzle.year(), puzzle.day())
+                        .is_some_and(|expected| expected.is_slow())
+                })
+                .collect()
+        }
+        _ => vec![Selection::from_args(rest)?],
+    };
+    Ok(BenchArgs {
+        puzzles,
+        runs,
+        warmup,
+        save,
+        baseline,
+        threshold,
+    })
+}
+
+/// Arguments of the `export` command.
+struct ExportArgs {
+    /// The puzzles to run.
+    puzzles: Vec<Selection>,
+    /// Path of the exported document.
+    out: String,
+}
+
+/// Runs the `export` command, which solves all (or the selected) puzzles and
+/// writes their answers and timings to a JSON document.
+fn run_export(args: Vec<String>, output: &Output) -> Result<(), SelectionError> {
+    let args = parse_export_args(args.into_iter())?;
+
+    let mut stream = open_jsonl(output);
+
+    let export = Export::run(args.puzzles, |progress| match progress {
+        Progress::Started(puzzle) => {
+            eprintln!("Solving {:4} day {:02} ...", puzzle.year(), puzzle.day());
+        }
+        Progress::Finished(summary) => stream_line(&mut stream, summary),
+    });
+
+    let out = args.out;
+    let written = export
+        .to_json()
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&out, json + "\n").map_err(|e| e.to_string()));
+    if let Err(err) = written {
+        eprintln!("Failed to write {}: {}", out, err);
+        process::exit(1);
+    }
+
+    let failed = export
+        .puzzles()
+        .iter()
+        .filter(|summary| summary.result().is_err())
+        .count();
+    eprintln!(
+        "Exported {} puzzles ({} failed) to {}",
+        export.puzzles().len(),
+        failed,
+        out
+    );
+    Ok(())
+}
+
+/// Parses the arguments of the `export` command.
+fn parse_export_args<I>(mut args: I) -> Result<ExportArgs, SelectionError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut out = export::EXPORT_PATH.to_owned();
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--out" => out = args.next().ok_or(SelectionError::BadFlag)?,
+            flag if flag.starts_with("--") => return Err(SelectionError::BadFlag),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+
+    let year = positional
+        .next()
+        .map(|year| year.parse().map_err(|_| SelectionError::BadYear))
+        .transpose()?;
+    let day = positional
+        .next()
+        .map(|day| day.parse().map_err(|_| SelectionError::BadDay))
+        .transpose()?;
+
+    Ok(ExportArgs {
+        puzzles: export::selections(year, day)?,
+        out,
+    })
+}
+
+/// Runs the `serve` command, which answers requests to solve puzzles over
+/// HTTP until the process is stopped.
+fn run_server(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    if args.len() > 1 {
+        return Err(SelectionError::BadFlag);
+    }
+    let addr = args
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| serve::DEFAULT_ADDR.to_owned());
+    eprintln!("Listening on http://{}", addr);
+
+    let served = serve::serve(&addr[..], |request, response| {
+        eprintln!(
+            "{} {} -> {}",
+            request.method(),
+            request.path(),
+            response.status()
+        );
+    });
+    if let Err(err) = served {
+        eprintln!("Failed to serve on {}: {}", addr, err);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Runs the `report` command, which solves every puzzle and writes a
+/// Markdown table of the results.
+fn run_report(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    let (out, include_slow) = parse_report_args(args.into_iter())?;
+
+    let key = AnswerKey::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load answer manifest: {}", err);
+        process::exit(1);
+    });
+
+    let report = Report::run(&key, include_slow, |puzzle| {
+        eprintln!("Solving {:4} day {:02} ...", puzzle.year(), puzzle.day());
+    });
+
+    if let Err(err) = fs::write(&out, report.to_markdown()) {
+        eprintln!("Failed to write {}: {}", out, err);
+        process::exit(1);
+    }
+    eprintln!("Wrote results table to {}", out);
+    Ok(())
+}
+
+/// Parses the arguments of the `report` command into the path of the
+/// output file and whether slow puzzles should be run.
+fn parse_report_args<I>(mut args: I) -> Result<(String, bool), SelectionError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut out = report::REPORT_PATH.to_owned();
+    let mut include_slow = false;
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--out" => out = args.next().ok_or(SelectionError::BadFlag)?,
+            "--include-slow" => include_slow = true,
+            _ => return Err(SelectionError::BadFlag),
+        }
+    }
+    Ok((out, include_slow))
+}
+
+/// Runs the `verify` command, which solves all (or the selected) puzzles and
+/// checks their answers against the answer manifest.
+fn run_verify(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    let (puzzles, include_slow, record) = parse_verify_args(args.into_iter())?;
+
+    let key = AnswerKey::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load answer manifest: {}", err);
+        process::exit(1);
+    });
+    let (puzzles, slow): (Vec<_>, Vec<_>) = puzzles.into_iter().partition(|puzzle| {
+        include_slow
+            || !key
+                .get(puzzle.year(), puzzle.day())
+                .is_some_and(|expected| expected.is_slow())
+    });
+
+    let jobs = thread::available_parallelism().map_or(1, usize::from);
+    let mut outcomes = Vec::new();
+    let results = Export::run_parallel(
+        puzzles,
+        jobs,
+        |puzzle| solve(puzzle, Limits::default()),
+        |progress| {
+            if let Progress::Finished(summary) = progress {
+                let puzzle = summary.puzzle();
+                let outcome = Outcome::check(&key, summary);
+                println!(
+                    "{:4} day {:02} ... {}",
+                    puzzle.year(),
+                    puzzle.day(),
+                    outcome
+                );
+                outcomes.push(outcome);
+            }
+        },
+    );
+
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|o| f(o)).count();
+    println!(
+        "\n{} matched, {} mismatched, {} failed, {} unrecorded, {} skipped",
+        count(|o| *o == Outcome::Matched),
+        count(|o| matches!(o, Outcome::Mismatched(_))),
+        count(|o| matches!(o, Outcome::Failed(_))),
+        count(|o| *o == Outcome::Unrecorded),
+        count(|o| *o == Outcome::NoInput) + slow.len(),
+    );
+
+    if record {
+        let unrecorded = results
+            .puzzles()
+            .iter()
+            .zip(&outcomes)
+            .filter(|(_, outcome)| **outcome == Outcome::Unrecorded)
+            .map(|(summary, _)| summary);
+        if let Err(err) = verify::record(answers::MANIFEST_PATH, unrecorded) {
+            eprintln!("Failed to record answers: {}", err);
+            process::exit(1);
+        }
+        eprintln!(
+            "Recorded {} new answers in {}",
+            count(|o| *o == Outcome::Unrecorded),
+            answers::MANIFEST_PATH
+        );
+    }
+
+    // A wrong answer counts as a failed solution.
+    if outcomes.iter().any(Outcome::is_failure) {
+        exit_with(Some(Status::Failed));
+    }
+    Ok(())
+}
+
+/// Parses the arguments of the `verify` command into the puzzles to check,
+/// whether slow puzzles should be run, and whether unrecorded answers should
+/// be added to the manifest.
+///
+/// Slow puzzles are always run when a single day is selected.
+fn parse_verify_args<I>(args: I) -> Result<(Vec<Selection>, bool, bool), SelectionError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut include_slow = false;
+    let mut record = false;
+    let mut positional = Vec::new();
+
+    for arg in args {
+        match &arg[..] {
+            "--include-slow" => include_slow = true,
+            "--record" => record = true,
+            flag if flag.starts_with("--") => return Err(SelectionError::BadFlag),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+
+    let year = positional
+        .next()
+        .map(|year| year.parse().map_err(|_| SelectionError::BadYear))
+        .transpose()?;
+    let day = positional
+        .next()
+        .map(|day| day.parse().map_err(|_| SelectionError::BadDay))
+        .transpose()?;
+
+    let puzzles = export::selections(year, day)?;
+    Ok((puzzles, include_slow || day.is_some(), record))
+}
+
+/// Runs the `progress` command, which compares the stars earned on the
+/// Advent of Code website against the days with solutions.
+fn run_progress(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    no_args(&args)?;
+    let session = Session::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load session: {}", err);
+        process::exit(1);
+    });
+
+    let calendar = Calendar::fetch(&session, |year| {
+        eprintln!("Fetching {} calendar ...", year);
+    })
+    .unwrap_or_else(|err| {
+        eprintln!("Failed to fetch progress: {}", err);
+        process::exit(1);
+    });
+
+    print!("{}", calendar.to_text());
+    Ok(())
+}
+
+/// Runs the `leaderboard` command, which prints the standings of a private
+/// leaderboard.
+fn run_leaderboard(args: Vec<String>, _output: &Output) -> Result<(), SelectionError> {
+    let (id, year) = parse_leaderboard_args(args.into_iter())?;
+
+    let session = Session::load().unwrap_or_else(|err| {
+        eprintln!("Failed to load session: {}", err);
+        process::exit(1);
+    });
+
+    let leaderboard = Leaderboard::fetch(&session, year, id).unwrap_or_else(|err| {
+        eprintln!("Failed to fetch leaderboard {}: {}", id, err);
+        process::exit(1);
+    });
+
+    print!("{}", leaderboard.to_table());
+    Ok(())
+}
+
+/// Parses the arguments of the `leaderboard` command into the ID of the
+/// leaderboard and the year of its event, which defaults to the most recent.
+fn parse_leaderboard_args<I>(mut args: I) -> Result<(u64, u16), SelectionError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut id = None;
+    let mut year = None;
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--year" => {
+                let arg = args.next().ok_or(SelectionError::BadFlag)?;
+                year = Some(arg.parse().map_err(|_| SelectionError::BadYear)?);
+            }
+            flag if flag.starts_with("--") => return Err(SelectionError::BadFlag),
+            _ if id.is_none() => id = Some(arg.parse().map_err(|_| SelectionError::BadFlag)?),
+            _ => return Err(SelectionError::BadFlag),
+        }
+    }
+
+    let id = id.ok_or(SelectionError::NoSelection)?;
+    let year = year.or_else(|| Config::current().year());
+    let year = year.unwrap_or_else(|| date::latest_release(date::unix_now()).0);
+    Ok((id, year))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| arg.to_owned()).collect()
+    }
 
-    println!("{}", if solution.is_ok() { "OK" } else { " FAILED" });
+    #[test]
+    fn dispatch_commands() {
+        let mut solve = args(&["2016", "2"]);
+        assert_eq!("solve", take_command(&mut solve).name);
+        assert_eq!(args(&["2016", "2"]), solve);
 
-    Summary::new(puzzle, solution, start.elapsed())
+        // Commands without arguments are not mistaken for a lone year.
+        let mut leaderboard = args(&["leaderboard"]);
+        assert_eq!("leaderboard", take_command(&mut leaderboard).name);
+        assert!(leaderboard.is_empty());
+        assert_eq!(
+            Err(SelectionError::NoSelection),
+            parse_leaderboard_args(leaderboard.into_iter())
+        );
+    }
 }
@@ -0,0 +1,81 @@
+//! CPU profiling of puzzle solutions.
+//!
+//! While a [`Profiler`] is running, the call stack of the process is sampled
+//! at a fixed frequency. Once it is finished, the samples are rendered as a
+//! flamegraph, which makes the hot loops of a solution easy to spot.
+
+use crate::common::puzzle::Selection;
+use pprof::{ProfilerGuard, ProfilerGuardBuilder};
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Default directory that flamegraphs are written to.
+pub const PROFILE_DIR: &str = "./target/profiles";
+
+/// Number of times per second that the call stack is sampled.
+const SAMPLE_FREQUENCY: i32 = 997;
+
+/// Libraries whose frames are left out of the samples.
+const BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+/// A running CPU profiler for a single puzzle.
+pub struct Profiler<'a> {
+    guard: ProfilerGuard<'a>,
+    year: u16,
+    day: u8,
+}
+
+impl Profiler<'_> {
+    /// Starts sampling the call stack on behalf of the given puzzle.
+    pub fn start(puzzle: &Selection) -> Result<Self, Box<dyn Error>> {
+        let guard = ProfilerGuardBuilder::default()
+            .frequency(SAMPLE_FREQUENCY)
+            .blocklist(BLOCKLIST)
+            .build()?;
+        Ok(Profiler {
+            guard,
+            year: puzzle.year(),
+            day: puzzle.day(),
+        })
+    }
+
+    /// Stops sampling and writes a flamegraph of the samples into `dir`,
+    /// returning its path.
+    ///
+    /// The flamegraph is named after the puzzle, e.g. `y2016-day14.svg`.
+    pub fn finish<P: AsRef<Path>>(self, dir: P) -> Result<PathBuf, Box<dyn Error>> {
+        let report = self.guard.report().build()?;
+
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("y{:4}-day{:02}.svg", self.year, self.day));
+        report.flamegraph(File::create(&path)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::util::hash::md5;
+    use std::env;
+
+    #[test]
+    fn write_flamegraph() {
+        let dir = env::temp_dir().join(format!("aoc-profile-{}", std::process::id()));
+        let puzzle = Selection::new(2016, 14);
+
+        let profiler = Profiler::start(&puzzle).unwrap();
+        let mut digest = md5(b"abc");
+        for _ in 0..20_000 {
+            digest = md5(&digest);
+        }
+        let path = profiler.finish(&dir).unwrap();
+
+        assert_eq!(dir.join("y2016-day14.svg"), path);
+        let svg = fs::read_to_string(&path).unwrap();
+        assert!(svg.contains("<svg"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+//! Comparing the stars earned on the Advent of Code website against the
+//! days that have been ported into this crate.
+//!
+//! The star count of each event is read from the site's event list, and the
+//! stars earned on each day from the calendar of every event with at least
+//! one star. A day counts as solved once it has earned any stars.
+
+use crate::site::Session;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+
+/// Pattern matching an event in the event list, along with its star count
+/// if any stars have been earned.
+const PATTERN_EVENT: &str =
+    r#"href="/(\d{4})">\[\d{4}\]</a>(?:\s*<span class="star-count">(\d+)\*</span>)?"#;
+
+/// Pattern matching a day on an event's calendar, along with its stars if
+/// any have been earned.
+const PATTERN_DAY: &str = r#"aria-label="Day (\d+)(?:, (one star|two stars))?""#;
+
+#[derive(Debug, Default)]
+/// The stars earned in each event, paired with the days ported into this
+/// crate.
+pub struct Calendar {
+    /// The stars earned on each day of each event with any stars.
+    years: BTreeMap<u16, BTreeMap<u8, u8>>,
+}
+
+impl Calendar {
+    /// Fetches the stars earned by the session's user.
+    ///
+    /// `progress` is called with each year before its calendar is fetched.
+    pub fn fetch<F>(session: &Session, mut progress: F) -> Result<Self, Box<dyn Error>>
+    where
+        F: FnMut(u16),
+    {
+        let events = parse_events(&session.get("/events")?);
+
+        let mut calendar = Calendar::default();
+        for (year, stars) in events {
+            if stars == 0 {
+                continue;
+            }
+            progress(year);
+            let days = parse_days(&session.get(&format!("/{}", year))?);
+            calendar.years.insert(year, days);
+        }
+        Ok(calendar)
+    }
+
+    /// Returns the total number of stars earned in the given year.
+    pub fn stars(&self, year: u16) -> u32 {
+        self.years
+            .get(&year)
+            .map_or(0, |days| days.values().map(|&s| u32::from(s)).sum())
+    }
+
+    /// Returns the days of the given year that have been solved on the site
+    /// but have no solution in this crate.
+    pub fn unported(&self, year: u16) -> Vec<u8> {
+        let ported = crate::days(year);
+        self.years.get(&year).map_or_else(Vec::new, |days| {
+            days.iter()
+                .filter(|&(day, &stars)| stars > 0 && !ported.contains(day))
+                .map(|(&day, _)| day)
+                .collect()
+        })
+    }
+
+    /// Renders a summary of each year's progress, listing the days that
+    /// have yet to be ported.
+    pub fn to_text(&self) -> String {
+        let mut years: Vec<u16> = self.years.keys().copied().collect();
+        years.extend(crate::YEARS.iter().map(|&(year, _)| year));
+        years.sort_unstable();
+        years.dedup();
+
+        let mut out = String::new();
+        for year in years {
+            let solved = self
+                .years
+                .get(&year)
+                .map_or(0, |days| days.values().filter(|&&s| s > 0).count());
+            writeln!(
+                out,
+                "{}: {:2} stars, {:2} days solved, {:2} ported",
+                year,
+                self.stars(year),
+                solved,
+                crate::days(year).len()
+            )
+            .unwrap();
+
+            let unported = self.unported(year);
+            if !unported.is_empty() {
+                let days: Vec<String> = unported.iter().map(u8::to_string).collect();
+                writeln!(out, "      not ported: {}", days.join(", ")).unwrap();
+            }
+        }
+        out
+    }
+}
+
+/// Parses the stars earned in each event from the site's event list.
+fn parse_events(html: &str) -> BTreeMap<u16, u32> {
+    let re = Regex::new(PATTERN_EVENT).unwrap();
+    re.captures_iter(html)
+        .filter_map(|caps| {
+            let year = caps[1].parse().ok()?;
+            let stars = caps.get(2).map_or(Some(0), |s| s.as_str().parse().ok())?;
+            Some((year, stars))
+        })
+        .collect()
+}
+
+/// Parses the stars earned on each day from an event's calendar.
+fn parse_days(html: &str) -> BTreeMap<u8, u8> {
+    let re = Regex::new(PATTERN_DAY).unwrap();
+    re.captures_iter(html)
+        .filter_map(|caps| {
+            let day = caps[1].parse().ok()?;
+            let stars = match caps.get(2).map(|s| s.as_str()) {
+                Some("two stars") => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENTS: &str = r#"
+        <div class="eventlist-event"><a href="/2017">[2017]</a> <span class="star-count">34*</span></div>
+        <div class="eventlist-event"><a href="/2016">[2016]</a> <span class="star-count">50*</span></div>
+        <div class="eventlist-event"><a href="/2021">[2021]</a></div>
+    "#;
+
+    const DAYS: &str = r#"
+        <a aria-label="Day 1, two stars" href="/2016/day/1" class="calendar-day1 calendar-verycomplete">
+        <a aria-label="Day 2, one star" href="/2016/day/2" class="calendar-day2 calendar-complete">
+        <a aria-label="Day 3" href="/2016/day/3" class="calendar-day3">
+    "#;
+
+    #[test]
+    fn parse_event_list() {
+        let events = parse_events(EVENTS);
+        let expected: BTreeMap<u16, u32> = vec![(2016, 50), (2017, 34), (2021, 0)]
+            .into_iter()
+            .collect();
+        assert_eq!(expected, events);
+    }
+
+    #[test]
+    fn parse_calendar() {
+        let days = parse_days(DAYS);
+        let expected: BTreeMap<u8, u8> = vec![(1, 2), (2, 1), (3, 0)].into_iter().collect();
+        assert_eq!(expected, days);
+    }
+
+    #[test]
+    fn find_unported_days() {
+        let mut calendar = Calendar::default();
+        let days = (1..=25).map(|day| (day, if day == 25 { 1 } else { 2 }));
+        calendar.years.insert(2021, days.clone().collect());
+        calendar.years.insert(2016, days.collect());
+
+        assert_eq!(49, calendar.stars(2016));
+        assert_eq!(vec![24], calendar.unported(2016));
+        assert_eq!((1..=25).collect::<Vec<u8>>(), calendar.unported(2021));
+        assert_eq!(0, calendar.stars(2015));
+        assert!(calendar.unported(2015).is_empty());
+
+        let text = calendar.to_text();
+        assert!(text.contains("2016: 49 stars, 25 days solved, 24 ported\n      not ported: 24\n"));
+        assert!(text.contains("2021: 49 stars, 25 days solved,  0 ported\n      not ported: 1, 2,"));
+    }
+}
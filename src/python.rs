@@ -0,0 +1,283 @@
+//! Bindings for using the solutions and utility algorithms from Python.
+//!
+//! Built with the `python` feature, the crate's shared library is an
+//! extension module named `aoc_solutions`. Puzzle inputs are always passed
+//! in as strings, and summaries are returned as dictionaries with the same
+//! layout as the documents written by the `export` command.
+//!
+//! The search helpers accept states of any hashable Python type, and call
+//! back into Python to find the neighbors of each state.
+
+use crate::common::puzzle::{Selection, Summary};
+use crate::common::util::hash::{knot, to_hex};
+use crate::common::util::{ocr, search};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+
+/// A Python object used as a search state, compared and hashed by Python's
+/// `__eq__` and `__hash__`.
+///
+/// Errors raised by either method are stashed in the search's error slot,
+/// which ends the search.
+struct State<'e>(PyObject, &'e RefCell<Option<PyErr>>);
+
+impl Clone for State<'_> {
+    fn clone(&self) -> Self {
+        Python::with_gil(|py| State(self.0.clone_ref(py), self.1))
+    }
+}
+
+impl PartialEq for State<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        Python::with_gil(|py| guard(self.1, || self.0.bind(py).eq(other.0.bind(py))))
+            .unwrap_or(false)
+    }
+}
+
+impl Eq for State<'_> {}
+
+impl Hash for State<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Python::with_gil(|py| guard(self.1, || self.0.bind(py).hash()))
+            .unwrap_or(0)
+            .hash(state);
+    }
+}
+
+/// Solves the puzzle for the given year and day against `input`, returning
+/// the summary of the run.
+#[pyfunction]
+fn solve(py: Python<'_>, year: u16, day: u8, input: &str) -> PyResult<PyObject> {
+    let puzzle = Selection::with_text(year, day, input);
+    let summary = Summary::measure(puzzle, crate::route);
+    let json = serde_json::to_value(&summary).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    to_python(py, &json)
+}
+
+/// Decodes the large block letters spelled out by a render of lit (`#`)
+/// pixels.
+#[pyfunction]
+fn ocr_decode(render: &str) -> PyResult<String> {
+    ocr::decode_large(render).map_err(PyValueError::new_err)
+}
+
+/// Returns the hexadecimal knot hash of the given message.
+#[pyfunction]
+fn knot_hash(msg: &str) -> String {
+    to_hex(&knot(msg.as_bytes()))
+}
+
+/// Returns the fewest steps from `start` to a state satisfying `is_goal`,
+/// where `neighbors(state)` returns the states one step away, or `None` if
+/// no such state is reachable.
+#[pyfunction]
+fn breadth_first(
+    py: Python<'_>,
+    start: PyObject,
+    neighbors: PyObject,
+    is_goal: PyObject,
+) -> PyResult<Option<usize>> {
+    let error = RefCell::new(None);
+    let neighbors = |state: &State| -> Vec<State> {
+        guard(&error, || {
+            let next = neighbors.call1(py, (state.0.clone_ref(py),))?;
+            next.bind(py)
+                .try_iter()?
+                .map(|s| Ok(State(s?.unbind(), &error)))
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+
+    let found = search::breadth_first(State(start, &error), neighbors)
+        .find(|(state, _)| {
+            guard(&error, || {
+                is_goal.call1(py, (state.0.clone_ref(py),))?.is_truthy(py)
+            })
+            .unwrap_or(true)
+        })
+        .map(|(_, steps)| steps);
+
+    match error.into_inner() {
+        Some(err) => Err(err),
+        None => Ok(found),
+    }
+}
+
+/// Returns the goal state nearest to `start` and its cost, where
+/// `neighbors(state)` returns `(state, cost)` pairs for the states one step
+/// away, or `None` if no goal state is reachable.
+#[pyfunction]
+fn dijkstra(
+    py: Python<'_>,
+    start: PyObject,
+    neighbors: PyObject,
+    is_goal: PyObject,
+) -> PyResult<Option<(PyObject, u64)>> {
+    let error = RefCell::new(None);
+    let neighbors = |state: &State| -> Vec<(State, u64)> {
+        guard(&error, || {
+            let next = neighbors.call1(py, (state.0.clone_ref(py),))?;
+            next.bind(py)
+                .try_iter()?
+                .map(|pair| {
+                    let (state, cost): (PyObject, u64) = pair?.extract()?;
+                    Ok((State(state, &error), cost))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    let is_goal = |state: &State| {
+        guard(&error, || {
+            is_goal.call1(py, (state.0.clone_ref(py),))?.is_truthy(py)
+        })
+        .unwrap_or(true)
+    };
+
+    let found = search::dijkstra(State(start, &error), neighbors, is_goal)
+        .map(|(state, cost)| (state.0, cost));
+
+    match error.into_inner() {
+        Some(err) => Err(err),
+        None => Ok(found),
+    }
+}
+
+/// Runs a callback into Python, stashing the first error it raises in
+/// `error` so that it can be reported once the search is over.
+///
+/// Once an error has been raised, no further callbacks are run, and the
+/// searches treat every state as a goal so that they stop early.
+fn guard<T, F>(error: &RefCell<Option<PyErr>>, f: F) -> Option<T>
+where
+    F: FnOnce() -> PyResult<T>,
+{
+    if error.borrow().is_some() {
+        return None;
+    }
+    f().map_err(|err| *error.borrow_mut() = Some(err)).ok()
+}
+
+/// Converts a JSON value to the equivalent Python object.
+fn to_python(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_pyobject(py)?.into_any().unbind(),
+            None => n.as_f64().into_pyobject(py)?.into_any().unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(to_python(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, field) in fields {
+                dict.set_item(key, to_python(py, field)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// The `aoc_solutions` extension module.
+#[pymodule]
+fn aoc_solutions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(ocr_decode, m)?)?;
+    m.add_function(wrap_pyfunction!(knot_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(breadth_first, m)?)?;
+    m.add_function(wrap_pyfunction!(dijkstra, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::ffi::c_str;
+    use std::ffi::CStr;
+
+    const HELPERS: &CStr = c_str!(
+        r#"
+class Unequal:
+    def __eq__(self, other):
+        raise ValueError("cannot compare")
+
+    def __hash__(self):
+        return 0
+
+class Unhashable:
+    def __hash__(self):
+        raise ValueError("cannot hash")
+
+def step(n):
+    return [n + 1, n * 2]
+
+def weighted_step(n):
+    return [(n + 1, 1), (n * 2, 3)]
+
+def is_ten(n):
+    return n == 10
+
+def unequal(n):
+    return [Unequal(), Unequal()]
+
+def unhashable(n):
+    return [(Unhashable(), 1)]
+"#
+    );
+
+    /// Runs `f` with the functions defined in `HELPERS`.
+    fn with_helpers<F>(f: F)
+    where
+        F: FnOnce(Python<'_>, &dyn Fn(&str) -> PyObject) -> PyResult<()>,
+    {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let helpers =
+                PyModule::from_code(py, HELPERS, c_str!("helpers.py"), c_str!("helpers"))?;
+            f(py, &|name| helpers.getattr(name).unwrap().unbind())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn search_from_python() {
+        with_helpers(|py, helper| {
+            let one = || 1i64.into_pyobject(py).unwrap().into_any().unbind();
+            assert_eq!(
+                Some(4),
+                breadth_first(py, one(), helper("step"), helper("is_ten"))?
+            );
+
+            let (goal, cost) =
+                dijkstra(py, one(), helper("weighted_step"), helper("is_ten"))?.unwrap();
+            assert_eq!(10, goal.extract::<i64>(py)?);
+            assert_eq!(7, cost);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn raise_from_comparisons() {
+        with_helpers(|py, helper| {
+            let one = || 1i64.into_pyobject(py).unwrap().into_any().unbind();
+            let err = breadth_first(py, one(), helper("unequal"), helper("is_ten")).unwrap_err();
+            assert_eq!("ValueError: cannot compare", err.to_string());
+
+            let err = dijkstra(py, one(), helper("unhashable"), helper("is_ten")).unwrap_err();
+            assert_eq!("ValueError: cannot hash", err.to_string());
+            Ok(())
+        });
+    }
+}
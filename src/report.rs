@@ -0,0 +1,165 @@
+//! Generating a Markdown table of the results of every solution.
+//!
+//! The table is built entirely from the solution registry ([`YEARS`]) and
+//! the answer manifest ([`AnswerKey`]): each implemented puzzle is run and
+//! its answers are checked against the manifest as by the `verify` command.
+//! The table has a row for each day and a column for each year.
+//!
+//! [`YEARS`]: crate::YEARS
+
+use crate::common::puzzle::{timings, AnswerKey, Selection, Summary};
+use crate::verify::Outcome;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Default location of the generated table.
+pub const REPORT_PATH: &str = "results.md";
+
+/// The number of days in each event.
+const DAYS_PER_YEAR: u8 = 25;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// The result of a single puzzle.
+pub enum Status {
+    /// The solution's answers matched the manifest.
+    Passed(Duration),
+    /// The solution ran, but the manifest has no answers to check.
+    Unverified(Duration),
+    /// The solution failed or produced the wrong answers.
+    Failed,
+    /// The puzzle was not run for the given reason.
+    Skipped(&'static str),
+}
+
+#[derive(Debug, Default)]
+/// The results of every implemented puzzle, indexed by year and day.
+pub struct Report {
+    results: BTreeMap<(u16, u8), Status>,
+}
+
+impl Report {
+    /// Runs every implemented puzzle and checks its answers against `key`.
+    ///
+    /// Puzzles without input are skipped, as are those marked as slow in the
+    /// manifest unless `include_slow` is set. `progress` is called with each
+    /// puzzle before it is run.
+    pub fn run<F>(key: &AnswerKey, include_slow: bool, mut progress: F) -> Self
+    where
+        F: FnMut(&Selection),
+    {
+        let mut report = Report::default();
+        for &(year, days) in crate::YEARS {
+            for &day in days {
+                let puzzle = Selection::new(year, day);
+
+                let status = if let Some(reason) = skip_reason(key, &puzzle, include_slow) {
+                    Status::Skipped(reason)
+                } else {
+                    progress(&puzzle);
+                    let summary = Summary::measure(puzzle, crate::route);
+                    match Outcome::check(key, &summary) {
+                        Outcome::Matched => Status::Passed(summary.duration()),
+                        Outcome::Unrecorded => Status::Unverified(summary.duration()),
+                        Outcome::NoInput => Status::Skipped("no input"),
+                        Outcome::Mismatched(_) | Outcome::Failed(_) => Status::Failed,
+                    }
+                };
+                report.results.insert((year, day), status);
+            }
+        }
+        report
+    }
+
+    /// Returns the result of the given puzzle, or `None` if it has no
+    /// solution.
+    pub fn get(&self, year: u16, day: u8) -> Option<&Status> {
+        self.results.get(&(year, day))
+    }
+
+    /// Renders this report as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let years: Vec<u16> = crate::YEARS.iter().map(|&(year, _)| year).collect();
+        let mut columns = vec!["Day".to_owned()];
+        columns.extend(years.iter().map(u16::to_string));
+
+        let rows = (1..=DAYS_PER_YEAR).map(|day| {
+            let mut row = vec![day.to_string()];
+            row.extend(
+                years
+                    .iter()
+                    .map(|&year| self.get(year, day).map_or_else(String::new, cell)),
+            );
+            row
+        });
+        let mut out = timings::markdown_table(&columns, rows);
+
+        let count = |f: fn(&Status) -> bool| self.results.values().filter(|s| f(s)).count();
+        write!(
+            out,
+            "\n{} passed, {} unverified, {} failed, {} skipped.\n\n\
+             ✓ answers match `resources/answers.toml`; \
+             ? answers are not in the manifest; \
+             ✗ the solution failed or its answers are wrong.\n",
+            count(|s| matches!(s, Status::Passed(_))),
+            count(|s| matches!(s, Status::Unverified(_))),
+            count(|s| matches!(s, Status::Failed)),
+            count(|s| matches!(s, Status::Skipped(_))),
+        )
+        .unwrap();
+        out
+    }
+}
+
+/// Returns the reason that a puzzle should not be run when checking every
+/// solution, or `None` if it should be run.
+///
+/// Puzzles without input are skipped, as are those marked as slow in the
+/// manifest unless `include_slow` is set.
+pub fn skip_reason(
+    key: &AnswerKey,
+    puzzle: &Selection,
+    include_slow: bool,
+) -> Option<&'static str> {
+    if !puzzle.path().exists() {
+        Some("no input")
+    } else if !include_slow
+        && key
+            .get(puzzle.year(), puzzle.day())
+            .is_some_and(|expected| expected.is_slow())
+    {
+        Some("slow")
+    } else {
+        None
+    }
+}
+
+/// Renders the table cell for a puzzle's result.
+fn cell(status: &Status) -> String {
+    match status {
+        Status::Passed(duration) => format!("✓ {:.3}s", duration.as_secs_f64()),
+        Status::Unverified(duration) => format!("? {:.3}s", duration.as_secs_f64()),
+        Status::Failed => "✗".to_owned(),
+        Status::Skipped(reason) => reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_markdown() {
+        let mut report = Report::default();
+        let results = vec![
+            ((2015, 1), Status::Passed(Duration::from_micros(1_250))),
+            ((2015, 2), Status::Unverified(Duration::from_millis(42))),
+            ((2016, 5), Status::Skipped("slow")),
+            ((2016, 8), Status::Failed),
+            ((2019, 1), Status::Skipped("no input")),
+        ];
+        report.results.extend(results);
+
+        insta::assert_snapshot!(report.to_markdown());
+    }
+}
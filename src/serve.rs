@@ -0,0 +1,329 @@
+//! A small HTTP server exposing the puzzle solutions.
+//!
+//! The server answers a single kind of request:
+//!
+//! - `GET /solve/{year}/{day}` solves the puzzle against its default input.
+//! - `POST /solve/{year}/{day}` solves the puzzle against the request body.
+//!
+//! Both respond with the JSON summary of the run, as written by the `export`
+//! command. Requests are handled one at a time, in the order they arrive.
+//! A solution that panics is answered with a `500` response, and a client
+//! that stalls while sending its request is dropped after `READ_TIMEOUT`.
+
+use crate::common::puzzle::{PuzzleError, Selection, SelectionError, Summary};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::panic;
+use std::time::Duration;
+
+/// Default address that the server listens on.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// Largest request body that will be accepted, in bytes.
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+/// Longest time to wait on a client while reading its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// An HTTP request, reduced to the parts that the server uses.
+pub struct Request {
+    method: String,
+    path: String,
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// An HTTP response with a JSON body.
+pub struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Request {
+    /// Builds a new request with the specified method, path, and optional
+    /// body.
+    pub fn new<M, P>(method: M, path: P, body: Option<String>) -> Self
+    where
+        M: ToString,
+        P: ToString,
+    {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body,
+        }
+    }
+
+    /// Returns the method of this request.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the path of this request.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Reads a request from the given stream.
+    fn read_from<R: Read>(stream: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (method, path) = match (parts.next(), parts.next()) {
+            (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+            _ => return Err(invalid_data("malformed request line")),
+        };
+
+        let mut content_length = None;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid_data("unexpected end of headers"));
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    let len = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| invalid_data("invalid content length"))?;
+                    content_length = Some(len);
+                }
+            }
+        }
+
+        let body = match content_length {
+            Some(len) if len > MAX_BODY_LEN => return Err(invalid_data("request body too large")),
+            Some(len) => {
+                let mut body = vec![0; len];
+                reader.read_exact(&mut body)?;
+                let body =
+                    String::from_utf8(body).map_err(|_| invalid_data("request body not UTF-8"))?;
+                Some(body)
+            }
+            None => None,
+        };
+
+        Ok(Request { method, path, body })
+    }
+}
+
+impl Response {
+    /// Builds a response with the given status whose body is a JSON object
+    /// describing an error.
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    /// Returns the HTTP status code of this response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the JSON body of this response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Writes this response to the given stream.
+    fn write_to<W: Write>(&self, mut stream: W) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason_phrase(self.status),
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Listens for requests on the given address until an error occurs.
+///
+/// `log` is called with each request and its response once it has been
+/// sent.
+pub fn serve<A, F>(addr: A, mut log: F) -> io::Result<()>
+where
+    A: ToSocketAddrs,
+    F: FnMut(&Request, &Response),
+{
+    let listener = TcpListener::bind(addr)?;
+    for stream in listener.incoming() {
+        if let Err(e) = stream.and_then(|stream| respond(stream, &mut log)) {
+            warn!("failed to handle request: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a single request from the stream and writes its response.
+fn respond<F>(stream: TcpStream, log: &mut F) -> io::Result<()>
+where
+    F: FnMut(&Request, &Response),
+{
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let request = match Request::read_from(&stream) {
+        Ok(request) => request,
+        Err(e) => return Response::error(400, &e.to_string()).write_to(&stream),
+    };
+    let response = handle_unwinding(&request);
+    response.write_to(&stream)?;
+    log(&request, &response);
+    Ok(())
+}
+
+/// Produces the response to a request.
+pub fn handle(request: &Request) -> Response {
+    let route = request
+        .path
+        .strip_prefix("/solve/")
+        .and_then(|rest| rest.split_once('/'));
+    let (year, day) = match route {
+        Some(route) => route,
+        None => return Response::error(404, "not found"),
+    };
+
+    let puzzle = match (year.parse(), day.parse()) {
+        (Ok(year), Ok(day)) => match (request.method.as_str(), &request.body) {
+            ("GET", _) => Selection::new(year, day),
+            ("POST", Some(body)) => Selection::with_text(year, day, body.as_str()),
+            ("POST", None) => return Response::error(411, "missing puzzle input"),
+            _ => return Response::error(405, "method not allowed"),
+        },
+        (Err(_), _) => return Response::error(400, SelectionError::BadYear.as_str()),
+        (_, Err(_)) => return Response::error(400, SelectionError::BadDay.as_str()),
+    };
+
+    let summary = Summary::measure(puzzle, crate::route);
+    let status = match summary.result() {
+        Ok(_) => 200,
+        Err(PuzzleError::Unimplemented(_)) | Err(PuzzleError::BadSelection(_)) => 404,
+        Err(_) => 500,
+    };
+    match serde_json::to_string(&summary) {
+        Ok(body) => Response { status, body },
+        Err(e) => Response::error(500, &e.to_string()),
+    }
+}
+
+/// Produces the response to a request, answering with a `500` response if
+/// handling the request panics.
+fn handle_unwinding(request: &Request) -> Response {
+    panic::catch_unwind(|| handle(request))
+        .unwrap_or_else(|_| Response::error(500, "solution panicked"))
+}
+
+/// Builds an `InvalidData` error with the given message.
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Returns the reason phrase for the status codes used by the server.
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const DISCS: &str = "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+                         Disc #2 has 2 positions; at time=0, it is at position 1.\n";
+
+    fn json(response: &Response) -> Value {
+        serde_json::from_str(response.body()).unwrap()
+    }
+
+    #[test]
+    fn solve_posted_input() {
+        let response = handle(&Request::new("POST", "/solve/2016/15", Some(DISCS.into())));
+
+        assert_eq!(200, response.status());
+        let summary = json(&response);
+        assert_eq!("5", summary["solution"]["part1"]["answer"]);
+        assert_eq!("85", summary["solution"]["part2"]["answer"]);
+    }
+
+    #[test]
+    fn reject_bad_requests() {
+        let status = |method, path, body: Option<&str>| {
+            handle(&Request::new(method, path, body.map(str::to_owned))).status()
+        };
+
+        assert_eq!(404, status("GET", "/", None));
+        assert_eq!(404, status("GET", "/solve/2016", None));
+        assert_eq!(400, status("GET", "/solve/two/15", None));
+        assert_eq!(400, status("GET", "/solve/2016/x", None));
+        assert_eq!(405, status("DELETE", "/solve/2016/15", None));
+        assert_eq!(411, status("POST", "/solve/2016/15", None));
+        assert_eq!(404, status("POST", "/solve/2016/26", Some("")));
+        assert_eq!(500, status("POST", "/solve/2016/15", Some("Disc #1")));
+    }
+
+    #[test]
+    fn recover_from_panics() {
+        // 2018 day 7 panics on malformed steps
+        let request = Request::new("POST", "/solve/2018/7", Some("garbage".into()));
+        let response = handle_unwinding(&request);
+
+        assert_eq!(500, response.status());
+        assert_eq!("solution panicked", json(&response)["error"]);
+
+        let request = Request::new("POST", "/solve/2016/15", Some(DISCS.into()));
+        assert_eq!(200, handle_unwinding(&request).status());
+    }
+
+    #[test]
+    fn read_and_write_http() {
+        let raw = format!(
+            "POST /solve/2016/15 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            DISCS.len(),
+            DISCS
+        );
+        let request = Request::read_from(raw.as_bytes()).unwrap();
+        assert_eq!(
+            Request::new("POST", "/solve/2016/15", Some(DISCS.into())),
+            request
+        );
+
+        let request = Request::read_from(&b"GET /solve/2016/15 HTTP/1.1\r\n\r\n"[..]).unwrap();
+        assert_eq!(Request::new("GET", "/solve/2016/15", None), request);
+
+        assert!(Request::read_from(&b"GET\r\n\r\n"[..]).is_err());
+        assert!(Request::read_from(&b"GET / HTTP/1.1\r\nContent-Length: 9\r\n\r\nab"[..]).is_err());
+
+        let mut out = Vec::new();
+        Response::error(404, "not found")
+            .write_to(&mut out)
+            .unwrap();
+        assert_eq!(
+            "HTTP/1.1 404 Not Found\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 21\r\n\
+             Connection: close\r\n\r\n\
+             {\"error\":\"not found\"}",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Access to the Advent of Code website on behalf of a logged-in user.
+//!
+//! Pages that depend on the user, such as their calendar or a private
+//! leaderboard, are only served to requests carrying the user's session
+//! cookie. The cookie's value is read from the `AOC_SESSION` environment
+//! variable or, failing that, from the [configuration](crate::config) or a
+//! `.session` file in the working directory.
+
+use crate::config::Config;
+use std::env;
+use std::error::Error;
+use std::fs;
+
+/// Environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// File holding the session token, if the environment variable is unset.
+pub const SESSION_FILE: &str = ".session";
+
+/// Root of the Advent of Code website.
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// User agent sent with each request, as asked of automated tools by the
+/// site's maintainer.
+const USER_AGENT: &str = "github.com/blueschu/Advent-Of-Rust";
+
+#[derive(Debug, Clone)]
+/// A logged-in session on the Advent of Code website.
+pub struct Session {
+    token: String,
+}
+
+impl Session {
+    /// Builds a session from the given token.
+    pub fn new<S: Into<String>>(token: S) -> Self {
+        Session {
+            token: token.into(),
+        }
+    }
+
+    /// Loads the session token from the environment, the configuration, or
+    /// the session file.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let configured = Config::current().session().map(str::to_owned);
+        let token = match env::var(SESSION_VAR).ok().or(configured) {
+            Some(token) => token,
+            None => fs::read_to_string(SESSION_FILE).map_err(|e| {
+                format!(
+                    "no session token in ${}, the configuration, or {} ({})",
+                    SESSION_VAR, SESSION_FILE, e
+                )
+            })?,
+        };
+        let token = token.trim();
+        if token.is_empty() {
+            return Err("session token is empty".into());
+        }
+        Ok(Session::new(token))
+    }
+
+    /// Fetches the page at the given path, e.g. `/2016/day/1/input`.
+    pub fn get(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        let body = ureq::get(&format!("{}{}", BASE_URL, path))
+            .set("Cookie", &format!("session={}", self.token))
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_string()?;
+        Ok(body)
+    }
+}
@@ -0,0 +1,104 @@
+//! Structured tracing of the solve pipeline.
+//!
+//! Every run through [`route`] is recorded as a tree of spans:
+//!
+//! - `solve`, with the puzzle's `year` and `day`, covers the whole run.
+//! - `setup` covers everything before the first part is solved: fetching
+//!   the input (the `fetch_input` span recorded by `aoc-core`, with a child
+//!   `download_input` span if the runner had to download it) and parsing it,
+//!   which is the remainder of the span.
+//! - `part`, with its number `n`, covers the computation of a single answer.
+//!   Solutions that compute both answers at once record a single `parts`
+//!   span instead.
+//!
+//! The spans are emitted through the macros that every solution already
+//! uses, so no day needs to be instrumented by hand. They cost next to
+//! nothing unless a subscriber, such as the one installed by [`install`], is
+//! listening.
+//!
+//! [`route`]: crate::route
+
+use std::cell::RefCell;
+use std::io;
+use tracing::span::EnteredSpan;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+thread_local! {
+    /// The `setup` span of the puzzle being solved on this thread, while it
+    /// is open.
+    static SETUP: RefCell<Option<EnteredSpan>> = const { RefCell::new(None) };
+}
+
+/// Opens the `setup` span of the puzzle being solved on this thread.
+pub(crate) fn begin_setup() {
+    let span = tracing::info_span!("setup").entered();
+    SETUP.with(|setup| *setup.borrow_mut() = Some(span));
+}
+
+/// Closes the `setup` span, if it is still open.
+pub(crate) fn end_setup() {
+    // Take the span out before dropping it, so that the borrow is released
+    // before the subscriber runs.
+    let span = SETUP.with(|setup| setup.borrow_mut().take());
+    drop(span);
+}
+
+/// Installs a subscriber that prints each span to stderr as it closes,
+/// along with the time spent inside it.
+///
+/// Does nothing if a global subscriber has already been installed.
+pub fn install() {
+    let _ = tracing_subscriber::fmt()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_target(false)
+        .with_writer(io::stderr)
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::puzzle::Selection;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::Id;
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use tracing_subscriber::registry::{LookupSpan, Registry};
+
+    /// A layer recording the path of each span as it closes.
+    #[derive(Clone, Default)]
+    struct Closed(Arc<Mutex<Vec<String>>>);
+
+    impl<S> Layer<S> for Closed
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_close(&self, id: Id, ctx: Context<S>) {
+            let span = ctx.span(&id).unwrap();
+            let path: Vec<_> = span.scope().from_root().map(|s| s.name()).collect();
+            self.0.lock().unwrap().push(path.join("/"));
+        }
+    }
+
+    #[test]
+    fn spans_cover_pipeline() {
+        let closed = Closed::default();
+        let subscriber = Registry::default().with(closed.clone());
+
+        let input = "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+                     Disc #2 has 2 positions; at time=0, it is at position 1.\n";
+        tracing::subscriber::with_default(subscriber, || {
+            crate::route(&Selection::with_text(2016, 15, input)).unwrap();
+        });
+
+        assert_eq!(
+            vec![
+                "solve/setup/fetch_input",
+                "solve/setup",
+                "solve/part",
+                "solve/part",
+                "solve",
+            ],
+            *closed.0.lock().unwrap()
+        );
+    }
+}
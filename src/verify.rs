@@ -0,0 +1,143 @@
+//! Checking solutions against the answer manifest.
+//!
+//! The `verify` command re-runs the selected solutions and compares each of
+//! their answers with the known-correct answers recorded in the manifest
+//! ([`AnswerKey`]), reporting every part that differs. Answers of puzzles
+//! that are not in the manifest yet may be recorded as their baseline.
+
+use crate::common::puzzle::answers::Expected;
+use crate::common::puzzle::{AnswerKey, Status, Summary};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// The result of checking a single solution against the manifest.
+pub enum Outcome {
+    /// Every recorded answer matched.
+    Matched,
+    /// Some answers differ from the recorded ones, as described.
+    Mismatched(String),
+    /// The solution ran, but the manifest has no answers for the puzzle.
+    Unrecorded,
+    /// The puzzle's input is missing, so its solution could not be checked.
+    NoInput,
+    /// The solution failed with the given error.
+    Failed(String),
+}
+
+impl Outcome {
+    /// Checks the answers of a run against `key`.
+    pub fn check(key: &AnswerKey, summary: &Summary) -> Self {
+        if summary.status() == Status::MissingInput {
+            return Outcome::NoInput;
+        }
+        let puzzle = summary.puzzle();
+        match (summary.result(), key.get(puzzle.year(), puzzle.day())) {
+            (Err(e), _) => Outcome::Failed(e.to_string()),
+            (Ok(_), None) => Outcome::Unrecorded,
+            (Ok(solution), Some(expected)) => match expected.check(solution) {
+                Ok(()) => Outcome::Matched,
+                Err(mismatch) => Outcome::Mismatched(mismatch),
+            },
+        }
+    }
+
+    /// Returns `true` if the solution ran but did not match the manifest.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Mismatched(_) | Outcome::Failed(_))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Matched => write!(f, "ok"),
+            Outcome::Mismatched(mismatch) => {
+                write!(f, "MISMATCH")?;
+                for part in mismatch.split("; ") {
+                    write!(f, "\n    {}", part)?;
+                }
+                Ok(())
+            }
+            Outcome::Unrecorded => write!(f, "unrecorded"),
+            Outcome::NoInput => write!(f, "skipped (no input)"),
+            Outcome::Failed(e) => write!(f, "FAILED: {}", e),
+        }
+    }
+}
+
+/// Appends the answers of each run to the manifest at `path`, recording
+/// them as the baseline for their puzzles.
+///
+/// Failed runs are left out. The caller is responsible for only recording
+/// puzzles that are not in the manifest yet.
+pub fn record<'a, P, I>(path: P, summaries: I) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = &'a Summary>,
+{
+    let mut manifest = OpenOptions::new().append(true).open(path)?;
+    for summary in summaries {
+        if let Ok(solution) = summary.result() {
+            let puzzle = summary.puzzle();
+            let entry = Expected::from_solution(solution).to_entry(puzzle.year(), puzzle.day());
+            write!(manifest, "\n{}", entry)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::puzzle::{Answer, Selection, Solution};
+    use std::fs;
+
+    fn run(day: u8, answer: i32) -> Summary {
+        let solution = Solution::new(Some(Answer::new(answer)), None);
+        Summary::measure(Selection::new(2018, day), |_| Ok(solution))
+    }
+
+    #[test]
+    fn check_outcomes() {
+        let key: AnswerKey = "[2018.12]\npart1 = \"3915\"\n".parse().unwrap();
+
+        assert_eq!(Outcome::Matched, Outcome::check(&key, &run(12, 3915)));
+        assert_eq!(
+            Outcome::Mismatched("part 1: expected `3915`, got `42`".to_owned()),
+            Outcome::check(&key, &run(12, 42))
+        );
+        assert_eq!(Outcome::Unrecorded, Outcome::check(&key, &run(13, 1)));
+
+        let failed = Summary::measure(Selection::new(2018, 12), |_| Err("oops".into()));
+        assert_eq!(
+            Outcome::Failed("oops".to_owned()),
+            Outcome::check(&key, &failed)
+        );
+        assert!(Outcome::check(&key, &failed).is_failure());
+        assert!(!Outcome::Unrecorded.is_failure());
+
+        let missing = Summary::measure(Selection::with_input(2018, 12, "/nonexistent"), |p| {
+            crate::common::puzzle::fetch_string(p)?;
+            Ok(Solution::empty())
+        });
+        assert_eq!(Outcome::NoInput, Outcome::check(&key, &missing));
+    }
+
+    #[test]
+    fn record_baseline() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        fs::write(&path, "[2018.12]\npart1 = \"3915\"\n").unwrap();
+
+        let failed = Summary::measure(Selection::new(2018, 14), |_| Err("oops".into()));
+        record(&path, &[run(13, 7), failed]).unwrap();
+
+        let key = AnswerKey::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Outcome::Matched, Outcome::check(&key, &run(12, 3915)));
+        assert_eq!(Outcome::Matched, Outcome::check(&key, &run(13, 7)));
+        assert!(key.get(2018, 14).is_none());
+    }
+}
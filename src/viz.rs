@@ -0,0 +1,79 @@
+//! Writing the visual answers of solutions to files.
+//!
+//! Some answers are drawn rather than computed, such as the letters lit on
+//! a screen, and some solutions can attach a rendering of how they found an
+//! answer when their puzzle is selected with visualizations enabled (see
+//! [`Answer::artifact`]). Rather than printing these to the terminal, the
+//! runner may write each to its own file under [`VIZ_DIR`], named after its
+//! puzzle and part, e.g. `out/2018_10_part1.txt`.
+
+use crate::common::puzzle::{Answer, Summary};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default directory that visualizations are written to.
+pub const VIZ_DIR: &str = "out";
+
+/// Returns the visual of an answer: its attached rendering, or the answer
+/// itself if it spans several lines.
+pub fn visual(answer: &Answer) -> Option<&str> {
+    answer
+        .artifact()
+        .or_else(|| Some(answer.ans()).filter(|ans| ans.contains('\n')))
+}
+
+/// Writes the visual of each answer of a run to `dir`, creating it if
+/// needed, and returns the paths written in order of part.
+///
+/// Nothing is written for a failed run or for answers without a visual.
+pub fn write<P: AsRef<Path>>(dir: P, summary: &Summary) -> io::Result<Vec<PathBuf>> {
+    let solution = match summary.result() {
+        Ok(solution) => solution,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let puzzle = summary.puzzle();
+
+    let mut written = Vec::new();
+    for (part, answer) in [(1, &solution.0), (2, &solution.1)] {
+        if let Some(visual) = answer.as_ref().and_then(visual) {
+            fs::create_dir_all(dir.as_ref())?;
+            let name = format!("{}_{:02}_part{}.txt", puzzle.year(), puzzle.day(), part);
+            let path = dir.as_ref().join(name);
+            fs::write(&path, visual)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::puzzle::{Selection, Solution};
+    use std::env;
+
+    #[test]
+    fn write_visuals() {
+        let dir = env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        let solution = Solution::new(
+            Some(Answer::new(8).with_artifact("#..\n.#.\n")),
+            Some(Answer::new("#.#\n###\n")),
+        );
+        let summary = Summary::measure(Selection::new(2016, 8), |_| Ok(solution));
+
+        let written = write(&dir, &summary).unwrap();
+        assert_eq!(
+            vec![dir.join("2016_08_part1.txt"), dir.join("2016_08_part2.txt")],
+            written
+        );
+        assert_eq!("#..\n.#.\n", fs::read_to_string(&written[0]).unwrap());
+        assert_eq!("#.#\n###\n", fs::read_to_string(&written[1]).unwrap());
+
+        let plain = Solution::new(Some(Answer::new(8)), None);
+        let summary = Summary::measure(Selection::new(2016, 9), |_| Ok(plain));
+        assert!(write(&dir, &summary).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
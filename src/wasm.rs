@@ -0,0 +1,38 @@
+//! Bindings for running the solutions from JavaScript when compiled to
+//! WebAssembly.
+//!
+//! Puzzle inputs are always passed in as strings, so no solution touches the
+//! filesystem, and timings are read from the host's `performance.now()`.
+
+use crate::common::puzzle::clock::{self, Clock};
+use crate::common::puzzle::{Selection, Summary};
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Returns the host's high-resolution timestamp in milliseconds.
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// A clock backed by the host's `performance.now()`.
+struct PerformanceClock;
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        Duration::from_secs_f64(performance_now().max(0.0) / 1000.0)
+    }
+}
+
+/// Solves the puzzle for the given year and day against `input`, returning
+/// the JSON summary of the run.
+///
+/// The summary holds either the solution's answers and timings or the error
+/// that it failed with, as written by the `export` command.
+#[wasm_bindgen]
+pub fn solve(year: u16, day: u8, input: &str) -> Result<String, JsValue> {
+    let puzzle = Selection::with_text(year, day, input);
+    let summary = clock::with_clock(PerformanceClock, || Summary::measure(puzzle, crate::route));
+    serde_json::to_string(&summary).map_err(|e| JsValue::from_str(&e.to_string()))
+}
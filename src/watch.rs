@@ -0,0 +1,142 @@
+//! Watching files for changes, to re-run a puzzle as its solution evolves.
+//!
+//! Changes are found by polling: each [`Watcher`] remembers the latest
+//! modification time and the number of files under its paths, searching
+//! directories recursively, and reports a change whenever either differs
+//! from the last poll. This needs no support from the platform and is cheap
+//! enough for the handful of files behind a single puzzle.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Time between successive polls of the watched paths.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The state of the watched paths at a single poll: the latest modification
+/// time among their files, if any exist, and the number of files.
+type Stamp = (Option<SystemTime>, usize);
+
+#[derive(Debug)]
+/// Polls a set of files and directories for changes.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamp: Stamp,
+}
+
+impl Watcher {
+    /// Starts watching the given paths, which need not exist yet.
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+        let stamp = stamp_all(&paths);
+        Watcher { paths, stamp }
+    }
+
+    /// Returns `true` if any watched file was created, modified, or removed
+    /// since the watcher was created or last reported a change.
+    pub fn changed(&mut self) -> bool {
+        let stamp = stamp_all(&self.paths);
+        if stamp == self.stamp {
+            return false;
+        }
+        self.stamp = stamp;
+        true
+    }
+}
+
+/// Blocks until one of the watchers reports a change, returning its index.
+pub fn wait_for_change(watchers: &mut [&mut Watcher]) -> usize {
+    loop {
+        if let Some(i) = watchers.iter_mut().position(|watcher| watcher.changed()) {
+            return i;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Returns the directories holding the sources of this workspace, as
+/// located when it was built.
+pub fn source_dirs() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    vec![root.join("src"), root.join("aoc-core").join("src")]
+}
+
+/// Returns the combined stamp of the given paths.
+fn stamp_all(paths: &[PathBuf]) -> Stamp {
+    let mut stamp = (None, 0);
+    for path in paths {
+        // Paths that cannot be read are treated as empty, so that they are
+        // reported as changed once they appear.
+        let _ = add_stamp(path, &mut stamp);
+    }
+    stamp
+}
+
+/// Adds the files under `path` to `stamp`.
+fn add_stamp(path: &Path, stamp: &mut Stamp) -> io::Result<()> {
+    let metadata = fs::metadata(path)?;
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            add_stamp(&entry?.path(), stamp)?;
+        }
+    } else {
+        let modified = metadata.modified()?;
+        stamp.0 = stamp.0.max(Some(modified));
+        stamp.1 += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::time::UNIX_EPOCH;
+
+    /// Sets the modification time of the given file to a fixed number of
+    /// seconds after the epoch, so that changes do not depend on the
+    /// resolution of the file system's clock.
+    fn touch(path: &Path, secs: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn detect_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let input = dir.join("input.txt");
+        let source = dir.join("src");
+        fs::create_dir_all(&source).unwrap();
+
+        let mut watcher = Watcher::new(vec![input.clone(), source.clone()]);
+        assert!(!watcher.changed());
+
+        fs::write(&input, "1").unwrap();
+        touch(&input, 1_000);
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        touch(&input, 2_000);
+        assert!(watcher.changed());
+
+        let module = source.join("day01.rs");
+        fs::write(&module, "").unwrap();
+        touch(&module, 10);
+        assert!(watcher.changed());
+        fs::remove_file(&module).unwrap();
+        assert!(watcher.changed());
+
+        let mut others = Watcher::new(vec![dir.join("missing")]);
+        touch(&input, 3_000);
+        assert_eq!(1, wait_for_change(&mut [&mut others, &mut watcher]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
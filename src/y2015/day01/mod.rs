@@ -2,43 +2,85 @@
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
+/// Integer type used to represent a floor.
+pub type Floor = i32;
+
+/// An iterator over the floors visited while following a sequence of
+/// elevator tokens.
+///
+/// Yields the floor reached after each token. `(` moves up one floor, `)`
+/// moves down one floor, and any other token stays on the current floor.
+#[derive(Clone, Debug)]
+pub struct FloorWalk<'a> {
+    tokens: std::slice::Iter<'a, u8>,
+    floor: Floor,
+}
+
+impl<'a> FloorWalk<'a> {
+    /// Begins a walk on the ground floor through the specified tokens.
+    pub fn new(tokens: &'a [u8]) -> Self {
+        FloorWalk { tokens: tokens.iter(), floor: 0 }
+    }
+
+    /// Returns the one-based positions of the tokens that cause one to
+    /// enter the basement from the ground floor.
+    pub fn basement_entries(self) -> Vec<usize> {
+        let mut previous = 0;
+        self.enumerate().filter_map(|(pos, floor)| {
+            let entered = previous >= 0 && floor < 0;
+            previous = floor;
+            if entered { Some(pos + 1) } else { None }
+        }).collect()
+    }
+
+    /// Returns the highest floor reached during the walk, including the
+    /// starting floor.
+    pub fn highest_floor(self) -> Floor {
+        self.fold(0, Floor::max)
+    }
+}
+
+impl<'a> Iterator for FloorWalk<'a> {
+    type Item = Floor;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.floor += match *self.tokens.next()? {
+            b'(' => 1,
+            b')' => -1,
+            _ => 0,
+        };
+        Some(self.floor)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tokens.size_hint()
+    }
+}
+
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
     let input = pio::fetch_string(puzzle)?;
 
-    let moves = parse_elevator_offset(input.as_bytes());
+    let moves = input.as_bytes();
 
     solve_parts! {
-        1 => final_floor(&moves),
-        2 => first_basement_pos(&moves)?
+        1 => final_floor(moves),
+        2 => first_basement_pos(moves)?
     }
 }
 
-/// Converts a byte slice into elevator offsets
-fn parse_elevator_offset(tokens: &[u8]) -> Vec<i8> {
-    tokens.iter().map(|&b| match b {
-        b'(' => 1,
-        b')' => -1,
-        _ => 0,
-    }).collect()
-}
-
 /// Returns the final floor one arrives on after following the specified
 /// elevator tokens.
-fn final_floor(moves: &[i8]) -> i16 {
-    moves.iter().map(|&b| i16::from(b)).sum()
+fn final_floor(tokens: &[u8]) -> Floor {
+    FloorWalk::new(tokens).last().unwrap_or(0)
 }
 
 /// Returns the position of the first move token that causes one to
 /// enter the basement i.e. enter a negative index.
-fn first_basement_pos(tokens: &[i8]) -> Result<usize, &'static str> {
-    let mut floor = 0_i16;
-    for (pos, &offset) in tokens.iter().enumerate() {
-        floor += i16::from(offset);
-        if floor == -1 {
-            return Ok(pos + 1);
-        }
-    }
-    Err("tokens never lead to basement")
+fn first_basement_pos(tokens: &[u8]) -> Result<usize, &'static str> {
+    FloorWalk::new(tokens)
+        .position(|floor| floor < 0)
+        .map(|pos| pos + 1)
+        .ok_or("tokens never lead to basement")
 }
 
 #[cfg(test)]
@@ -47,36 +89,38 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(74, 1795, Pz::new(2015, 1))
+        assert_solution!(Pz::new(2015, 1))
     }
 
-    #[test]
-    fn ex1() {
-        let test_cases: [(i8, &'static [u8]); 9] = [
-            (0, b"(())"),
-            (0, b"()()"),
-            (3, b"((("),
-            (3, b"(()(()("),
-            (3, b"))((((("),
-            (-1, b"())"),
-            (-1, b"))("),
-            (-3, b")())())"),
-            (-3, b")))"),
-        ];
-
-        for &(expected, input) in test_cases.iter() {
-            let moves = parse_elevator_offset(input);
-            assert_eq!(i16::from(expected), final_floor(&moves));
+    example_cases! {
+        ex1: |input: &str| final_floor(input.as_bytes()) => {
+            nested_pairs: "(())" => 0,
+            adjacent_pairs: "()()" => 0,
+            only_up: "(((" => 3,
+            mostly_up: "(()(()(" => 3,
+            down_then_up: "))(((((" => 3,
+            one_below: "())" => -1,
+            one_below_ending_up: "))(" => -1,
+            three_below: ")())())" => -3,
+            only_down: ")))" => -3,
+        }
+
+        ex2: |input: &str| first_basement_pos(input.as_bytes()) => {
+            first_char: ")" => Ok(1),
+            fifth_char: "()())" => Ok(5),
         }
     }
 
     #[test]
-    fn ex2() {
-        let test_cases: [(usize, &'static [u8]); 2] = [(1, b")"), (5, b"()())")];
+    fn floor_walk() {
+        let floors: Vec<Floor> = FloorWalk::new(b"(()))\n").collect();
+        assert_eq!(vec![1, 2, 1, 0, -1, -1], floors);
 
-        for &(expected, input) in test_cases.iter() {
-            let moves = parse_elevator_offset(input);
-            assert_eq!(expected, first_basement_pos(&moves).unwrap());
-        }
+        assert_eq!(vec![5, 15], FloorWalk::new(b"()()))()(((()))").basement_entries());
+        assert_eq!(Vec::<usize>::new(), FloorWalk::new(b"(()").basement_entries());
+
+        assert_eq!(2, FloorWalk::new(b"(()(").highest_floor());
+        assert_eq!(0, FloorWalk::new(b")))").highest_floor());
+        assert_eq!(0, FloorWalk::new(b"").highest_floor());
     }
 }
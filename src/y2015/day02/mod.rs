@@ -3,38 +3,52 @@
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input: Vec<present::Present> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|s| s.parse())
-        .collect::<Result<_, _>>()?;
+    let input: Vec<present::Present> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     solve_parts! {
-        1 =>  input.iter().map(present::Present::wrapping_paper).sum::<u32>(),
-        2 =>  input.iter().map(present::Present::ribbon).sum::<u32>()
+        1 => total(&input, present::Present::wrapping_paper)?,
+        2 => total(&input, present::Present::ribbon)?
     }
 }
 
+/// Returns the sum of the specified measurement over all of the presents,
+/// or an error if the sum overflows.
+fn total<F>(presents: &[present::Present], measure: F) -> Result<u64, &'static str>
+    where F: Fn(&present::Present) -> Option<u64>
+{
+    presents
+        .iter()
+        .try_fold(0_u64, |acc, p| measure(p).and_then(|m| acc.checked_add(m)))
+        .ok_or("total overflowed")
+}
+
 mod present {
     use std::str::FromStr;
 
     /// A right-rectangular prism present.
     pub struct Present {
-        sides: [u8; 3],
+        /// Dimensions of this present, sorted in ascending order.
+        ///
+        /// Each dimension fits in a `u32`, so the product of any two of them
+        /// cannot overflow.
+        sides: [u64; 3],
     }
 
     impl Present {
         /// Returns the amount of wrapping paper required to wrap this
-        /// present.
-        pub fn wrapping_paper(&self) -> u32 {
-            (3 * u32::from(self.sides[0]) * u32::from(self.sides[1]))
-                + (2 * u32::from(self.sides[1]) * u32::from(self.sides[2]))
-                + (2 * u32::from(self.sides[2]) * u32::from(self.sides[0]))
+        /// present, or `None` if the amount would overflow.
+        pub fn wrapping_paper(&self) -> Option<u64> {
+            let [a, b, c] = self.sides;
+            (a * b).checked_mul(3)?
+                .checked_add((b * c).checked_mul(2)?)?
+                .checked_add((c * a).checked_mul(2)?)
         }
 
-        /// Returns the amount of ribbon required to wrap this present.
-        pub fn ribbon(&self) -> u32 {
-            u32::from(2 * (self.sides[0] + self.sides[1]))
-                + (u32::from(self.sides[0]) * u32::from(self.sides[1]) * u32::from(self.sides[2]))
+        /// Returns the amount of ribbon required to wrap this present, or
+        /// `None` if the amount would overflow.
+        pub fn ribbon(&self) -> Option<u64> {
+            let [a, b, c] = self.sides;
+            (a * b).checked_mul(c)?.checked_add(2 * (a + b))
         }
     }
 
@@ -42,14 +56,14 @@ mod present {
         type Err = &'static str;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let mut dims: Vec<u8> = s
+            let mut dims: Vec<u64> = s
                 .split('x')
-                .map(str::parse)
-                .collect::<Result<Vec<u8>, _>>()
+                .map(|dim| dim.trim().parse::<u32>().map(u64::from))
+                .collect::<Result<Vec<u64>, _>>()
                 .map_err(|_| "malformed dimension")?;
 
             if 3 == dims.len() {
-                dims.sort();
+                dims.sort_unstable();
                 Ok(Present {
                     sides: [dims[0], dims[1], dims[2]],
                 })
@@ -66,7 +80,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(1_588_178, 3_783_758, Pz::new(2015, 2));
+        assert_solution!(Pz::new(2015, 2));
     }
 
     #[test]
@@ -75,8 +89,23 @@ mod tests {
 
         for &(paper, ribbon, input) in test_cases.iter() {
             let present: present::Present = input.parse().unwrap();
-            assert_eq!(u32::from(paper), present.wrapping_paper());
-            assert_eq!(u32::from(ribbon), present.ribbon());
+            assert_eq!(Some(u64::from(paper)), present.wrapping_paper());
+            assert_eq!(Some(u64::from(ribbon)), present.ribbon());
         }
     }
+
+    #[test]
+    fn large_presents() {
+        let present: present::Present = " 300 x1000x  2 ".parse().unwrap();
+        assert_eq!(Some(3 * 600 + 2 * 300_000 + 2 * 2000), present.wrapping_paper());
+        assert_eq!(Some(2 * 302 + 600_000), present.ribbon());
+
+        let max = u64::from(u32::MAX);
+        let present: present::Present = format!("{0}x{0}x{0}", max).parse().unwrap();
+        assert_eq!(None, present.wrapping_paper());
+        assert_eq!(None, present.ribbon());
+
+        assert!("1x2x4294967296".parse::<present::Present>().is_err());
+        assert!("1x2".parse::<present::Present>().is_err());
+    }
 }
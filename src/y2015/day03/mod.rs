@@ -16,32 +16,33 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
 /// Returns the total number of houses visited when taking the
 /// specified route (Part One).
 fn visit_houses(moves: &[Pt<i16>]) -> usize {
-    let mut pos = Pt::origin();
-    let mut visited: HashSet<Pt<i16>> = HashSet::new();
-    visited.insert(pos);
-
-    for instr in moves {
-        pos += *instr;
-        visited.insert(pos);
-    }
-
-    visited.len()
+    visit_houses_n(moves, 1)
 }
 
 /// Returns the total number of houses visited when two entities
 /// alternate taking instructions from the specified route (Part Two).
 fn visit_houses_parallel(moves: &[Pt<i16>]) -> usize {
-    let mut pos_santa = Pt::origin();
-    let mut pos_robot = Pt::origin();
-    let mut visited: HashSet<Pt<i16>> = HashSet::new();
-    visited.insert(pos_santa);
+    visit_houses_n(moves, 2)
+}
 
-    for instr in moves.chunks(2) {
-        pos_santa += instr[0];
-        pos_robot += instr[1]; // panics if input length is odd
+/// Returns the total number of houses visited when `n` entities take
+/// turns following instructions from the specified route.
+///
+/// If the route does not divide evenly between the entities, the
+/// trailing instructions are followed by the first few entities.
+///
+/// Panics if `n` is zero.
+fn visit_houses_n(moves: &[Pt<i16>], n: usize) -> usize {
+    assert_ne!(0, n, "at least one entity must deliver presents");
+    let mut positions = vec![Pt::origin(); n];
+    let mut visited: HashSet<Pt<i16>> = HashSet::new();
+    visited.insert(Pt::origin());
 
-        visited.insert(pos_santa);
-        visited.insert(pos_robot);
+    for instr in moves.chunks(n) {
+        for (pos, &offset) in positions.iter_mut().zip(instr) {
+            *pos += offset;
+            visited.insert(*pos);
+        }
     }
 
     visited.len()
@@ -64,21 +65,29 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(2572, 2631, Pz::new(2015, 3))
+        assert_solution!(Pz::new(2015, 3))
     }
 
-    #[test]
-    fn ex_both() {
-        let test_cases: [(usize, usize, &'static str); 3] = [
-            (3, 3, "^>"),
-            (4, 3, "^>v<"),
-            (2, 11, "^v^v^v^v^v")
-        ];
-
-        for &(alone, with_robot, input) in test_cases.iter() {
+    example_cases! {
+        ex_both: |input| {
             let moves = parse_input(input).unwrap();
-            assert_eq!(alone, visit_houses(&moves));
-            assert_eq!(with_robot, visit_houses_parallel(&moves));
+            (visit_houses(&moves), visit_houses_parallel(&moves))
+        } => {
+            single_turn: "^>" => (3, 3),
+            square: "^>v<" => (4, 3),
+            back_and_forth: "^v^v^v^v^v" => (2, 11),
         }
     }
+
+    #[test]
+    fn many_entities() {
+        let moves = parse_input("^>v<^").unwrap();
+
+        // Odd-length routes leave the trailing instruction to the first entity
+        assert_eq!(3, visit_houses_parallel(&moves));
+        assert_eq!(6, visit_houses_n(&moves, 3));
+        assert_eq!(6, visit_houses_n(&moves, 4));
+        assert_eq!(5, visit_houses_n(&moves, 10));
+        assert_eq!(1, visit_houses_n(&[], 3));
+    }
 }
@@ -0,0 +1,187 @@
+//! Solution for 2015 Day 06
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use std::str::FromStr;
+
+/// The width and height of the light grid.
+const GRID_SIZE: usize = 1000;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input: Vec<Instr> = pio::fetch_line_index(puzzle)?.parse_each()?;
+
+    solve_parts! {
+        1 => {
+            let mut grid = LightGrid::new(GRID_SIZE, false);
+            for instr in input.iter() {
+                grid.apply(instr, switch)?;
+            }
+            grid.lights.iter().filter(|&&lit| lit).count()
+        },
+        2 => {
+            let mut grid = LightGrid::new(GRID_SIZE, 0_u8);
+            for instr in input.iter() {
+                grid.apply(instr, adjust_brightness)?;
+            }
+            grid.lights.iter().map(|&b| u64::from(b)).sum::<u64>()
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The action that an instruction performs on a region of lights.
+enum Action {
+    On,
+    Off,
+    Toggle,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// An instruction to perform an action on every light in a rectangle.
+struct Instr {
+    action: Action,
+    /// The corner of the rectangle closest to the origin, as `(x, y)`.
+    from: (usize, usize),
+    /// The corner of the rectangle farthest from the origin, as `(x, y)`.
+    through: (usize, usize),
+}
+
+impl FromStr for Instr {
+    type Err = String;
+
+    /// Parses an instruction of the form `<action> x,y through x,y`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_corner = |corner: &str| -> Result<(usize, usize), String> {
+            let (x, y) = corner.split_once(',')
+                .ok_or_else(|| format!("malformed corner `{}` in `{}`", corner, s))?;
+            let parse = |n: &str| n.parse::<usize>().ok().filter(|&n| n < GRID_SIZE)
+                .ok_or_else(|| format!("bad coordinate `{}` in `{}`", n, s));
+            Ok((parse(x)?, parse(y)?))
+        };
+
+        let (action, rest) = [
+            ("turn on ", Action::On),
+            ("turn off ", Action::Off),
+            ("toggle ", Action::Toggle),
+        ].iter()
+            .find_map(|&(prefix, action)| s.strip_prefix(prefix).map(|rest| (action, rest)))
+            .ok_or_else(|| format!("unknown action in `{}`", s))?;
+
+        let (from, through) = rest.split_once(" through ")
+            .ok_or_else(|| format!("malformed instruction `{}`", s))?;
+        let (from, through) = (parse_corner(from)?, parse_corner(through)?);
+        if from.0 > through.0 || from.1 > through.1 {
+            return Err(format!("reversed rectangle in `{}`", s));
+        }
+
+        Ok(Instr { action, from, through })
+    }
+}
+
+/// A square grid of lights whose states are of type `T`.
+struct LightGrid<T> {
+    size: usize,
+    /// Light states in row-major order.
+    lights: Vec<T>,
+}
+
+impl<T: Copy> LightGrid<T> {
+    /// Builds a grid with `size` rows and columns of lights in the
+    /// specified initial state.
+    fn new(size: usize, initial: T) -> Self {
+        LightGrid { size, lights: vec![initial; size * size] }
+    }
+
+    /// Applies an instruction to this grid, updating each light in its
+    /// rectangle with `update`.
+    ///
+    /// An error is returned if `update` fails for any light, in which case
+    /// the grid is left partially updated.
+    fn apply<F>(&mut self, instr: &Instr, update: F) -> Result<(), &'static str>
+        where F: Fn(Action, T) -> Option<T>
+    {
+        let ((x0, y0), (x1, y1)) = (instr.from, instr.through);
+        for row in self.lights.chunks_mut(self.size).take(y1 + 1).skip(y0) {
+            for light in row[x0..=x1].iter_mut() {
+                *light = update(instr.action, *light).ok_or("light cannot be updated")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Update rule for part one: lights are either on or off.
+fn switch(action: Action, lit: bool) -> Option<bool> {
+    Some(match action {
+        Action::On => true,
+        Action::Off => false,
+        Action::Toggle => !lit,
+    })
+}
+
+/// Update rule for part two: lights have a brightness of zero or more.
+///
+/// Returns `None` if the brightness would overflow.
+fn adjust_brightness(action: Action, brightness: u8) -> Option<u8> {
+    match action {
+        Action::On => brightness.checked_add(1),
+        Action::Off => Some(brightness.saturating_sub(1)),
+        Action::Toggle => brightness.checked_add(2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::parse_lines;
+
+    /// Applies the instructions to a fresh grid and returns the grid.
+    fn run<T, F>(instrs: &[&str], initial: T, update: F) -> LightGrid<T>
+        where T: Copy, F: Fn(Action, T) -> Option<T> + Copy
+    {
+        let mut grid = LightGrid::new(GRID_SIZE, initial);
+        for instr in parse_lines::<Instr, _>(instrs) {
+            grid.apply(&instr, update).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn ex1() {
+        let count_lit = |grid: LightGrid<bool>| grid.lights.iter().filter(|&&l| l).count();
+
+        assert_eq!(1_000_000, count_lit(run(&["turn on 0,0 through 999,999"], false, switch)));
+        assert_eq!(1000, count_lit(run(&["toggle 0,0 through 999,0"], false, switch)));
+        assert_eq!(999_996, count_lit(run(&[
+            "turn on 0,0 through 999,999",
+            "turn off 499,499 through 500,500",
+        ], false, switch)));
+    }
+
+    #[test]
+    fn ex2() {
+        let brightness = |grid: LightGrid<u8>| grid.lights.iter().map(|&b| u64::from(b)).sum::<u64>();
+
+        assert_eq!(1, brightness(run(&["turn on 0,0 through 0,0"], 0, adjust_brightness)));
+        assert_eq!(2_000_000, brightness(run(&["toggle 0,0 through 999,999"], 0, adjust_brightness)));
+        assert_eq!(0, brightness(run(&["turn off 0,0 through 999,999"], 0, adjust_brightness)));
+    }
+
+    #[test]
+    fn parse_instr() {
+        assert_eq!(
+            Ok(Instr { action: Action::Toggle, from: (461, 550), through: (564, 900) }),
+            "toggle 461,550 through 564,900".parse()
+        );
+        assert!("turn up 0,0 through 1,1".parse::<Instr>().is_err());
+        assert!("turn on 0,0 through 1000,1".parse::<Instr>().is_err());
+        assert!("turn on 5,0 through 1,1".parse::<Instr>().is_err());
+        assert!("turn on 0,0 to 1,1".parse::<Instr>().is_err());
+    }
+
+    #[test]
+    fn brightness_overflow() {
+        let mut grid = LightGrid::new(GRID_SIZE, u8::MAX - 1);
+        let instr = "toggle 0,0 through 0,0".parse().unwrap();
+        assert!(grid.apply(&instr, adjust_brightness).is_err());
+    }
+}
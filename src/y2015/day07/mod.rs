@@ -0,0 +1,212 @@
+//! Solution for 2015 Day 07
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// A signal carried by a wire.
+type Signal = u16;
+
+/// The wire whose signal is measured.
+const OUTPUT_WIRE: &str = "a";
+
+/// The wire that is overridden with the output signal in part two.
+const OVERRIDE_WIRE: &str = "b";
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let circuit = Circuit::parse(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => circuit.signal(OUTPUT_WIRE)?,
+        2 => {
+            let mut rewired = circuit.clone();
+            rewired.set_signal(OVERRIDE_WIRE, circuit.signal(OUTPUT_WIRE)?);
+            rewired.signal(OUTPUT_WIRE)?
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// An input to a gate; either a wire or a constant signal.
+enum Operand {
+    Wire(String),
+    Value(Signal),
+}
+
+impl FromStr for Operand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = s.parse() {
+            Ok(Operand::Value(value))
+        } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_lowercase()) {
+            Ok(Operand::Wire(s.to_owned()))
+        } else {
+            Err(format!("invalid operand `{}`", s))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// A gate that provides a signal to a wire.
+enum Gate {
+    Direct(Operand),
+    And(Operand, Operand),
+    Or(Operand, Operand),
+    LShift(Operand, Operand),
+    RShift(Operand, Operand),
+    Not(Operand),
+}
+
+impl FromStr for Gate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *s.split_whitespace().collect::<Vec<_>>() {
+            [a] => Ok(Gate::Direct(a.parse()?)),
+            ["NOT", a] => Ok(Gate::Not(a.parse()?)),
+            [a, "AND", b] => Ok(Gate::And(a.parse()?, b.parse()?)),
+            [a, "OR", b] => Ok(Gate::Or(a.parse()?, b.parse()?)),
+            [a, "LSHIFT", b] => Ok(Gate::LShift(a.parse()?, b.parse()?)),
+            [a, "RSHIFT", b] => Ok(Gate::RShift(a.parse()?, b.parse()?)),
+            _ => Err(format!("invalid gate `{}`", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A circuit of wires, each of which is driven by exactly one gate.
+struct Circuit {
+    gates: HashMap<String, Gate>,
+}
+
+impl Circuit {
+    /// Parses a circuit from a list of connections of the form
+    /// `<gate> -> <wire>`.
+    fn parse<T>(lines: &[T]) -> Result<Self, String>
+        where T: AsRef<str>
+    {
+        let mut gates = HashMap::with_capacity(lines.len());
+        for line in lines.iter().map(AsRef::as_ref) {
+            let (gate, wire) = line.split_once(" -> ")
+                .ok_or_else(|| format!("malformed connection `{}`", line))?;
+            let wire = match wire.trim().parse()? {
+                Operand::Wire(wire) => wire,
+                Operand::Value(_) => return Err(format!("cannot connect to signal `{}`", wire)),
+            };
+            if gates.contains_key(&wire) {
+                return Err(format!("wire `{}` is driven more than once", wire));
+            }
+            gates.insert(wire, gate.parse()?);
+        }
+        Ok(Circuit { gates })
+    }
+
+    /// Replaces the gate driving `wire` with the constant `signal`.
+    fn set_signal(&mut self, wire: &str, signal: Signal) {
+        self.gates.insert(wire.to_owned(), Gate::Direct(Operand::Value(signal)));
+    }
+
+    /// Returns the signal carried by the specified wire.
+    fn signal(&self, wire: &str) -> Result<Signal, String> {
+        Evaluator { circuit: self, memo: HashMap::new(), pending: HashSet::new() }
+            .wire_signal(wire)
+    }
+}
+
+/// A memoized evaluation of the signals in a circuit.
+struct Evaluator<'a> {
+    circuit: &'a Circuit,
+    /// The signals of the wires that have been evaluated so far.
+    memo: HashMap<&'a str, Signal>,
+    /// The wires that are currently being evaluated, used to detect loops.
+    pending: HashSet<&'a str>,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Returns the signal carried by the specified wire.
+    fn wire_signal(&mut self, wire: &str) -> Result<Signal, String> {
+        if let Some(&signal) = self.memo.get(wire) {
+            return Ok(signal);
+        }
+        let (wire, gate) = self.circuit.gates.get_key_value(wire)
+            .ok_or_else(|| format!("wire `{}` is not connected", wire))?;
+        if !self.pending.insert(wire) {
+            return Err(format!("wire `{}` depends on itself", wire));
+        }
+
+        let signal = match *gate {
+            Gate::Direct(ref a) => self.operand(a)?,
+            Gate::Not(ref a) => !self.operand(a)?,
+            Gate::And(ref a, ref b) => self.operand(a)? & self.operand(b)?,
+            Gate::Or(ref a, ref b) => self.operand(a)? | self.operand(b)?,
+            Gate::LShift(ref a, ref b) => {
+                let (a, b) = (self.operand(a)?, self.operand(b)?);
+                a.checked_shl(u32::from(b)).unwrap_or(0)
+            }
+            Gate::RShift(ref a, ref b) => {
+                let (a, b) = (self.operand(a)?, self.operand(b)?);
+                a.checked_shr(u32::from(b)).unwrap_or(0)
+            }
+        };
+
+        self.pending.remove(wire.as_str());
+        self.memo.insert(wire, signal);
+        Ok(signal)
+    }
+
+    /// Returns the signal provided by the specified operand.
+    fn operand(&mut self, operand: &Operand) -> Result<Signal, String> {
+        match *operand {
+            Operand::Value(value) => Ok(value),
+            Operand::Wire(ref wire) => self.wire_signal(wire),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let circuit = Circuit::parse(&[
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+        ]).unwrap();
+
+        let expected = [
+            ("d", 72), ("e", 507), ("f", 492), ("g", 114),
+            ("h", 65412), ("i", 65079), ("x", 123), ("y", 456),
+        ];
+        for &(wire, signal) in expected.iter() {
+            assert_eq!(Ok(signal), circuit.signal(wire), "{}", wire);
+        }
+    }
+
+    #[test]
+    fn rewire() {
+        let mut circuit = Circuit::parse(&["b -> a", "1 AND c -> b", "7 -> c"]).unwrap();
+        assert_eq!(Ok(1), circuit.signal("a"));
+        circuit.set_signal("b", 42);
+        assert_eq!(Ok(42), circuit.signal("a"));
+    }
+
+    #[test]
+    fn reject_bad_circuits() {
+        assert!(Circuit::parse(&["123 -> 4"]).is_err());
+        assert!(Circuit::parse(&["x XOR y -> z"]).is_err());
+        assert!(Circuit::parse(&["1 -> x", "2 -> x"]).is_err());
+        assert!(Circuit::parse(&["NOT x"]).is_err());
+
+        let circuit = Circuit::parse(&["x -> y", "NOT y -> x", "q -> z"]).unwrap();
+        assert!(circuit.signal("x").is_err());
+        assert!(circuit.signal("z").is_err());
+    }
+}
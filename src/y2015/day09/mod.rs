@@ -0,0 +1,108 @@
+//! Solution for 2015 Day 09
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::permutations;
+use std::collections::HashMap;
+
+/// A distance between two cities.
+type Distance = u32;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let map = DistanceMap::parse(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        both => map.route_extremes().ok_or("no route visits every city")?
+    }
+}
+
+/// The distances between pairs of cities.
+struct DistanceMap {
+    /// The distance between each pair of cities, indexed by the cities'
+    /// order of appearance in the input.
+    distances: Vec<Vec<Option<Distance>>>,
+}
+
+impl DistanceMap {
+    /// Parses a list of distances of the form `<city> to <city> = <distance>`.
+    fn parse<T>(lines: &[T]) -> Result<Self, String>
+        where T: AsRef<str>
+    {
+        let mut cities: HashMap<&str, usize> = HashMap::new();
+        let mut edges = Vec::with_capacity(lines.len());
+
+        for line in lines.iter().map(AsRef::as_ref) {
+            let (from, to, dist) = match *line.split_whitespace().collect::<Vec<_>>() {
+                [from, "to", to, "=", dist] => (from, to, dist),
+                _ => return Err(format!("malformed distance `{}`", line)),
+            };
+            let dist: Distance = dist.parse()
+                .map_err(|e| format!("bad distance in `{}`: {}", line, e))?;
+            let next_index = cities.len();
+            let from = *cities.entry(from).or_insert(next_index);
+            let next_index = cities.len();
+            let to = *cities.entry(to).or_insert(next_index);
+            if from == to {
+                return Err(format!("city is its own neighbor in `{}`", line));
+            }
+            edges.push((from, to, dist));
+        }
+
+        let mut distances = vec![vec![None; cities.len()]; cities.len()];
+        for (from, to, dist) in edges {
+            distances[from][to] = Some(dist);
+            distances[to][from] = Some(dist);
+        }
+        Ok(DistanceMap { distances })
+    }
+
+    /// Returns the total distance of a route that visits the cities in the
+    /// specified order, or `None` if any leg of the route is unknown.
+    fn route_distance(&self, route: &[usize]) -> Option<Distance> {
+        route.windows(2).map(|leg| self.distances[leg[0]][leg[1]]).sum()
+    }
+
+    /// Returns the distances of the shortest and longest routes that visit
+    /// every city exactly once, or `None` if no such route exists.
+    fn route_extremes(&self) -> Option<(Distance, Distance)> {
+        permutations((0..self.distances.len()).collect())
+            // A route and its reverse are equally long
+            .filter(|route| route.first() <= route.last())
+            .filter_map(|route| self.route_distance(&route))
+            .fold(None, |extremes, dist| match extremes {
+                None => Some((dist, dist)),
+                Some((min, max)) => Some((min.min(dist), max.max(dist))),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex_both() {
+        let map = DistanceMap::parse(&[
+            "London to Dublin = 464",
+            "London to Belfast = 518",
+            "Dublin to Belfast = 141",
+        ]).unwrap();
+        assert_eq!(Some((605, 982)), map.route_extremes());
+    }
+
+    #[test]
+    fn missing_legs() {
+        // A and C are only connected through B
+        let map = DistanceMap::parse(&["A to B = 1", "B to C = 2"]).unwrap();
+        assert_eq!(Some((3, 3)), map.route_extremes());
+
+        let map = DistanceMap::parse(&["A to B = 1", "C to D = 2"]).unwrap();
+        assert_eq!(None, map.route_extremes());
+    }
+
+    #[test]
+    fn reject_bad_distances() {
+        assert!(DistanceMap::parse(&["A to B 1"]).is_err());
+        assert!(DistanceMap::parse(&["A to B = -1"]).is_err());
+        assert!(DistanceMap::parse(&["A to A = 1"]).is_err());
+    }
+}
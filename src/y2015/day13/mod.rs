@@ -0,0 +1,162 @@
+//! Solution for 2015 Day 13
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::permutations;
+use std::collections::HashMap;
+
+/// A change in a guest's happiness.
+type Happiness = i32;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let table = SeatingPreferences::parse(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => table.max_happiness().ok_or("there are no guests to seat")?,
+        2 => table.with_apathetic_guest().max_happiness().unwrap() // always has a guest
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The happiness that each guest gains or loses when seated next to each
+/// other guest.
+struct SeatingPreferences {
+    /// The change in happiness of guest `i` when seated next to guest `j`,
+    /// indexed as `[i][j]`. Pairs of guests without a listed preference are
+    /// indifferent to one another.
+    happiness: Vec<Vec<Happiness>>,
+}
+
+impl SeatingPreferences {
+    /// Parses a list of preferences of the form
+    /// `<guest> would <gain|lose> <n> happiness units by sitting next to <guest>.`
+    fn parse<T>(lines: &[T]) -> Result<Self, String>
+        where T: AsRef<str>
+    {
+        let mut guests: HashMap<&str, usize> = HashMap::new();
+        let mut prefs = Vec::with_capacity(lines.len());
+
+        for line in lines.iter().map(AsRef::as_ref) {
+            let words: Vec<&str> = line.trim_end_matches('.').split_whitespace().collect();
+            let (guest, sign, amount, neighbor) = match *words {
+                [guest, "would", "gain", amount, "happiness", "units", "by", "sitting", "next", "to", neighbor] =>
+                    (guest, 1, amount, neighbor),
+                [guest, "would", "lose", amount, "happiness", "units", "by", "sitting", "next", "to", neighbor] =>
+                    (guest, -1, amount, neighbor),
+                _ => return Err(format!("malformed preference `{}`", line)),
+            };
+            let amount: Happiness = amount.parse()
+                .map_err(|e| format!("bad happiness in `{}`: {}", line, e))?;
+
+            let next_index = guests.len();
+            let guest = *guests.entry(guest).or_insert(next_index);
+            let next_index = guests.len();
+            let neighbor = *guests.entry(neighbor).or_insert(next_index);
+            if guest == neighbor {
+                return Err(format!("guest cannot sit next to themself in `{}`", line));
+            }
+            prefs.push((guest, neighbor, sign * amount));
+        }
+
+        let mut happiness = vec![vec![0; guests.len()]; guests.len()];
+        for (guest, neighbor, change) in prefs {
+            happiness[guest][neighbor] = change;
+        }
+        Ok(SeatingPreferences { happiness })
+    }
+
+    /// Returns these preferences with an additional guest who is indifferent
+    /// to everyone, and to whom everyone is indifferent.
+    fn with_apathetic_guest(&self) -> Self {
+        let mut happiness = self.happiness.clone();
+        for row in happiness.iter_mut() {
+            row.push(0);
+        }
+        happiness.push(vec![0; happiness.len() + 1]);
+        SeatingPreferences { happiness }
+    }
+
+    /// Returns the total change in happiness when guests are seated around
+    /// a circular table in the specified order.
+    fn total_happiness(&self, seating: &[usize]) -> Happiness {
+        let neighbors = seating.iter().zip(seating.iter().cycle().skip(1));
+        neighbors.take(seating.len())
+            .map(|(&a, &b)| self.happiness[a][b] + self.happiness[b][a])
+            .sum()
+    }
+
+    /// Returns the greatest total change in happiness over all seatings, or
+    /// `None` if there are no guests.
+    fn max_happiness(&self) -> Option<Happiness> {
+        let guest_count = self.happiness.len();
+        if guest_count == 0 {
+            return None;
+        }
+        // Rotations of a circular seating are equivalent, so the first
+        // guest is always seated in the same place
+        permutations((1..guest_count).collect())
+            .map(|mut seating| {
+                seating.insert(0, 0);
+                self.total_happiness(&seating)
+            })
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: [&str; 12] = [
+        "Alice would gain 54 happiness units by sitting next to Bob.",
+        "Alice would lose 79 happiness units by sitting next to Carol.",
+        "Alice would lose 2 happiness units by sitting next to David.",
+        "Bob would gain 83 happiness units by sitting next to Alice.",
+        "Bob would lose 7 happiness units by sitting next to Carol.",
+        "Bob would lose 63 happiness units by sitting next to David.",
+        "Carol would lose 62 happiness units by sitting next to Alice.",
+        "Carol would gain 60 happiness units by sitting next to Bob.",
+        "Carol would gain 55 happiness units by sitting next to David.",
+        "David would gain 46 happiness units by sitting next to Alice.",
+        "David would lose 7 happiness units by sitting next to Bob.",
+        "David would gain 41 happiness units by sitting next to Carol.",
+    ];
+
+    #[test]
+    fn ex1() {
+        let table = SeatingPreferences::parse(&EXAMPLE_INPUT).unwrap();
+        assert_eq!(Some(330), table.max_happiness());
+    }
+
+    #[test]
+    fn apathetic_guest() {
+        let table = SeatingPreferences::parse(&EXAMPLE_INPUT).unwrap().with_apathetic_guest();
+        assert_eq!(5, table.happiness.len());
+        assert!(table.happiness.iter().all(|row| row.len() == 5 && row[4] == 0));
+        // The new guest breaks up the least happy pair of the best seating,
+        // David and Alice
+        assert_eq!(Some(330 - 46 + 2), table.max_happiness());
+    }
+
+    #[test]
+    fn small_tables() {
+        assert_eq!(None, SeatingPreferences::parse::<&str>(&[]).unwrap().max_happiness());
+
+        let pair = SeatingPreferences::parse(&[
+            "A would gain 3 happiness units by sitting next to B.",
+            "B would lose 1 happiness units by sitting next to A.",
+        ]).unwrap();
+        // Each guest sits on both sides of the other
+        assert_eq!(Some(4), pair.max_happiness());
+    }
+
+    #[test]
+    fn reject_bad_preferences() {
+        assert!(SeatingPreferences::parse(&["A would gain 3 happiness units."]).is_err());
+        assert!(SeatingPreferences::parse(&[
+            "A would win 3 happiness units by sitting next to B."
+        ]).is_err());
+        assert!(SeatingPreferences::parse(&[
+            "A would gain 3 happiness units by sitting next to A."
+        ]).is_err());
+    }
+}
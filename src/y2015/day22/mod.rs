@@ -0,0 +1,273 @@
+//! Solution for 2015 Day 22
+//!
+//! Each fight is modeled as a graph whose nodes are the game states at the
+//! beginning of the player's turns, and whose edges are spells weighted by
+//! their mana cost. The least mana needed to win is then the cost of the
+//! cheapest path to a state in which the boss has been defeated.
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::search::dijkstra;
+
+/// A number of hit points.
+type HitPoints = i32;
+
+/// An amount of mana.
+type Mana = u32;
+
+/// The hit points that the player begins with.
+const PLAYER_HIT_POINTS: HitPoints = 50;
+
+/// The mana that the player begins with.
+const PLAYER_MANA: Mana = 500;
+
+/// The hit points lost by the player at the beginning of each of their turns
+/// on hard difficulty.
+const HARD_MODE_DRAIN: HitPoints = 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A spell that the player can cast.
+enum Spell {
+    MagicMissile,
+    Drain,
+    Shield,
+    Poison,
+    Recharge,
+}
+
+impl Spell {
+    /// Every spell that the player knows.
+    const ALL: [Spell; 5] = [
+        Spell::MagicMissile,
+        Spell::Drain,
+        Spell::Shield,
+        Spell::Poison,
+        Spell::Recharge,
+    ];
+
+    /// Returns the mana needed to cast this spell.
+    fn cost(self) -> Mana {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
+    }
+}
+
+/// The number of turns that the shield effect lasts.
+const SHIELD_TURNS: u8 = 6;
+/// The armor granted while the shield effect is active.
+const SHIELD_ARMOR: HitPoints = 7;
+/// The number of turns that the poison effect lasts.
+const POISON_TURNS: u8 = 6;
+/// The damage dealt by the poison effect each turn.
+const POISON_DAMAGE: HitPoints = 3;
+/// The number of turns that the recharge effect lasts.
+const RECHARGE_TURNS: u8 = 5;
+/// The mana restored by the recharge effect each turn.
+const RECHARGE_MANA: Mana = 101;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+/// The state of a fight at the beginning of one of the player's turns.
+struct GameState {
+    player_hp: HitPoints,
+    mana: Mana,
+    boss_hp: HitPoints,
+    /// The remaining turns of each effect.
+    shield: u8,
+    poison: u8,
+    recharge: u8,
+}
+
+impl GameState {
+    /// Returns true if the boss has been defeated.
+    fn is_won(&self) -> bool {
+        self.boss_hp <= 0
+    }
+
+    /// Applies every active effect for one turn, returning the player's
+    /// armor for the turn.
+    fn apply_effects(&mut self) -> HitPoints {
+        let mut armor = 0;
+        if self.shield > 0 {
+            armor = SHIELD_ARMOR;
+            self.shield -= 1;
+        }
+        if self.poison > 0 {
+            self.boss_hp -= POISON_DAMAGE;
+            self.poison -= 1;
+        }
+        if self.recharge > 0 {
+            self.mana += RECHARGE_MANA;
+            self.recharge -= 1;
+        }
+        armor
+    }
+
+    /// Casts a spell, or returns `false` if the spell cannot be cast.
+    fn cast(&mut self, spell: Spell) -> bool {
+        if self.mana < spell.cost() {
+            return false;
+        }
+        match spell {
+            Spell::MagicMissile => self.boss_hp -= 4,
+            Spell::Drain => {
+                self.boss_hp -= 2;
+                self.player_hp += 2;
+            }
+            Spell::Shield if self.shield == 0 => self.shield = SHIELD_TURNS,
+            Spell::Poison if self.poison == 0 => self.poison = POISON_TURNS,
+            Spell::Recharge if self.recharge == 0 => self.recharge = RECHARGE_TURNS,
+            // Effects cannot be cast while they are active
+            _ => return false,
+        }
+        self.mana -= spell.cost();
+        true
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// The rules of a fight against a boss.
+struct Fight {
+    boss_damage: HitPoints,
+    /// Whether the player loses hit points at the beginning of their turns.
+    hard_mode: bool,
+}
+
+impl Fight {
+    /// Returns the states that can follow the specified state after one
+    /// round of the fight, along with the mana spent to reach them. Rounds
+    /// in which the player dies are omitted.
+    fn next_states(&self, state: &GameState) -> Vec<(GameState, Mana)> {
+        let mut state = *state;
+        if self.hard_mode {
+            state.player_hp -= HARD_MODE_DRAIN;
+            if state.player_hp <= 0 {
+                return Vec::new();
+            }
+        }
+        state.apply_effects();
+        if state.is_won() {
+            return vec![(state, 0)];
+        }
+
+        Spell::ALL.iter().filter_map(|&spell| {
+            let mut next = state;
+            if !next.cast(spell) {
+                return None;
+            }
+            if !next.is_won() {
+                // The boss's turn
+                let armor = next.apply_effects();
+                if !next.is_won() {
+                    next.player_hp -= (self.boss_damage - armor).max(1);
+                    if next.player_hp <= 0 {
+                        return None;
+                    }
+                }
+            }
+            Some((next, spell.cost()))
+        }).collect()
+    }
+
+    /// Returns the least mana the player can spend to win the fight from the
+    /// specified state, or `None` if the fight cannot be won.
+    fn least_mana_to_win(&self, start: GameState) -> Option<Mana> {
+        dijkstra(start, |state| self.next_states(state), GameState::is_won)
+            .map(|(_, mana)| mana)
+    }
+}
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let (boss_hp, boss_damage) = parse_boss(&pio::fetch_string(puzzle)?)?;
+    let start = GameState {
+        player_hp: PLAYER_HIT_POINTS,
+        mana: PLAYER_MANA,
+        boss_hp,
+        shield: 0,
+        poison: 0,
+        recharge: 0,
+    };
+
+    solve_parts! {
+        1 => Fight { boss_damage, hard_mode: false }.least_mana_to_win(start)
+            .ok_or("the boss cannot be defeated")?,
+        2 => Fight { boss_damage, hard_mode: true }.least_mana_to_win(start)
+            .ok_or("the boss cannot be defeated on hard mode")?
+    }
+}
+
+/// Parses the boss's hit points and damage.
+fn parse_boss(input: &str) -> Result<(HitPoints, HitPoints), String> {
+    let mut hit_points = None;
+    let mut damage = None;
+
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (stat, value) = line.split_once(':')
+            .ok_or_else(|| format!("malformed boss stat `{}`", line))?;
+        let value: HitPoints = value.trim().parse()
+            .map_err(|e| format!("bad boss stat `{}`: {}", line, e))?;
+        match stat {
+            "Hit Points" => hit_points = Some(value),
+            "Damage" => damage = Some(value),
+            _ => return Err(format!("unknown boss stat `{}`", stat)),
+        }
+    }
+    match (hit_points, damage) {
+        (Some(hit_points), Some(damage)) => Ok((hit_points, damage)),
+        _ => Err("boss stats must include hit points and damage".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The starting state of the example fights.
+    fn example_start(boss_hp: HitPoints) -> GameState {
+        GameState { player_hp: 10, mana: 250, boss_hp, shield: 0, poison: 0, recharge: 0 }
+    }
+
+    #[test]
+    fn ex1() {
+        let fight = Fight { boss_damage: 8, hard_mode: false };
+        // Poison, then Magic Missile
+        assert_eq!(Some(173 + 53), fight.least_mana_to_win(example_start(13)));
+    }
+
+    #[test]
+    fn ex2() {
+        let fight = Fight { boss_damage: 8, hard_mode: false };
+        // Recharge, Shield, Drain, Poison, then Magic Missile
+        assert_eq!(Some(229 + 113 + 73 + 173 + 53), fight.least_mana_to_win(example_start(14)));
+    }
+
+    #[test]
+    fn hard_mode() {
+        let fight = Fight { boss_damage: 8, hard_mode: true };
+        assert_eq!(None, fight.least_mana_to_win(example_start(14)));
+
+        let mut start = example_start(13);
+        start.player_hp = 11;
+        assert_eq!(Some(173 + 53), fight.least_mana_to_win(start));
+    }
+
+    #[test]
+    fn effects() {
+        let mut state = example_start(14);
+        assert!(state.cast(Spell::Shield));
+        assert!(!state.cast(Spell::Shield));
+        assert_eq!(SHIELD_ARMOR, state.apply_effects());
+        assert!(!state.cast(Spell::Recharge));
+        assert_eq!(250 - 113, state.mana);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok((58, 9)), parse_boss("Hit Points: 58\nDamage: 9\n"));
+        assert!(parse_boss("Hit Points: 58\n").is_err());
+        assert!(parse_boss("Hit Points: 58\nArmor: 2\nDamage: 9").is_err());
+    }
+}
@@ -3,12 +3,31 @@ pub mod day02;
 pub mod day03;
 pub mod day04;
 pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day09;
+pub mod day13;
+pub mod day22;
 
 route_days! {
     1 => day01,
     2 => day02,
     3 => day03,
     4 => day04,
-    5 => day05
+    5 => day05,
+    6 => day06,
+    7 => day07,
+    9 => day09,
+    13 => day13,
+    22 => day22
+}
 
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_implemented_days() {
+        assert_routes!(2015, [1, 2, 3, 4, 5, 6, 7, 9, 13, 22]);
+    }
+}
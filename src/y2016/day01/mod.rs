@@ -60,11 +60,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution! {
-            332,
-            166,
-            Pz::new(2016,1)
-        }
+        assert_solution!(Pz::new(2016, 1))
     }
 
     #[test]
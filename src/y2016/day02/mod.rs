@@ -1,71 +1,61 @@
 //! Solution for 2016 Day 02.
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
-use crate::common::util::Pt;
+use crate::common::util::{Keypad, Pt};
 
-/// The keypad used to determine the passcode during part one.
-const KEYPAD_ONE: [&[char]; 3] = [
-    &['1', '2', '3'],
-    &['4', '5', '6'],
-    &['7', '8', '9'],
-];
+/// The layout of the keypad used to determine the passcode during part one.
+const KEYPAD_ONE: &str = "\
+    123
+    456
+    789";
 
-/// The "finger" starting position on the part one keypad.
-const START_ONE: Pt<i8> = Pt { x: 1, y: 1 };
+/// The layout of the keypad used to determine the passcode during part two.
+const KEYPAD_TWO: &str = "\
+    xx1xx
+    x234x
+    56789
+    xABCx
+    xxDxx";
 
-/// The keypad used to determine the passcode during part two.
-const KEYPAD_TWO: [&[char]; 5] = [
-    &['x', 'x', '1', 'x', 'x'],
-    &['x', '2', '3', '4', 'x'],
-    &['5', '6', '7', '8', '9'],
-    &['x', 'A', 'B', 'C', 'x'],
-    &['x', 'x', 'D', 'x', 'x'],
-];
-
-/// The "finger" starting position on the part two keypad.
-const START_TWO: Pt<i8> = Pt { x: 0, y: 2 };
+/// The key that the "finger" starts on for both keypads.
+const START_KEY: char = '5';
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input = pio::fetch_lines(puzzle)?;
+    let lines = pio::fetch_line_index(puzzle)?;
+    let input = lines.to_vec();
 
     solve_parts! {
-        1 => press_keycode(&KEYPAD_ONE, &input, START_ONE)?,
-        2 => press_keycode(&KEYPAD_TWO, &input, START_TWO)?
+        1 => press_keycode(&KEYPAD_ONE.parse()?, &input, START_KEY)?,
+        2 => press_keycode(&KEYPAD_TWO.parse()?, &input, START_KEY)?
     }
 }
 
 /// Returns the passcode resulting from following the specified
-/// instructions of the specified keypad.
+/// instructions of the specified keypad, starting at `start_key`.
 ///
-/// An error is returned if an invalid instruction is found.
+/// An error is returned if an invalid instruction is found or if the
+/// starting key is not on the keypad.
 fn press_keycode<S: AsRef<str>>(
-    keypad: &[&[char]],
+    keypad: &Keypad,
     instr: &[S],
-    start: Pt<i8>,
+    start_key: char,
 ) -> Result<String, String> {
-    // keypad assumed to be a square
-    let dim = keypad.len() - 1;
-    let mut finger = start; // copy point
+    let mut finger = keypad
+        .position_of(start_key)
+        .ok_or_else(|| format!("Start key not on keypad: {}", start_key))?;
 
     instr.iter().map(|line| {
         for byte in line.as_ref().as_bytes() {
-            let next = finger + match *byte {
-                b'U' => Pt::<i8>::n(),
+            let dir = match *byte {
+                b'U' => Pt::n(),
                 b'R' => Pt::e(),
                 b'D' => Pt::s(),
                 b'L' => Pt::w(),
                 b => return Err(format!("Bad direction: {}", b as char))
             };
-            {
-                let Pt { x, y } = next;
-                if (x >= 0 && x <= dim as i8)
-                    && (y >= 0 && y <= dim as i8)
-                    && keypad[dim - y as usize][x as usize] != 'x' {
-                    finger = next;
-                }
-            }
+            finger = keypad.step(finger, dir);
         }
-        Ok(keypad[dim - finger.y as usize][finger.x as usize])
+        Ok(keypad.key_at(finger).unwrap()) // finger only rests on keys
     }).collect()
 }
 
@@ -75,7 +65,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!("99332", "DD483", Pz::new(2016, 2))
+        assert_solution!(Pz::new(2016, 2))
     }
 
     #[test]
@@ -84,7 +74,7 @@ mod tests {
 
         assert_eq!(
             "1985",
-            press_keycode(&KEYPAD_ONE, &instr, START_TWO).unwrap()
+            press_keycode(&KEYPAD_ONE.parse().unwrap(), &instr, START_KEY).unwrap()
         );
     }
 
@@ -94,7 +84,7 @@ mod tests {
 
         assert_eq!(
             "5DB3",
-            press_keycode(&KEYPAD_TWO, &instr, START_TWO).unwrap()
+            press_keycode(&KEYPAD_TWO.parse().unwrap(), &instr, START_KEY).unwrap()
         );
     }
 }
@@ -1,55 +1,94 @@
 //! Solution for 2016 Day 03.
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::geom::Triangle;
 
-#[derive(Debug)]
-struct Triangle(u16, u16, u16);
-
-impl Triangle {
-    fn is_valid(&self) -> bool {
-        let Triangle(a, b, c) = *self;
-        if a > b && a > c {
-            b + c > a
-        } else if b > c {
-            a + c > b
-        } else {
-            a + b > c
-        }
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input = pio::fetch_line_index(puzzle)?;
+
+    solve_parts! {
+        1 => count_valid(triangles_by_row(&input))?,
+        2 => count_valid(triangles_by_col(&input))?
     }
 }
 
-pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let tri_desc: Vec<Vec<u16>> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|token| token.parse())
-                .collect()
-        }).collect::<Result<_, _>>()?;
+/// Returns the number of valid triangles produced by the specified
+/// iterator, or the first error that it yields.
+fn count_valid<I>(triangles: I) -> Result<usize, String>
+    where I: IntoIterator<Item = Result<Triangle, String>>
+{
+    triangles.into_iter().try_fold(0, |count, tri| {
+        Ok(count + tri?.is_valid() as usize)
+    })
+}
 
-    solve_parts! {
-        1 => triangles_by_row(&tri_desc).into_iter().filter(|tri| tri.is_valid()).count(),
-        2 => triangles_by_col(&tri_desc).into_iter().filter(|tri| tri.is_valid()).count()
+/// Parses a row of exactly three side lengths.
+fn parse_row(line: &str) -> Result<[u32; 3], String> {
+    let mut sides = [0; 3];
+    let mut tokens = line.split_whitespace();
+
+    for side in sides.iter_mut() {
+        *side = tokens.next()
+            .ok_or_else(|| format!("too few sides in row `{}`", line))?
+            .parse()
+            .map_err(|e| format!("bad side in row `{}`: {}", line, e))?;
+    }
+    if tokens.next().is_some() {
+        return Err(format!("too many sides in row `{}`", line));
     }
+    Ok(sides)
+}
+
+/// Returns an iterator over the triangles described by each row.
+fn triangles_by_row<'a, I>(lines: I) -> impl Iterator<Item = Result<Triangle, String>> + 'a
+    where I: IntoIterator<Item = &'a str>, I::IntoIter: 'a
+{
+    lines.into_iter().map(|line| {
+        parse_row(line).map(|[a, b, c]| Triangle(a, b, c))
+    })
 }
 
-fn triangles_by_row(tri_desc: &[Vec<u16>]) -> Vec<Triangle> {
-    tri_desc.iter().map(|sides| {
-        debug_assert!(sides.len() == 3);
-        Triangle(sides[0], sides[1], sides[2])
-    }).collect()
+/// Returns an iterator over the triangles described by the columns of
+/// each group of three rows.
+fn triangles_by_col<'a, I>(lines: I) -> ColumnTriangles<I::IntoIter>
+    where I: IntoIterator<Item = &'a str>
+{
+    ColumnTriangles { lines: lines.into_iter(), rows: [[0; 3]; 3], next_col: 3 }
 }
 
-fn triangles_by_col(tri_desc: &[Vec<u16>]) -> Vec<Triangle> {
-    tri_desc.chunks(3).flat_map(|three_tri| {
-        three_tri[0].iter()
-            .zip(three_tri[1].iter())
-            .zip(three_tri[2].iter())
-            .map(|col| {
-                let ((a, b), c) = col;
-                Triangle(*a, *b, *c)
-            })
-    }).collect()
+/// Iterator over the triangles described by the columns of groups of three
+/// rows. Only one group of rows is held at a time.
+struct ColumnTriangles<I> {
+    lines: I,
+    /// The current group of rows.
+    rows: [[u32; 3]; 3],
+    /// The column of `rows` that describes the next triangle.
+    next_col: usize,
+}
+
+impl<'a, I> Iterator for ColumnTriangles<I> where I: Iterator<Item = &'a str> {
+    type Item = Result<Triangle, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_col == 3 {
+            // Read the next group of rows
+            let first = self.lines.next()?;
+            for i in 0..3 {
+                let line = if i == 0 { Some(first) } else { self.lines.next() };
+                let row = line
+                    .ok_or_else(|| "row count is not a multiple of three".to_owned())
+                    .and_then(parse_row);
+                match row {
+                    Ok(row) => self.rows[i] = row,
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            self.next_col = 0;
+        }
+        let col = self.next_col;
+        self.next_col += 1;
+        Some(Ok(Triangle(self.rows[0][col], self.rows[1][col], self.rows[2][col])))
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +97,40 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(983, 1836, Pz::new(2016, 3));
+        assert_solution!(Pz::new(2016, 3));
+    }
+
+    #[test]
+    fn ex1() {
+        assert_eq!(Ok(0), count_valid(triangles_by_row(vec!["5 10 25"])));
+    }
+
+    #[test]
+    fn ex2() {
+        let lines = [
+            "101 301 501",
+            "102 302 502",
+            "103 303 503",
+            "201 401 601",
+            "202 402 602",
+            "203 403 603",
+        ];
+        let triangles: Vec<(u32, u32, u32)> = triangles_by_col(lines.iter().cloned())
+            .map(|tri| tri.map(|Triangle(a, b, c)| (a, b, c)))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec![
+            (101, 102, 103), (301, 302, 303), (501, 502, 503),
+            (201, 202, 203), (401, 402, 403), (601, 602, 603),
+        ], triangles);
+    }
+
+    #[test]
+    fn reject_bad_rows() {
+        assert!(count_valid(triangles_by_row(vec!["1 2"])).is_err());
+        assert!(count_valid(triangles_by_row(vec!["1 2 3 4"])).is_err());
+        assert!(count_valid(triangles_by_row(vec!["1 2 x"])).is_err());
+        assert!(count_valid(triangles_by_col(vec!["1 2 3", "1 2 3"])).is_err());
+        assert!(count_valid(triangles_by_col(vec!["1 2 3", "1 2", "1 2 3"])).is_err());
     }
 }
@@ -10,7 +10,7 @@ const PART_TWO_NEEDLE: &str = "northpole object storage";
 struct RoomListing<'a> {
     name: &'a str,
     expected_checksum: &'a str,
-    sector: u16,
+    sector: u32,
     name_freq: BTreeMap<char, u8>,
 }
 
@@ -23,22 +23,37 @@ impl<'a> RoomListing<'a> {
         &check[..] == self.expected_checksum
     }
 
-    /// Builds a room from a string slice.
+    /// Builds a room from a string slice of the form
+    /// `<name>-<sector>[<checksum>]`.
     fn parse_str(line: &'a str) -> Result<Self, &'static str> {
-        let (name, sec_and_check) = line.split_at(line.len() - 11);
+        let line = line.trim();
+        let (rest, checksum) = line
+            .strip_suffix(']')
+            .and_then(|rest| rest.rsplit_once('['))
+            .ok_or("missing room checksum")?;
+        let (name, sector) = rest.rsplit_once('-').ok_or("missing room sector")?;
+
+        if sector.is_empty() || sector.len() > 6 || !sector.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("malformed sector");
+        }
+        if checksum.len() != 5 || !checksum.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err("malformed checksum");
+        }
+
         let mut name_freq = BTreeMap::new();
 
         for letter in name.chars() {
-            if letter == '-' {
-                continue;
+            match letter {
+                '-' => continue,
+                'a'..='z' => *name_freq.entry(letter).or_insert(0u8) += 1,
+                _ => return Err("room name contains non-lowercase letters"),
             }
-            *name_freq.entry(letter).or_insert(0u8) += 1
         }
 
         Ok(RoomListing {
             name,
-            expected_checksum: &sec_and_check[5..10],
-            sector: sec_and_check[1..4].parse().map_err(|_|"malformed sector")?,
+            expected_checksum: checksum,
+            sector: sector.parse().map_err(|_| "malformed sector")?,
             name_freq,
         })
     }
@@ -47,15 +62,15 @@ impl<'a> RoomListing<'a> {
     fn decrypted_name(&self) -> String {
         self.name.bytes().map(|l| {
             if l == b'-' { return ' '; }
-            (((u16::from(l - b'a') + self.sector) % 26) as u8 + b'a') as char
+            (((u32::from(l - b'a') + self.sector % 26) % 26) as u8 + b'a') as char
         }).collect()
     }
 }
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input = pio::fetch_lines(puzzle)?;
+    let input = pio::fetch_line_index(puzzle)?;
 
-    let rooms = parse_input(&input[..])?;
+    let rooms = parse_input(&input)?;
 
     solve_parts! {
         1 => part_one(&rooms),
@@ -64,12 +79,12 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
     }
 }
 
-fn parse_input<'a, S: AsRef<str>>(
-    lines: &'a [S]
-) -> Result<Vec<RoomListing<'a>>, &'static str> {
+fn parse_input<'a, I>(lines: I) -> Result<Vec<RoomListing<'a>>, &'static str>
+    where I: IntoIterator<Item = &'a str>
+{
     lines
-        .iter()
-        .map(|line| RoomListing::parse_str(line.as_ref()))
+        .into_iter()
+        .map(RoomListing::parse_str)
         .collect()
 }
 
@@ -79,14 +94,14 @@ fn part_one(rooms: &[RoomListing<'_>]) -> u32 {
     rooms
         .iter()
         .filter(|&r| r.is_real())
-        .map(|room| u32::from(room.sector))
+        .map(|room| room.sector)
         .sum()
 }
 
 /// Returns the sector id of the room whose decrypted name matches the
 /// specified needle or None if no such room is found.
-fn part_two(rooms: &[RoomListing<'_>], needle: &str) -> Option<u16> {
-    let decrypted: Vec<(String, u16)> = rooms.iter().map(|room| {
+fn part_two(rooms: &[RoomListing<'_>], needle: &str) -> Option<u32> {
+    let decrypted: Vec<(String, u32)> = rooms.iter().map(|room| {
         (room.decrypted_name(), room.sector)
     }).collect();
 
@@ -102,7 +117,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(409_147, 991, Pz::new(2016, 4))
+        assert_solution!(Pz::new(2016, 4))
     }
 
     #[test]
@@ -114,7 +129,7 @@ mod tests {
             "totally-real-room-200[decoy]",
         ];
 
-        let sol = part_one(&parse_input(&rooms[..]).unwrap());
+        let sol = part_one(&parse_input(rooms.iter().cloned()).unwrap());
 
         assert_eq!(1514, sol);
     }
@@ -140,6 +155,40 @@ mod tests {
         assert_eq!(1, *room.name_freq.get(&'x').unwrap());
     }
 
+    #[test]
+    fn parse_sector_lengths() {
+        for &(line, sector) in &[
+            ("a-b-c-7[abcde]", 7),
+            ("a-b-c-42[abcde]", 42),
+            ("a-b-c-123456[abcde]", 123_456),
+        ] {
+            let room = RoomListing::parse_str(line).unwrap();
+            assert_eq!(sector, room.sector);
+            assert_eq!("a-b-c", room.name);
+            assert_eq!("abcde", room.expected_checksum);
+        }
+
+        let room = RoomListing::parse_str("qzmt-zixmtkozy-ivhz-100053[abcde]").unwrap();
+        assert_eq!("very encrypted name", room.decrypted_name());
+    }
+
+    #[test]
+    fn reject_malformed_rooms() {
+        for line in &[
+            "",
+            "abc",
+            "a-b-c-[abcde]",
+            "a-b-c-1234567[abcde]",
+            "a-b-c-12x[abcde]",
+            "a-b-c-123[abcd]",
+            "a-b-c-123[abcde",
+            "a-b-c123[abcde]",
+            "a-B-c-123[abcde]",
+        ] {
+            assert!(RoomListing::parse_str(line).is_err(), "{}", line);
+        }
+    }
+
     #[test]
     fn room_is_real() {
         let real = RoomListing::parse_str("aaaaa-bbb-z-y-x-123[abxyz]")
@@ -1,27 +1,32 @@
 //! Solution for 2016 Day 05.
 
-use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
-
-use crypto::digest::Digest;
-use crypto::md5::Md5;
+use crate::common::puzzle::{
+    cancel, input as pio, Cancelled, Result as PuzzleResult, Selection as Pz,
+};
+use crate::common::util::hash;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
     let input = pio::fetch_string(puzzle)?;
 
     solve_parts! {
-        both => generate_passwords(input.trim_end().as_bytes())
+        both => generate_passwords(input.trim_end().as_bytes())?
     }
 }
 
+/// Bitmask in which all eight password positions are filled.
+const ALL_FILLED: u8 = 0xff;
+
 // Both passwords are generated simultaneously to avoid repeating the
 // hashing cycle.
-fn generate_passwords(door_id: &[u8]) -> (String, String) {
-    let mut hasher = Md5::new();
-
+//
+// The partially decrypted passwords are logged as each character is found,
+// which gives a suitably cinematic experience when run with `-v`. The
+// search gives up if the run is cancelled.
+fn generate_passwords(door_id: &[u8]) -> Result<(String, String), Cancelled> {
     // Part one's password
     let mut output_one = 0_u32;
-    // Whether part one's password has been fully generated
-    let mut one_finished = false;
+    // The number of characters generated for part one's password
+    let mut one_len = 0_u8;
 
     // Part two's password
     let mut output_two = 0_u32;
@@ -29,61 +34,107 @@ fn generate_passwords(door_id: &[u8]) -> (String, String) {
     // Each bit maps to one nibble (hex char) in the password.
     let mut two_filled = 0_u8;
 
-    for index in 0..u64::max_value() {
-        hasher.input(door_id);
-        hasher.input(index.to_string().as_bytes());
-
-        let mut result = [0_u8; 16];
-        hasher.result(&mut result);
-
+    for (_, result) in hash::counter_hashes(door_id) {
+        cancel::check()?;
         // Check if first 5 chars in hex representation would be 0
         if u16::from(result[0]) + u16::from(result[1]) + u16::from(result[2] >> 4) == 0 {
-            if !one_finished {
-                output_one = (output_one << 4) + u32::from(result[2]);
-                if output_one >> 28 != 0 {
-                    one_finished = true;
-                }
+            if one_len < 8 {
+                output_one |= u32::from(result[2]) << ((7 - one_len) * 4);
+                one_len += 1;
             }
 
             // Check if there are empty positions left and that the 6th hex char
             // point to a valid location
-            if two_filled != 0xff && result[2] < 8 {
+            if two_filled != ALL_FILLED && result[2] < 8 {
                 let pos_offset = (7 - result[2]) * 4;
                 // Check that the position hasn't occurred before
                 if two_filled & (1 << result[2]) == 0 {
                     // Insert 7th hex char at the designated position
                     output_two += u32::from(result[3] >> 4) << pos_offset;
                     two_filled |= 1 << result[2];
-                    // Drop a print statement here for the "cinematic experience"
                 }
             }
-        }
 
-        hasher.reset();
+            log_progress(output_one, one_len, output_two, two_filled);
+        }
 
-        if one_finished && two_filled == 0xff {
-            return (format!("{:08x}", output_one), format!("{:08x}", output_two));
+        if one_len == 8 && two_filled == ALL_FILLED {
+            return Ok((format!("{:08x}", output_one), format!("{:08x}", output_two)));
         }
     }
     unreachable!()
 }
 
+/// Logs both partially decrypted passwords, given how many characters of
+/// the first have been found and which positions of the second are filled.
+fn log_progress(output_one: u32, one_len: u8, output_two: u32, two_filled: u8) {
+    info!(
+        "decrypting {}  {}",
+        render_partial(output_one, filled_prefix(one_len)),
+        render_partial(output_two, two_filled.reverse_bits()),
+    );
+}
+
+/// Returns the mask of the first `len` password positions, as used by
+/// `render_partial`.
+fn filled_prefix(len: u8) -> u8 {
+    !ALL_FILLED.checked_shr(u32::from(len)).unwrap_or(0)
+}
+
+/// Renders a partially decrypted password, with placeholders at the
+/// positions that have not been filled.
+///
+/// Bit `7 - i` of `filled` indicates whether position `i` is filled.
+fn render_partial(password: u32, filled: u8) -> String {
+    (0..8)
+        .map(|pos| {
+            if filled & (0x80 >> pos) == 0 {
+                '_'
+            } else {
+                let nibble = (password >> ((7 - pos) * 4)) & 0xf;
+                std::char::from_digit(nibble, 16).unwrap()
+            }
+        })
+        .collect()
+}
+
 // These tests take several minutes to run so they are ignored by default
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::log::{self, Level};
+
+    #[test]
+    fn render_partial_passwords() {
+        assert_eq!("________", render_partial(0, 0));
+        assert_eq!("18f_____", render_partial(0x18f0_0000, 0b1110_0000));
+        assert_eq!("_5____e_", render_partial(0x0500_00e0, 0b0100_0010));
+        assert_eq!("05ace8e3", render_partial(0x05ac_e8e3, ALL_FILLED));
+        assert_eq!("________", render_partial(0x18f4_7a30, filled_prefix(0)));
+        assert_eq!("18f_____", render_partial(0x18f0_0000, filled_prefix(3)));
+        assert_eq!("18f47a30", render_partial(0x18f4_7a30, filled_prefix(8)));
+    }
+
+    #[test]
+    fn log_finished_passwords() {
+        let ((), messages) = log::capture(|| log_progress(0x18f4_7a30, 8, 0x05ac_e8e3, ALL_FILLED));
+        assert_eq!(
+            vec![(Level::Info, "decrypting 18f47a30  05ace8e3".to_owned())],
+            messages
+        );
+    }
 
     #[test]
     #[ignore]
     fn solution() {
-        assert_solution!("801b56a7", "424a0197", Pz::new(2016, 5))
+        assert_solution!(Pz::new(2016, 5))
     }
 
     #[test]
     #[ignore]
     fn ex_both() {
-        let (one, two) = generate_passwords(b"abc");
+        let (one, two) = generate_passwords(b"abc").unwrap();
 
         assert_eq!("18f47a30", &one[..]);
         assert_eq!("05ace8e3", &two[..]);
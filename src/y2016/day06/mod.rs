@@ -1,38 +1,50 @@
 //! Solution for 2016 Day 06.
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input: Vec<_> = pio::fetch_lines(puzzle)?;
+    let input = pio::fetch_line_index(puzzle)?;
 
     solve_parts! {
-        both => repetition_correct(&input[..])
+        both => repetition_correct(&input.to_vec())?
     }
 }
 
 /// "Error corrects" the specified transmission by locating the most frequent
 /// (part one) and least frequent (part two) character at each message position.
-fn repetition_correct(transmissions: &[String]) -> (String, String) {
-    let msg_width = transmissions.first().unwrap().len();
-    let mut msg_one: Vec<u8> = Vec::with_capacity(msg_width);
-    let mut msg_two: Vec<u8> = Vec::with_capacity(msg_width);
-    let mut freq_buf = HashMap::new();
+///
+/// The message is as wide as the longest transmission. Transmissions that are
+/// too short to reach a position are ignored for that position. Ties are
+/// broken in favor of the character that sorts first.
+///
+/// An error is returned if there are no non-empty transmissions.
+fn repetition_correct<S: AsRef<str>>(transmissions: &[S]) -> Result<(String, String), &'static str> {
+    let transmissions: Vec<Vec<char>> = transmissions
+        .iter()
+        .map(|line| line.as_ref().chars().collect())
+        .collect();
+    let msg_width = transmissions.iter().map(Vec::len).max().unwrap_or(0);
+    if msg_width == 0 {
+        return Err("no transmissions to correct");
+    }
+
+    let mut msg_one = String::with_capacity(msg_width);
+    let mut msg_two = String::with_capacity(msg_width);
 
     for col in 0..msg_width {
-        for letter in transmissions.iter().map(|line| line.as_bytes()[col]) {
-            *freq_buf.entry(letter).or_insert(0u16) += 1
+        let mut freq: BTreeMap<char, u32> = BTreeMap::new();
+        for &letter in transmissions.iter().filter_map(|line| line.get(col)) {
+            *freq.entry(letter).or_insert(0) += 1
         }
-        let mut elems: Vec<(u8, u16)> = freq_buf.drain().collect();
-        elems.sort_by_key(|e| e.1);
 
-        msg_one.push(elems.last().unwrap().0);
-        msg_two.push(elems.first().unwrap().0);
+        // Every column contains at least one letter from the longest line
+        let most = freq.iter().rev().max_by_key(|e| e.1).unwrap();
+        let least = freq.iter().min_by_key(|e| e.1).unwrap();
+        msg_one.push(*most.0);
+        msg_two.push(*least.0);
     }
-    (
-        String::from_utf8(msg_one).expect("message contains invalid uft8"),
-        String::from_utf8(msg_two).expect("message contains invalid uft8"),
-    )
+    Ok((msg_one, msg_two))
 }
 
 #[cfg(test)]
@@ -41,7 +53,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!("afwlyyyq", "bhkzekao", Pz::new(2016, 6))
+        assert_solution!(Pz::new(2016, 6))
     }
 
     #[test]
@@ -65,8 +77,20 @@ mod tests {
             "enarar".to_owned(),
         ];
 
-        let (one, two) = repetition_correct(&transmission);
+        let (one, two) = repetition_correct(&transmission).unwrap();
         assert_eq!("easter", one);
         assert_eq!("advent", two);
     }
+
+    #[test]
+    fn ragged_transmissions() {
+        let transmission = ["ab", "abcd", "", "xbc", "ayé", "é"];
+
+        let (one, two) = repetition_correct(&transmission).unwrap();
+        assert_eq!("abcd", one);
+        assert_eq!("xyéd", two);
+
+        assert!(repetition_correct::<&str>(&[]).is_err());
+        assert!(repetition_correct(&["", ""]).is_err());
+    }
 }
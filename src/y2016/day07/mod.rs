@@ -2,83 +2,92 @@
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
-pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input = pio::fetch_lines(puzzle)?;
-
-    solve_parts! {
-         1 => input.iter().filter(supports_snooping).count(),
-         2 => input.iter().filter(supports_listening).count()
-    }
+/// An IPv7 address, made up of supernet sequences separated by hypernet
+/// sequences enclosed in square brackets.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Ipv7Addr<'a> {
+    addr: &'a str,
 }
 
-/// Checks if an IPv7 string supports "transport-layer snooping"
-fn supports_snooping<S: AsRef<str>>(ipv7: &S) -> bool {
-    let byte_wins = ipv7.as_ref().as_bytes().windows(4);
-
-    let mut in_brackets = false;
-    let mut found_abba = false;
-
-    for sec in byte_wins {
-        if sec[3] == b'[' || sec[3] == b']' {
-            in_brackets = !in_brackets;
-            continue;
-        }
-
-        // Check for "abba" pattern
-        // Middle checked first so that the check will end if a bracket is found
-        if sec[2] == sec[1] && sec[0] == sec[3] && sec[0] != sec[1] {
-            if in_brackets {
-                return false;
+impl<'a> Ipv7Addr<'a> {
+    /// Parses an IPv7 address.
+    ///
+    /// Fails if the address has unbalanced or nested brackets.
+    pub fn parse(addr: &'a str) -> Result<Self, &'static str> {
+        let mut in_brackets = false;
+        for b in addr.bytes() {
+            match (b, in_brackets) {
+                (b'[', false) | (b']', true) => in_brackets = !in_brackets,
+                (b'[', true) => return Err("nested hypernet sequence"),
+                (b']', false) => return Err("unopened hypernet sequence"),
+                _ => (),
             }
-            found_abba = true;
         }
+        if in_brackets {
+            return Err("unclosed hypernet sequence");
+        }
+        Ok(Ipv7Addr { addr })
     }
 
-    found_abba
-}
-
-/// Checks if an IPv7 string supports "super-secret listening"
-fn supports_listening<S: AsRef<str>>(ipv7: &S) -> bool {
-    let byte_wins = ipv7.as_ref().as_bytes().windows(3);
-
-    let mut in_brackets = false;
+    /// Returns an iterator over this address's sequences, each paired
+    /// with `true` if it is a hypernet sequence.
+    fn segments(&self) -> impl Iterator<Item = (bool, &'a str)> {
+        // Brackets are balanced and unnested, so segments alternate between
+        // supernet and hypernet sequences.
+        self.addr.split(['[', ']'])
+            .enumerate()
+            .map(|(i, seg)| (i % 2 == 1, seg))
+    }
 
-    // ABA sequences discovered outside brackets, inserted as (A, B)
-    let mut critical_pairs_out: Vec<(u8, u8)> = Vec::new();
-    // BAB sequences discovered outside brackets, inserted as (A, B)
-    let mut critical_pairs_in: Vec<(u8, u8)> = Vec::new();
+    /// Returns an iterator over the sequences outside of square brackets.
+    pub fn supernets(&self) -> impl Iterator<Item = &'a str> {
+        self.segments().filter(|&(hyper, _)| !hyper).map(|(_, seg)| seg)
+    }
 
-    for sec in byte_wins {
-        if sec[2] == b'[' || sec[2] == b']' {
-            in_brackets = !in_brackets;
-            continue;
-        }
+    /// Returns an iterator over the sequences inside of square brackets.
+    pub fn hypernets(&self) -> impl Iterator<Item = &'a str> {
+        self.segments().filter(|&(hyper, _)| hyper).map(|(_, seg)| seg)
+    }
 
-        // Check for "aba" pattern
-        if sec[0] == sec[2] && sec[0] != sec[1] {
-            let pair: (u8, u8);
+    /// Returns true if this address supports "transport-layer snooping".
+    pub fn supports_tls(&self) -> bool {
+        self.supernets().any(has_abba) && !self.hypernets().any(has_abba)
+    }
 
-            if in_brackets {
-                pair = (sec[1], sec[0]);
+    /// Returns true if this address supports "super-secret listening".
+    pub fn supports_ssl(&self) -> bool {
+        self.supernets().flat_map(abas).any(|(a, b)| {
+            self.hypernets().any(|seg| abas(seg).any(|bab| bab == (b, a)))
+        })
+    }
+}
 
-                if critical_pairs_out.contains(&pair) {
-                    return true;
-                }
+/// Returns true if the sequence contains an "abba" pattern.
+fn has_abba(seq: &str) -> bool {
+    seq.as_bytes()
+        .windows(4)
+        .any(|w| w[0] == w[3] && w[1] == w[2] && w[0] != w[1])
+}
 
-                critical_pairs_in.push(pair)
-            } else {
-                pair = (sec[0], sec[1]);
+/// Returns an iterator over the "aba" patterns in the sequence, as (A, B)
+/// pairs.
+fn abas(seq: &str) -> impl Iterator<Item = (u8, u8)> + '_ {
+    seq.as_bytes()
+        .windows(3)
+        .filter(|w| w[0] == w[2] && w[0] != w[1])
+        .map(|w| (w[0], w[1]))
+}
 
-                if critical_pairs_in.contains(&pair) {
-                    return true;
-                }
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input = pio::fetch_line_index(puzzle)?;
+    let addrs: Vec<Ipv7Addr<'_>> = input.iter()
+        .map(Ipv7Addr::parse)
+        .collect::<Result<_, _>>()?;
 
-                critical_pairs_out.push(pair)
-            }
-        }
+    solve_parts! {
+         1 => addrs.iter().filter(|addr| addr.supports_tls()).count(),
+         2 => addrs.iter().filter(|addr| addr.supports_ssl()).count()
     }
-
-    false
 }
 
 #[cfg(test)]
@@ -87,32 +96,56 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(110, 242, Pz::new(2016, 7));
+        assert_solution!(Pz::new(2016, 7));
+    }
+
+    fn tls(addr: &str) -> bool {
+        Ipv7Addr::parse(addr).unwrap().supports_tls()
+    }
+
+    fn ssl(addr: &str) -> bool {
+        Ipv7Addr::parse(addr).unwrap().supports_ssl()
     }
 
     #[test]
     fn ex1() {
-        assert!(supports_snooping(&"abba[mnop]qrst"));
-        assert!(supports_snooping(&"ioxxoj[asdfgh]zxcvbn"));
-
-        assert!(!supports_snooping(&"abcd[bddb]xyyx"));
-        assert!(!supports_snooping(&"aaaa[qwer]tyui"));
-
-        assert!(supports_snooping(
-            &"aaaa[qwegrnerngoer]tuiaaaa[qwer]gnyuiaaaa[qwer]tyyt"
-        ));
-        assert!(supports_snooping(
-            &"aaaa[qwegrnerngoer]uiiuaaa[qwer]ugnyuiaaaa[qwer]tyui"
-        ));
-        assert!(!supports_snooping(&"abbangggg[abba]abbageghiehgei"));
+        assert!(tls("abba[mnop]qrst"));
+        assert!(tls("ioxxoj[asdfgh]zxcvbn"));
+
+        assert!(!tls("abcd[bddb]xyyx"));
+        assert!(!tls("aaaa[qwer]tyui"));
+
+        assert!(tls("aaaa[qwegrnerngoer]tuiaaaa[qwer]gnyuiaaaa[qwer]tyyt"));
+        assert!(tls("aaaa[qwegrnerngoer]uiiuaaa[qwer]ugnyuiaaaa[qwer]tyui"));
+        assert!(!tls("abbangggg[abba]abbageghiehgei"));
     }
 
     #[test]
     fn ex2() {
-        assert!(supports_listening(&"aba[bab]xyz"));
-        assert!(supports_listening(&"aaa[kek]eke"));
-        assert!(supports_listening(&"zazbz[bzb]cdb"));
+        assert!(ssl("aba[bab]xyz"));
+        assert!(ssl("aaa[kek]eke"));
+        assert!(ssl("zazbz[bzb]cdb"));
 
-        assert!(!supports_listening(&"xyx[xyx]xyx"));
+        assert!(!ssl("xyx[xyx]xyx"));
+        // Patterns must not span sequences
+        assert!(!ssl("ab[a]bab"));
+    }
+
+    #[test]
+    fn segments() {
+        let addr = Ipv7Addr::parse("ab[cd]ef[]gh").unwrap();
+        assert_eq!(vec!["ab", "ef", "gh"], addr.supernets().collect::<Vec<_>>());
+        assert_eq!(vec!["cd", ""], addr.hypernets().collect::<Vec<_>>());
+
+        assert!(has_abba("xabba"));
+        assert!(!has_abba("aaaa"));
+        assert_eq!(vec![(b'a', b'b'), (b'b', b'a')], abas("abab").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reject_bad_brackets() {
+        for addr in &["ab[cd", "ab]cd", "ab[c[d]]e", "ab[cd]]"] {
+            assert!(Ipv7Addr::parse(addr).is_err(), "{}", addr);
+        }
     }
 }
@@ -2,7 +2,7 @@
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
-mod screen {
+pub mod screen {
     use std::str::FromStr;
 
     use std::{fmt, str};
@@ -29,48 +29,28 @@ mod screen {
         type Err = &'static str;
 
         fn from_str(line: &str) -> Result<Self, Self::Err> {
-            let bytes = line.as_bytes();
-            match bytes.len() {
-                len @ 8 ..= 10 => { // We caught a rectangle instruction!
-                    let div = bytes.iter().enumerate()
-                        .find(|&(_, &b)| b == b'x')
-                        .map(|pair| pair.0)
-                        .ok_or("malformed rect instr")?;
-                    let row: usize = str::from_utf8(&bytes[div - 2..div])
-                        .unwrap()
-                        .trim_start()
-                        .parse()
-                        .map_err(|_| "invalid rect row")?;
-                    let col: usize = str::from_utf8(&bytes[div + 1..len])
-                        .unwrap()
-                        .trim_start()
-                        .parse()
-                        .map_err(|_| "invalid rect col")?;
-                    Ok(ScreenInstruction::Rect { x: row, y: col })
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens[..] {
+                ["rect", dims] => { // We caught a rectangle instruction!
+                    let (x, y) = dims.split_once('x').ok_or("malformed rect instr")?;
+                    Ok(ScreenInstruction::Rect {
+                        x: x.parse().map_err(|_| "invalid rect row")?,
+                        y: y.parse().map_err(|_| "invalid rect col")?,
+                    })
                 }
-                len if bytes[7] == b'c' => { // Smells like a column rotation
-                    let col: usize = str::from_utf8(&bytes[16..18])
-                        .unwrap()
-                        .trim_end()
-                        .parse()
-                        .map_err(|_| "invalid col")?;
-                    let offset: RotOffset = str::from_utf8(&bytes[21..len])
-                        .unwrap()
-                        .trim_start()
-                        .parse()
+                ["rotate", "column", col, "by", offset] => { // Smells like a column rotation
+                    let col: usize = col.strip_prefix("x=")
+                        .and_then(|col| col.parse().ok())
+                        .ok_or("invalid col")?;
+                    let offset: RotOffset = offset.parse()
                         .map_err(|_| "invalid col rot offset")?;
                     Ok(ScreenInstruction::RotCol { col, offset })
                 }
-                len if bytes[7] == b'r' => { // Row rot? Row rot.
-                    let row: usize = str::from_utf8(&bytes[13..15])
-                        .unwrap()
-                        .trim_end()
-                        .parse()
-                        .map_err(|_| "invalid shift row")?;
-                    let offset: RotOffset = str::from_utf8(&bytes[18..len])
-                        .unwrap()
-                        .trim_start()
-                        .parse()
+                ["rotate", "row", row, "by", offset] => { // Row rot? Row rot.
+                    let row: usize = row.strip_prefix("y=")
+                        .and_then(|row| row.parse().ok())
+                        .ok_or("invalid shift row")?;
+                    let offset: RotOffset = offset.parse()
                         .map_err(|_| "invalid row rot offset")?;
                     Ok(ScreenInstruction::RotRow { row, offset })
                 }
@@ -96,13 +76,71 @@ mod screen {
         }
 
         /// Updates this screen's pixels according to the specified instruction.
-        pub fn process_instr(&mut self, instr: &ScreenInstruction) {
+        ///
+        /// Returns an error without changing any pixels if the instruction
+        /// reaches outside of this screen.
+        pub fn process_instr(&mut self, instr: &ScreenInstruction) -> Result<(), &'static str> {
             use self::ScreenInstruction as Instr;
+            self.check_bounds(instr)?;
             match *instr {
                 Instr::Rect { x, y } => self.run_rect(x, y),
-                Instr::RotRow { row, offset } => self.run_rot_row(row, offset),
-                Instr::RotCol { col, offset } => self.run_rot_col(col, offset),
+                Instr::RotRow { row, offset } => self.run_rot_row(row, offset.into()),
+                Instr::RotCol { col, offset } => self.run_rot_col(col, offset.into()),
             }
+            Ok(())
+        }
+
+        /// Reverts the changes made to this screen's pixels by the specified
+        /// instruction.
+        ///
+        /// Rotations are fully reversible. A rect instruction lights every
+        /// pixel in its area regardless of their prior states, so those
+        /// pixels are left lit and their `(x, y)` positions are returned as
+        /// ambiguous. The returned collection is empty for rotations.
+        ///
+        /// Returns an error without changing any pixels if the instruction
+        /// reaches outside of this screen.
+        pub fn unprocess_instr(
+            &mut self,
+            instr: &ScreenInstruction,
+        ) -> Result<Vec<(usize, usize)>, &'static str> {
+            use self::ScreenInstruction as Instr;
+            self.check_bounds(instr)?;
+            match *instr {
+                Instr::Rect { x, y } => {
+                    return Ok((0..y)
+                        .flat_map(|row| (0..x).map(move |col| (col, row)))
+                        .collect());
+                }
+                Instr::RotRow { row, offset } => {
+                    let offset = usize::from(offset) % self.dim_x;
+                    self.run_rot_row(row, self.dim_x - offset)
+                }
+                Instr::RotCol { col, offset } => {
+                    let offset = usize::from(offset) % self.dim_y;
+                    self.run_rot_col(col, self.dim_y - offset)
+                }
+            }
+            Ok(Vec::new())
+        }
+
+        /// Returns an error if the specified instruction reaches outside of
+        /// this screen.
+        fn check_bounds(&self, instr: &ScreenInstruction) -> Result<(), &'static str> {
+            use self::ScreenInstruction as Instr;
+            match *instr {
+                Instr::Rect { x, y } if x > self.dim_x || y > self.dim_y => {
+                    Err("rect is larger than the screen")
+                }
+                Instr::RotRow { row, .. } if row >= self.dim_y => Err("rotated row is off the screen"),
+                Instr::RotCol { col, .. } if col >= self.dim_x => Err("rotated col is off the screen"),
+                _ => Ok(()),
+            }
+        }
+
+        /// Returns true if the pixel at the specified position is lit.
+        pub fn is_lit(&self, x: usize, y: usize) -> bool {
+            self.pixels[y] & (1 << (63 - x)) != 0
         }
 
         fn run_rect(&mut self, x: usize, y: usize) {
@@ -114,8 +152,9 @@ mod screen {
             }
         }
 
-        fn run_rot_row(&mut self, row: usize, offset: RotOffset) {
-            let offset = offset % 64;
+        fn run_rot_row(&mut self, row: usize, offset: usize) {
+            // Rotating by the screen's width has no effect
+            let offset = (offset % self.dim_x) as u8;
             let dim_offset = 64 - self.dim_x as u8;
             let mut bits = self.pixels[row];
 
@@ -129,9 +168,9 @@ mod screen {
             self.pixels[row] = bits;
         }
 
-        fn run_rot_col(&mut self, col: usize, offset: RotOffset) {
+        fn run_rot_col(&mut self, col: usize, offset: usize) {
             debug_assert!(col <= 64);
-            let split = self.dim_y - (offset as usize % self.dim_y);
+            let split = self.dim_y - (offset % self.dim_y);
             let col_offset = 63 - col;
             let selector = 1_u64 << col_offset;
 
@@ -188,17 +227,14 @@ mod screen {
 use self::screen::*;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input: Vec<_> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+    let input: Vec<_> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     let mut screen = MiniScreen::new(50, 6);
 
     solve_parts! {
         1 => {
             for instr in &input {
-                screen.process_instr(instr);
+                screen.process_instr(instr)?;
             }
             screen.pixel_count()
         },
@@ -209,23 +245,11 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const PART_TWO_SOLUTION: &str = r######"
-" ##  #### ###  #  # ###  #### ###    ## ###   ### "
-"#  # #    #  # #  # #  #    # #  #    # #  # #    "
-"#  # ###  ###  #  # #  #   #  ###     # #  # #    "
-"#### #    #  # #  # ###   #   #  #    # ###   ##  "
-"#  # #    #  # #  # #    #    #  # #  # #       # "
-"#  # #    ###   ##  #    #### ###   ##  #    ###  "
-"######;
+    use crate::common::testing::parse_lines;
 
     #[test]
     fn solution() {
-        assert_solution! {
-            123,
-            PART_TWO_SOLUTION,
-            Pz::new(2016, 8)
-        };
+        assert_solution!(Pz::new(2016, 8));
     }
 
     #[test]
@@ -239,8 +263,8 @@ mod tests {
 
         let mut screen = MiniScreen::new(7, 3);
 
-        for instr in input.iter().map(|l| l.parse().unwrap()) {
-            screen.process_instr(&instr);
+        for instr in parse_lines::<ScreenInstruction, _>(&input) {
+            screen.process_instr(&instr).unwrap();
         }
 
         assert_eq!(6, screen.pixel_count());
@@ -250,6 +274,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snapshot_render() {
+        let mut screen = MiniScreen::new(50, 6);
+        let input = pio::fetch_lines(&Pz::new(2016, 8)).unwrap();
+        for instr in parse_lines::<ScreenInstruction, _>(input) {
+            screen.process_instr(&instr).unwrap();
+        }
+        insta::assert_snapshot!(screen);
+    }
+
+    #[test]
+    fn unprocess_rotations() {
+        const DIM_X: usize = 7;
+        const DIM_Y: usize = 3;
+
+        let mut screen = MiniScreen::new(DIM_X, DIM_Y);
+        let input = ["rect 3x2", "rotate column x=1 by 1", "rotate row y=0 by 4"];
+        for instr in parse_lines::<ScreenInstruction, _>(&input) {
+            screen.process_instr(&instr).unwrap();
+        }
+        let original = format!("{:?}", screen);
+
+        for offset in 0..=2 * DIM_X as u8 + 1 {
+            for row in 0..DIM_Y {
+                let instr = ScreenInstruction::RotRow { row, offset };
+                screen.process_instr(&instr).unwrap();
+                assert_eq!(
+                    usize::from(offset) % DIM_X == 0,
+                    format!("{:?}", screen) == original,
+                    "{:?}", instr
+                );
+                assert!(screen.unprocess_instr(&instr).unwrap().is_empty());
+                assert_eq!(original, format!("{:?}", screen), "{:?}", instr);
+            }
+            for col in 0..DIM_X {
+                let instr = ScreenInstruction::RotCol { col, offset };
+                screen.process_instr(&instr).unwrap();
+                assert!(screen.unprocess_instr(&instr).unwrap().is_empty());
+                assert_eq!(original, format!("{:?}", screen), "{:?}", instr);
+            }
+        }
+    }
+
+    #[test]
+    fn unprocess_rect() {
+        let mut screen = MiniScreen::new(4, 2);
+        let instr = ScreenInstruction::Rect { x: 2, y: 2 };
+        screen.process_instr(&instr).unwrap();
+
+        assert_eq!(
+            Ok(vec![(0, 0), (1, 0), (0, 1), (1, 1)]),
+            screen.unprocess_instr(&instr)
+        );
+        assert!(screen.is_lit(1, 1));
+        assert!(!screen.is_lit(2, 1));
+    }
+
+    #[test]
+    fn reject_off_screen_instr() {
+        let lines = [
+            "rect 100x2", "rect 3x10", "rect 8x1", "rotate row y=99 by 1", "rotate row y=3 by 1",
+            "rotate column x=7 by 1",
+        ];
+        for instr in parse_lines::<ScreenInstruction, _>(&lines) {
+            let mut screen = MiniScreen::new(7, 3);
+            assert!(screen.process_instr(&instr).is_err(), "{:?}", instr);
+            assert!(screen.unprocess_instr(&instr).is_err(), "{:?}", instr);
+            assert_eq!(0, screen.pixel_count(), "{:?}", instr);
+        }
+    }
+
     #[test]
     fn parse_screen_instr() {
         use self::ScreenInstruction as SI;
@@ -273,4 +368,16 @@ mod tests {
             panic!("Wrong instruction: expected RotRow")
         }
     }
+
+    #[test]
+    fn reject_malformed_screen_instr() {
+        let lines = [
+            "", "rect", "rect x", "rect 3x", "rotate row y by 1", "rotate row y=0 by",
+            "rotate column x=é by 1", "rotate column\u{80}\u{80}\u{80}by 1",
+            "rotate row y=1 by 256",
+        ];
+        for line in lines.iter() {
+            assert!(line.parse::<ScreenInstruction>().is_err(), "{:?}", line);
+        }
+    }
 }
@@ -1,29 +1,41 @@
 //! Solution for 2016 Day 10
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::extract_ints;
 
 use regex::Regex;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
 /// Regex pattern for a bot's pass instruction
 const PATTERN_PASS: &str =
-    "^bot (\\d{1,3}) gives low to (bot|output) (\\d{1,3}) and high to (bot|output) (\\d{1,3})$";
+    "^bot (\\d+) gives low to (bot|output) (\\d+) and high to (bot|output) (\\d+)$";
 
 /// Regex pattern for a bot initializer instruction
-const PATTERN_INIT: &str = "^value (\\d{1,3}) goes to bot (\\d{1,3})$";
+const PATTERN_INIT: &str = "^value (\\d+) goes to bot (\\d+)$";
+
+/// Output bins whose first chips are multiplied in part two.
+const PRODUCT_OUTPUTS: [BotId; 3] = [0, 1, 2];
 
 /// Chip combination to watch for in part one.
-const WATCHED_CHIPS: (u8, u8) = (17, 61);
+///
+/// May be overridden with the `watch` parameter, e.g. `--param watch=2,5`.
+const WATCHED_CHIPS: (ChipValue, ChipValue) = (17, 61);
 
 mod bot {
     /// A microchip value
-    pub type ChipValue = u8;
+    pub type ChipValue = u32;
+
+    /// Identifier of a bot or output bin
+    pub type BotId = u32;
+
+    /// The chips placed in each output bin, in the order they arrived.
+    pub type OutputBins = std::collections::BTreeMap<BotId, Vec<ChipValue>>;
 
     /// Destination for a bot to pass its chips to.
     pub enum ChipDest {
-        Bot(u8),
-        Output(u8),
+        Bot(BotId),
+        Output(BotId),
     }
 
     /// A bot carrying at most two ordered chips.
@@ -40,11 +52,11 @@ mod bot {
 
         /// Passes a chip to a bot.
         ///
-        /// Panics if the bots "hands" are full i.e. if it already has
-        /// two chips.
-        pub fn give_chip(&mut self, chip: ChipValue) {
+        /// An error is returned if the bots "hands" are full i.e. if it
+        /// already has two chips.
+        pub fn give_chip(&mut self, chip: ChipValue) -> Result<(), &'static str> {
             if self.high.is_some() {
-                panic!("bot already has its hands full!");
+                return Err("bot already has its hands full!");
             }
 
             match self.low {
@@ -56,6 +68,7 @@ mod bot {
                 }
                 None => self.low = Some(chip)
             }
+            Ok(())
         }
 
         /// Returns true is the bots is carrying two chips.
@@ -80,20 +93,54 @@ use self::bot::*;
 /// Description of where a bot should send its chips once its "hands"
 /// are full.
 pub struct PassDirective {
-    bot: u8,
+    bot: BotId,
     low: ChipDest,
     high: ChipDest,
 }
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let (pass_instr, mut bots) = parse_input(&pio::fetch_lines(puzzle)?);
+    let (pass_instr, mut bots) = parse_input(&pio::fetch_line_index(puzzle)?.to_vec())?;
+    let watch_for = match puzzle.param("watch") {
+        Some(pair) => parse_chip_pair(pair)?,
+        None => WATCHED_CHIPS,
+    };
 
     solve_parts! {
-        both => pass_chips(&pass_instr, &mut bots, WATCHED_CHIPS)
+        both => {
+            let (watched_bot, outputs) = pass_chips(&pass_instr, &mut bots, watch_for)?;
+            (
+                watched_bot
+                    .ok_or_else(|| format!("No bot found comparing chips {:?}", watch_for))?,
+                output_product(&outputs, &PRODUCT_OUTPUTS)?,
+            )
+        }
     }
 }
 
-fn parse_input<T>(lines: &[T]) -> (Vec<PassDirective>, HashMap<u8, Bot>)
+/// Returns the product of the first chip in each of the specified output
+/// bins.
+///
+/// An error is returned if any of the bins are empty.
+fn output_product(outputs: &OutputBins, ids: &[BotId]) -> Result<u64, String> {
+    ids.iter()
+        .map(|id| {
+            outputs.get(id)
+                .and_then(|out| out.first())
+                .map(|&chip| u64::from(chip))
+                .ok_or_else(|| format!("No chip found in output {}", id))
+        })
+        .product()
+}
+
+/// Parses a pair of chip values of the form `<low>,<high>`.
+fn parse_chip_pair(pair: &str) -> Result<(ChipValue, ChipValue), String> {
+    match extract_ints::<ChipValue>(pair).collect::<Vec<_>>()[..] {
+        [one, two] => Ok((one.min(two), one.max(two))),
+        _ => Err(format!("Malformed chip pair: {}", pair)),
+    }
+}
+
+fn parse_input<T>(lines: &[T]) -> Result<(Vec<PassDirective>, HashMap<BotId, Bot>), String>
     where T: AsRef<str>
 {
     let re_pass = Regex::new(PATTERN_PASS).unwrap();
@@ -102,56 +149,53 @@ fn parse_input<T>(lines: &[T]) -> (Vec<PassDirective>, HashMap<u8, Bot>)
     let mut pass_instr = Vec::new();
     let mut bots = HashMap::new();
 
+    // Matched digit groups may still overflow
+    let parse_num = |num: &str, line: &str| {
+        num.parse().map_err(|_| format!("Number out of range: {}", line))
+    };
+
     for line in lines {
         let line = line.as_ref();
 
         if let Some(pass) = re_pass.captures(line) {
-            let bot = pass.get(1).unwrap().as_str().parse().unwrap();
-            let low = {
-                let id = pass.get(3).unwrap().as_str().parse().unwrap();
-                match pass.get(2).unwrap().as_str() {
-                    "output" => ChipDest::Output(id),
-                    "bot" => ChipDest::Bot(id),
-                    _ => panic!("unknown low chip destination"),
-                }
-            };
-            let high = {
-                let id = pass.get(5).unwrap().as_str().parse().unwrap();
-                match pass.get(4).unwrap().as_str() {
-                    "output" => ChipDest::Output(id),
-                    "bot" => ChipDest::Bot(id),
-                    _ => panic!("unknown high chip destination"),
-                }
+            let bot = parse_num(&pass[1], line)?;
+            let dest = |kind: &str, id: &str| -> Result<ChipDest, String> {
+                let id = parse_num(id, line)?;
+                Ok(if kind == "output" { ChipDest::Output(id) } else { ChipDest::Bot(id) })
             };
+            let low = dest(&pass[2], &pass[3])?;
+            let high = dest(&pass[4], &pass[5])?;
             pass_instr.push(PassDirective { bot, low, high });
         } else if let Some(init) = re_init.captures(line) {
-            bots.entry(init.get(2).unwrap().as_str().parse().unwrap())
+            bots.entry(parse_num(&init[2], line)?)
                 .or_insert_with(Bot::new)
-                .give_chip(init.get(1).unwrap().as_str().parse().unwrap());
+                .give_chip(parse_num(&init[1], line)?)?;
         } else {
-            panic!(format!("Malformed line: {}", line))
+            return Err(format!("Malformed line: {}", line));
         }
     }
 
-    (pass_instr, bots)
+    Ok((pass_instr, bots))
 }
 
 /// Transfers chips between bots (and outputs) according to the specified
 /// pass directives.
 ///
 /// Returns a tuple containing (1) the bot responsible for comparing the watched
-/// chips and (2) the product of the first chip in outputs `0`, `1`, and `2`.
+/// chips, if any, and (2) the chips placed in each output bin.
+///
+/// An error is returned if the chips cannot be passed as directed.
 fn pass_chips(
     pass_instr: &[PassDirective],
-    bots: &mut HashMap<u8, Bot>,
+    bots: &mut HashMap<BotId, Bot>,
     watch_for: (ChipValue, ChipValue),
-) -> (u8, u32) {
-    let mut watched_bot: Option<u8> = None;
-    let mut outputs = BTreeMap::new();
+) -> Result<(Option<BotId>, OutputBins), String> {
+    let mut watched_bot: Option<BotId> = None;
+    let mut outputs = OutputBins::new();
 
     loop {
         // Copy the ids of the bots with two chips
-        let ready_bots: Vec<u8> = bots
+        let ready_bots: Vec<BotId> = bots
             .iter()
             .filter(|e| e.1.can_pass())
             .map(|e| *e.0)
@@ -170,37 +214,22 @@ fn pass_chips(
 
             let directive = pass_instr.iter()
                 .find(|p| p.bot == bot_id)
-                .expect("no pass instruction found");
-
-            match directive.low {
-                ChipDest::Bot(id) => bots.entry(id)
-                    .or_insert_with(Bot::new)
-                    .give_chip(chips.0),
-                ChipDest::Output(id) => outputs.entry(id)
-                    .or_insert_with(Vec::new)
-                    .push(chips.0)
-            }
-
-            match directive.high {
-                ChipDest::Bot(id) => bots.entry(id)
-                    .or_insert_with(Bot::new)
-                    .give_chip(chips.1),
-                ChipDest::Output(id) => outputs.entry(id)
-                    .or_insert_with(Vec::new)
-                    .push(chips.1)
+                .ok_or_else(|| format!("No pass instruction found for bot {}", bot_id))?;
+
+            for &(dest, chip) in &[(&directive.low, chips.0), (&directive.high, chips.1)] {
+                match *dest {
+                    ChipDest::Bot(id) => bots.entry(id)
+                        .or_insert_with(Bot::new)
+                        .give_chip(chip)?,
+                    ChipDest::Output(id) => outputs.entry(id)
+                        .or_default()
+                        .push(chip)
+                }
             }
         }
     }
 
-    (
-        watched_bot.expect(
-            &format!("No bot found comparing chips {:?}", &watch_for)[..]
-        ),
-        outputs
-            .range(0..3)
-            .map(|(_, out)| u32::from(*out.first().unwrap()))
-            .product(),
-    )
+    Ok((watched_bot, outputs))
 }
 
 #[cfg(test)]
@@ -209,11 +238,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution! {
-            141,
-            1209,
-            Pz::new(2016, 10)
-        }
+        assert_solution!(Pz::new(2016, 10))
     }
 
     #[test]
@@ -227,12 +252,61 @@ mod tests {
             "value 2 goes to bot 2",
         ];
 
-        let (watched_bot, output_product) = {
-            let (instr, mut bots) = parse_input(&instr);
-            pass_chips(&instr, &mut bots, (2, 5))
+        let (watched_bot, outputs) = {
+            let (instr, mut bots) = parse_input(&instr).unwrap();
+            pass_chips(&instr, &mut bots, (2, 5)).unwrap()
         };
 
-        assert_eq!(2, watched_bot);
-        assert_eq!(30, output_product);
+        assert_eq!(Some(2), watched_bot);
+        assert_eq!(
+            vec![(0, vec![5]), (1, vec![2]), (2, vec![3])],
+            outputs.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn large_values() {
+        let instr = [
+            "value 5000 goes to bot 1000",
+            "value 70000 goes to bot 1000",
+            "value 9 goes to bot 2",
+            "bot 1000 gives low to output 0 and high to bot 2",
+            "bot 2 gives low to output 1 and high to output 2",
+        ];
+
+        let (instr, mut bots) = parse_input(&instr).unwrap();
+        let (watched_bot, outputs) = pass_chips(&instr, &mut bots, (5000, 70000)).unwrap();
+        assert_eq!(Some(1000), watched_bot);
+        assert_eq!(Ok(5000 * 9 * 70000), output_product(&outputs, &PRODUCT_OUTPUTS));
+    }
+
+    #[test]
+    fn unwatched_chips() {
+        let instr = [
+            "value 5 goes to bot 2",
+            "value 2 goes to bot 2",
+            "value 7 goes to bot 1",
+            "value 3 goes to bot 1",
+            "bot 2 gives low to output 1 and high to output 0",
+            "bot 1 gives low to output 2 and high to output 3",
+        ];
+
+        let (instr, mut bots) = parse_input(&instr).unwrap();
+        let (watched_bot, outputs) = pass_chips(&instr, &mut bots, (17, 61)).unwrap();
+        assert_eq!(None, watched_bot);
+        assert_eq!(Some(&vec![7]), outputs.get(&3));
+        assert_eq!(Ok(5 * 2 * 3), output_product(&outputs, &PRODUCT_OUTPUTS));
+        assert_eq!(
+            Err("No chip found in output 4".to_owned()),
+            output_product(&outputs, &[0, 4])
+        );
+        assert!(parse_input(&["value x goes to bot 2"]).is_err());
+        assert!(parse_input(&["value 99999999999 goes to bot 2"]).is_err());
+    }
+
+    #[test]
+    fn chip_pair_param() {
+        assert_eq!(Ok((2, 5)), parse_chip_pair("5,2"));
+        assert!(parse_chip_pair("5").is_err());
     }
 }
@@ -0,0 +1,242 @@
+//! Solution for 2016 Day 11
+//!
+//! The facility is searched breadth-first for the shortest sequence of
+//! elevator trips. Since elements are interchangeable, states are stored in
+//! a canonical form that only records *where* each generator-microchip pair
+//! is, not which element it is. States that differ only by the naming of
+//! their elements are therefore visited once.
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::search::breadth_first;
+
+use std::collections::HashMap;
+
+/// Index of a floor, starting from zero for the first floor.
+type Floor = u8;
+
+/// The floors of a generator and its compatible microchip, indexed by
+/// `GEN` and `CHIP`.
+type Pair = [Floor; 2];
+
+/// Index of the generator's floor in a `Pair`.
+const GEN: usize = 0;
+
+/// Index of the microchip's floor in a `Pair`.
+const CHIP: usize = 1;
+
+/// The number of floors in the facility.
+const FLOOR_COUNT: Floor = 4;
+
+/// The number of bits used to store one floor in a packed state.
+const FLOOR_BITS: u32 = 2;
+
+/// The greatest number of pairs that fit in a packed state.
+const MAX_PAIRS: usize = 15;
+
+/// The number of extra pairs found on the first floor in part two.
+const EXTRA_PAIRS: usize = 2;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+/// Canonical encoding of the positions of the elevator and every item.
+///
+/// Pairs are sorted before being packed into a single integer, two bits per
+/// floor, so that equivalent states compare and hash equal.
+struct State {
+    elevator: Floor,
+    pair_count: u8,
+    packed: u64,
+}
+
+impl State {
+    /// Builds the canonical state for the given elevator position and pairs.
+    fn new(elevator: Floor, mut pairs: Vec<Pair>) -> Result<Self, String> {
+        if pairs.len() > MAX_PAIRS {
+            return Err(format!("facility may contain at most {} elements", MAX_PAIRS));
+        }
+        if elevator >= FLOOR_COUNT || pairs.iter().flatten().any(|&f| f >= FLOOR_COUNT) {
+            return Err(format!("facility has only {} floors", FLOOR_COUNT));
+        }
+        Ok(Self::canonical(elevator, &mut pairs))
+    }
+
+    /// Builds the canonical state for the given elevator position and pairs,
+    /// which must be in bounds.
+    fn canonical(elevator: Floor, pairs: &mut [Pair]) -> Self {
+        pairs.sort_unstable();
+        let packed = pairs.iter().fold(0, |acc, pair| {
+            (acc << (2 * FLOOR_BITS))
+                | u64::from(pair[GEN]) << FLOOR_BITS
+                | u64::from(pair[CHIP])
+        });
+        State { elevator, pair_count: pairs.len() as u8, packed }
+    }
+
+    /// Unpacks this state's pairs in sorted order.
+    fn pairs(&self) -> Vec<Pair> {
+        let mask = (1 << FLOOR_BITS) - 1;
+        (0..u32::from(self.pair_count)).rev().map(|i| {
+            let pair = self.packed >> (2 * FLOOR_BITS * i);
+            [(pair >> FLOOR_BITS & mask) as Floor, (pair & mask) as Floor]
+        }).collect()
+    }
+
+    /// Returns true if every item has been brought to the top floor.
+    fn is_complete(&self) -> bool {
+        self.pairs().iter().flatten().all(|&f| f == FLOOR_COUNT - 1)
+    }
+
+    /// Returns the safe states that can be reached with one elevator trip.
+    fn next_states(&self) -> Vec<State> {
+        let pairs = self.pairs();
+        // Items on the elevator's floor, as (pair index, GEN or CHIP)
+        let here: Vec<(usize, usize)> = pairs.iter().enumerate()
+            .flat_map(|(i, pair)| {
+                pair.iter().enumerate()
+                    .filter(|&(_, &floor)| floor == self.elevator)
+                    .map(move |(kind, _)| (i, kind))
+            })
+            .collect();
+
+        let mut targets = Vec::with_capacity(2);
+        if self.elevator + 1 < FLOOR_COUNT {
+            targets.push(self.elevator + 1);
+        }
+        // Items are never brought down to floors that have been emptied
+        let lowest = pairs.iter().flatten().copied().min().unwrap_or(self.elevator);
+        if self.elevator > lowest {
+            targets.push(self.elevator - 1);
+        }
+
+        let mut states = Vec::new();
+        for &target in targets.iter() {
+            for (first_index, &(i, kind)) in here.iter().enumerate() {
+                for &(j, other_kind) in here[first_index..].iter() {
+                    let mut moved = pairs.clone();
+                    moved[i][kind] = target;
+                    moved[j][other_kind] = target;
+                    if is_safe(&moved) {
+                        states.push(State::canonical(target, &mut moved));
+                    }
+                }
+            }
+        }
+        states
+    }
+}
+
+/// Returns true if no microchip is on a floor with a generator other than
+/// its own while unprotected by its own generator.
+fn is_safe(pairs: &[Pair]) -> bool {
+    pairs.iter().all(|pair| {
+        pair[GEN] == pair[CHIP] || !pairs.iter().any(|other| other[GEN] == pair[CHIP])
+    })
+}
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let pairs = parse_floors(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => min_steps(State::new(0, pairs.clone())?)?,
+        2 => {
+            let mut pairs = pairs;
+            pairs.extend([[0, 0]; EXTRA_PAIRS].iter());
+            min_steps(State::new(0, pairs)?)?
+        }
+    }
+}
+
+/// Returns the fewest elevator trips needed to bring every item to the top
+/// floor.
+fn min_steps(start: State) -> Result<usize, &'static str> {
+    breadth_first(start, State::next_states)
+        .find(|(state, _)| state.is_complete())
+        .map(|(_, steps)| steps)
+        .ok_or("items cannot be safely brought to the top floor")
+}
+
+/// Parses the description of each floor into the floors of each
+/// generator-microchip pair.
+fn parse_floors<T>(lines: &[T]) -> Result<Vec<Pair>, String>
+    where T: AsRef<str>
+{
+    if lines.len() > FLOOR_COUNT as usize {
+        return Err(format!("facility has only {} floors", FLOOR_COUNT));
+    }
+    // Floors of each element's generator and microchip
+    let mut elements: HashMap<&str, [Option<Floor>; 2]> = HashMap::new();
+
+    for (floor, line) in lines.iter().enumerate() {
+        let words: Vec<&str> = line.as_ref()
+            .split_whitespace()
+            .map(|w| w.trim_end_matches([',', '.']))
+            .collect();
+
+        for (prev, word) in words.iter().zip(words.iter().skip(1)) {
+            let (element, kind) = match *word {
+                "generator" => (*prev, GEN),
+                "microchip" => match prev.strip_suffix("-compatible") {
+                    Some(element) => (element, CHIP),
+                    None => return Err(format!("unknown microchip element `{}`", prev)),
+                }
+                _ => continue,
+            };
+            let slot = &mut elements.entry(element).or_default()[kind];
+            if slot.is_some() {
+                return Err(format!("duplicate item for element `{}`", element));
+            }
+            *slot = Some(floor as Floor);
+        }
+    }
+
+    elements.into_iter().map(|(element, pair)| match pair {
+        [Some(gen), Some(chip)] => Ok([gen, chip]),
+        _ => Err(format!("element `{}` is missing its generator or microchip", element)),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: [&str; 4] = [
+        "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.",
+        "The second floor contains a hydrogen generator.",
+        "The third floor contains a lithium generator.",
+        "The fourth floor contains nothing relevant.",
+    ];
+
+    #[test]
+    fn ex1() {
+        let pairs = parse_floors(&EXAMPLE_INPUT).unwrap();
+        assert_eq!(Ok(11), min_steps(State::new(0, pairs).unwrap()));
+    }
+
+    #[test]
+    fn canonical_states() {
+        let state = State::new(2, vec![[3, 1], [0, 2], [1, 1]]).unwrap();
+        assert_eq!(vec![[0, 2], [1, 1], [3, 1]], state.pairs());
+        assert_eq!(state, State::new(2, vec![[1, 1], [3, 1], [0, 2]]).unwrap());
+        assert_ne!(state, State::new(1, vec![[1, 1], [3, 1], [0, 2]]).unwrap());
+
+        assert!(State::new(0, vec![[0, 4]]).is_err());
+        assert!(State::new(0, vec![[0, 0]; MAX_PAIRS + 1]).is_err());
+    }
+
+    #[test]
+    fn safety() {
+        assert!(is_safe(&[[0, 0], [2, 1]]));
+        assert!(is_safe(&[[1, 0], [1, 0]]));
+        assert!(!is_safe(&[[0, 1], [1, 1]]));
+        assert!(!is_safe(&[[0, 0], [1, 0]]));
+    }
+
+    #[test]
+    fn reject_bad_floors() {
+        assert!(parse_floors(&["a hydrogen generator."]).is_err());
+        assert!(parse_floors(&[
+            "a hydrogen generator and a hydrogen generator",
+            "a hydrogen-compatible microchip",
+        ]).is_err());
+        assert!(parse_floors(&["", "", "", "", ""]).is_err());
+    }
+}
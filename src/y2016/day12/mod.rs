@@ -2,10 +2,10 @@
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
-pub mod assembunny;
+use crate::common::vm::assembunny;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input = parse_instructions(&pio::fetch_lines(puzzle)?)?;
+    let input = parse_instructions(&pio::fetch_line_index(puzzle)?.to_vec())?;
 
     solve_parts! {
         1 => {
@@ -29,12 +29,10 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
 }
 
 /// Parses the specified lines into assembunny instructions.
-///
-/// Panics on underlying parse errors.
-fn parse_instructions<T>(lines: &[T]) -> Result<Vec<assembunny::Instr>, &'static str>
+fn parse_instructions<T>(lines: &[T]) -> Result<Vec<assembunny::Instr>, String>
     where T: AsRef<str>
 {
-    lines.iter().map(|l| l.as_ref().parse()).collect()
+    assembunny::parse_program(lines)
 }
 
 #[cfg(test)]
@@ -43,7 +41,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(318_009, 9_227_663, Pz::new(2016, 12))
+        assert_solution!(Pz::new(2016, 12))
     }
 
     #[test]
@@ -69,4 +67,22 @@ mod tests {
 
         assert_eq!(42, interp.registers()[&b'a'.into()]);
     }
+
+    #[test]
+    fn annotated_multiply() {
+        use self::assembunny::Interpreter;
+
+        let lines: Vec<&str> = include_str!("multiply.asm").lines().collect();
+        let assem_instr = parse_instructions(&lines).unwrap();
+
+        let mut interp = Interpreter::new(&assem_instr);
+        interp.registers_mut()[&b'b'.into()] = 6;
+        interp.registers_mut()[&b'c'.into()] = 7;
+
+        while !interp.done() {
+            interp.execute_next();
+        }
+
+        assert_eq!(42, interp.registers()[&b'a'.into()]);
+    }
 }
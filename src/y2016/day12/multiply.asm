@@ -0,0 +1,12 @@
+; Multiplies registers b and c, storing the product in a.
+;
+; Both operands must be positive. Registers b and c are left as zero; d is used
+; as scratch space.
+
+        cpy 0 a
+outer:  cpy b d             ; a += b, c times
+inner:  inc a
+        dec d
+        jnz d inner
+        dec c
+        jnz c outer
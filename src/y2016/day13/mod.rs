@@ -0,0 +1,110 @@
+//! Solution for 2016 Day 13
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::Pt;
+use crate::common::util::search::breadth_first;
+
+/// A position in the cubicle maze. Coordinates are never negative.
+type Pos = Pt<i32>;
+
+/// The location of the cubicle to reach in part one.
+const TARGET: (i32, i32) = (31, 39);
+
+/// The maximum number of steps taken in part two.
+const STEP_LIMIT: usize = 50;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let maze = Maze(pio::fetch_string(puzzle)?.trim().parse()?);
+
+    solve_parts! {
+        1 => maze.shortest_path(Pt { x: TARGET.0, y: TARGET.1 })
+            .ok_or("target cubicle is unreachable")?,
+        2 => maze.reachable_within(STEP_LIMIT)
+    }
+}
+
+/// A maze of cubicles whose walls are determined by a designer's favorite
+/// number.
+struct Maze(u32);
+
+impl Maze {
+    /// The location from which every walk through the maze begins.
+    const START: Pos = Pt { x: 1, y: 1 };
+
+    /// Returns true if the specified position is an open space.
+    ///
+    /// Positions with negative coordinates are treated as walls.
+    fn is_open(&self, pos: Pos) -> bool {
+        if pos.x < 0 || pos.y < 0 {
+            return false;
+        }
+        let (x, y) = (pos.x as u64, pos.y as u64);
+        let value = x * x + 3 * x + 2 * x * y + y + y * y + u64::from(self.0);
+        value.count_ones() % 2 == 0
+    }
+
+    /// Returns the open positions adjacent to the specified position.
+    fn open_neighbors(&self, pos: &Pos) -> Vec<Pos> {
+        pos.nb_card().into_iter().filter(|&nb| self.is_open(nb)).collect()
+    }
+
+    /// Returns the fewest number of steps needed to reach the target from
+    /// the start, or `None` if the target is unreachable.
+    fn shortest_path(&self, target: Pos) -> Option<usize> {
+        if !self.is_open(target) {
+            return None;
+        }
+        breadth_first(Self::START, |pos| self.open_neighbors(pos))
+            .find(|&(pos, _)| pos == target)
+            .map(|(_, steps)| steps)
+    }
+
+    /// Returns the number of distinct positions that can be reached from
+    /// the start in at most `steps` steps, including the start itself.
+    fn reachable_within(&self, steps: usize) -> usize {
+        breadth_first(Self::START, |pos| self.open_neighbors(pos))
+            .take_while(|&(_, dist)| dist <= steps)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let maze = Maze(10);
+        let layout = [
+            ".#.####.##",
+            "..#..#...#",
+            "#....##...",
+            "###.#.###.",
+            ".##..#..#.",
+            "..##....#.",
+            "#...##.###",
+        ];
+        for (y, row) in layout.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                let pos = Pt { x: x as i32, y: y as i32 };
+                assert_eq!(cell == '.', maze.is_open(pos), "{:?}", pos);
+            }
+        }
+
+        assert_eq!(Some(11), maze.shortest_path(Pt { x: 7, y: 4 }));
+    }
+
+    #[test]
+    fn unreachable_target() {
+        assert_eq!(None, Maze(10).shortest_path(Pt { x: 1, y: 0 }));
+        assert_eq!(None, Maze(10).shortest_path(Pt { x: -1, y: 1 }));
+    }
+
+    #[test]
+    fn reachable() {
+        let maze = Maze(10);
+        assert_eq!(1, maze.reachable_within(0));
+        // (1, 1) -> (0, 1), (1, 2)
+        assert_eq!(3, maze.reachable_within(1));
+    }
+}
@@ -1,58 +1,90 @@
 //! Solution for 2016 Day 14
-use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
-
-use crypto::digest::Digest;
-use crypto::md5::Md5;
+use crate::common::puzzle::{
+    cancel, input as pio, Cancelled, Result as PuzzleResult, Selection as Pz,
+};
+use crate::common::util::hash;
 
 use std::collections::HashMap;
 
+/// Number of one-time pad keys to generate.
+///
+/// May be overridden with the `keys` parameter.
+const KEY_COUNT: usize = 64;
+
+/// Number of additional hashing rounds used for key stretching in part two.
+///
+/// May be overridden with the `stretch` parameter.
+const STRETCH_ROUNDS: u32 = 2016;
+
+/// Number of hashes following a triple byte in which its quintuple must
+/// appear for the triple to be a key.
+const KEY_WINDOW: KeyIndex = 1000;
+
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
     let input = pio::fetch_string(puzzle)?;
     let input = input.trim_end().as_bytes();
 
+    let key_count = puzzle.param_or("keys", KEY_COUNT)?;
+    let stretch_rounds: u32 = puzzle.param_or("stretch", STRETCH_ROUNDS)?;
+    if key_count == 0 {
+        return Err("at least one key must be generated".into());
+    }
+
     solve_parts! {
-        1 => generate_pad_keys(&input, 1).get(63).unwrap(),
-        2 => generate_pad_keys(&input, 1 + 2016).get(63).unwrap()
+        1 => generate_pad_keys(input, 1, key_count)?[key_count - 1],
+        2 => generate_pad_keys(input, 1 + stretch_rounds, key_count)?[key_count - 1]
     }
 }
 
 // Index of a valid generated one-time pad key
-type KeyIndex = u16;
+type KeyIndex = u64;
 
-/// Returns the first 64 generator indexes that produce valid
+/// Returns the first `key_count` generator indexes that produce valid
 /// one-time pad keys.
-fn generate_pad_keys(seed: &[u8], stretch_factor: u16) -> Vec<KeyIndex> {
-    let mut out = Vec::with_capacity(64);
-    let mut round: KeyIndex = 0;
-    let mut keys_found = 0_u8;
+///
+/// The `stretch_factor` is the number of times that each index is hashed.
+/// Gives up if the run is cancelled, since the search may be long.
+fn generate_pad_keys(
+    seed: &[u8],
+    stretch_factor: u32,
+    key_count: usize,
+) -> Result<Vec<KeyIndex>, Cancelled> {
+    let mut out = Vec::with_capacity(key_count);
 
     // Could swap with BTree Map for ordered keys
     // Doing so would remove the need to sort the generated keys
     let mut possible_keys: HashMap<KeyIndex, u8> = HashMap::new();
 
-    while keys_found < 64 {
-        let next_key = {
-            let mut input = Vec::from(seed);
-            input.extend(round.to_string().as_bytes());
+    let hashes = hash::counter_hashes(seed).stretched(stretch_factor.saturating_sub(1));
 
-            md5_stretch(&input[..], stretch_factor)
-        };
+    // Keys are confirmed out of order, so a key with a lower index may
+    // still be confirmed until the window of the last needed key closes.
+    let mut last_needed: Option<KeyIndex> = None;
+
+    for (round, digest) in hashes {
+        cancel::check()?;
+        if last_needed.is_some_and(|last| round > last + KEY_WINDOW) {
+            break;
+        }
+        let next_key = hash::to_hex(&digest);
 
         // Check for quintuple byte
         if let Some(rep_byte) = find_repeating_byte(&next_key[..], 5) {
-            let age_limit = if round < 1000 { 0 } else { round - 1000 };
+            let age_limit = round.saturating_sub(KEY_WINDOW);
+            let found = out.len();
 
             out.extend(possible_keys
                 .iter()
                 .filter(|&(&gen_index, &rep)| {
                     rep == rep_byte && gen_index >= age_limit
                 })
-                .map(|(&gen_index, _)| {
-                    keys_found += 1;
-                    gen_index
-                })
+                .map(|(&gen_index, _)| gen_index)
             );
 
+            if out.len() > found && out.len() >= key_count {
+                last_needed = Some(*out.select_nth_unstable(key_count - 1).1);
+            }
+
             possible_keys = possible_keys
                 .into_iter()
                 .filter(|&(gen_index, rep)| {
@@ -65,12 +97,11 @@ fn generate_pad_keys(seed: &[u8], stretch_factor: u16) -> Vec<KeyIndex> {
         if let Some(rep_byte) = find_repeating_byte(&next_key[..], 3) {
             possible_keys.insert(round, rep_byte);
         }
-
-        round += 1;
     }
 
     out.sort();
-    out
+    out.truncate(key_count);
+    Ok(out)
 }
 
 /// Returns the first byte in the specified string slice that repeats
@@ -90,23 +121,6 @@ fn find_repeating_byte(s: &str, rep_count: usize) -> Option<u8> {
     None
 }
 
-/// Repeatedly computes the MD5 digest of the string according to the
-/// specified stretch factor.
-fn md5_stretch(s: &[u8], stretch_factor: u16) -> String {
-    let mut hasher = Md5::new();
-    let mut result = String::new();
-
-    hasher.input(&s);
-
-    for _ in 0..stretch_factor {
-        hasher.input_str(&result[..]);
-        result = hasher.result_str();
-        hasher.reset();
-    }
-
-    result
-}
-
 #[cfg(test)]
 // Tests associated with part two are ignored by default as they
 // take a few minutes to run (w-wooo! key stretching...).
@@ -116,28 +130,37 @@ mod tests {
     #[test]
     #[ignore]
     fn solution() {
-        assert_solution! {
-            23890,
-            22696,
-            Pz::new(2016, 14)
-        }
+        assert_solution!(Pz::new(2016, 14))
     }
 
     #[test]
     fn ex1() {
-        let keys = generate_pad_keys(b"abc", 1);
+        let keys = generate_pad_keys(b"abc", 1, 64).unwrap();
 
+        assert_eq!(39, keys[0]);
+        assert_eq!(92, keys[1]);
         assert_eq!(22728, *keys.get(63).unwrap());
     }
 
     #[test]
     #[ignore]
     fn ex2() {
-        let keys = generate_pad_keys(b"abc", 1 + 2016);
+        let keys = generate_pad_keys(b"abc", 1 + 2016, 64).unwrap();
 
+        assert_eq!(10, keys[0]);
         assert_eq!(22551, *keys.get(63).unwrap())
     }
 
+    #[test]
+    fn key_count() {
+        let keys = generate_pad_keys(b"abc", 1, 2).unwrap();
+        assert_eq!(vec![39, 92], keys);
+
+        let keys = generate_pad_keys(b"abc", 1, 100).unwrap();
+        assert_eq!(100, keys.len());
+        assert_eq!(&generate_pad_keys(b"abc", 1, 64).unwrap()[..], &keys[..64]);
+    }
+
     #[test]
     fn find_byte_repeats() {
         // Edge cases
@@ -160,11 +183,17 @@ mod tests {
 
     #[test]
     fn stretch_md5() {
-        const INPUT: &[u8] = b"abc0";
+        let md5_stretch = |stretch_factor: u32| {
+            let (_, digest) = hash::counter_hashes(b"abc")
+                .stretched(stretch_factor - 1)
+                .next()
+                .unwrap();
+            hash::to_hex(&digest)
+        };
 
-        assert_eq!("577571be4de9dcce85a041ba0410f29f", md5_stretch(&INPUT, 1));
-        assert_eq!("eec80a0c92dc8a0777c619d9bb51e910", md5_stretch(&INPUT, 2));
-        assert_eq!("16062ce768787384c81fe17a7a60c7e3", md5_stretch(&INPUT, 3));
-        assert_eq!("a107ff634856bb300138cac6568c0f24", md5_stretch(&INPUT, 2017));
+        assert_eq!("577571be4de9dcce85a041ba0410f29f", md5_stretch(1));
+        assert_eq!("eec80a0c92dc8a0777c619d9bb51e910", md5_stretch(2));
+        assert_eq!("16062ce768787384c81fe17a7a60c7e3", md5_stretch(3));
+        assert_eq!("a107ff634856bb300138cac6568c0f24", md5_stretch(2017));
     }
 }
@@ -1,71 +1,98 @@
 //! Solution for 2016 Day 15
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::{crt, extract_ints, CrtError};
 
 use std::str::FromStr;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let mut input: Vec<Disc> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+    let mut input: Vec<Disc> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     solve_parts! {
-        1 => required_delay(&input),
+        1 => required_delay(&input)?,
         2 => {
             input.push(Disc { pos: 0, range: 11 });
-            required_delay(&input)
+            required_delay(&input)?
         }
     }
 }
 
 /// A Disc from the puzzle's input
-struct Disc {
+pub(crate) struct Disc {
     /// This Disc's start position at time=0
-    pos: u8,
+    pos: u64,
     /// The number of possible position for this Disc.
-    range: u8,
+    range: u64,
 }
 
 impl FromStr for Disc {
     type Err = &'static str;
 
-    // Assumes disc depth falls in [0,9] and range falls in [0, 99]
+    /// Parses a disc from a line of the form
+    /// `Disc #<depth> has <range> positions; at time=<time>, it is at position <pos>.`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Disc {
-            pos: s[54..s.len() - 1]
-                .trim_start()
-                .parse()
-                .map_err(|_| "malformed disc starting position")?,
-            range: s[12..14]
-                .trim_end()
-                .parse()
-                .map_err(|_| "malformed disc depth")?,
-        })
+        let ints: Vec<u64> = extract_ints(s).collect();
+        match ints[..] {
+            [_depth, range, time, pos] if range > 0 => {
+                // Wind the disc back to its position at time=0, taking care
+                // not to overflow for very large discs
+                let (pos, time) = (pos % range, time % range);
+                Ok(Disc {
+                    pos: if pos >= time {
+                        pos - time
+                    } else {
+                        range - (time - pos)
+                    },
+                    range,
+                })
+            }
+            [_, _, _, _] => Err("disc must have at least one position"),
+            _ => Err("malformed disc"),
+        }
     }
 }
 
 /// Returns the "delay" one must wait in order for a ball to
 /// successfully fall through all the specified discs.
-fn required_delay(discs: &[Disc]) -> u32 {
-    let mut delay = 0_u32;
-    loop {
-        let check = |(depth, disc): (usize, &Disc)| {
-            (1 + delay + depth as u32 + u32::from(disc.pos)) % u32::from(disc.range) != 0
-        };
-        if !discs.iter().enumerate().any(check) {
-            break delay;
-        }
-        delay += 1;
-    }
+///
+/// The disc at depth `d` (starting from `1`) lets the ball through when
+/// `delay + d + pos ≡ 0 (mod range)`, so each disc contributes the
+/// congruence `delay ≡ -(d + pos) (mod range)`. The least delay
+/// satisfying all of them is found with the Chinese Remainder Theorem.
+///
+/// Returns an error if the discs never align, or if they only align with a
+/// period too long to represent.
+fn required_delay(discs: &[Disc]) -> Result<u64, &'static str> {
+    crt(discs.iter().enumerate().map(|(depth, disc)| {
+        // Summed as a `u128` since both terms may be close to `u64::MAX`
+        let offset = (depth as u128 + 1 + u128::from(disc.pos)) % u128::from(disc.range);
+        ((disc.range - offset as u64) % disc.range, disc.range)
+    }))
+    .map(|(delay, _)| delay)
+    .map_err(|e| match e {
+        CrtError::ZeroModulus => "disc must have at least one position",
+        CrtError::NoSolution => "discs never align",
+        CrtError::Overflow => "discs align with a period that does not fit in a u64",
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::testing::{parse_lines, FakeInputs};
 
     #[test]
     fn solution() {
-        assert_solution!(122_318, 3_208_583, Pz::new(2016, 15))
+        assert_solution!(Pz::new(2016, 15))
+    }
+
+    #[test]
+    fn solution_fake_input() {
+        let inputs = FakeInputs::new().with(
+            &Pz::new(2016, 15),
+            "Disc #1 has 5 positions; at time=0, it is at position 4.\n\
+             Disc #2 has 2 positions; at time=0, it is at position 1.\n",
+        );
+        assert_solution!(5, 85, Pz::new(2016, 15), inputs = inputs)
     }
 
     #[test]
@@ -75,21 +102,113 @@ mod tests {
             "Disc #2 has 2 positions; at time=0, it is at position 1.",
         ];
 
-        let delay = required_delay(
-            &discs.iter().map(|&l| l.parse().unwrap()).collect::<Vec<_>>()
-        );
+        let delay = required_delay(&parse_lines::<Disc, _>(&discs));
+
+        assert_eq!(Ok(5), delay);
+    }
+
+    #[test]
+    fn large_discs() {
+        let discs = [
+            Disc {
+                pos: 7,
+                range: 1_000_000_007,
+            },
+            Disc {
+                pos: 0,
+                range: 998_244_353,
+            },
+            Disc { pos: 3, range: 17 },
+        ];
+
+        let delay = required_delay(&discs).unwrap();
+
+        for (depth, disc) in discs.iter().enumerate() {
+            assert_eq!(0, (delay + depth as u64 + 1 + disc.pos) % disc.range);
+        }
+    }
+
+    #[test]
+    fn discs_never_align() {
+        let discs = [Disc { pos: 0, range: 2 }, Disc { pos: 0, range: 2 }];
 
-        assert_eq!(5, delay);
+        assert_eq!(Err("discs never align"), required_delay(&discs));
+    }
+
+    #[test]
+    fn discs_near_u64_max() {
+        let discs = [
+            Disc {
+                pos: u64::MAX - 1,
+                range: u64::MAX,
+            },
+            Disc {
+                pos: u64::MAX - 2,
+                range: u64::MAX,
+            },
+        ];
+
+        let delay = required_delay(&discs).unwrap();
+
+        for (depth, disc) in discs.iter().enumerate() {
+            let sum = u128::from(delay) + depth as u128 + 1 + u128::from(disc.pos);
+            assert_eq!(0, sum % u128::from(disc.range));
+        }
+    }
+
+    #[test]
+    fn discs_period_overflows() {
+        // Coprime ranges around 2^33, whose product does not fit in a u64
+        let discs = [
+            Disc {
+                pos: 0,
+                range: (1 << 33) + 1,
+            },
+            Disc {
+                pos: 0,
+                range: (1 << 33) + 3,
+            },
+        ];
+
+        assert_eq!(
+            Err("discs align with a period that does not fit in a u64"),
+            required_delay(&discs)
+        );
     }
 
     #[test]
     fn parse_disc() {
-        let disc: Disc = "Disc #1 has 5 positions; at time=0, it is at position 4.".parse().unwrap();
+        let disc: Disc = "Disc #1 has 5 positions; at time=0, it is at position 4."
+            .parse()
+            .unwrap();
         assert_eq!(5, disc.range);
         assert_eq!(4, disc.pos);
 
-        let disc: Disc = "Disc #1 has 50 positions; at time=0, it is at position 48.".parse().unwrap();
+        let disc: Disc = "Disc #1 has 50 positions; at time=0, it is at position 48."
+            .parse()
+            .unwrap();
         assert_eq!(50, disc.range);
         assert_eq!(48, disc.pos);
+
+        let disc: Disc = "Disc #12 has 1009 positions;  at time=0, it is at position 512."
+            .parse()
+            .unwrap();
+        assert_eq!(1009, disc.range);
+        assert_eq!(512, disc.pos);
+
+        let disc: Disc = "Disc #3 has 7 positions; at time=3, it is at position 1."
+            .parse()
+            .unwrap();
+        assert_eq!(7, disc.range);
+        assert_eq!(5, disc.pos);
+
+        assert!("Disc #1 has 0 positions; at time=0, it is at position 0."
+            .parse::<Disc>()
+            .is_err());
+        assert!("Disc #1 has 5 positions.".parse::<Disc>().is_err());
+
+        let disc: Disc = "Disc #1 has 18446744073709551615 positions; at time=1, it is at position 18446744073709551614."
+            .parse().unwrap();
+        assert_eq!(u64::MAX - 2, disc.pos);
     }
 }
@@ -40,11 +40,12 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
     }
 }
 
-mod diskfiller {
+pub mod diskfiller {
     /// Generator of data to fill a disk.
     ///
     /// Data (unfortunately) stored as a `Vec` of bools  - despite the
     /// horrendous memory inefficiency - for ease of implementation.
+    #[derive(Default)]
     pub struct DiskFiller {
         data: Vec<bool>,
         cut: usize,
@@ -53,12 +54,12 @@ mod diskfiller {
     impl DiskFiller {
         /// Creates a new DiskFiller.
         pub fn new() -> Self {
-            DiskFiller { data: Vec::new(), cut: 0 }
+            Self::default()
         }
 
         /// Appends the specified data to this disk filler's data.
         pub fn input(&mut self, data: &[bool]) {
-            self.data.extend_from_slice(&data);
+            self.data.extend_from_slice(data);
         }
 
         /// Generates data of at least the specified length based
@@ -123,30 +124,18 @@ mod diskfiller {
     mod tests {
         use super::*;
 
-        #[test]
-        fn generate_filler_data() {
-            let test_cases: [(&'static [bool], &'static str); 4] = [
-                (&[true], "100"),
-                (&[false], "001"),
-                (&[true, true, true, true, true], "11111000000"),
-                (
-                    &[
-                        true, true, true, true,
-                        false, false, false, false,
-                        true, false, true, false
-                    ],
-                    "1111000010100101011110000",
-                ),
-            ];
-
-            let mut filler = DiskFiller::new();
-
-            for &(input, expected) in test_cases.iter() {
-                filler.input(&input);
-                filler.generate_to_length(expected.len());
-
-                assert_eq!(expected, filler.result_str());
-                filler.reset();
+        example_cases! {
+            generate_filler_data: |input: &str| {
+                let bits: Vec<bool> = input.bytes().map(|b| b == b'1').collect();
+                let mut filler = DiskFiller::new();
+                filler.input(&bits);
+                filler.generate_to_length(2 * bits.len() + 1);
+                filler.result_str()
+            } => {
+                single_one: "1" => "100",
+                single_zero: "0" => "001",
+                all_ones: "11111" => "11111000000",
+                mixed: "111100001010" => "1111000010100101011110000",
             }
         }
 
@@ -171,7 +160,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!("11111000111110000", "10111100110110100", Pz::new(2016, 16))
+        assert_solution!(Pz::new(2016, 16))
     }
 
     #[test]
@@ -1,41 +1,26 @@
 //! Solution for 2016 Day 17
-//!
-//! While capable of determining the shortest route to
-//! a vault destination (part one), this solution evidently
-//! fails to generate all possible routes as it is unable
-//! to determine the length of the longest route (part two).
-//!
-//! I have been unable to find the root of this bug. For now,
-//! I am pushing the solution to part one with the part two
-//! boilerplate commented out.
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
-use crate::common::util::Pt;
 
-/// The final destination in the vault
-const VAULT_DEST: Pt<i8> = Pt { x: 3, y: 0 };
-
-/// The starting location in the vault
-const VAULT_START: Pt<i8> = Pt { x: 0, y: 3 };
+/// The width and height of the vault.
+const VAULT_SIZE: i8 = 4;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
     let input = pio::fetch_string(puzzle)?;
 
-    let mut nav = vault::VaultNavigator::new(VAULT_DEST);
+    let mut nav = vault::VaultNavigator::new(VAULT_SIZE, VAULT_SIZE)?;
+
+    nav.find_routes(input.trim_end())?;
 
     solve_parts! {
-        1 => {
-            nav.find_routes(VAULT_START, input.trim_end());
-            nav.shortest_route().unwrap()
-        }
-        //, 2 => nav.longest_route().unwrap().len()
+        1 => nav.shortest_route().ok_or("no route to the vault")?.clone(),
+        2 => nav.longest_route().ok_or("no route to the vault")?.len()
     }
 }
 
-mod vault {
+pub mod vault {
+    use crate::common::util::hash::md5;
     use crate::common::util::Pt;
-    use crypto::digest::Digest;
-    use crypto::md5::Md5;
 
     /// A Position within a vault.
     type VaultPos = Pt<i8>;
@@ -43,30 +28,42 @@ mod vault {
     /// A direction specifying a position offset.
     type Dir = Pt<i8>;
 
-    impl Dir {
-        /// Attempts to convert a direction into a char.
-        fn into_char(self) -> Option<char> {
-            if self == Dir::n() {
-                Some('U')
-            } else if self == Dir::e() {
-                Some('R')
-            } else if self == Dir::s() {
-                Some('D')
-            } else if self == Dir::w() {
-                Some('L')
-            } else {
-                None
-            }
+    /// Attempts to convert a direction into a char.
+    fn dir_char(dir: Dir) -> Option<char> {
+        if dir == Dir::n() {
+            Some('U')
+        } else if dir == Dir::e() {
+            Some('R')
+        } else if dir == Dir::s() {
+            Some('D')
+        } else if dir == Dir::w() {
+            Some('L')
+        } else {
+            None
         }
     }
 
+    /// The default hex digit above which a door is open.
+    const OPEN_THRESHOLD: u8 = 0xA;
+
     #[derive(Debug)]
     /// A navigator between two points in a secured vault.
+    ///
+    /// Rooms in the vault are addressed by points whose `y` component
+    /// increases upwards, so that the bottom-left room is at the origin.
     pub struct VaultNavigator {
         /// The paths that lead to this vault's destination.
         valid_routes: Vec<String>,
+        /// The number of rooms in each row of the vault.
+        width: i8,
+        /// The number of rooms in each column of the vault.
+        height: i8,
+        /// The starting position within the vault.
+        start: VaultPos,
         /// The desired destination within the vault.
         destination: VaultPos,
+        /// The hex digit above which a door is open.
+        open_threshold: u8,
     }
 
     #[derive(Debug)]
@@ -81,77 +78,106 @@ mod vault {
     }
 
     impl VaultNavigator {
-        /// Builds a `VaultNavigator` with the specified destination.
-        pub fn new(destination: VaultPos) -> Self {
-            VaultNavigator { valid_routes: Vec::new(), destination }
+        /// Builds a `VaultNavigator` for a vault with the specified
+        /// dimensions.
+        ///
+        /// The navigator starts in the top-left room and has its destination
+        /// in the bottom-right room. Doors are open for hex digits above
+        /// `0xA`.
+        pub fn new(width: i8, height: i8) -> Result<Self, &'static str> {
+            if width <= 0 || height <= 0 {
+                return Err("vault must contain at least one room");
+            }
+            Ok(VaultNavigator {
+                valid_routes: Vec::new(),
+                width,
+                height,
+                start: Pt { x: 0, y: height - 1 },
+                destination: Pt { x: width - 1, y: 0 },
+                open_threshold: OPEN_THRESHOLD,
+            })
         }
 
-        /// Determines all the possible routes leading from the `start_pos`
-        /// to this vault's destination based on the specified passcode.
-        pub fn find_routes(&mut self, start_pos: VaultPos, passcode: &str) {
-            self.reset();
-            if start_pos == self.destination {
-                return;
-            }
+        /// Sets the starting position within the vault.
+        pub fn with_start(mut self, start: VaultPos) -> Self {
+            self.start = start;
+            self
+        }
 
-            let mut path_stack = Vec::new();
+        /// Sets the desired destination within the vault.
+        pub fn with_destination(mut self, destination: VaultPos) -> Self {
+            self.destination = destination;
+            self
+        }
 
-            // Assumes square vault
-            let max_pos = self.destination.x;
+        /// Sets the hex digit above which a door is open.
+        pub fn with_open_threshold(mut self, open_threshold: u8) -> Self {
+            self.open_threshold = open_threshold;
+            self
+        }
 
-            let start = VaultPath {
-                pathcode: String::from(passcode),
-                pos: start_pos,
-                doors: doors_at_path_end(&passcode)
-                    .into_iter()
-                    .filter(|&dir| {
-                        let next = start_pos + dir;
-                        next.x >= 0 && next.x <= max_pos && next.y >= 0 && next.y <= max_pos
-                    })
-                    .collect(),
-            };
+        /// Returns true if the specified position is inside of the vault.
+        fn contains(&self, pos: VaultPos) -> bool {
+            pos.x >= 0 && pos.x < self.width && pos.y >= 0 && pos.y < self.height
+        }
 
-            path_stack.push(start);
+        /// Returns the doors leading to other rooms that are open at the
+        /// end of a path.
+        fn open_doors(&self, pathcode: &str, pos: VaultPos) -> Vec<Dir> {
+            doors_at_path_end(pathcode, self.open_threshold)
+                .into_iter()
+                .filter(|&dir| self.contains(pos + dir))
+                .collect()
+        }
 
-            while !path_stack.is_empty() {
-                // Should optimize repetitive popping/re-pushing to the stack
-                let mut current = path_stack.pop().unwrap();
+        /// Determines all the possible routes leading from the start to
+        /// this vault's destination based on the specified passcode.
+        ///
+        /// Fails if the start or destination are outside of the vault.
+        pub fn find_routes(&mut self, passcode: &str) -> Result<(), &'static str> {
+            self.reset();
+            if !self.contains(self.start) || !self.contains(self.destination) {
+                return Err("vault start and destination must be inside the vault");
+            }
+            if self.start == self.destination {
+                return Ok(());
+            }
 
-                if !current.doors.is_empty() {
-                    let door_selection = current.doors.pop().unwrap();
+            let mut path_stack = vec![VaultPath {
+                pathcode: String::from(passcode),
+                pos: self.start,
+                doors: self.open_doors(passcode, self.start),
+            }];
 
+            // Should optimize repetitive popping/re-pushing to the stack
+            while let Some(mut current) = path_stack.pop() {
+                if let Some(door_selection) = current.doors.pop() {
                     let mut pathcode = current.pathcode.clone();
                     pathcode.push(
-                        door_selection.into_char().expect("illegal direction")
+                        dir_char(door_selection).expect("illegal direction")
                     );
 
                     let pos = current.pos + door_selection;
 
+                    // Revisit this path later to explore its remaining doors
+                    path_stack.push(current);
+
                     if pos == self.destination {
                         self.valid_routes.push(
                             pathcode[passcode.len()..].to_owned()
                         );
                     } else {
                         let next = VaultPath {
-                            doors: doors_at_path_end(&pathcode)
-                                .into_iter()
-                                .filter(|&dir| {
-                                    let next = pos + dir;
-                                    next.x >= 0
-                                        && next.x <= max_pos
-                                        && next.y >= 0
-                                        && next.y <= max_pos
-                                })
-                                .collect(),
+                            doors: self.open_doors(&pathcode, pos),
                             pathcode,
                             pos,
                         };
 
-                        path_stack.push(current);
                         path_stack.push(next);
                     }
                 }
             }
+            Ok(())
         }
 
         /// Clears all valid routes
@@ -169,31 +195,26 @@ mod vault {
         /// Returns the longest valid route found.
         ///
         /// If no valid routes were found, `None` is returned.
-        pub fn _longest_route(&self) -> Option<&String> {
+        pub fn longest_route(&self) -> Option<&String> {
             self.valid_routes.iter().max_by_key(|&path| path.len())
         }
     }
 
-    /// Returns the paths at the end of a path based on its MD5 digest.
-    fn doors_at_path_end(pathcode: &str) -> Vec<Dir> {
+    /// Returns the doors at the end of a path based on its MD5 digest.
+    ///
+    /// A door is open if its hex digit is above `open_threshold`.
+    fn doors_at_path_end(pathcode: &str, open_threshold: u8) -> Vec<Dir> {
         let mut doors = Vec::with_capacity(4);
-        let mut result = [0_u8; 16];
-
-        {
-            let mut hasher = Md5::new();
-            hasher.input_str(&pathcode);
-            hasher.result(&mut result);
-        }
+        let result = md5(pathcode.as_bytes());
 
-        const OPEN_THRESHOLD: u8 = 0xA;
         // First char - up
-        if result[0] >> 4 > OPEN_THRESHOLD { doors.push(Dir::n()); }
+        if result[0] >> 4 > open_threshold { doors.push(Dir::n()); }
         // Second char - down
-        if result[0] & 0xf > OPEN_THRESHOLD { doors.push(Dir::s()); }
+        if result[0] & 0xf > open_threshold { doors.push(Dir::s()); }
         // Third char - left
-        if result[1] >> 4 > OPEN_THRESHOLD { doors.push(Dir::w()); }
+        if result[1] >> 4 > open_threshold { doors.push(Dir::w()); }
         // Fourth char - right
-        if result[1] & 0xf > OPEN_THRESHOLD { doors.push(Dir::e()); }
+        if result[1] & 0xf > open_threshold { doors.push(Dir::e()); }
 
         doors
     }
@@ -204,20 +225,26 @@ mod vault {
 
         #[test]
         fn find_doors() {
-            let doors = doors_at_path_end("hijkl");
+            let doors = doors_at_path_end("hijkl", OPEN_THRESHOLD);
             assert_eq!([Dir::n(), Dir::s(), Dir::w()], &doors[..]);
 
-            let doors = doors_at_path_end("hijklD");
+            let doors = doors_at_path_end("hijklD", OPEN_THRESHOLD);
             assert_eq!([Dir::n(), Dir::w(), Dir::e()], &doors[..]);
 
-            let doors = doors_at_path_end("hijklDR");
+            let doors = doors_at_path_end("hijklDR", OPEN_THRESHOLD);
             assert!(doors.is_empty());
 
-            let doors = doors_at_path_end("hijklDU");
+            let doors = doors_at_path_end("hijklDU", OPEN_THRESHOLD);
             assert_eq!([Dir::e()], &doors[..]);
 
-            let doors = doors_at_path_end("hijklDUR");
+            let doors = doors_at_path_end("hijklDUR", OPEN_THRESHOLD);
             assert!(doors.is_empty());
+
+            // hijkl hashes to ced9...
+            let doors = doors_at_path_end("hijkl", 0xC);
+            assert_eq!([Dir::s(), Dir::w()], &doors[..]);
+            let doors = doors_at_path_end("hijkl", 0x0);
+            assert_eq!([Dir::n(), Dir::s(), Dir::w(), Dir::e()], &doors[..]);
         }
     }
 }
@@ -228,27 +255,48 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!("DDRRULRDRD", Pz::new(2016, 17))
+        assert_solution!(Pz::new(2016, 17))
     }
 
-    #[test]
-    fn ex_both() {
-        let mut nav = vault::VaultNavigator::new(VAULT_DEST);
-
-        let test_cases = [
-            ("hijkl", None, None),
-            ("ihgpwlah", Some("DDRRRD"), Some(370)),
-            ("kglvqrro", Some("DDUDRLRRUDRD"), Some(492)),
-            ("ulqzkmiv", Some("DRURDRUDDLLDLUURRDULRLDUUDDDRR"), Some(830)),
-        ];
-
-        for &(input, expected_one, _expected_two) in test_cases.iter() {
-            nav.find_routes(VAULT_START, input);
-
-            assert_eq!(expected_one, nav.shortest_route().map(String::as_ref));
-
-            // Temporarily (hopefully) omitting part two's example check
-            // assert_eq!(expected_two, nav.longest_route().map(String::len));
+    example_cases! {
+        ex_both: |input| {
+            let mut nav = vault::VaultNavigator::new(VAULT_SIZE, VAULT_SIZE).unwrap();
+            nav.find_routes(input).unwrap();
+            (nav.shortest_route().cloned(), nav.longest_route().map(String::len))
+        } => {
+            trapped: "hijkl" => (None, None),
+            short: "ihgpwlah" => (Some("DDRRRD".to_owned()), Some(370)),
+            medium: "kglvqrro" => (Some("DDUDRLRRUDRD".to_owned()), Some(492)),
+            long: "ulqzkmiv" => (Some("DRURDRUDDLLDLUURRDULRLDUUDDDRR".to_owned()), Some(830)),
         }
     }
+
+    #[test]
+    fn vault_variants() {
+        use crate::common::util::Pt;
+
+        // The only door out of the first room is locked
+        let mut nav = vault::VaultNavigator::new(2, 1).unwrap();
+        nav.find_routes("hijkl").unwrap();
+        assert_eq!(None, nav.shortest_route());
+
+        // Unless all doors are open
+        let mut nav = vault::VaultNavigator::new(2, 1).unwrap().with_open_threshold(0);
+        nav.find_routes("hijkl").unwrap();
+        assert_eq!(Some("R"), nav.shortest_route().map(String::as_ref));
+
+        // Retracing the example's steps with the start and end swapped
+        let mut nav = vault::VaultNavigator::new(VAULT_SIZE, VAULT_SIZE)
+            .unwrap()
+            .with_start(Pt { x: 0, y: 2 })
+            .with_destination(Pt { x: 0, y: 3 });
+        nav.find_routes("hijklD").unwrap();
+        assert_eq!(Some("U"), nav.shortest_route().map(String::as_ref));
+
+        assert!(vault::VaultNavigator::new(0, 4).is_err());
+        let mut nav = vault::VaultNavigator::new(4, 4)
+            .unwrap()
+            .with_destination(Pt { x: 4, y: 0 });
+        assert!(nav.find_routes("hijkl").is_err());
+    }
 }
@@ -16,34 +16,38 @@
 //! [josephus]: https://en.wikipedia.org/wiki/Josephus_problem
 
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
     let input = pio::fetch_string(puzzle)?;
     let input = input.trim_end().parse()?;
 
     solve_parts! {
-        1 => part_one(input),
-        2 => part_two(input)
+        1 => steal_from_left(input).ok_or("no elves in the circle")?,
+        2 => steal_from_across(input).ok_or("no elves in the circle")?
     }
 }
 
-/// Returns the greatest power of `base` less than or equal to `cap`.
-fn greatest_power(base: u32, cap: u32) -> u32 {
-    if cap < base {
+/// Returns the greatest power of `base` less than or equal to `cap`, or
+/// `0` if `cap` is `0`.
+fn greatest_power(base: u64, cap: u64) -> u64 {
+    if cap == 0 {
         return 0;
     }
-    let mut acc = base;
+    let mut acc = 1_u64;
     loop {
-        let next = acc * base;
-        if next > cap {
-            break acc;
+        match acc.checked_mul(base) {
+            Some(next) if next <= cap => acc = next,
+            _ => break acc,
         }
-        acc = next;
     }
 }
 
 /// Return the least power of `base` greater than or equal to `shoe`.
-fn least_power(base: u32, shoe: u32) -> u32 {
+///
+/// Computed over `u128` since the power may not fit in a `u64`.
+fn least_power(base: u128, shoe: u128) -> u128 {
     let mut acc = base;
     loop {
         if acc >= shoe {
@@ -53,19 +57,24 @@ fn least_power(base: u32, shoe: u32) -> u32 {
     }
 }
 
-/// Returns the elf who will obtain all the presents in part one.
-fn part_one(elf_count: u32) -> u32 {
-    debug_assert_ne!(0, elf_count);
-    if 1 == elf_count {
-        return 1;
+/// Returns the elf who will obtain all the presents when each elf steals
+/// from the elf to their left (part one), or `None` if there are no elves.
+///
+/// This is the Josephus problem with every second elf eliminated: writing
+/// the group size as `2^m + offset`, the winner is `2 * offset + 1`.
+pub fn steal_from_left(elf_count: u64) -> Option<u64> {
+    if elf_count == 0 {
+        return None;
     }
 
     let origin = greatest_power(2, elf_count);
     let offset = elf_count - origin;
-    2 * offset + 1
+    Some(2 * offset + 1)
 }
 
-/// Returns the elf who will obtain all the presents in part two.
+/// Returns the elf who will obtain all the presents when each elf steals
+/// from the elf directly across the circle (part two), or `None` if there
+/// are no elves.
 ///
 /// From what I gather, this function resets every `3^m` values.
 /// After each reset, the first `3^(m-1)` values are follow a slope
@@ -94,38 +103,59 @@ fn part_one(elf_count: u32) -> u32 {
 /// | 81         | 81       | Reach `3^m`  |
 /// | 82         | 1        | Resets again |
 ///
-/// The complete data may be found [here](./relationship_data.txt).
-fn part_two(elf_count: u32) -> u32 {
-    debug_assert_ne!(0, elf_count);
-    if 1 == elf_count {
-        return 1;
+/// The complete data may be found [here](./relationship_data.txt), and may
+/// be regenerated with `cargo run --example day19_relationships`.
+pub fn steal_from_across(elf_count: u64) -> Option<u64> {
+    match elf_count {
+        0 => return None,
+        1 => return Some(1),
+        _ => {}
     }
 
+    let elf_count = u128::from(elf_count);
     let pow_above = least_power(3, elf_count);
     let pow_below = pow_above / 3;
     let mid = pow_above - pow_below;
 
-    if elf_count > mid {
+    let winner = if elf_count > mid {
         2 * elf_count - pow_above
     } else {
         pow_below - (mid - elf_count)
-    }
+    };
+    // The winner is never numbered higher than the group size
+    Some(winner as u64)
 }
 
-#[deprecated]
-#[allow(dead_code)]
-/// Returns the elf who will obtain all the presents in part two.
+/// Returns the elf who will obtain all the presents when each elf steals
+/// from the elf to their left, or `None` if there are no elves.
 ///
-/// Used to generate relationship table for cleaner solution.
+/// Computed by simulating the exchange, so `steal_from_left` should be
+/// preferred for anything but small groups.
+pub fn simulate_steal_from_left(elf_count: usize) -> Option<usize> {
+    let mut elves: VecDeque<usize> = (1..=elf_count).collect();
+
+    while elves.len() > 1 {
+        // The elf at the front steals from the next elf, then waits for
+        // their next turn at the back of the circle
+        elves.rotate_left(1);
+        elves.pop_front();
+    }
+    elves.pop_front()
+}
+
+/// Returns the elf who will obtain all the presents when each elf steals
+/// from the elf directly across the circle, or `None` if there are no
+/// elves.
 ///
-/// This implementation is outstandingly inefficient in both memory
-/// and time due to shifting/copying elements during each "elf" removal.
-fn part_two_brute_force(elf_count: usize) -> usize {
-    debug_assert_ne!(0, elf_count);
-    let mut elves: Vec<usize> = (0..elf_count).collect();
+/// Computed by simulating the exchange, so `steal_from_across` should be
+/// preferred for anything but small groups. This implementation is
+/// outstandingly inefficient in both memory and time due to
+/// shifting/copying elements during each "elf" removal.
+pub fn simulate_steal_from_across(elf_count: usize) -> Option<usize> {
+    let mut elves: Vec<usize> = (1..=elf_count).collect();
     let mut finger = 0_usize;
 
-    while elves.len() != 1 {
+    while elves.len() > 1 {
         let len = elves.len();
         let offset = (finger + (len / 2)) % len;
         elves.remove(offset);
@@ -135,7 +165,25 @@ fn part_two_brute_force(elf_count: usize) -> usize {
         finger %= len - 1;
     }
 
-    *elves.get(0).unwrap() + 1
+    elves.first().cloned()
+}
+
+/// Returns a table mapping each group size in the specified range to the
+/// winning elf for both parts, as computed by simulation.
+///
+/// Used to generate the [relationship table](./relationship_data.txt)
+/// that revealed the closed form of `steal_from_across`.
+pub fn relationship_table(group_sizes: RangeInclusive<usize>) -> String {
+    let mut table = String::from("Group Size | Take-From-Adjacent | Take-From-Across\n");
+    for elf_count in group_sizes.filter(|&n| n > 0) {
+        table.push_str(&format!(
+            "{:>7}    |{:>11}         |{:>10}\n",
+            elf_count,
+            simulate_steal_from_left(elf_count).unwrap(),
+            simulate_steal_from_across(elf_count).unwrap(),
+        ));
+    }
+    table
 }
 
 #[cfg(test)]
@@ -144,19 +192,54 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(1_808_357, 1_407_007, Pz::new(2016, 19))
+        assert_solution!(Pz::new(2016, 19))
     }
 
     #[test]
     fn ex1() {
-        assert_eq!(3, part_one(5));
-        assert_eq!(5, part_one(10));
-        assert_eq!(9, part_one(20));
+        assert_eq!(Some(3), steal_from_left(5));
+        assert_eq!(Some(5), steal_from_left(10));
+        assert_eq!(Some(9), steal_from_left(20));
     }
 
     #[test]
     fn ex2() {
-        assert_eq!(2, part_two(5));
+        assert_eq!(Some(2), steal_from_across(5));
+    }
+
+    #[test]
+    fn matches_simulation() {
+        for elf_count in 0..300 {
+            assert_eq!(
+                simulate_steal_from_left(elf_count).map(|n| n as u64),
+                steal_from_left(elf_count as u64)
+            );
+            assert_eq!(
+                simulate_steal_from_across(elf_count).map(|n| n as u64),
+                steal_from_across(elf_count as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn reproduce_relationship_data() {
+        assert_eq!(include_str!("relationship_data.txt"), relationship_table(1..=254));
+    }
+
+    #[test]
+    fn huge_groups() {
+        assert_eq!(None, steal_from_left(0));
+        assert_eq!(None, steal_from_across(0));
+        assert_eq!(Some(1), steal_from_left(1));
+        assert_eq!(Some(1), steal_from_across(1));
+        assert_eq!(Some(1), steal_from_left(1 << 63));
+        assert_eq!(Some(u64::MAX), steal_from_left(u64::MAX));
+
+        // 3^40 is the greatest power of three that fits in a u64
+        let pow = 3_u64.pow(40);
+        assert_eq!(Some(pow), steal_from_across(pow));
+        assert_eq!(Some(1), steal_from_across(pow + 1));
+        assert_eq!(Some(u64::MAX - pow), steal_from_across(u64::MAX));
     }
 
     #[test]
@@ -167,6 +250,8 @@ mod tests {
         assert_eq!(16, greatest_power(2, 17));
         assert_eq!(0, greatest_power(2, 0));
         assert_eq!(2, greatest_power(2, 2));
+        assert_eq!(1, greatest_power(2, 1));
+        assert_eq!(1 << 63, greatest_power(2, u64::MAX));
     }
 
     #[test]
@@ -0,0 +1,77 @@
+//! Solution for 2016 Day 20
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::RangeSet;
+
+use std::ops::RangeInclusive;
+
+/// The range of valid IP addresses.
+const IP_SPACE: RangeInclusive<u64> = 0..=u32::MAX as u64;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let blocked = parse_blacklist(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => lowest_allowed(&blocked, IP_SPACE).ok_or("every IP address is blocked")?,
+        2 => count_allowed(&blocked, IP_SPACE)
+    }
+}
+
+/// Parses a blacklist of inclusive IP ranges of the form `low-high`.
+fn parse_blacklist<T>(lines: &[T]) -> Result<RangeSet, String>
+    where T: AsRef<str>
+{
+    lines.iter().map(|line| {
+        let line = line.as_ref();
+        let (low, high) = line.split_once('-')
+            .ok_or_else(|| format!("malformed IP range `{}`", line))?;
+        let parse_ip = |ip: &str| ip.trim().parse::<u32>()
+            .map(u64::from)
+            .map_err(|e| format!("bad IP address in range `{}`: {}", line, e));
+        let (low, high) = (parse_ip(low)?, parse_ip(high)?);
+        if low > high {
+            return Err(format!("IP range `{}` is reversed", line));
+        }
+        Ok(low..=high)
+    }).collect()
+}
+
+/// Returns the lowest address in `space` that is not blocked.
+fn lowest_allowed(blocked: &RangeSet, space: RangeInclusive<u64>) -> Option<u64> {
+    blocked.gaps(space).next().map(|gap| *gap.start())
+}
+
+/// Returns the number of addresses in `space` that are not blocked.
+fn count_allowed(blocked: &RangeSet, space: RangeInclusive<u64>) -> u64 {
+    blocked.gaps(space).map(|gap| gap.end() - gap.start() + 1).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex_both() {
+        let blocked = parse_blacklist(&["5-8", "0-2", "4-7"]).unwrap();
+        assert_eq!(Some(3), lowest_allowed(&blocked, 0..=9));
+        assert_eq!(2, count_allowed(&blocked, 0..=9));
+    }
+
+    #[test]
+    fn full_space() {
+        let blocked = parse_blacklist(&["0-4294967294"]).unwrap();
+        assert_eq!(1, count_allowed(&blocked, IP_SPACE));
+        assert_eq!(Some(u64::from(u32::MAX)), lowest_allowed(&blocked, IP_SPACE));
+
+        let blocked = parse_blacklist(&["0-4294967295"]).unwrap();
+        assert_eq!(0, count_allowed(&blocked, IP_SPACE));
+        assert_eq!(None, lowest_allowed(&blocked, IP_SPACE));
+    }
+
+    #[test]
+    fn reject_bad_ranges() {
+        assert!(parse_blacklist(&["5"]).is_err());
+        assert!(parse_blacklist(&["8-5"]).is_err());
+        assert!(parse_blacklist(&["0-4294967296"]).is_err());
+    }
+}
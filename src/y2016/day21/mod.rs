@@ -9,30 +9,24 @@ const BYTES_TO_SCRAMBLE: &[u8; 8] = b"abcdefgh";
 const BYTES_TO_UNSCRAMBLE: &[u8; 8] = b"fbgdceah";
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input: Vec<scrambler::ScrambleRule> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+    let input: Vec<scrambler::ScrambleRule> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     solve_parts! {
         1 => {
             let mut word = scrambler::WordScrambler::new(BYTES_TO_SCRAMBLE);
-            for rule in input.iter() {
-                word.apply_rule(rule).expect("failed to apply rule");
-            }
-            word.into_word()
+            word.apply_all(&input).last().unwrap_or_else(|| Ok(word.to_string()))?
         },
         2 => {
             let mut word = scrambler::WordScrambler::new(BYTES_TO_UNSCRAMBLE);
             for rule in input.iter().rev() {
-                word.reverse_rule(rule).expect("failed to apply rule");
+                word.reverse_rule(rule)?;
             }
             word.into_word()
         }
     }
 }
 
-mod scrambler {
+pub(crate) mod scrambler {
     use crate::common::util::RotateSigned;
     use std::error::Error;
     use std::{fmt, str};
@@ -46,16 +40,17 @@ mod scrambler {
 
     impl Error for ScrambleRuleParseError {
         fn description(&self) -> &str {
-            &self.reason
+            self.reason
         }
     }
 
     impl fmt::Display for ScrambleRuleParseError {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            writeln!(f, "Failed to parse rule: {}", self.reason)
+            write!(f, "Failed to parse rule `{}`: {}", self.rule, self.reason)
         }
     }
 
+    #[derive(Debug, Clone, Eq, PartialEq)]
     /// A rule describing how to scramble the bytes within a word.
     pub enum ScrambleRule {
         SwapPos(usize, usize),
@@ -68,12 +63,10 @@ mod scrambler {
 
     impl ScrambleRule {
         pub fn is_own_reverse(&self) -> bool {
-            match *self {
-                ScrambleRule::SwapPos(..)
-                | ScrambleRule::SwapLet(..)
-                | ScrambleRule::RevRange { .. } => true,
-                _ => false,
-            }
+            matches!(
+                *self,
+                ScrambleRule::SwapPos(..) | ScrambleRule::SwapLet(..) | ScrambleRule::RevRange { .. }
+            )
         }
     }
 
@@ -81,80 +74,122 @@ mod scrambler {
         type Err = ScrambleRuleParseError;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let bytes = s.as_bytes();
-            // Match rules by unique columns; assumes correct formatting
-            Ok(match bytes[7] {
-                b's' => match bytes[0] {
-                    b's' => ScrambleRule::SwapPos(
-                        s[14..15].parse()
-                            .map_err(|_| ScrambleRuleParseError {
-                                rule: s.to_owned(),
-                                reason: "malformed swap index one",
-                            })?,
-                        s[30..31].parse()
-                            .map_err(|_| ScrambleRuleParseError {
-                                rule: s.to_owned(),
-                                reason: "malformed swap index two",
-                            })?,
-                    ),
-                    b'm' => ScrambleRule::Move {
-                        target: s[14..15].parse()
-                            .map_err(|_| ScrambleRuleParseError {
-                                rule: s.to_owned(),
-                                reason: "malformed move target",
-                            })?,
-                        dest: s[28..29].parse()
-                            .map_err(|_| ScrambleRuleParseError {
-                                rule: s.to_owned(),
-                                reason: "malformed move dest",
-                            })?,
-                    },
-                    _ => return Err(ScrambleRuleParseError {
-                        rule: s.to_owned(),
-                        reason: "unknown scramble rule",
-                    })
-                },
-                b't' => ScrambleRule::SwapLet(bytes[12], bytes[26]),
-                b'l' => ScrambleRule::RotByPos {
-                    mag: -s[12..13].parse()
-                        .map_err(|_| ScrambleRuleParseError {
-                            rule: s.to_owned(),
-                            reason: "malformed left rot mag",
-                        })?,
-                },
-                b'r' => ScrambleRule::RotByPos {
-                    mag: s[13..14].parse()
-                        .map_err(|_| ScrambleRuleParseError {
-                            rule: s.to_owned(),
-                            reason: "malformed right rot mag",
-                        })?,
+            let err = |reason| ScrambleRuleParseError {
+                rule: s.to_owned(),
+                reason,
+            };
+            let pos = |token: &str, reason| token.parse::<usize>().map_err(|_| err(reason));
+            let letter = |token: &str, reason| match token.as_bytes() {
+                &[b] => Ok(b),
+                _ => Err(err(reason)),
+            };
+
+            let tokens: Vec<&str> = s.split_whitespace().collect();
+            Ok(match tokens[..] {
+                ["swap", "position", one, "with", "position", two] => ScrambleRule::SwapPos(
+                    pos(one, "malformed swap index one")?,
+                    pos(two, "malformed swap index two")?,
+                ),
+                ["swap", "letter", one, "with", "letter", two] => ScrambleRule::SwapLet(
+                    letter(one, "malformed swap letter one")?,
+                    letter(two, "malformed swap letter two")?,
+                ),
+                ["rotate", dir @ "left", mag, "step"]
+                | ["rotate", dir @ "left", mag, "steps"]
+                | ["rotate", dir @ "right", mag, "step"]
+                | ["rotate", dir @ "right", mag, "steps"] => {
+                    let mag = mag
+                        .parse::<isize>()
+                        .ok()
+                        .and_then(|mag| if dir == "left" { mag.checked_neg() } else { Some(mag) })
+                        .ok_or_else(|| err("malformed rot magnitude"))?;
+                    ScrambleRule::RotByPos { mag }
+                }
+                ["rotate", "based", "on", "position", "of", "letter", det] => {
+                    ScrambleRule::RotByLet {
+                        det: letter(det, "malformed rot letter")?,
+                    }
+                }
+                ["reverse", "positions", start, "through", end] => ScrambleRule::RevRange {
+                    start: pos(start, "malformed rev range start")?,
+                    end: pos(end, "malformed rev range end")?,
                 },
-                b'b' => ScrambleRule::RotByLet { det: bytes[35] },
-                b' ' => ScrambleRule::RevRange {
-                    start: s[18..19].parse()
-                        .map_err(|_| ScrambleRuleParseError {
-                            rule: s.to_owned(),
-                            reason: "malformed rev range start",
-                        })?,
-                    end: s[28..29].parse()
-                        .map_err(|_| ScrambleRuleParseError {
-                            rule: s.to_owned(),
-                            reason: "malformed rev range end",
-                        })?,
+                ["move", "position", target, "to", "position", dest] => ScrambleRule::Move {
+                    target: pos(target, "malformed move target")?,
+                    dest: pos(dest, "malformed move dest")?,
                 },
-                _ => return Err(ScrambleRuleParseError {
-                    rule: s.to_owned(),
-                    reason: "unknown scramble rule",
-                })
+                _ => return Err(err("unknown scramble rule")),
             })
         }
     }
 
+    impl fmt::Display for ScrambleRule {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match *self {
+                ScrambleRule::SwapPos(one, two) => {
+                    write!(f, "swap position {} with position {}", one, two)
+                }
+                ScrambleRule::SwapLet(one, two) => {
+                    write!(f, "swap letter {} with letter {}", one as char, two as char)
+                }
+                ScrambleRule::RotByPos { mag } => write!(
+                    f,
+                    "rotate {} {} step{}",
+                    if mag < 0 { "left" } else { "right" },
+                    mag.abs(),
+                    if mag.abs() == 1 { "" } else { "s" },
+                ),
+                ScrambleRule::RotByLet { det } => {
+                    write!(f, "rotate based on position of letter {}", det as char)
+                }
+                ScrambleRule::RevRange { start, end } => {
+                    write!(f, "reverse positions {} through {}", start, end)
+                }
+                ScrambleRule::Move { target, dest } => {
+                    write!(f, "move position {} to position {}", target, dest)
+                }
+            }
+        }
+    }
+
     /// Scrambles the bytes in a word.
     pub struct WordScrambler {
         word_bytes: Vec<u8>,
     }
 
+    /// Iterator over the intermediate words produced while applying a
+    /// sequence of rules to a `WordScrambler`.
+    pub struct ApplyAll<'a, I> {
+        scrambler: &'a mut WordScrambler,
+        rules: I,
+        /// Whether a rule has failed to apply.
+        failed: bool,
+    }
+
+    impl<'a, I> Iterator for ApplyAll<'a, I> where I: Iterator<Item = &'a ScrambleRule> {
+        type Item = Result<String, &'static str>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.failed {
+                return None;
+            }
+            let rule = self.rules.next()?;
+            Some(match self.scrambler.apply_rule(rule) {
+                Ok(()) => Ok(self.scrambler.to_string()),
+                Err(e) => {
+                    self.failed = true;
+                    Err(e)
+                }
+            })
+        }
+    }
+
+    impl fmt::Display for WordScrambler {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&String::from_utf8_lossy(&self.word_bytes))
+        }
+    }
+
     impl WordScrambler {
         /// Builds a new word scrambler for the specified word.
         pub fn new(word: &[u8]) -> Self {
@@ -165,18 +200,29 @@ mod scrambler {
 
         /// Scrambles this scrambler's word according to the specified
         /// rule.
+        ///
+        /// An error is returned if the rule refers to a position or letter
+        /// that is not in the word.
         pub fn apply_rule(
             &mut self,
             rule: &ScrambleRule,
         ) -> Result<(), &'static str> {
+            self.check_positions(rule)?;
             match *rule {
                 ScrambleRule::SwapPos(one, two) => self.word_bytes.swap(one, two),
                 ScrambleRule::SwapLet(let_one, let_two) => {
-                    let one = self.index_of(let_one)
-                        .ok_or("no such letter in word")?;
-                    let two = self.index_of(let_two)
-                        .ok_or("no such letter in word")?;
-                    self.word_bytes.swap(one, two);
+                    if self.index_of(let_one).is_none() || self.index_of(let_two).is_none() {
+                        return Err("no such letter in word");
+                    }
+                    // Every occurrence is exchanged so that the rule remains
+                    // its own reverse for words with repeated letters
+                    for byte in &mut self.word_bytes {
+                        if *byte == let_one {
+                            *byte = let_two;
+                        } else if *byte == let_two {
+                            *byte = let_one;
+                        }
+                    }
                 }
                 ScrambleRule::RotByPos { mag } => self.rotate(mag),
                 ScrambleRule::RotByLet { det } => {
@@ -185,8 +231,12 @@ mod scrambler {
                     mag += if mag >= 4 { 2 } else { 1 };
                     self.rotate(mag);
                 }
-                ScrambleRule::RevRange { start, end } =>
-                    self.word_bytes[start..=end].reverse(),
+                ScrambleRule::RevRange { start, end } => {
+                    if start > end {
+                        return Err("reversed range ends before it starts");
+                    }
+                    self.word_bytes[start..=end].reverse()
+                }
                 ScrambleRule::Move { target, dest } => {
                     let payload = self.word_bytes.remove(target);
                     self.word_bytes.insert(dest, payload);
@@ -195,17 +245,33 @@ mod scrambler {
             Ok(())
         }
 
+        /// Returns an iterator that scrambles this scrambler's word according
+        /// to each of the specified rules in turn, yielding the word after
+        /// each rule is applied.
+        ///
+        /// If a rule cannot be applied, its error is yielded and iteration
+        /// ends.
+        pub fn apply_all<'a, I>(&'a mut self, rules: I) -> ApplyAll<'a, I::IntoIter>
+            where I: IntoIterator<Item = &'a ScrambleRule>
+        {
+            ApplyAll { scrambler: self, rules: rules.into_iter(), failed: false }
+        }
+
         /// Reverses prior scrambling to this scrambler's word
         /// according to the specified rule.
+        ///
+        /// An error is returned if the rule refers to a position or letter
+        /// that is not in the word, or if the rule cannot be reversed
+        /// unambiguously.
         pub fn reverse_rule(&mut self, rule: &ScrambleRule) -> Result<(), &'static str> {
             if rule.is_own_reverse() {
                 self.apply_rule(rule)?;
             } else {
+                self.check_positions(rule)?;
                 match *rule {
                     ScrambleRule::RotByPos { mag } => self.rotate(-mag),
                     ScrambleRule::RotByLet { det } => {
-                        let pos = self.index_of(det).ok_or("no such letter in word")?;
-                        let mag = self.find_prior_let_rot(pos);
+                        let mag = self.find_prior_let_rot(det)?;
                         self.rotate(mag)
                     }
                     ScrambleRule::Move { target, dest } => {
@@ -242,22 +308,48 @@ mod scrambler {
             self.word_bytes.rotate_signed(mag)
         }
 
+        /// Returns an error if the specified rule refers to a position
+        /// outside of this scrambler's word.
+        fn check_positions(&self, rule: &ScrambleRule) -> Result<(), &'static str> {
+            let in_word = |pos: usize| pos < self.word_bytes.len();
+            let valid = match *rule {
+                ScrambleRule::SwapPos(one, two) => in_word(one) && in_word(two),
+                ScrambleRule::RevRange { start, end } => in_word(start) && in_word(end),
+                ScrambleRule::Move { target, dest } => in_word(target) && in_word(dest),
+                _ => true,
+            };
+            if valid { Ok(()) } else { Err("rule position is outside of the word") }
+        }
+
         /// Determines the rotation required to restore a rotation
         /// by letter scramble.
         ///
-        /// *NOTE*: Short words cannot be recovered as multiple
-        /// indexes may produce the same rotation. For example,
-        /// with a 5-byte word, a rotation about a letter at either
-        /// index `1` or index `4` will result in the byte landing
-        /// at index `2`.
-        fn find_prior_let_rot(&self, cur_pos: usize) -> isize {
-            let len = self.word_bytes.len();
-            for i in 0..len {
-                if (2 * i + if i >= 4 { 2 } else { 1 }) % len == cur_pos {
-                    return i as isize - cur_pos as isize;
-                }
+        /// Every rotation of the current word is tried, and those that
+        /// scramble back into the current word are kept. For some words
+        /// (notably short ones), multiple prior words may produce the
+        /// same rotation. For example, with a 5-byte word, a rotation
+        /// about a letter at either index `1` or index `4` will result
+        /// in the byte landing at index `2`. An error is returned in
+        /// that case, since the prior word cannot be determined.
+        fn find_prior_let_rot(&self, det: u8) -> Result<isize, &'static str> {
+            let rule = ScrambleRule::RotByLet { det };
+            let mut priors = (0..self.word_bytes.len() as isize)
+                .map(|mag| {
+                    let mut prior = WordScrambler::new(&self.word_bytes);
+                    prior.rotate(mag);
+                    (mag, prior)
+                })
+                .filter(|(_, prior)| {
+                    let mut scrambled = WordScrambler::new(&prior.word_bytes);
+                    scrambled.apply_rule(&rule).is_ok() && scrambled.word_bytes == self.word_bytes
+                });
+
+            let (mag, first) = priors.next().ok_or("no such letter in word")?;
+            // Distinct rotations only agree if the word has repeated letters
+            if priors.any(|(_, other)| other.word_bytes != first.word_bytes) {
+                return Err("letter rotation cannot be reversed unambiguously");
             }
-            panic!("impossible letter pos")
+            Ok(mag)
         }
     }
 
@@ -320,16 +412,150 @@ mod scrambler {
                 _ => panic!("failed to parse left rot"),
             }
         }
+
+        #[test]
+        fn parse_multi_digit_rules() {
+            match "swap position 12 with position 103".parse().unwrap() {
+                ScrambleRule::SwapPos(one, two) => {
+                    assert_eq!(12, one);
+                    assert_eq!(103, two);
+                }
+                _ => panic!("failed to parse position swap"),
+            }
+
+            match "rotate left  15 steps".parse().unwrap() {
+                ScrambleRule::RotByPos { mag } => {
+                    assert_eq!(-15, mag);
+                }
+                _ => panic!("failed to parse left rot"),
+            }
+
+            match "  reverse positions 10 through 24 ".parse().unwrap() {
+                ScrambleRule::RevRange { start, end } => {
+                    assert_eq!(10, start);
+                    assert_eq!(24, end);
+                }
+                _ => panic!("failed to parse reverse range"),
+            }
+
+            match "move position 20 to position 3".parse().unwrap() {
+                ScrambleRule::Move { target, dest } => {
+                    assert_eq!(20, target);
+                    assert_eq!(3, dest);
+                }
+                _ => panic!("failed to parse move"),
+            }
+        }
+
+        #[test]
+        fn reject_malformed_rules() {
+            for &(rule, reason) in &[
+                ("swap position x with position 1", "malformed swap index one"),
+                ("swap letter ab with letter c", "malformed swap letter one"),
+                ("rotate right many steps", "malformed rot magnitude"),
+                ("reverse positions 1 through", "unknown scramble rule"),
+                ("move position 1 to position -2", "malformed move dest"),
+                ("shuffle everything", "unknown scramble rule"),
+                ("", "unknown scramble rule"),
+            ] {
+                let err = rule.parse::<ScrambleRule>().err().unwrap();
+                assert_eq!(reason, err.reason);
+            }
+
+            let rule = format!("rotate left {} steps", isize::MIN);
+            let err = rule.parse::<ScrambleRule>().err().unwrap();
+            assert_eq!("malformed rot magnitude", err.reason);
+        }
+
+        mod properties {
+            use super::*;
+            use proptest::collection::vec;
+            use proptest::prelude::*;
+            use proptest::sample::select;
+
+            /// Strategy generating arbitrary rules over positions below `len`
+            /// and the specified letters.
+            fn rule(len: usize, letters: Vec<u8>) -> impl Strategy<Value=ScrambleRule> {
+                let letter = select(letters);
+                prop_oneof![
+                    (0..len, 0..len).prop_map(|(one, two)| ScrambleRule::SwapPos(one, two)),
+                    (letter.clone(), letter.clone())
+                        .prop_map(|(one, two)| ScrambleRule::SwapLet(one, two)),
+                    (-20..=20_isize).prop_map(|mag| ScrambleRule::RotByPos { mag }),
+                    letter.prop_map(|det| ScrambleRule::RotByLet { det }),
+                    (0..len, 0..len).prop_map(|(one, two)| ScrambleRule::RevRange {
+                        start: one.min(two),
+                        end: one.max(two),
+                    }),
+                    (0..len, 0..len).prop_map(|(target, dest)| ScrambleRule::Move { target, dest }),
+                ]
+            }
+
+            /// Strategy generating a word over a small alphabet, so that
+            /// repeated letters are common, along with a sequence of rules
+            /// that may be applied to it.
+            fn word_and_rules() -> impl Strategy<Value=(Vec<u8>, Vec<ScrambleRule>)> {
+                vec(b'a'..=b'f', 1..10).prop_flat_map(|word| {
+                    let rules = vec(rule(word.len(), word.clone()), 0..20);
+                    (Just(word), rules)
+                })
+            }
+
+            proptest! {
+                #[test]
+                fn format_parse_round_trip(rule in rule(1000, (b'a'..=b'z').collect())) {
+                    prop_assert_eq!(&rule, &rule.to_string().parse::<ScrambleRule>().unwrap());
+                }
+
+                #[test]
+                fn unscramble_restores_word((word, rules) in word_and_rules()) {
+                    let mut scrambler = WordScrambler::new(&word);
+                    for rule in &rules {
+                        scrambler.apply_rule(rule).unwrap();
+                    }
+
+                    for rule in rules.iter().rev() {
+                        let before = scrambler.word_bytes.clone();
+                        if scrambler.reverse_rule(rule).is_err() {
+                            // Only letter rotations may be ambiguous, and a
+                            // failed reversal must leave the word untouched
+                            let is_let_rot = matches!(rule, ScrambleRule::RotByLet { .. });
+                            prop_assert!(is_let_rot, "`{}` could not be reversed", rule);
+                            prop_assert_eq!(before, scrambler.word_bytes);
+                            return Ok(());
+                        }
+                    }
+                    prop_assert_eq!(word, scrambler.word_bytes);
+                }
+
+                #[test]
+                fn reversal_never_wrong(word in vec(b'a'..=b'f', 1..10), index in any::<usize>()) {
+                    // Each rotation by a letter of the word must either
+                    // reverse exactly or report ambiguity
+                    let rule = ScrambleRule::RotByLet { det: word[index % word.len()] };
+                    let mut scrambler = WordScrambler::new(&word);
+                    scrambler.apply_rule(&rule).unwrap();
+                    if scrambler.reverse_rule(&rule).is_ok() {
+                        let mut rescrambled = WordScrambler::new(&scrambler.word_bytes);
+                        rescrambled.apply_rule(&rule).unwrap();
+                        let mut expected = WordScrambler::new(&word);
+                        expected.apply_rule(&rule).unwrap();
+                        prop_assert_eq!(expected.word_bytes, rescrambled.word_bytes);
+                    }
+                }
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::testing::parse_lines;
 
     #[test]
     fn solution() {
-        assert_solution!("fdhbcgea", "egfbcadh", Pz::new(2016, 21));
+        assert_solution!(Pz::new(2016, 21));
     }
 
     #[test]
@@ -353,6 +579,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn apply_all_steps() {
+        let rules: [(&'static str, &'static str); 8] = [
+            ("swap position 4 with position 0", "ebcda"),
+            ("swap letter d with letter b", "edcba"),
+            ("reverse positions 0 through 4", "abcde"),
+            ("rotate left 1 step", "bcdea"),
+            ("move position 1 to position 4", "bdeac"),
+            ("move position 3 to position 0", "abdec"),
+            ("rotate based on position of letter b", "ecabd"),
+            ("rotate based on position of letter d", "decab"),
+        ];
+        let parsed: Vec<scrambler::ScrambleRule> = parse_lines(rules.iter().map(|(rule, _)| rule));
+
+        let mut word = scrambler::WordScrambler::new(b"abcde");
+        assert_eq!("abcde", word.to_string());
+
+        let steps: Vec<String> = word.apply_all(&parsed).collect::<Result<_, _>>().unwrap();
+        let expected: Vec<&str> = rules.iter().map(|&(_, result)| result).collect();
+        assert_eq!(expected, steps);
+        assert_eq!("decab", word.to_string());
+    }
+
     #[test]
     fn ex2() {
         let rules: [&'static str; 14] = [
@@ -384,4 +633,70 @@ mod tests {
             assert_eq!(WORD, scrambler.word_bytes());
         }
     }
+
+    #[test]
+    fn reverse_any_length() {
+        const LETTERS: &[u8] = b"abcdefghijkl";
+
+        for len in 1..=LETTERS.len() {
+            let word = &LETTERS[..len];
+            for &letter in word {
+                let rule = scrambler::ScrambleRule::RotByLet { det: letter };
+                let mut scrambler = scrambler::WordScrambler::new(word);
+                scrambler.apply_rule(&rule).unwrap();
+                // Reversal may be ambiguous, but must never be wrong
+                if scrambler.reverse_rule(&rule).is_ok() {
+                    assert_eq!(word, scrambler.word_bytes());
+                } else {
+                    assert!(len != 1 && len != 3 && len != 8);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_ambiguous() {
+        // Both "abcde" (c at index 2) and "deabc" (c at index 4) rotate
+        // into "cdeab".
+        let mut scrambler = scrambler::WordScrambler::new(b"cdeab");
+        let rule = "rotate based on position of letter c".parse().unwrap();
+        assert!(scrambler.reverse_rule(&rule).is_err());
+        assert_eq!(b"cdeab", scrambler.word_bytes());
+
+        // Repeated letters may still permit a unique prior word
+        let mut scrambler = scrambler::WordScrambler::new(b"aaaa");
+        let rule = "rotate based on position of letter a".parse().unwrap();
+        scrambler.reverse_rule(&rule).unwrap();
+        assert_eq!(b"aaaa", scrambler.word_bytes());
+    }
+
+    #[test]
+    fn apply_all_stops_on_error() {
+        let rules: Vec<scrambler::ScrambleRule> = parse_lines(&[
+            "rotate left 1 step",
+            "swap position 0 with position 9",
+            "rotate left 1 step",
+        ]);
+
+        let mut word = scrambler::WordScrambler::new(b"abc");
+        let steps: Vec<_> = word.apply_all(&rules).collect();
+        assert_eq!(2, steps.len());
+        assert_eq!(Ok("bca".to_owned()), steps[0]);
+        assert!(steps[1].is_err());
+    }
+
+    #[test]
+    fn reject_bad_positions() {
+        let mut scrambler = scrambler::WordScrambler::new(b"abc");
+        for rule in &[
+            "swap position 0 with position 3",
+            "reverse positions 1 through 5",
+            "move position 4 to position 0",
+            "swap letter a with letter z",
+        ] {
+            assert!(scrambler.apply_rule(&rule.parse().unwrap()).is_err());
+            assert!(scrambler.reverse_rule(&rule.parse().unwrap()).is_err());
+            assert_eq!(b"abc", scrambler.word_bytes());
+        }
+    }
 }
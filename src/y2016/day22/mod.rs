@@ -0,0 +1,239 @@
+//! Solution for 2016 Day 22
+//!
+//! Part two is solved under the same assumptions as the puzzle's example:
+//! all data fits on every node except for a few very large "wall" nodes,
+//! so data can only be shuffled by moving it into the single empty node.
+//! This turns the grid into a sliding puzzle in which the empty node is
+//! first walked up to the goal data, after which each step of the goal data
+//! towards the origin costs five moves.
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz, Solution};
+use crate::common::util::{extract_ints, Pt};
+use crate::common::util::search::breadth_first;
+
+use std::fmt;
+
+/// A position in the storage grid.
+type Pos = Pt<i32>;
+
+/// A number of terabytes.
+type Terabytes = u32;
+
+/// The number of moves needed to advance the goal data one node towards the
+/// origin once the empty node is directly ahead of it: four moves to bring
+/// the empty node back around in front of the data, plus one to move the
+/// data into it.
+const MOVES_PER_ADVANCE: usize = 5;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A storage node's disk usage.
+struct Node {
+    size: Terabytes,
+    used: Terabytes,
+}
+
+impl Node {
+    /// Returns the amount of free space on this node.
+    fn avail(&self) -> Terabytes {
+        self.size - self.used
+    }
+}
+
+/// A rectangular grid of storage nodes.
+struct StorageGrid {
+    width: usize,
+    height: usize,
+    /// Nodes in row-major order.
+    nodes: Vec<Node>,
+}
+
+impl StorageGrid {
+    /// Parses a `df`-style listing of every node in the grid. Lines that do
+    /// not describe a node, such as the command and header, are skipped.
+    fn parse<T>(lines: &[T]) -> Result<Self, String>
+        where T: AsRef<str>
+    {
+        let mut entries = Vec::with_capacity(lines.len());
+        for line in lines.iter().map(AsRef::as_ref) {
+            if !line.starts_with("/dev/grid/node-") {
+                continue;
+            }
+            match *extract_ints::<u32>(line).collect::<Vec<_>>() {
+                [x, y, size, used, avail, _] if used + avail == size => {
+                    entries.push(((x as usize, y as usize), Node { size, used }))
+                }
+                _ => return Err(format!("malformed node listing `{}`", line)),
+            }
+        }
+
+        let width = entries.iter().map(|&((x, _), _)| x + 1).max().unwrap_or(0);
+        let height = entries.iter().map(|&((_, y), _)| y + 1).max().unwrap_or(0);
+        let mut nodes = vec![None; width * height];
+        for ((x, y), node) in entries {
+            if nodes[y * width + x].replace(node).is_some() {
+                return Err(format!("duplicate listing for node x{}-y{}", x, y));
+            }
+        }
+        let nodes = nodes.into_iter().collect::<Option<_>>()
+            .ok_or("node listing does not describe a complete grid")?;
+
+        Ok(StorageGrid { width, height, nodes })
+    }
+
+    /// Returns the node at the specified position, or `None` if the position
+    /// is outside of the grid.
+    fn node(&self, pos: Pos) -> Option<&Node> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return None;
+        }
+        self.nodes.get(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Returns the position of every node in row-major order.
+    fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| Pt { x: x as i32, y: y as i32 })
+        })
+    }
+
+    /// Returns the number of viable pairs of nodes: pairs of distinct nodes
+    /// `(a, b)` such that `a` is not empty and its data would fit on `b`.
+    fn viable_pairs(&self) -> usize {
+        self.nodes.iter().enumerate()
+            .filter(|(_, a)| a.used != 0)
+            .map(|(i, a)| {
+                self.nodes.iter().enumerate()
+                    .filter(|&(j, b)| i != j && a.used <= b.avail())
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Returns the position of the only empty node.
+    fn empty_node(&self) -> Result<Pos, &'static str> {
+        let mut empty = self.positions().filter(|&pos| self.node(pos).unwrap().used == 0);
+        match (empty.next(), empty.next()) {
+            (Some(pos), None) => Ok(pos),
+            (None, _) => Err("grid has no empty node"),
+            _ => Err("grid has more than one empty node"),
+        }
+    }
+
+    /// Returns the position of the data that must be brought to the origin.
+    fn goal_data(&self) -> Pos {
+        Pt { x: self.width as i32 - 1, y: 0 }
+    }
+
+    /// Returns true if the data on the node at `pos` can never be moved,
+    /// since it would not fit on the empty node.
+    fn is_wall(&self, pos: Pos, empty: &Node) -> bool {
+        self.node(pos).is_none_or(|node| node.used > empty.size)
+    }
+
+    /// Returns the fewest moves needed to bring the goal data to the origin.
+    fn min_moves_to_goal(&self) -> Result<usize, &'static str> {
+        let empty_pos = self.empty_node()?;
+        let empty = *self.node(empty_pos).unwrap();
+        let goal = self.goal_data();
+        if goal.x == 0 {
+            return Ok(0);
+        }
+        // The node directly ahead of the goal data
+        let ahead = goal + Pt::w();
+
+        let (_, hole_dist) = breadth_first(empty_pos, |&pos| {
+            pos.nb_card().into_iter()
+                .filter(|&nb| nb != goal && !self.is_wall(nb, &empty))
+                .collect::<Vec<_>>()
+        })
+            .find(|&(pos, _)| pos == ahead)
+            .ok_or("empty node cannot reach the goal data")?;
+
+        // Move the empty node ahead of the data, swap them, then advance the
+        // data along the remaining row
+        Ok(hole_dist + 1 + MOVES_PER_ADVANCE * ahead.x as usize)
+    }
+}
+
+impl fmt::Display for StorageGrid {
+    /// Renders the grid using the puzzle's notation: `_` for the empty node,
+    /// `#` for walls, `G` for the goal data and `.` for all other nodes.
+    /// The origin is wrapped in parentheses.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let empty = self.empty_node().ok().and_then(|pos| self.node(pos));
+        for pos in self.positions() {
+            let node = self.node(pos).unwrap();
+            let c = if pos == self.goal_data() {
+                'G'
+            } else if node.used == 0 {
+                '_'
+            } else if empty.is_some_and(|empty| self.is_wall(pos, empty)) {
+                '#'
+            } else {
+                '.'
+            };
+            if pos == Pt::origin() {
+                write!(f, "({})", c)?;
+            } else {
+                write!(f, " {} ", c)?;
+            }
+            if pos.x as usize + 1 == self.width {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let grid = StorageGrid::parse(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => grid.viable_pairs(),
+        2 => grid.min_moves_to_goal()?
+    }.map(|solution: Solution| if puzzle.visualize() {
+        solution.with_artifact(2, grid.to_string())
+    } else {
+        solution
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: [&str; 10] = [
+        "Filesystem            Size  Used  Avail  Use%",
+        "/dev/grid/node-x0-y0   10T    8T     2T   80%",
+        "/dev/grid/node-x0-y1   11T    6T     5T   54%",
+        "/dev/grid/node-x0-y2   32T   28T     4T   87%",
+        "/dev/grid/node-x1-y0    9T    7T     2T   77%",
+        "/dev/grid/node-x1-y1    8T    0T     8T    0%",
+        "/dev/grid/node-x1-y2   11T    7T     4T   63%",
+        "/dev/grid/node-x2-y0   10T    6T     4T   60%",
+        "/dev/grid/node-x2-y1    9T    8T     1T   88%",
+        "/dev/grid/node-x2-y2    9T    6T     3T   66%",
+    ];
+
+    #[test]
+    fn ex_both() {
+        let grid = StorageGrid::parse(&EXAMPLE_INPUT).unwrap();
+        assert_eq!((3, 3), (grid.width, grid.height));
+        assert_eq!(7, grid.viable_pairs());
+        assert_eq!(Ok(7), grid.min_moves_to_goal());
+        assert_eq!("(.) .  G \n .  _  . \n #  .  . \n", grid.to_string());
+    }
+
+    #[test]
+    fn reject_bad_listings() {
+        assert!(StorageGrid::parse(&["/dev/grid/node-x0-y0 10T 8T"]).is_err());
+        assert!(StorageGrid::parse(&["/dev/grid/node-x0-y0 10T 8T 3T 80%"]).is_err());
+        assert!(StorageGrid::parse(&[
+            "/dev/grid/node-x0-y0 10T 8T 2T 80%",
+            "/dev/grid/node-x1-y1 10T 8T 2T 80%",
+        ]).is_err());
+
+        let full = StorageGrid::parse(&["/dev/grid/node-x0-y0 10T 8T 2T 80%"]).unwrap();
+        assert!(full.min_moves_to_goal().is_err());
+    }
+}
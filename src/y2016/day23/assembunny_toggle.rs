@@ -4,7 +4,7 @@
 //! forms of instructions.
 
 use std::str::FromStr;
-use crate::y2016::day12::assembunny::{
+use crate::common::vm::assembunny::{
     Instr,
     ValueToken,
     RegisterKey,
@@ -13,7 +13,7 @@ use crate::y2016::day12::assembunny::{
     Register,
 };
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub enum InstrWrapper {
     /// An Assembunny Instruction
     Instr(Instr),
@@ -55,19 +55,38 @@ impl FromStr for InstrWrapper {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.parse() {
             Ok(instr) => Ok(InstrWrapper::Instr(instr)),
-            Err(e) => match &s[..3] {
-                "tgl" => Ok(InstrWrapper::Toggle(s[4..5].parse()?)),
+            Err(e) => match *s.split_whitespace().collect::<Vec<_>>() {
+                ["tgl", reg] => Ok(InstrWrapper::Toggle(reg.parse()?)),
                 _ => Err(e)
             }
         }
     }
 }
 
+#[derive(Debug, Copy, Clone)]
+/// A record of a `tgl` instruction modifying the program.
+pub struct ToggleRecord {
+    /// The number of instructions that had been executed before the toggle.
+    pub step: u64,
+    /// The position of the `tgl` instruction.
+    pub source: usize,
+    /// The position of the toggled instruction.
+    pub target: usize,
+    /// The instruction before it was toggled.
+    pub before: InstrWrapper,
+    /// The instruction after it was toggled.
+    pub after: InstrWrapper,
+}
+
 /// Interpreter for toggleable assembunny instructions.
 pub struct Interpreter {
     prog: Vec<InstrWrapper>,
     pos: usize,
     reg: MiniRegisterTable,
+    /// The number of instructions executed so far.
+    steps: u64,
+    /// The modifications made to the program by `tgl` instructions.
+    toggle_log: Vec<ToggleRecord>,
 }
 
 impl Interpreter {
@@ -77,11 +96,18 @@ impl Interpreter {
             prog,
             pos: 0,
             reg: MiniRegisterTable::default(),
+            steps: 0,
+            toggle_log: Vec::new(),
         }
     }
 
     /// Execute the instruction at this interpreters read position and
     /// increments/decrement the position accordingly.
+    ///
+    /// Jumping to a position before the start of the program halts the
+    /// interpreter.
+    ///
+    /// Panics if this interpreter is done.
     pub fn execute_next(&mut self) {
         let mut step = 1_isize;
         let instr = self.prog[self.pos];
@@ -97,15 +123,41 @@ impl Interpreter {
                 }
             }
             InstrWrapper::Toggle(index) => {
-                let instr_pos = self.pos + self.reg[&index] as usize;
-                if instr_pos < self.prog.len() {
-                    self.prog[instr_pos] = self.prog[instr_pos].into_toggled();
+                // Toggles targeting instructions outside of the program
+                // do nothing
+                if let Some(target) = self.offset_pos(self.reg[&index] as isize) {
+                    let before = self.prog[target];
+                    let after = before.into_toggled();
+                    self.prog[target] = after;
+                    self.toggle_log.push(ToggleRecord {
+                        step: self.steps,
+                        source: self.pos,
+                        target,
+                        before,
+                        after,
+                    });
                 }
             }
             InstrWrapper::MalformedCopy(..) => () // do nothing
         }
 
-        self.pos = (self.pos as isize + step) as usize;
+        self.steps += 1;
+        self.pos = self.offset_pos(step).unwrap_or(self.prog.len());
+    }
+
+    /// Returns the position `offset` instructions away from the current
+    /// position, or `None` if that position is outside of the program.
+    fn offset_pos(&self, offset: isize) -> Option<usize> {
+        (self.pos as isize)
+            .checked_add(offset)
+            .filter(|&pos| pos >= 0 && (pos as usize) < self.prog.len())
+            .map(|pos| pos as usize)
+    }
+
+    /// Returns the modifications made to the program by `tgl` instructions
+    /// so far, in the order that they occurred.
+    pub fn toggle_log(&self) -> &[ToggleRecord] {
+        &self.toggle_log
     }
 
     /// Returns true if this interpreter has finished executing its
@@ -140,6 +192,7 @@ impl Interpreter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::testing::parse_lines;
 
     #[test]
     fn parse_instr() {
@@ -160,6 +213,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reject_malformed_toggle() {
+        for s in &["", "tg", "tgl", "tgl ", "tgl e", "tgl a b"] {
+            assert!(s.parse::<InstrWrapper>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn toggle_out_of_range() {
+        let prog = parse_lines(&["cpy -5 a", "tgl a", "cpy 9 a", "tgl a", "inc b"]);
+        let mut interp = Interpreter::new(prog);
+
+        while !interp.done() {
+            interp.execute_next();
+        }
+
+        assert_eq!(1, interp.registers()[&b'b'.into()]);
+        assert!(interp.toggle_log().is_empty());
+    }
+
+    #[test]
+    fn toggle_log() {
+        let prog = parse_lines(&["cpy 2 a", "tgl a", "dec b", "inc c"]);
+        let mut interp = Interpreter::new(prog);
+
+        while !interp.done() {
+            interp.execute_next();
+        }
+
+        assert_eq!(1, interp.toggle_log().len());
+        let record = interp.toggle_log()[0];
+        assert_eq!((1, 1, 3), (record.step, record.source, record.target));
+        match (record.before, record.after) {
+            (InstrWrapper::Instr(Instr::Inc(_)), InstrWrapper::Instr(Instr::Dec(reg))) => {
+                assert_eq!(b'c', reg.key());
+            }
+            _ => panic!("unexpected toggle record {:?}", record)
+        }
+        assert_eq!(-1, interp.registers()[&b'c'.into()]);
+    }
+
     #[test]
     fn toggle_instr() {
         // Inc becomes dec
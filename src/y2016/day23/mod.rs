@@ -3,7 +3,10 @@
 //! Part two take a good two-three minute to run due to my reluctance
 //! to optimize out the addition loops in the assembunny input.
 
-use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::puzzle::{
+    cancel, input as pio, Cancelled, Result as PuzzleResult, Selection as Pz,
+};
+use crate::common::vm::assembunny;
 
 /// Initial value of register `a` in part one.
 const EGG_COUNT_ONE: i32 = 7;
@@ -11,10 +14,13 @@ const EGG_COUNT_ONE: i32 = 7;
 /// Initial value of register `a` in part two.
 const EGG_COUNT_TWO: i32 = 12;
 
+/// Number of instructions executed between checks for cancellation.
+const CANCEL_INTERVAL: u32 = 1 << 16;
+
 pub mod assembunny_toggle;
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input = parse_instructions(&pio::fetch_lines(puzzle)?)?;
+    let input = parse_instructions(&pio::fetch_line_index(puzzle)?.to_vec())?;
 
     solve_parts! {
         1 => {
@@ -23,9 +29,7 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
             );
             interpreter.registers_mut()[&b'a'.into()] = EGG_COUNT_ONE;
 
-            while !interpreter.done() {
-                interpreter.execute_next();
-            }
+            run_to_end(&mut interpreter)?;
             interpreter.registers()[&b'a'.into()]
         },
         2 => {
@@ -34,21 +38,30 @@ pub fn solve(puzzle: &Pz) -> PuzzleResult {
             );
             interpreter.registers_mut()[&b'a'.into()] = EGG_COUNT_TWO;
 
-            while !interpreter.done() {
-                interpreter.execute_next();
-            }
+            run_to_end(&mut interpreter)?;
             interpreter.registers()[&b'a'.into()]
         }
     }
 }
 
+/// Runs the interpreter until it halts, giving up if the run is cancelled.
+fn run_to_end(interpreter: &mut assembunny_toggle::Interpreter) -> Result<(), Cancelled> {
+    let mut executed = 0_u32;
+    while !interpreter.done() {
+        interpreter.execute_next();
+        executed = (executed + 1) % CANCEL_INTERVAL;
+        if executed == 0 {
+            cancel::check()?;
+        }
+    }
+    Ok(())
+}
+
 /// Parses the specified lines into assembunny instructions.
-///
-/// Panics on underlying parse errors.
-fn parse_instructions<T>(lines: &[T]) -> Result<Vec<assembunny_toggle::InstrWrapper>, &'static str>
+fn parse_instructions<T>(lines: &[T]) -> Result<Vec<assembunny_toggle::InstrWrapper>, String>
     where T: AsRef<str>
 {
-    lines.iter().map(|l| l.as_ref().parse()).collect()
+    assembunny::parse_program(lines)
 }
 
 #[cfg(test)]
@@ -59,7 +72,7 @@ mod tests {
     #[ignore]
     // Take a bit over two minutes to run; ignored by default
     fn solution() {
-        assert_solution!(11004, 479_007_564, Pz::new(2016, 23))
+        assert_solution!(Pz::new(2016, 23))
     }
 
     #[test]
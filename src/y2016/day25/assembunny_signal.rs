@@ -0,0 +1,164 @@
+//! # Assembunny With Output
+//!
+//! Expands upon the assembunny instruction set with an `out` instruction
+//! that transmits a value, and provides an interpreter that can be iterated
+//! over to collect the transmitted values.
+
+use std::str::FromStr;
+use crate::common::vm::assembunny::{
+    Instr,
+    ValueToken,
+    MiniRegisterTable,
+    Register,
+};
+
+#[derive(Debug, Copy, Clone)]
+pub enum SignalInstr {
+    /// An Assembunny Instruction
+    Instr(Instr),
+    /// An instruction that transmits a value
+    Out(ValueToken),
+}
+
+impl FromStr for SignalInstr {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(instr) => Ok(SignalInstr::Instr(instr)),
+            Err(e) => match *s.split_whitespace().collect::<Vec<_>>() {
+                ["out", val] => Ok(SignalInstr::Out(val.parse()?)),
+                _ => Err(e)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Interpreter for assembunny instructions that transmit values.
+///
+/// Iterating over the interpreter runs the program until it transmits its
+/// next value. Iteration ends when the program halts.
+pub struct Interpreter<'a> {
+    prog: &'a [SignalInstr],
+    pos: usize,
+    reg: MiniRegisterTable,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Builds a new interpreter around a set of instructions.
+    pub fn new(prog: &'a [SignalInstr]) -> Self {
+        Interpreter {
+            prog,
+            pos: 0,
+            reg: MiniRegisterTable::default(),
+        }
+    }
+
+    /// Execute the instruction at this interpreters read position and
+    /// increments/decrement the position accordingly.
+    ///
+    /// Returns the transmitted value if the instruction was an `out`
+    /// instruction. Jumping to a position before the start of the program
+    /// halts the interpreter.
+    ///
+    /// Panics if this interpreter is done.
+    pub fn execute_next(&mut self) -> Option<Register> {
+        let mut step = 1_isize;
+        let mut output = None;
+
+        match self.prog[self.pos] {
+            SignalInstr::Instr(ref instr) => match *instr {
+                Instr::Inc(ref reg) => self.reg[reg] += 1,
+                Instr::Dec(ref reg) => self.reg[reg] -= 1,
+                Instr::Copy(val, ref reg) => self.reg[reg] = self.token_value(val),
+                Instr::Jnz(cond, mag) => if self.token_value(cond) != 0 {
+                    step = self.token_value(mag) as isize
+                }
+            }
+            SignalInstr::Out(val) => output = Some(self.token_value(val)),
+        }
+
+        self.pos = (self.pos as isize)
+            .checked_add(step)
+            .filter(|&pos| pos >= 0)
+            .map_or(self.prog.len(), |pos| pos as usize);
+        output
+    }
+
+    /// Returns true if this interpreter has finished executing its
+    /// instructions.
+    pub fn done(&self) -> bool {
+        self.pos >= self.prog.len()
+    }
+
+    /// Returns this interpreter's read position.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a read-only reference to this interpreters set of registers.
+    pub fn registers(&self) -> &MiniRegisterTable {
+        &self.reg
+    }
+
+    /// Returns a *mutable* reference to this interpreters set of registers.
+    pub fn registers_mut(&mut self) -> &mut MiniRegisterTable {
+        &mut self.reg
+    }
+
+    /// Returns the value associated with the specified value token.
+    fn token_value(&self, token: ValueToken) -> Register {
+        match token {
+            ValueToken::Literal(lit) => i32::from(lit.value()),
+            ValueToken::Register(ref key) => self.reg[key],
+        }
+    }
+}
+
+impl<'a> Iterator for Interpreter<'a> {
+    type Item = Register;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done() {
+            if let Some(value) = self.execute_next() {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::vm::assembunny::parse_program;
+
+    #[test]
+    fn parse_out() {
+        match "out a".parse() {
+            Ok(SignalInstr::Out(ValueToken::Register(reg))) => assert_eq!(b'a', reg.key()),
+            _ => panic!("failed to parse out instr")
+        }
+        match "out -3".parse() {
+            Ok(SignalInstr::Out(ValueToken::Literal(lit))) => assert_eq!(-3, lit.value()),
+            _ => panic!("failed to parse out instr")
+        }
+        assert!("out".parse::<SignalInstr>().is_err());
+        assert!("out e".parse::<SignalInstr>().is_err());
+        assert!("cpy 1 a".parse::<SignalInstr>().is_ok());
+    }
+
+    #[test]
+    fn output_iterator() {
+        let prog: Vec<SignalInstr> = parse_program(&[
+            "cpy 3 a",
+            "loop: out a",
+            "dec a",
+            "jnz a loop",
+        ]).unwrap();
+
+        let interp = Interpreter::new(&prog);
+        assert_eq!(vec![3, 2, 1], interp.collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,33 @@
+; Repeatedly transmits the bits of `a + 6`, least significant bit first.
+;
+; This mirrors the structure of the puzzle input, with a smaller offset.
+
+        cpy a d
+        cpy 2 c         ; d = a + 2 * 3
+mul:    cpy 3 b
+add:    inc d
+        dec b
+        jnz b add
+        dec c
+        jnz c mul
+restart:
+        cpy d a
+emit:   cpy a b         ; a = b / 2, c = 2 - b % 2
+        cpy 0 a
+half:   cpy 2 c
+sub:    jnz b 2
+        jnz 1 parity
+        dec b
+        dec c
+        jnz c sub
+        inc a
+        jnz 1 half
+parity: cpy 2 b         ; b = 2 - c
+bit:    jnz c 2
+        jnz 1 4
+        dec b
+        dec c
+        jnz 1 bit
+        out b
+        jnz a emit
+        jnz 1 restart
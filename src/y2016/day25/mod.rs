@@ -0,0 +1,101 @@
+//! Solution for 2016 Day 25
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::vm::assembunny::{self, Register};
+
+use std::collections::HashSet;
+
+pub mod assembunny_signal;
+
+use self::assembunny_signal::{Interpreter, SignalInstr};
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input = parse_instructions(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts! {
+        1 => find_clock_seed(&input).ok_or("no initial value produces a clock signal")?
+    }
+}
+
+/// Number of instructions each seed may execute before it is assumed not to
+/// produce a clock signal.
+const STEP_LIMIT: usize = 1_000_000;
+
+/// Parses the specified lines into assembunny instructions.
+fn parse_instructions<T>(lines: &[T]) -> Result<Vec<SignalInstr>, String>
+    where T: AsRef<str>
+{
+    assembunny::parse_program(lines)
+}
+
+/// Returns the lowest positive initial value of register `a` that causes
+/// the program to transmit a clock signal.
+fn find_clock_seed(prog: &[SignalInstr]) -> Option<Register> {
+    (1..=Register::MAX).find(|&seed| transmits_clock_signal(prog, seed))
+}
+
+/// Returns true if the program transmits the infinite signal `0, 1, 0, 1, ...`
+/// when register `a` is initialized to `seed`.
+///
+/// The signal is known to repeat forever once the interpreter transmits from
+/// a state (position and registers) that it has transmitted from before while
+/// expecting the same next value. Programs that have not been found to
+/// repeat within `STEP_LIMIT` instructions are not considered clocks.
+fn transmits_clock_signal(prog: &[SignalInstr], seed: Register) -> bool {
+    let mut interp = Interpreter::new(prog);
+    interp.registers_mut()[&b'a'.into()] = seed;
+
+    let mut seen = HashSet::new();
+    let mut expected = 0;
+
+    for _ in 0..STEP_LIMIT {
+        if interp.done() {
+            // The program halted
+            return false;
+        }
+        let value = match interp.execute_next() {
+            Some(value) => value,
+            None => continue,
+        };
+        if value != expected {
+            return false;
+        }
+        expected = 1 - expected;
+        if !seen.insert((interp.position(), interp.registers().clone(), expected)) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_signal() {
+        let lines: Vec<&str> = include_str!("clock.asm").lines().collect();
+        let prog = parse_instructions(&lines).unwrap();
+
+        // Transmits the bits of `a + 6`, least significant first
+        assert!(!transmits_clock_signal(&prog, 1));
+        assert!(transmits_clock_signal(&prog, 4));
+        assert!(transmits_clock_signal(&prog, 36));
+        assert_eq!(Some(4), find_clock_seed(&prog));
+    }
+
+    #[test]
+    fn halting_program() {
+        let prog = parse_instructions(&["out 0", "out 1"]).unwrap();
+        assert!(!transmits_clock_signal(&prog, 1));
+    }
+
+    #[test]
+    fn silent_program() {
+        let prog = parse_instructions(&["jnz 1 0"]).unwrap();
+        assert!(!transmits_clock_signal(&prog, 1));
+
+        let prog = parse_instructions(&["out 0", "out 1", "jnz 1 0"]).unwrap();
+        assert!(!transmits_clock_signal(&prog, 1));
+    }
+}
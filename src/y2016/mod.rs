@@ -8,15 +8,20 @@ pub mod day07;
 pub mod day08;
 pub mod day09;
 pub mod day10;
+pub mod day11;
 pub mod day12;
+pub mod day13;
 pub mod day14;
 pub mod day15;
 pub mod day16;
 pub mod day17;
 pub mod day18;
 pub mod day19;
+pub mod day20;
 pub mod day21;
+pub mod day22;
 pub mod day23;
+pub mod day25;
 
 route_days! {
     1 => day01,
@@ -29,13 +34,28 @@ route_days! {
     8 => day08,
     9 => day09,
     10 => day10,
+    11 => day11,
     12 => day12,
+    13 => day13,
     14 => day14,
     15 => day15,
     16 => day16,
     17 => day17,
     18 => day18,
     19 => day19,
+    20 => day20,
     21 => day21,
-    23 => day23
+    22 => day22,
+    23 => day23,
+    25 => day25,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_implemented_days() {
+        assert_routes!(2016, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 25]);
+    }
 }
@@ -0,0 +1,69 @@
+//! Solution for Advent of Code [2017 Day 01](https://adventofcode.com/2017/day/1).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let digits = parse_digits(pio::fetch_string(puzzle)?.trim())?;
+
+    solve_parts!(
+        1 => captcha_sum(&digits, 1),
+        2 => captcha_sum(&digits, digits.len() / 2)
+    )
+}
+
+/// Parses a sequence of decimal digits.
+fn parse_digits(s: &str) -> Result<Vec<u8>, String> {
+    s.chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| format!("captcha contains non-digit character '{}'", c))
+        })
+        .collect()
+}
+
+/// Returns the sum of all digits that match the digit `offset` positions
+/// ahead of them, treating the sequence as circular.
+fn captcha_sum(digits: &[u8], offset: usize) -> u32 {
+    digits
+        .iter()
+        .zip(digits.iter().cycle().skip(offset))
+        .filter(|(a, b)| a == b)
+        .map(|(&a, _)| u32::from(a))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let test_cases = [("1122", 3), ("1111", 4), ("1234", 0), ("91212129", 9)];
+
+        for &(input, expected) in test_cases.iter() {
+            assert_eq!(expected, captcha_sum(&parse_digits(input).unwrap(), 1));
+        }
+    }
+
+    #[test]
+    fn ex2() {
+        let test_cases = [
+            ("1212", 6),
+            ("1221", 0),
+            ("123425", 4),
+            ("123123", 12),
+            ("12131415", 4),
+        ];
+
+        for &(input, expected) in test_cases.iter() {
+            let digits = parse_digits(input).unwrap();
+            assert_eq!(expected, captcha_sum(&digits, digits.len() / 2));
+        }
+    }
+
+    #[test]
+    fn reject_non_digits() {
+        assert!(parse_digits("12a4").is_err());
+    }
+}
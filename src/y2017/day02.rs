@@ -0,0 +1,83 @@
+//! Solution for Advent of Code [2017 Day 02](https://adventofcode.com/2017/day/2).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+
+/// A row of a spreadsheet.
+type Row = Vec<u32>;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let rows = parse_rows(&pio::fetch_line_index(puzzle)?.to_vec())?;
+
+    solve_parts!(
+        1 => checksum(&rows, row_range)?,
+        2 => checksum(&rows, row_quotient)?
+    )
+}
+
+/// Parses lines of whitespace-separated integers into spreadsheet rows.
+fn parse_rows<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Row>, String> {
+    lines
+        .iter()
+        .map(|line| {
+            line.as_ref()
+                .split_whitespace()
+                .map(|cell| {
+                    cell.parse()
+                        .map_err(|e| format!("bad spreadsheet cell `{}`: {}", cell, e))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the sum of the values that `row_value` computes for each row.
+fn checksum<F>(rows: &[Row], row_value: F) -> Result<u32, &'static str>
+where
+    F: Fn(&[u32]) -> Result<u32, &'static str>,
+{
+    rows.iter()
+        .try_fold(0, |sum, row| Ok(sum + row_value(row)?))
+}
+
+/// Returns the difference between the largest and smallest values in a row.
+fn row_range(row: &[u32]) -> Result<u32, &'static str> {
+    match (row.iter().min(), row.iter().max()) {
+        (Some(min), Some(max)) => Ok(max - min),
+        _ => Err("spreadsheet row is empty"),
+    }
+}
+
+/// Returns the quotient of the only two values in a row where one evenly
+/// divides the other.
+fn row_quotient(row: &[u32]) -> Result<u32, &'static str> {
+    row.iter()
+        .enumerate()
+        .flat_map(|(i, &a)| row[i + 1..].iter().map(move |&b| (a.max(b), a.min(b))))
+        .find(|&(big, small)| small != 0 && big % small == 0)
+        .map(|(big, small)| big / small)
+        .ok_or("spreadsheet row contains no evenly divisible values")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let rows = parse_rows(&["5 1 9 5", "7 5 3", "2 4 6 8"]).unwrap();
+        assert_eq!(Ok(18), checksum(&rows, row_range));
+    }
+
+    #[test]
+    fn ex2() {
+        let rows = parse_rows(&["5 9 2 8", "9 4 7 3", "3 8 6 5"]).unwrap();
+        assert_eq!(Ok(9), checksum(&rows, row_quotient));
+    }
+
+    #[test]
+    fn reject_bad_rows() {
+        assert!(parse_rows(&["5 x 2"]).is_err());
+        assert!(checksum(&[vec![]], row_range).is_err());
+        assert!(checksum(&[vec![3, 5, 7]], row_quotient).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Solution for Advent of Code [2017 Day 03](https://adventofcode.com/2017/day/3).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::{HGrid, Pt};
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input: u64 = pio::fetch_string(puzzle)?.trim().parse()?;
+    if input == 0 {
+        return Err("spiral memory squares are numbered from 1".into());
+    }
+
+    solve_parts!(
+        1 => square_position(input).dist_manh(Pt::origin()),
+        2 => first_stress_test_value_above(input)
+    )
+}
+
+/// Returns an iterator over the positions of the squares of spiral memory,
+/// beginning with square 1 at the origin.
+fn spiral() -> impl Iterator<Item = Pt<i64>> {
+    let mut pos = Pt::origin();
+    let mut dir = Pt::e();
+    // Side lengths grow by one after every second turn
+    let mut side_len = 1;
+    let mut side_step = 0;
+    let mut turns = 0;
+
+    std::iter::from_fn(move || {
+        let current = pos;
+        pos += dir;
+        side_step += 1;
+        if side_step == side_len {
+            dir = dir.rot90l();
+            side_step = 0;
+            turns += 1;
+            if turns % 2 == 0 {
+                side_len += 1;
+            }
+        }
+        Some(current)
+    })
+}
+
+/// Returns the position of the specified square in spiral memory.
+fn square_position(square: u64) -> Pt<i64> {
+    spiral().nth(square as usize - 1).unwrap() // spiral never ends
+}
+
+/// Returns the first value written during the stress test that is larger
+/// than `threshold`.
+///
+/// During the stress test, each square is written with the sum of the values
+/// in all adjacent squares that have already been written.
+fn first_stress_test_value_above(threshold: u64) -> u64 {
+    let mut written: HGrid<i64, u64> = HGrid::new();
+    written.insert(Pt::origin(), 1);
+
+    spiral()
+        .skip(1)
+        .map(|pos| {
+            let value = pos.nb_ord().iter().filter_map(|nb| written.get(nb)).sum();
+            written.insert(pos, value);
+            value
+        })
+        .find(|&value| value > threshold)
+        .unwrap() // values increase without bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let test_cases = [(1, 0), (12, 3), (23, 2), (1024, 31)];
+
+        for &(square, expected) in test_cases.iter() {
+            assert_eq!(expected, square_position(square).dist_manh(Pt::origin()));
+        }
+    }
+
+    #[test]
+    fn ex2() {
+        let test_cases = [(1, 2), (2, 4), (4, 5), (5, 10), (26, 54), (747, 806)];
+
+        for &(threshold, expected) in test_cases.iter() {
+            assert_eq!(expected, first_stress_test_value_above(threshold));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Solution for Advent of Code [2017 Day 04](https://adventofcode.com/2017/day/4).
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input = pio::fetch_line_index(puzzle)?;
+
+    solve_parts!(
+        1 => input.iter().filter(|p| is_valid(p, str::to_owned)).count(),
+        2 => input.iter().filter(|p| is_valid(p, sorted_letters)).count()
+    )
+}
+
+/// Returns `true` if no two words in the passphrase have the same key.
+fn is_valid<F, K>(passphrase: &str, key: F) -> bool
+where
+    F: Fn(&str) -> K,
+    K: Eq + Hash,
+{
+    let mut seen = HashSet::new();
+    passphrase
+        .split_whitespace()
+        .all(|word| seen.insert(key(word)))
+}
+
+/// Returns the letters of a word in sorted order. Two words are anagrams of
+/// each other exactly when their sorted letters are equal.
+fn sorted_letters(word: &str) -> Vec<char> {
+    let mut letters: Vec<char> = word.chars().collect();
+    letters.sort_unstable();
+    letters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        assert!(is_valid("aa bb cc dd ee", str::to_owned));
+        assert!(!is_valid("aa bb cc dd aa", str::to_owned));
+        assert!(is_valid("aa bb cc dd aaa", str::to_owned));
+    }
+
+    #[test]
+    fn ex2() {
+        assert!(is_valid("abcde fghij", sorted_letters));
+        assert!(!is_valid("abcde xyz ecdab", sorted_letters));
+        assert!(is_valid("a ab abc abd abf abj", sorted_letters));
+        assert!(is_valid("iiii oiii ooii oooi oooo", sorted_letters));
+        assert!(!is_valid("oiii ioii iioi iiio", sorted_letters));
+    }
+}
@@ -0,0 +1,69 @@
+//! Solution for Advent of Code [2017 Day 05](https://adventofcode.com/2017/day/5).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+
+/// A jump offset in the maze.
+type Offset = i32;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let offsets: Vec<Offset> = pio::fetch_line_index(puzzle)?.parse_each()?;
+
+    solve_parts!(
+        1 => count_steps_to_exit(offsets.clone(), increment),
+        2 => count_steps_to_exit(offsets, strange_update)
+    )
+}
+
+/// Offset update rule for part one.
+fn increment(offset: Offset) -> Offset {
+    offset + 1
+}
+
+/// Offset update rule for part two.
+fn strange_update(offset: Offset) -> Offset {
+    if offset >= 3 {
+        offset - 1
+    } else {
+        offset + 1
+    }
+}
+
+/// Returns the number of jumps taken to exit the maze.
+///
+/// After each jump, the offset of the instruction that was just used is
+/// replaced by the result of `update`.
+fn count_steps_to_exit<F>(mut offsets: Vec<Offset>, update: F) -> u64
+where
+    F: Fn(Offset) -> Offset,
+{
+    let mut pos = 0_isize;
+    let mut steps = 0;
+
+    while pos >= 0 && (pos as usize) < offsets.len() {
+        let offset = &mut offsets[pos as usize];
+        pos += *offset as isize;
+        *offset = update(*offset);
+        steps += 1;
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_OFFSETS: [Offset; 5] = [0, 3, 0, 1, -3];
+
+    #[test]
+    fn ex1() {
+        assert_eq!(5, count_steps_to_exit(EXAMPLE_OFFSETS.to_vec(), increment));
+    }
+
+    #[test]
+    fn ex2() {
+        assert_eq!(
+            10,
+            count_steps_to_exit(EXAMPLE_OFFSETS.to_vec(), strange_update)
+        );
+    }
+}
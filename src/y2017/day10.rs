@@ -0,0 +1,59 @@
+//! Solution for Advent of Code [2017 Day 10](https://adventofcode.com/2017/day/10).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::hash;
+
+/// The number of elements in the circular list.
+const LIST_SIZE: usize = 256;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let input = pio::fetch_string(puzzle)?;
+    let input = input.trim();
+
+    solve_parts!(
+        1 => {
+            let lengths = parse_lengths(input)?;
+            check_product(&hash::sparse_hash(LIST_SIZE, &lengths, 1))
+        },
+        2 => hash::to_hex(&hash::knot(input.as_bytes()))
+    )
+}
+
+/// Parses a comma-separated list of lengths, none of which may exceed the
+/// size of the circular list.
+fn parse_lengths(s: &str) -> Result<Vec<usize>, String> {
+    s.split(',')
+        .map(|token| {
+            let len: usize = token
+                .trim()
+                .parse()
+                .map_err(|e| format!("bad length `{}`: {}", token, e))?;
+            if len > LIST_SIZE {
+                return Err(format!("length {} exceeds list size {}", len, LIST_SIZE));
+            }
+            Ok(len)
+        })
+        .collect()
+}
+
+/// Returns the product of the first two elements of a sparse hash.
+fn check_product(sparse: &[u8]) -> u32 {
+    u32::from(sparse[0]) * u32::from(sparse[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let lengths = parse_lengths("3, 4, 1, 5").unwrap();
+        assert_eq!(12, check_product(&hash::sparse_hash(5, &lengths, 1)));
+    }
+
+    #[test]
+    fn reject_bad_lengths() {
+        assert!(parse_lengths("3,x").is_err());
+        assert!(parse_lengths("257").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! Solution for Advent of Code [2017 Day 14](https://adventofcode.com/2017/day/14).
+
+use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
+use crate::common::util::{hash, DisjointSet};
+
+/// The width and height of the disk grid.
+const GRID_SIZE: usize = 128;
+
+/// The used/free state of every square of the disk, stored as one row of
+/// bits per knot hash. The most significant bit of the first byte
+/// is the leftmost square.
+type DiskGrid = Vec<hash::Digest>;
+
+pub fn solve(puzzle: &Pz) -> PuzzleResult {
+    let key = pio::fetch_string(puzzle)?;
+    let grid = build_grid(key.trim());
+
+    solve_parts!(
+        1 => count_used(&grid),
+        2 => count_regions(&grid)
+    )
+}
+
+/// Builds the disk grid from the knot hashes of the key and each row number.
+fn build_grid(key: &str) -> DiskGrid {
+    (0..GRID_SIZE)
+        .map(|row| hash::knot(format!("{}-{}", key, row).as_bytes()))
+        .collect()
+}
+
+/// Returns `true` if the square at the given position is used.
+fn is_used(grid: &DiskGrid, row: usize, col: usize) -> bool {
+    grid[row][col / 8] & (0x80 >> (col % 8)) != 0
+}
+
+/// Returns the number of used squares in the grid.
+fn count_used(grid: &DiskGrid) -> u32 {
+    grid.iter()
+        .flat_map(|row| row.iter())
+        .map(|byte| byte.count_ones())
+        .sum()
+}
+
+/// Returns the number of regions of orthogonally adjacent used squares.
+fn count_regions(grid: &DiskGrid) -> usize {
+    let mut regions = DisjointSet::new(GRID_SIZE * GRID_SIZE);
+    let mut free_count = 0;
+
+    for row in 0..GRID_SIZE {
+        for col in 0..GRID_SIZE {
+            if !is_used(grid, row, col) {
+                free_count += 1;
+                continue;
+            }
+            let index = row * GRID_SIZE + col;
+            if col + 1 < GRID_SIZE && is_used(grid, row, col + 1) {
+                regions.union(index, index + 1);
+            }
+            if row + 1 < GRID_SIZE && is_used(grid, row + 1, col) {
+                regions.union(index, index + GRID_SIZE);
+            }
+        }
+    }
+    // Free squares each remain in their own set
+    regions.set_count() - free_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex_both() {
+        let grid = build_grid("flqrgnkx");
+
+        // ##.#.#..
+        // .#.#.#.#
+        let top_left: Vec<bool> = (0..8).map(|col| is_used(&grid, 0, col)).collect();
+        assert_eq!(
+            vec![true, true, false, true, false, true, false, false],
+            top_left
+        );
+        assert!(!is_used(&grid, 1, 0));
+        assert!(is_used(&grid, 1, 7));
+
+        assert_eq!(8108, count_used(&grid));
+        assert_eq!(1242, count_regions(&grid));
+    }
+}
@@ -0,0 +1,27 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day10;
+pub mod day14;
+
+route_days! {
+    1 => day01,
+    2 => day02,
+    3 => day03,
+    4 => day04,
+    5 => day05,
+    10 => day10,
+    14 => day14,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_implemented_days() {
+        assert_routes!(2017, [1, 2, 3, 4, 5, 10, 14]);
+    }
+}
@@ -5,10 +5,7 @@ use std::collections::HashSet;
 use crate::common::puzzle::{input as pio, Result as PuzzleResult, Selection as Pz};
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let input: Vec<i32> = pio::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+    let input: Vec<i32> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     solve_parts!(
         1 => input.iter().sum::<i32>(),
@@ -39,7 +36,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(518, 72889, Pz::new(2018, 1))
+        assert_solution!(Pz::new(2018, 1))
     }
 
     #[test]
@@ -117,7 +117,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(6422, "qcslyvphgkrmdawljuefotxbh", Pz::new(2018, 2))
+        assert_solution!(Pz::new(2018, 2))
     }
 
     #[test]
@@ -147,9 +147,7 @@ mod fabric {
 }
 
 pub fn solve(puzzle: &Pz) -> PuzzleResult {
-    let claims: Vec<fabric::Claim> = pio::fetch_lines(puzzle)?.into_iter()
-        .map(|line| line.parse())
-        .collect::<Result<_, _>>()?;
+    let claims: Vec<fabric::Claim> = pio::fetch_line_index(puzzle)?.parse_each()?;
 
     let mut fabric = fabric::FabricSheet::new();
 
@@ -172,7 +170,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(113_576, 825, Pz::new(2018, 3))
+        assert_solution!(Pz::new(2018, 3))
     }
 
     #[test]
@@ -1,8 +1,9 @@
 //! Solution for Advent of Code [2018 Day 04](https://adventofcode.com/2018/day/4).
 
 use crate::common::puzzle;
+use std::fmt::Write;
 
-mod guard;
+pub mod guard;
 
 #[cfg(test)]
 const EXAMPLE_INPUT: &[&str] = &[
@@ -26,8 +27,8 @@ const EXAMPLE_INPUT: &[&str] = &[
 ];
 
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
-    let lines = puzzle::fetch_lines(puzzle)?;
-    let log = guard::GuardLog::parse_lines(&lines)?;
+    let lines = puzzle::fetch_line_index(puzzle)?;
+    let log = guard::GuardLog::parse_lines(&lines.to_vec())?;
     solve_parts!(
         1 => {
             let (most_sleepy_guard, most_slept_minute) = log.compute_most_sleepy_guard();
@@ -39,6 +40,56 @@ pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
         },
 
     )
+    .map(|solution: puzzle::Solution| {
+        if puzzle.visualize() {
+            solution.with_artifact(1, render_heat_map(&log))
+        } else {
+            solution
+        }
+    })
+}
+
+/// Renders a heat map of how often each guard is asleep during each minute of
+/// the midnight hour. Darker characters denote more frequent sleep.
+fn render_heat_map(log: &guard::GuardLog) -> String {
+    const SHADES: &[u8] = b" .:-=+*#%@";
+
+    let histograms: Vec<_> = log
+        .guards()
+        .into_iter()
+        .map(|id| (id, log.minute_histogram(id)))
+        .collect();
+    let max_count = histograms
+        .iter()
+        .flat_map(|(_, histogram)| histogram.iter())
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut render = format!(
+        "{:>6} |{:<60}| total\n",
+        "guard", "00  05  10  15  20  25  30  35  40  45  50  55"
+    );
+    for (id, histogram) in histograms {
+        let row: String = histogram
+            .iter()
+            .map(|&count| {
+                let shade =
+                    (usize::from(count) * (SHADES.len() - 1)).div_ceil(usize::from(max_count));
+                SHADES[shade] as char
+            })
+            .collect();
+        writeln!(
+            render,
+            "{:>6} |{}| {}",
+            format!("#{}", id),
+            row,
+            log.total_sleep(id).as_secs() / 60
+        )
+        .unwrap();
+    }
+    render
 }
 
 #[cfg(test)]
@@ -47,7 +98,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(95199, 7887, puzzle::Selection::new(2018, 4))
+        assert_solution!(puzzle::Selection::new(2018, 4))
     }
 
     #[test]
@@ -63,4 +114,20 @@ mod tests {
             (99, 45)
         );
     }
+
+    #[test]
+    fn heat_map() {
+        let log = guard::GuardLog::parse_lines(EXAMPLE_INPUT).unwrap();
+        let render = render_heat_map(&log);
+        let rows: Vec<&str> = render.lines().collect();
+
+        assert_eq!(3, rows.len());
+        assert_eq!(
+            "   #10 |     -------------------*---- -------------------------     | 50",
+            rows[1]
+        );
+        assert!(rows[2].starts_with("   #99 |"));
+        assert!(rows[2].ends_with("| 30"));
+        assert_eq!(Some('@'), rows[2].chars().nth(8 + 45));
+    }
 }
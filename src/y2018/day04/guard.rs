@@ -1,20 +1,23 @@
 //! Analysis of guard napping behavior during late-night shifts.
 
-use chrono::{NaiveDateTime, Timelike};
+use crate::common::util::date::Timestamp;
 use std::cmp;
 use std::collections::HashMap;
-use std::error::Error;
 use std::str::FromStr;
 use std::time::Duration;
 
 /// The integer type used to represent a Guard's unique ID.
 pub type GuardId = u16;
 
-/// The integer type used to occurrences of an event.
-type Counter = u16;
+/// The integer type used to count occurrences of an event.
+pub type Counter = u16;
 
-/// The integer type used to represent a minute..
-type Minute = usize;
+/// The integer type used to represent a minute.
+pub type Minute = usize;
+
+/// The number of times a guard was asleep during each minute of the
+/// midnight hour.
+pub type MinuteHistogram = [Counter; GuardLog::MINUTES_PER_HOUR];
 
 /// Type used to count occurrences of events associated with different guards.
 type GuardCounter = HashMap<GuardId, Counter>;
@@ -34,14 +37,12 @@ enum GuardAction {
 /// input for sorting the log entries by timestamp and resolving which
 /// log entries apply to which guard. After this initial analysis, all
 /// `GuardLogEntry`s are condensed  into `Shift` instances.
-struct GuardLogEntry {
-    timestamp: NaiveDateTime,
+pub(crate) struct GuardLogEntry {
+    timestamp: Timestamp,
     action: GuardAction,
 }
 
 impl GuardLogEntry {
-    const DATE_TIME_FORMAT: &'static str = "%Y-%m-%d %H:%M";
-
     /// Returns the ID of the guard associated with this entry if this entry
     /// denotes the beginning of a new shift. Otherwise, returns `None`.
     pub fn guard_id(&self) -> Option<GuardId> {
@@ -63,38 +64,35 @@ impl FromStr for GuardLogEntry {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         const BEGIN_TIMESTAMP: usize = 1;
         const END_TIMESTAMP: usize = 17;
-        let timestamp = NaiveDateTime::parse_from_str(
-            &s[BEGIN_TIMESTAMP..END_TIMESTAMP],
-            Self::DATE_TIME_FORMAT,
-        )
-        .map_err(|e| {
-            format!(
-                "failed to parse guard log entry: bad timestamp - {}",
-                e.description()
-            )
-        })?;
+        let timestamp: Timestamp = s
+            .get(BEGIN_TIMESTAMP..END_TIMESTAMP)
+            .ok_or("failed to parse guard log entry: missing timestamp")?
+            .parse()
+            .map_err(|e| format!("failed to parse guard log entry: bad timestamp - {}", e))?;
 
         const ACTION_BYTE: usize = 19;
         const BEGIN_ID: usize = 26;
-        let action = match s.as_bytes()[ACTION_BYTE] {
-            b'G' => {
-                let end = s[BEGIN_ID..]
-                    .find(' ')
+        let action = match s.as_bytes().get(ACTION_BYTE) {
+            Some(b'G') => {
+                let id = s
+                    .get(BEGIN_ID..)
+                    .and_then(|rest| rest.split_once(' '))
+                    .map(|(id, _)| id)
                     .ok_or("failed to parse guard log entry: missing guard number")?;
                 GuardAction::Begin(
-                    s[BEGIN_ID..BEGIN_ID + end]
-                        .parse()
+                    id.parse()
                         .map_err(|_| "failed to parse guard log entry: malformed guard number")?,
                 )
             }
-            b'f' => GuardAction::Sleep,
-            b'w' => GuardAction::Wake,
-            c => {
+            Some(b'f') => GuardAction::Sleep,
+            Some(b'w') => GuardAction::Wake,
+            Some(&c) => {
                 return Err(format!(
                     "failed to parse guard log entry: unknown guard action beginning with '{}'",
                     c as char,
                 ));
             }
+            None => return Err("failed to parse guard log entry: missing action".to_owned()),
         };
         Ok(GuardLogEntry { timestamp, action })
     }
@@ -102,7 +100,7 @@ impl FromStr for GuardLogEntry {
 
 impl PartialOrd for GuardLogEntry {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        self.timestamp.partial_cmp(&other.timestamp)
+        Some(self.cmp(other))
     }
 }
 
@@ -124,7 +122,7 @@ pub struct GuardLog {
 }
 
 impl GuardLog {
-    const MINUTES_PER_HOUR: usize = 60;
+    pub const MINUTES_PER_HOUR: usize = 60;
 
     /// Attempts to parse a sequence of log lines into a `GuardLog`.
     pub fn parse_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, String> {
@@ -160,18 +158,60 @@ impl GuardLog {
         })
     }
 
-    /// Returns a tuple containing 1) the ID of the guard who slept for the most
-    /// minutes and 2) the minute during which that guard slept the most.
-    pub fn compute_most_sleepy_guard(&self) -> (GuardId, Minute) {
+    /// Returns the shifts recorded in this log in chronological order.
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shift_log
+    }
+
+    /// Returns the IDs of every guard that worked a shift, in ascending order.
+    pub fn guards(&self) -> Vec<GuardId> {
+        let mut guards: Vec<GuardId> = self.shift_log.iter().map(Shift::guard).collect();
+        guards.sort_unstable();
+        guards.dedup();
+        guards
+    }
+
+    /// Returns the shifts worked by the given guard in chronological order.
+    pub fn guard_shifts(&self, guard: GuardId) -> impl Iterator<Item = &Shift> {
+        self.shift_log
+            .iter()
+            .filter(move |shift| shift.guard == guard)
+    }
+
+    /// Returns the total duration of time that each guard spent asleep.
+    ///
+    /// Guards that worked a shift but never slept are included with a
+    /// duration of zero.
+    pub fn sleep_totals(&self) -> HashMap<GuardId, Duration> {
         let mut guard_nap_time: HashMap<GuardId, Duration> = HashMap::new();
 
         for shift in self.shift_log.iter() {
             // Compute the duration of time that each guard was asleep
-            let nap_total = shift.naps.iter().map(Nap::duration).sum();
-            *guard_nap_time.entry(shift.guard).or_default() += nap_total;
+            *guard_nap_time.entry(shift.guard).or_default() += shift.sleep_duration();
         }
+        guard_nap_time
+    }
 
-        let (most_sleepy_guard, _) = guard_nap_time
+    /// Returns the total duration of time that the given guard spent asleep.
+    pub fn total_sleep(&self, guard: GuardId) -> Duration {
+        self.guard_shifts(guard).map(Shift::sleep_duration).sum()
+    }
+
+    /// Returns the number of times that the given guard was asleep during
+    /// each minute of the midnight hour.
+    pub fn minute_histogram(&self, guard: GuardId) -> MinuteHistogram {
+        let mut histogram = [0; Self::MINUTES_PER_HOUR];
+        for (count, guard_map) in histogram.iter_mut().zip(self.minute_log.iter()) {
+            *count = guard_map.get(&guard).copied().unwrap_or_default();
+        }
+        histogram
+    }
+
+    /// Returns a tuple containing 1) the ID of the guard who slept for the most
+    /// minutes and 2) the minute during which that guard slept the most.
+    pub fn compute_most_sleepy_guard(&self) -> (GuardId, Minute) {
+        let (most_sleepy_guard, _) = self
+            .sleep_totals()
             .into_iter()
             // Find the guard who was asleep for the greatest duration of time.
             .max_by_key(|pair| pair.1)
@@ -234,29 +274,53 @@ impl GuardLog {
 
 #[derive(Debug, PartialEq, Eq)]
 /// A span of time during which a guard is asleep.
-struct Nap {
-    start: NaiveDateTime,
-    end: NaiveDateTime,
+pub struct Nap {
+    start: Timestamp,
+    end: Timestamp,
 }
 
 impl Nap {
-    /// Returns the duration between this `Nap`'s start and end times.
-    fn duration(&self) -> Duration {
+    /// Returns the time at which the guard fell asleep.
+    pub fn start(&self) -> Timestamp {
+        self.start
+    }
+
+    /// Returns the time at which the guard woke up.
+    pub fn end(&self) -> Timestamp {
         self.end
-            .signed_duration_since(self.start)
-            .to_std()
-            .expect("nap end time occurred before nap start")
+    }
+
+    /// Returns the duration between this `Nap`'s start and end times.
+    pub fn duration(&self) -> Duration {
+        let minutes = self.end.minutes_since(self.start);
+        assert!(minutes >= 0, "nap end time occurred before nap start");
+        Duration::from_secs(minutes as u64 * 60)
     }
 }
 
 #[derive(Debug)]
 /// A single shift associating a guard with the naps they take.
-struct Shift {
+pub struct Shift {
     guard: GuardId,
     naps: Vec<Nap>,
 }
 
 impl Shift {
+    /// Returns the ID of the guard who worked this shift.
+    pub fn guard(&self) -> GuardId {
+        self.guard
+    }
+
+    /// Returns the naps taken during this shift in chronological order.
+    pub fn naps(&self) -> &[Nap] {
+        &self.naps
+    }
+
+    /// Returns the total duration of time spent asleep during this shift.
+    pub fn sleep_duration(&self) -> Duration {
+        self.naps.iter().map(Nap::duration).sum()
+    }
+
     /// Attempts to parse a sequence of `GuardLogEntry`s into a shift
     /// associated with the specified guard.
     fn from_guard_and_entries(
@@ -286,10 +350,13 @@ impl Shift {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
 
     use super::super::EXAMPLE_INPUT;
 
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
     #[test]
     fn parse_guard_log_entry() {
         assert_eq!(
@@ -298,11 +365,26 @@ mod tests {
                 .unwrap(),
             GuardLogEntry {
                 action: GuardAction::Begin(10),
-                timestamp: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 0, 0),
+                timestamp: ts("1518-11-01 00:00"),
             }
         )
     }
 
+    #[test]
+    fn reject_truncated_guard_log_entry() {
+        let lines = [
+            "",
+            "[1518-11-01 00:00]",
+            "[1518-11-01 00:00] G",
+            "[1518-11-01 00:00] Guard #10",
+            "[1518-11-01 00:00] Guard é10 begins shift",
+            "[1518-11-01 00:00] éé",
+        ];
+        for line in lines.iter() {
+            assert!(line.parse::<GuardLogEntry>().is_err(), "{:?}", line);
+        }
+    }
+
     #[test]
     fn shift_from_guard_and_entries() {
         let guard = 10;
@@ -320,12 +402,12 @@ mod tests {
             &shift.naps[..2],
             &[
                 Nap {
-                    start: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 5, 0),
-                    end: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 25, 0),
+                    start: ts("1518-11-01 00:05"),
+                    end: ts("1518-11-01 00:25"),
                 },
                 Nap {
-                    start: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 30, 0),
-                    end: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 55, 0),
+                    start: ts("1518-11-01 00:30"),
+                    end: ts("1518-11-01 00:55"),
                 },
             ],
         );
@@ -341,22 +423,46 @@ mod tests {
             first_shift.naps,
             &[
                 Nap {
-                    start: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 5, 0),
-                    end: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 25, 0),
+                    start: ts("1518-11-01 00:05"),
+                    end: ts("1518-11-01 00:25"),
                 },
                 Nap {
-                    start: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 30, 0),
-                    end: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 55, 0),
+                    start: ts("1518-11-01 00:30"),
+                    end: ts("1518-11-01 00:55"),
                 }
             ],
         )
     }
 
+    #[test]
+    fn guard_statistics() {
+        let log = GuardLog::parse_lines(EXAMPLE_INPUT).unwrap();
+        assert_eq!(log.guards(), &[10, 99]);
+        assert_eq!(log.total_sleep(10).as_secs(), 50 * 60);
+        assert_eq!(log.total_sleep(99).as_secs(), 30 * 60);
+        assert_eq!(log.total_sleep(42).as_secs(), 0);
+        assert_eq!(log.sleep_totals().len(), 2);
+
+        let shifts: Vec<&Shift> = log.guard_shifts(99).collect();
+        assert_eq!(shifts.len(), 3);
+        assert_eq!(shifts[0].naps()[0].start(), ts("1518-11-02 00:40"));
+        assert_eq!(shifts[0].sleep_duration().as_secs(), 10 * 60);
+
+        let histogram = log.minute_histogram(10);
+        assert_eq!(histogram[4], 0);
+        assert_eq!(histogram[5], 1);
+        assert_eq!(histogram[24], 2);
+        assert_eq!(histogram[25], 1);
+        assert_eq!(histogram[55], 0);
+        assert_eq!(log.minute_histogram(99)[45], 3);
+        assert!(log.minute_histogram(42).iter().all(|&c| c == 0));
+    }
+
     #[test]
     fn nap_duration() {
         let nap = Nap {
-            start: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 5, 0),
-            end: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 25, 0),
+            start: ts("1518-11-01 00:05"),
+            end: ts("1518-11-01 00:25"),
         };
 
         assert_eq!(nap.duration().as_secs(), 20 * 60);
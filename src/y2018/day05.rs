@@ -95,7 +95,10 @@ impl ReducedPolymer {
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
     let input = puzzle::fetch_string(puzzle)?;
 
-    let polymer = input.trim_end().parse::<Polymer>()?;
+    let polymer = input
+        .trim_end()
+        .parse::<Polymer>()
+        .map_err(puzzle::PuzzleError::parse)?;
     let removed_unit_polymers = polymer.removed_unit_types_vec();
 
     solve_parts!(
@@ -132,7 +135,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(11042, 6872, puzzle::Selection::new(2018, 5))
+        assert_solution!(puzzle::Selection::new(2018, 5))
     }
 
     #[test]
@@ -2,7 +2,6 @@
 
 use crate::common::puzzle;
 use crate::common::util::{Grid, GridIter, Pt};
-use std::i32;
 
 /// The integral type used to represent point coordinates.
 type PointScalar = i32;
@@ -63,7 +62,7 @@ impl PointGrid {
     ///
     /// The returned iterator will yield points "left-to-right" and "bottom-
     /// to-top".
-    pub fn iter(&self) -> GridIter<PointScalar> {
+    pub fn iter(&self) -> GridIter<'_, PointScalar> {
         self.grid.iter()
     }
 
@@ -78,11 +77,18 @@ impl PointGrid {
         let mut area_counts: Vec<Option<Counter>> = vec![Some(0); self.points.len()];
 
         for point in self.iter() {
-            let closest_pt = self.closest_pt_index(point);
+            // Coordinates that are equally far from several points do not
+            // count towards any point's area.
+            let closest_pt = match self.closest_pt_index(point) {
+                Some(index) => index,
+                None => continue,
+            };
             if self.grid.pt_on_edge(point) {
                 area_counts[closest_pt] = None;
             } else {
-                area_counts[closest_pt].as_mut().map(|count| *count += 1);
+                if let Some(count) = area_counts[closest_pt].as_mut() {
+                    *count += 1;
+                }
             }
         }
 
@@ -131,7 +137,7 @@ impl PointGrid {
             // until we find a ring with no point that has an acceptable
             // distance score. All larger rings must only have points that
             // are too far from all of the points on this grid.
-            .take_while(|coord_ring| coord_ring.len() > 0)
+            .take_while(|coord_ring| !coord_ring.is_empty())
             // Count the number of coordinates that have safe distances
             .map(|coord_ring| coord_ring.len() as Counter)
             .sum()
@@ -140,14 +146,28 @@ impl PointGrid {
     /// Returns the index of the point in `self.points` that is nearest to
     /// the specified point.
     ///
-    /// Ties are undefined.
-    fn closest_pt_index(&self, pt: Point) -> usize {
-        self.points
-            .iter()
-            .enumerate()
-            .min_by_key(|(_, &grid_point)| grid_point.loc.dist_manh(pt))
-            .expect("grid MUST HAVE at least one point")
-            .0
+    /// If multiple points are tied for being the nearest, `None` is returned.
+    fn closest_pt_index(&self, pt: Point) -> Option<usize> {
+        let mut closest: Option<(usize, PointScalar)> = None;
+        let mut tied = false;
+
+        for (index, grid_point) in self.points.iter().enumerate() {
+            let dist = grid_point.loc.dist_manh(pt);
+            match closest {
+                Some((_, min_dist)) if dist > min_dist => {}
+                Some((_, min_dist)) if dist == min_dist => tied = true,
+                _ => {
+                    closest = Some((index, dist));
+                    tied = false;
+                }
+            }
+        }
+
+        if tied {
+            None
+        } else {
+            closest.map(|(index, _)| index)
+        }
     }
 
     /// Computes the sum of the distances between the specified point and each
@@ -207,8 +227,8 @@ impl Iterator for PointRingIter {
 }
 
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
-    let input = puzzle::fetch_lines(puzzle)?;
-    let points = parse_point_lines(&input)?;
+    let input = puzzle::fetch_line_index(puzzle)?;
+    let points = parse_point_lines(&input.to_vec())?;
     let grid = PointGrid::new(points);
 
     solve_parts!(
@@ -247,6 +267,14 @@ fn parse_point_lines<S: AsRef<str>>(lines: &[S]) -> Result<Vec<NamedPoint>, &'st
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::testing;
+
+    /// Returns the grid of points given in the example.
+    fn example_grid() -> PointGrid {
+        let input = testing::example(2018, 6, 1);
+        let lines: Vec<&str> = input.lines().collect();
+        PointGrid::new(parse_point_lines(&lines).unwrap())
+    }
 
     #[test]
     fn point_ring_iter_returns_correct_rings() {
@@ -274,22 +302,30 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(4011, 46054, puzzle::Selection::new(2018, 6))
+        assert_solution!(puzzle::Selection::new(2018, 6))
     }
 
     #[test]
     fn ex1() {
-        let input = ["1, 1", "1, 6", "8, 3", "3, 4", "5, 5", "8, 9"];
-        let points = parse_point_lines(&input).unwrap();
-        let grid = PointGrid::new(points);
+        let grid = example_grid();
         assert_eq!((b'E', 17), grid.find_most_accessible_point());
     }
 
+    #[test]
+    fn equidistant_coordinates() {
+        let grid = example_grid();
+
+        // Equally far from A and E
+        assert_eq!(None, grid.closest_pt_index(Pt { x: 5, y: 1 }));
+        // Equally far from B and D
+        assert_eq!(None, grid.closest_pt_index(Pt { x: 1, y: 4 }));
+        // Nearest to E only
+        assert_eq!(Some(4), grid.closest_pt_index(Pt { x: 5, y: 2 }));
+    }
+
     #[test]
     fn ex2() {
-        let input = ["1, 1", "1, 6", "8, 3", "3, 4", "5, 5", "8, 9"];
-        let points = parse_point_lines(&input).unwrap();
-        let grid = PointGrid::new(points);
+        let grid = example_grid();
         assert_eq!(16, grid.find_largest_safe_region(32));
     }
 }
@@ -7,12 +7,16 @@ use std::str::FromStr;
 /// The maximum number of steps that can exist.
 const MAX_STEP_COUNT: usize = 26;
 
-/// The number of elves available to help, plus one.
+/// The default number of elves available to help, plus one.
+///
+/// Can be overridden with the `workers` puzzle parameter.
 ///
 /// For Part 2.
 const WORKER_COUNT: usize = 5;
 
-/// The base amount of time each step takes to complete.
+/// The default base amount of time each step takes to complete.
+///
+/// Can be overridden with the `base_duration` puzzle parameter.
 ///
 /// For Part 2.
 const BASE_STEP_DURATION: Second = 60;
@@ -33,7 +37,7 @@ struct DependencyEntry {
     requires: StepID,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 /// A worker that can work on a step for a duration of time.
 ///
 /// For Part 2.
@@ -163,19 +167,12 @@ impl Worker {
     }
 }
 
-impl Default for Worker {
-    fn default() -> Self {
-        Self {
-            task: None,
-            time_left: 0,
-        }
-    }
-}
-
 impl StepSimulator {
-    /// Constructs a `StepSimulator` from the given list of step dependencies
-    /// with the specified number of workers.
-    fn new(steps: &[DependencyEntry], worker_count: usize, base_step_duration: Second) -> Self {
+    /// Constructs a `StepSimulator` from the given list of step dependencies.
+    ///
+    /// The simulator starts with `WORKER_COUNT` workers and a base step
+    /// duration of `BASE_STEP_DURATION`.
+    fn new(steps: &[DependencyEntry]) -> Self {
         let mut requirements_map: [Option<HashSet<StepID>>; MAX_STEP_COUNT] = Default::default();
         for &step in steps {
             let req_set = requirements_map[step.target.as_index()].get_or_insert_with(HashSet::new);
@@ -186,12 +183,30 @@ impl StepSimulator {
         Self {
             requirements_map,
             completed_steps: Vec::new(),
-            workers: vec![Default::default(); worker_count],
+            workers: vec![Default::default(); WORKER_COUNT],
             current_time: 0,
-            base_step_duration,
+            base_step_duration: BASE_STEP_DURATION,
         }
     }
 
+    /// Sets the number of workers available to complete steps.
+    ///
+    /// Panics if `worker_count` is zero.
+    fn with_workers(mut self, worker_count: usize) -> Self {
+        assert!(
+            worker_count > 0,
+            "step simulator MUST have at least one worker"
+        );
+        self.workers = vec![Default::default(); worker_count];
+        self
+    }
+
+    /// Sets the base amount of time each step takes to complete.
+    fn with_base_step_duration(mut self, base_step_duration: Second) -> Self {
+        self.base_step_duration = base_step_duration;
+        self
+    }
+
     /// Compute the order in which the steps may be completed.
     ///
     /// Solves Part 1 of the problem.
@@ -295,10 +310,8 @@ impl StepSimulator {
     ///
     /// For both parts.
     fn complete_step(&mut self, step: StepID) {
-        for step_reqs in self.requirements_map.iter_mut() {
-            if let Some(req_set) = step_reqs {
-                req_set.remove(&step);
-            }
+        for req_set in self.requirements_map.iter_mut().flatten() {
+            req_set.remove(&step);
         }
         self.completed_steps.push(step)
     }
@@ -336,10 +349,16 @@ impl StepSimulator {
 }
 
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
-    let input = puzzle::fetch_lines(puzzle)?;
+    let input = puzzle::fetch_line_index(puzzle)?;
     let dependencies: Vec<DependencyEntry> = input.iter().map(|s| s.parse().unwrap()).collect();
 
-    let simulator = StepSimulator::new(&dependencies, WORKER_COUNT, BASE_STEP_DURATION);
+    let worker_count: usize = puzzle.param_or("workers", WORKER_COUNT)?;
+    if worker_count == 0 {
+        return Err("at least one worker is required".into());
+    }
+    let simulator = StepSimulator::new(&dependencies)
+        .with_workers(worker_count)
+        .with_base_step_duration(puzzle.param_or("base_duration", BASE_STEP_DURATION)?);
 
     solve_parts!(
         1 => simulator
@@ -355,6 +374,7 @@ pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::testing;
 
     const EXAMPLE_STEP_DESCRIPTION: &[&str] = &[
         "Step C must be finished before step A can begin.",
@@ -371,38 +391,36 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(
-            "CQSWKZFJONPBEUMXADLYIGVRHT",
-            914,
-            puzzle::Selection::new(2018, 7)
-        )
+        assert_solution!(puzzle::Selection::new(2018, 7))
     }
 
     #[test]
     fn ex1() {
-        let dependencies: Vec<DependencyEntry> = EXAMPLE_STEP_DESCRIPTION
-            .iter()
-            .map(|s| s.parse().unwrap())
+        let dependencies: Vec<DependencyEntry> = testing::parse_lines(EXAMPLE_STEP_DESCRIPTION);
+        let step_order: String = StepSimulator::new(&dependencies)
+            .compute_timeless_step_order()
+            .into_iter()
+            .map(StepID::as_char)
             .collect();
-        let step_order: String =
-            StepSimulator::new(&dependencies, EXAMPLE_WORKER_COUNT, EXAMPLE_STEP_DURATION)
-                .compute_timeless_step_order()
-                .into_iter()
-                .map(StepID::as_char)
-                .collect();
         assert_eq!(step_order, "CABDFE");
     }
 
     #[test]
     fn ex2() {
-        let dependencies: Vec<DependencyEntry> = EXAMPLE_STEP_DESCRIPTION
-            .iter()
-            .map(|s| s.parse().unwrap())
-            .collect();
-        let mut simulator =
-            StepSimulator::new(&dependencies, EXAMPLE_WORKER_COUNT, EXAMPLE_STEP_DURATION);
-        simulator.base_step_duration = 0;
-        let completion_time = simulator.simulate_tasks_brute_force().1;
+        let dependencies: Vec<DependencyEntry> = testing::parse_lines(EXAMPLE_STEP_DESCRIPTION);
+        let completion_time = StepSimulator::new(&dependencies)
+            .with_workers(EXAMPLE_WORKER_COUNT)
+            .with_base_step_duration(EXAMPLE_STEP_DURATION)
+            .simulate_tasks_brute_force()
+            .1;
         assert_eq!(completion_time, 15)
     }
+
+    #[test]
+    fn ex_both() {
+        let puzzle = testing::selection(2018, 7, &EXAMPLE_STEP_DESCRIPTION.join("\n"))
+            .with_param("workers", EXAMPLE_WORKER_COUNT)
+            .with_param("base_duration", EXAMPLE_STEP_DURATION);
+        assert_eq!(testing::solution("CABDFE", 15), solve(&puzzle).unwrap());
+    }
 }
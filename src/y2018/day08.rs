@@ -16,51 +16,45 @@ mod tree {
 
     impl Node {
         /// Constructs a tree from the given list of numbers.
-        pub fn tree_from_number_list(desc: &[Data]) -> Node {
-            let root_child_count = desc[0] as usize;
-            let root_meta_count = desc[1] as usize;
-            let (root_children, unused) = Self::children_from_list(&desc[2..], root_child_count);
-            if unused.len() != root_meta_count {
-                panic!(format!("root node has too much meta data: expected {} number, but {} numbers are left after parsing children", root_meta_count, unused.len()));
-            }
-
-            Self {
-                children: root_children,
-                metadata: Vec::from(unused),
+        ///
+        /// An error is returned if the list is truncated, or if numbers
+        /// remain after the root node's metadata.
+        pub fn tree_from_number_list(desc: &[Data]) -> Result<Node, &'static str> {
+            let (root, unused) = Self::parse_node(desc)?;
+            if !unused.is_empty() {
+                return Err("numbers remain after the root node's metadata");
             }
+            Ok(root)
         }
 
-        /// Parses `n=children_left` `Node`s from the given list of numbers
-        /// and returns 1) the parsed nodes and 2) the numbers left after
-        /// parsing `n` nodes.
-        fn children_from_list(mut desc: &[Data], mut children_left: usize) -> (Vec<Node>, &[Data]) {
-            // 2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2
-            let mut nodes = Vec::new();
-
-            while children_left != 0 {
-                // Look at the next child in the description
-                let child_count = desc[0] as usize;
-                let meta_count = desc[1] as usize;
-
-                if child_count == 0 {
-                    let (metadata, rest) = desc[2..].split_at(meta_count);
-                    nodes.push(Node {
-                        children: Vec::new(),
-                        metadata: Vec::from(metadata),
-                    });
-                    desc = rest;
-                } else {
-                    let (children, rest) = Self::children_from_list(&desc[2..], child_count);
-                    let (metadata, unused) = rest.split_at(meta_count);
-                    nodes.push(Node {
-                        children,
-                        metadata: Vec::from(metadata),
-                    });
-                    desc = unused
+        /// Parses the `Node` at the beginning of the given list of numbers
+        /// and returns 1) the parsed node and 2) the numbers that follow it.
+        fn parse_node(desc: &[Data]) -> Result<(Node, &[Data]), &'static str> {
+            const TRUNCATED: &str = "number list ends in the middle of a node";
+
+            let (child_count, meta_count, mut desc) = match *desc {
+                [child_count, meta_count, ref rest @ ..] => {
+                    (child_count as usize, meta_count as usize, rest)
                 }
-                children_left -= 1;
+                _ => return Err(TRUNCATED),
+            };
+
+            let mut children = Vec::new();
+            for _ in 0..child_count {
+                let (child, rest) = Self::parse_node(desc)?;
+                children.push(child);
+                desc = rest;
+            }
+
+            if desc.len() < meta_count {
+                return Err(TRUNCATED);
             }
-            (nodes, desc)
+            let (metadata, rest) = desc.split_at(meta_count);
+            let node = Node {
+                children,
+                metadata: metadata.to_vec(),
+            };
+            Ok((node, rest))
         }
 
         /// Returns the sum of all of the metadata entries contained
@@ -105,7 +99,7 @@ mod tree {
 
         #[test]
         fn parse_tree_from_number_list() {
-            let root_node = Node::tree_from_number_list(EXAMPLE_TREE_DESC);
+            let root_node = Node::tree_from_number_list(EXAMPLE_TREE_DESC).unwrap();
             let excepted_tree = Node {
                 children: vec![
                     Node {
@@ -124,17 +118,26 @@ mod tree {
             };
             assert_eq!(root_node, excepted_tree);
         }
+
+        #[test]
+        fn reject_malformed_list() {
+            assert!(Node::tree_from_number_list(&[]).is_err());
+            assert!(Node::tree_from_number_list(&[0]).is_err());
+            assert!(Node::tree_from_number_list(&[0, 2, 1]).is_err());
+            assert!(Node::tree_from_number_list(&[1, 0, 0, 1]).is_err());
+            assert!(Node::tree_from_number_list(&[0, 1, 1, 1]).is_err());
+            assert!(Node::tree_from_number_list(&[0, 0]).is_ok());
+        }
     }
 }
 
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
     let input: Vec<tree::Data> = puzzle::fetch_string(puzzle)?
-        .trim_end()
-        .split(' ')
+        .split_whitespace()
         .map(|s| s.parse())
         .collect::<Result<_, _>>()?;
 
-    let root_node = tree::Node::tree_from_number_list(&input);
+    let root_node = tree::Node::tree_from_number_list(&input)?;
 
     solve_parts!(
         1 => root_node.branch_metadata_sum(),
@@ -151,18 +154,23 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(48260, 25981, puzzle::Selection::new(2018, 8))
+        assert_solution!(puzzle::Selection::new(2018, 8))
     }
 
     #[test]
     fn ex1() {
-        let root_node = tree::Node::tree_from_number_list(EXAMPLE_TREE_DESC);
+        let root_node = tree::Node::tree_from_number_list(EXAMPLE_TREE_DESC).unwrap();
         assert_eq!(138, root_node.branch_metadata_sum());
     }
 
     #[test]
     fn ex2() {
-        let root_node = tree::Node::tree_from_number_list(EXAMPLE_TREE_DESC);
+        let root_node = tree::Node::tree_from_number_list(EXAMPLE_TREE_DESC).unwrap();
         assert_eq!(66, root_node.compute_node_value());
     }
+
+    #[test]
+    fn ex_solve() {
+        assert_example!(2018, 8, 1, solve => (138, 66));
+    }
 }
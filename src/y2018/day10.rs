@@ -2,7 +2,7 @@
 
 use crate::common::{
     puzzle,
-    util::{Grid, IntoPoint, Pt},
+    util::{ocr, Grid, IntoPoint, Pt},
 };
 use std::collections::HashSet;
 use std::fmt;
@@ -16,7 +16,7 @@ type Scalar = i32;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 /// A floating light with a position and velocity.
-struct Light {
+pub(crate) struct Light {
     pos: Pt<Scalar>,
     vel: Pt<Scalar>,
 }
@@ -194,76 +194,45 @@ impl fmt::Display for LightScreen {
 }
 
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
-    let input: Vec<Light> = puzzle::fetch_lines(puzzle)?
-        .into_iter()
-        .map(|s| s.parse())
-        .collect::<Result<_, _>>()?;
+    let input: Vec<Light> = puzzle::fetch_line_index(puzzle)?.parse_each()?;
 
     let mut light_grid = LightScreen { lights: input };
 
     let min_frame = light_grid.advance_to_most_compressed_frame(GROWTH_TOLERANCE);
 
+    let render = light_grid.to_string();
+
     solve_parts!(
-        both => {
-            (
-                // Part 1
-                format!(
-                    ":: :: :: Frame #{} (mean centroid dist.: {}) :: :: ::\n{}",
-                    min_frame,
-                    light_grid.compute_mean_dist(),
-                    light_grid
-                ),
-                // Part 2
-                min_frame
-            )
-        }
+        1 => ocr::decode_large(&render)?,
+        2 => min_frame
     )
+    .map(|solution: puzzle::Solution| {
+        if puzzle.visualize() {
+            let frame = format!(
+                ":: :: :: Frame #{} (mean centroid dist.: {}) :: :: ::\n{}",
+                min_frame,
+                light_grid.compute_mean_dist(),
+                render
+            );
+            solution.with_artifact(1, frame)
+        } else {
+            solution
+        }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const EXAMPLE_INPUT: &[&str] = &[
-        "position=< 9,  1> velocity=< 0,  2>",
-        "position=< 7,  0> velocity=<-1,  0>",
-        "position=< 3, -2> velocity=<-1,  1>",
-        "position=< 6, 10> velocity=<-2, -1>",
-        "position=< 2, -4> velocity=< 2,  2>",
-        "position=<-6, 10> velocity=< 2, -2>",
-        "position=< 1,  8> velocity=< 1, -1>",
-        "position=< 1,  7> velocity=< 1,  0>",
-        "position=<-3, 11> velocity=< 1, -2>",
-        "position=< 7,  6> velocity=<-1, -1>",
-        "position=<-2,  3> velocity=< 1,  0>",
-        "position=<-4,  3> velocity=< 2,  0>",
-        "position=<10, -3> velocity=<-1,  1>",
-        "position=< 5, 11> velocity=< 1, -2>",
-        "position=< 4,  7> velocity=< 0, -1>",
-        "position=< 8, -2> velocity=< 0,  1>",
-        "position=<15,  0> velocity=<-2,  0>",
-        "position=< 1,  6> velocity=< 1,  0>",
-        "position=< 8,  9> velocity=< 0, -1>",
-        "position=< 3,  3> velocity=<-1,  1>",
-        "position=< 0,  5> velocity=< 0, -1>",
-        "position=<-2,  2> velocity=< 2,  0>",
-        "position=< 5, -2> velocity=< 1,  2>",
-        "position=< 1,  4> velocity=< 2,  1>",
-        "position=<-2,  7> velocity=< 2, -2>",
-        "position=< 3,  6> velocity=<-1, -1>",
-        "position=< 5,  0> velocity=< 1,  0>",
-        "position=<-6,  0> velocity=< 2,  0>",
-        "position=< 5,  9> velocity=< 1, -2>",
-        "position=<14,  7> velocity=<-2,  0>",
-        "position=<-3,  6> velocity=< 2, -1>",
-    ];
+    use crate::common::testing::parse_lines;
 
     #[test]
     fn parse_light() {
-        let lights: Vec<Light> = EXAMPLE_INPUT[..3]
-            .iter()
-            .map(|&s| s.parse().unwrap())
-            .collect();
+        let lights: Vec<Light> = parse_lines(&[
+            "position=< 9,  1> velocity=< 0,  2>",
+            "position=< 7,  0> velocity=<-1,  0>",
+            "position=< 3, -2> velocity=<-1,  1>",
+        ]);
 
         assert_eq!(
             lights,
@@ -286,43 +255,42 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!(
-            // "RLEZNRAN"
-            ":: :: :: Frame #10240 (mean centroid dist.: 6367) :: :: ::\n\
-             #####...#.......######..######..#....#..#####.....##....#....#\n\
-             #....#..#.......#............#..##...#..#....#...#..#...##...#\n\
-             #....#..#.......#............#..##...#..#....#..#....#..##...#\n\
-             #....#..#.......#...........#...#.#..#..#....#..#....#..#.#..#\n\
-             #####...#.......#####......#....#.#..#..#####...#....#..#.#..#\n\
-             #..#....#.......#.........#.....#..#.#..#..#....######..#..#.#\n\
-             #...#...#.......#........#......#..#.#..#...#...#....#..#..#.#\n\
-             #...#...#.......#.......#.......#...##..#...#...#....#..#...##\n\
-             #....#..#.......#.......#.......#...##..#....#..#....#..#...##\n\
-             #....#..######..######..######..#....#..#....#..#....#..#....#\n",
-            10240,
-            puzzle::Selection::new(2018, 10)
-        );
+        assert_solution!(puzzle::Selection::new(2018, 10));
     }
 
     #[test]
     fn ex_both() {
-        let mut light_grid = LightScreen {
-            lights: EXAMPLE_INPUT.iter().map(|&s| s.parse().unwrap()).collect(),
+        let most_compressed_frame = |input: &str| {
+            let mut light_grid = LightScreen {
+                lights: parse_lines(input.lines()),
+            };
+            let min_frame = light_grid.advance_to_most_compressed_frame(GROWTH_TOLERANCE);
+            (light_grid.to_string(), min_frame)
         };
-        let min_frame = light_grid.advance_to_most_compressed_frame(GROWTH_TOLERANCE);
 
-        assert_eq!(
-            "#...#..###\n\
-             #...#...#.\n\
-             #...#...#.\n\
-             #####...#.\n\
-             #...#...#.\n\
-             #...#...#.\n\
-             #...#...#.\n\
-             #...#..###\n",
-            format!("{}", light_grid),
+        assert_example!(
+            2018, 10, 1,
+            most_compressed_frame => (
+                "#...#..###\n\
+                 #...#...#.\n\
+                 #...#...#.\n\
+                 #####...#.\n\
+                 #...#...#.\n\
+                 #...#...#.\n\
+                 #...#...#.\n\
+                 #...#..###\n"
+                    .to_owned(),
+                3
+            )
         );
+    }
 
-        assert_eq!(3, min_frame);
+    #[test]
+    fn snapshot_render() {
+        let mut light_grid = LightScreen {
+            lights: parse_lines(puzzle::fetch_lines(&puzzle::Selection::new(2018, 10)).unwrap()),
+        };
+        light_grid.advance_to_most_compressed_frame(GROWTH_TOLERANCE);
+        insta::assert_snapshot!(light_grid);
     }
 }
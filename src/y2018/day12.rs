@@ -1,63 +1,74 @@
 //! Solution for Advent of Code [2018 Day 12](https://adventofcode.com/2018/day/12).
 
 use crate::common::puzzle;
+use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::{Debug, Write};
 use std::str::FromStr;
 
-const GENERATION_COUNT_1: usize = 20;
+const GENERATION_COUNT_1: u64 = 20;
 
-const GENERATION_COUNT_2: usize = 5_000_0000_000;
+const GENERATION_COUNT_2: u64 = 50_000_000_000;
 
-/// The size of a plant generation rule for this puzzle.
-const RULE_WIDTH: usize = 5;
+/// The largest supported size of a plant generation rule.
+///
+/// A rule set stores one bit for each of the `2^width` possible patterns.
+const MAX_RULE_WIDTH: usize = 15;
 
 /// A sequence of "pots" either either contain (`true`) or do not contain
 /// (`false`) a plant.
 struct PlantSequence(Vec<bool>);
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-/// Ordinal in [0,31] identifying one of the 32 different possible plant patterns
-/// for this puzzle.
+/// Ordinal identifying one of the `2^width` different possible plant patterns
+/// of a given width.
 ///
 /// # Examples
 ///
+/// For patterns of width 5:
+///
 /// - `0` corresponds to the pattern `.....`
 /// - `1` corresponds to the pattern `#....`
 /// - `2` corresponds to the pattern `.#...`
 /// - `3` corresponds to the pattern `##...`
 /// - `31` corresponds to the pattern `#####`
 struct PlantPattern {
-    ord: u8,
+    ord: u32,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 /// A compact representing of the generation rules that describe how
 /// the potted plants evolve between generations.
 ///
-/// Each of the 32 bits in this rule set describe the output state of the
-/// plant rule with the ordinal value matching the bits index.
+/// Each of the `2^width` bits in this rule set describe the output state of
+/// the plant rule with the ordinal value matching the bits index.
 ///
-/// For example, the first bit corresponds the the plant pattern with ordinal
-/// value `0`, which represents the pattern "`.....`". A `1` in this position
-/// means that the pattern "`.....`" will produce a plant in the next generation,
-/// i.e. "`..... => #`". A `0` in this position means that no plant will be
-/// produced in the next generation, i.e. "`..... => .`".
+/// For example, with a width of 5, the first bit corresponds the the plant
+/// pattern with ordinal value `0`, which represents the pattern "`.....`".
+/// A `1` in this position means that the pattern "`.....`" will produce a
+/// plant in the next generation, i.e. "`..... => #`". A `0` in this position
+/// means that no plant will be produced in the next generation, i.e.
+/// "`..... => .`".
 ///
 /// Similarly, third bit corresponds to the plant pattern with ordinal `2`,
-/// which represents the pattern "`##...`". A `1` in this position corresponds
-/// to the rule "`##... => #`", and a `0` corresponds to the rule
-/// "`##... => .`".
+/// which represents the pattern "`.#...`". A `1` in this position corresponds
+/// to the rule "`.#... => #`", and a `0` corresponds to the rule
+/// "`.#... => .`".
 struct RuleSet {
-    rules: u32,
+    /// The number of pots in each of this rule set's patterns. Always odd.
+    width: usize,
+    rules: Vec<u64>,
 }
 
 #[derive(Debug)]
 struct PlantSimulator {
-    /// The location of the central pot in the plant sequence.
-    center: usize,
+    /// The number of the leftmost pot in `plants`.
+    ///
+    /// The pots on either end of `plants` always contain a plant, unless
+    /// there are no plants at all.
+    first_pot: isize,
     /// The current generation of potted plants.
-    plants: PlantSequence,
+    plants: VecDeque<bool>,
     /// The rules that govern how future generation of potted plants are
     /// produced.
     rule_set: RuleSet,
@@ -71,80 +82,82 @@ impl FromStr for PlantSequence {
             .map(|b| match b {
                 b'.' => Ok(false),
                 b'#' => Ok(true),
-                _ => Err("invalid character found in initial state"),
+                _ => Err("invalid character found in plant sequence"),
             })
             .collect::<Result<_, _>>()
-            .map(|seq| Self(seq))
+            .map(Self)
     }
 }
 
 impl PlantPattern {
-    fn from_slice(plants: &[bool]) -> Result<Self, String> {
-        if plants.len() != RULE_WIDTH {
-            return Err(format!(
-                "plant pattern MUST have a length of {}, {} received",
-                RULE_WIDTH,
-                plants.len()
-            ));
-        }
-        let mut bits = 0;
-        for i in 0..RULE_WIDTH as u8 {
-            if plants[i as usize] {
-                bits |= 1 << i;
-            }
-        }
-        Ok(Self { ord: bits })
-    }
-}
-
-impl Debug for PlantPattern {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        for i in 0..RULE_WIDTH as u8 {
-            f.write_char(if self.ord & (1 << i) == 0 { '.' } else { '#' })?
-        }
-        Ok(())
+    fn from_pots<'a, I>(pots: I) -> Self
+    where
+        I: IntoIterator<Item = &'a bool>,
+    {
+        let ord = pots
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, &has_plant)| has_plant)
+            .fold(0, |bits, (i, _)| bits | 1 << i);
+        Self { ord }
     }
 }
 
 impl RuleSet {
-    fn new() -> Self {
-        Self {
-            rules: Default::default(),
+    /// Creates a rule set for patterns of the given width in which no
+    /// pattern produces a plant.
+    fn new(width: usize) -> Result<Self, String> {
+        if width.is_multiple_of(2) || width > MAX_RULE_WIDTH {
+            return Err(format!(
+                "plant patterns MUST have an odd length of at most {}, {} received",
+                MAX_RULE_WIDTH, width
+            ));
         }
+        Ok(Self {
+            width,
+            rules: vec![0; (1_usize << width).div_ceil(64)],
+        })
     }
 
     fn set_pattern(&mut self, pattern: PlantPattern, result: bool) {
+        let (word, bit) = (pattern.ord as usize / 64, pattern.ord % 64);
         if result {
-            self.rules |= 1 << pattern.ord as u32
+            self.rules[word] |= 1 << bit
         } else {
-            self.rules &= !(1 << pattern.ord as u32);
+            self.rules[word] &= !(1 << bit);
         }
     }
 
-    fn check_pattern(self, pattern: PlantPattern) -> bool {
-        self.rules & (1 << pattern.ord as u32) != 0
+    fn check_pattern(&self, pattern: PlantPattern) -> bool {
+        let (word, bit) = (pattern.ord as usize / 64, pattern.ord % 64);
+        self.rules[word] & (1 << bit) != 0
+    }
+
+    /// Returns a representation of the given pattern.
+    fn pattern_str(&self, pattern: PlantPattern) -> String {
+        (0..self.width)
+            .map(|i| if pattern.ord & (1 << i) == 0 { '.' } else { '#' })
+            .collect()
     }
 }
 
 impl Debug for RuleSet {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.write_char('[')?;
         if f.alternate() {
             f.write_char('\n')?
         }
-        for i in 0..32 {
+        for ord in 0..1 << self.width {
+            let pattern = PlantPattern { ord };
             if f.alternate() {
                 f.write_str("\t")?
             }
-            f.write_str(&format!(
-                "{:?} => {},",
-                PlantPattern { ord: i },
-                if self.check_pattern(PlantPattern { ord: i }) {
-                    '#'
-                } else {
-                    '.'
-                }
-            ))?;
+            write!(
+                f,
+                "{} => {},",
+                self.pattern_str(pattern),
+                if self.check_pattern(pattern) { '#' } else { '.' }
+            )?;
             f.write_char(if f.alternate() { '\n' } else { ' ' })?;
         }
         f.write_char(']')
@@ -153,73 +166,66 @@ impl Debug for RuleSet {
 
 impl PlantSimulator {
     fn new(rules: RuleSet, initial_state: PlantSequence) -> Self {
-        const PADDING_WIDTH: usize = 4;
-        let mut plants: PlantSequence = PlantSequence(Vec::with_capacity(
-            initial_state.0.len() + PADDING_WIDTH * 2,
-        ));
-        // Include four empty pots of padding to handle '....# => #' or '#.... => #' cases
-        for _ in 0..PADDING_WIDTH {
-            plants.0.push(false);
-        }
-        plants.0.extend(initial_state.0);
-        for _ in 0..PADDING_WIDTH {
-            plants.0.push(false);
-        }
-
-        Self {
-            center: PADDING_WIDTH,
-            plants,
+        let mut sim = Self {
+            first_pot: 0,
+            plants: initial_state.0.into(),
             rule_set: rules,
-        }
+        };
+        sim.trim();
+        sim
     }
 
     fn next_generation(&mut self) {
-        // The number of pots in the PlantGeneration to skip over when
-        // determing what pots contain plants in the next generation.
-        const RULE_PADDING: usize = (RULE_WIDTH - 1) / 2;
-        let plant_count = self.plants.0.len();
-
-        let mut next_generation: PlantSequence = PlantSequence(vec![false; plant_count]);
-
-        for i in RULE_PADDING..plant_count - RULE_PADDING {
-            next_generation.0[i] = self.rule_set.check_pattern(
-                PlantPattern::from_slice(&self.plants.0[i - RULE_PADDING..=i + RULE_PADDING])
-                    .unwrap(),
-            );
+        // The number of pots on either side of a pot that determine whether
+        // it contains a plant in the next generation.
+        let rule_padding = self.rule_set.width / 2;
+
+        // Pad the current generation with enough empty pots that every pot
+        // that could contain a plant in the next generation has a full
+        // pattern around it.
+        for _ in 0..2 * rule_padding {
+            self.plants.push_front(false);
+            self.plants.push_back(false);
         }
 
-        // If generation produced a plant in the leftmost checked slot,
-        // add an additional empty pot to account for the expanding
-        // size of the pot sequence.
-        if next_generation.0[RULE_PADDING] {
-            next_generation.0.insert(0, false);
-            self.center += 1;
-        }
+        let rule_set = &self.rule_set;
+        let next_generation: VecDeque<bool> = self
+            .plants
+            .make_contiguous()
+            .windows(rule_set.width)
+            .map(|window| rule_set.check_pattern(PlantPattern::from_pots(window)))
+            .collect();
+
+        self.plants = next_generation;
+        self.first_pot -= rule_padding as isize;
+        self.trim();
+    }
 
-        // If generation produced a plant in the rightmost checked slot,
-        // add an additional empty pot to account for the expanding
-        // size of the pot sequence.
-        if next_generation.0[plant_count - RULE_PADDING - 1] {
-            next_generation.0.push(false);
+    /// Removes the empty pots from either end of the current generation.
+    fn trim(&mut self) {
+        while self.plants.front() == Some(&false) {
+            self.plants.pop_front();
+            self.first_pot += 1;
+        }
+        while self.plants.back() == Some(&false) {
+            self.plants.pop_back();
         }
-        self.plants = next_generation
     }
 
     /// Computes the signed sum of the indices of the pots in this simulator
     /// that currently contain a plant.
     fn pot_checksum(&self) -> isize {
         self.plants
-            .0
             .iter()
             .enumerate()
             .filter(|&(_, &has_plant)| has_plant)
-            .map(|(i, _)| i as isize - self.center as isize)
+            .map(|(i, _)| i as isize + self.first_pot)
             .sum()
     }
 }
 
 impl Debug for PlantSequence {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.write_str(
             &self
                 .0
@@ -230,21 +236,47 @@ impl Debug for PlantSequence {
     }
 }
 
-fn parse_input<S: AsRef<str>>(input: S) -> Result<PlantSimulator, &'static str> {
-    const INITIAL_STATE_START: usize = "initial state: ".len();
+fn parse_input<S: AsRef<str>>(input: S) -> Result<PlantSimulator, String> {
+    const INITIAL_STATE_PREFIX: &str = "initial state: ";
     let mut input_lines = input.as_ref().lines();
 
     // Parse first line of the input as the initial state
-    let initial_state: PlantSequence =
-        input_lines.next().ok_or("input must not be empty")?[INITIAL_STATE_START..].parse()?;
-    let mut rule_set = RuleSet::new();
+    let initial_state: PlantSequence = input_lines
+        .next()
+        .ok_or("input must not be empty")?
+        .strip_prefix(INITIAL_STATE_PREFIX)
+        .ok_or("missing initial state")?
+        .parse()?;
 
     // Parse all lines after line 2 as the plant rules
+    let mut rule_set: Option<RuleSet> = None;
     for l in input_lines.skip(1) {
-        let pattern = l[0..RULE_WIDTH]
-            .parse::<PlantSequence>()
-            .map(|pat| PlantPattern::from_slice(&pat.0[..]).unwrap())?;
-        rule_set.set_pattern(pattern, l.as_bytes()[9] == b'#');
+        let (pattern, result) = l
+            .split_once(" => ")
+            .ok_or_else(|| format!("malformed plant rule: {}", l))?;
+        let pattern: PlantSequence = pattern.parse()?;
+        let result = match result {
+            "#" => true,
+            "." => false,
+            _ => return Err(format!("malformed plant rule result: {}", l)),
+        };
+
+        let rule_set = match rule_set {
+            Some(ref mut rule_set) => rule_set,
+            None => rule_set.get_or_insert(RuleSet::new(pattern.0.len())?),
+        };
+        if pattern.0.len() != rule_set.width {
+            return Err(format!(
+                "plant rules MUST all have the same width, {} received",
+                pattern.0.len()
+            ));
+        }
+        rule_set.set_pattern(PlantPattern::from_pots(&pattern.0), result);
+    }
+
+    let rule_set = rule_set.ok_or("input contains no plant rules")?;
+    if rule_set.check_pattern(PlantPattern { ord: 0 }) {
+        return Err("empty pots MUST NOT produce plants".to_owned());
     }
     Ok(PlantSimulator::new(rule_set, initial_state))
 }
@@ -252,9 +284,11 @@ fn parse_input<S: AsRef<str>>(input: S) -> Result<PlantSimulator, &'static str>
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
     let input = puzzle::fetch_string(puzzle)?;
 
-    let solver = |generations: usize| -> Result<isize, &'static str> {
+    let solver = |generations: u64| -> Result<isize, String> {
         let mut sim = parse_input(&input)?;
         for _ in 0..generations {
+            // Part two runs for far too long, so give up if cancelled.
+            puzzle::cancel::check().map_err(|e| e.to_string())?;
             sim.next_generation();
         }
         Ok(sim.pot_checksum())
@@ -273,35 +307,54 @@ pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
 mod tests {
     use super::*;
 
-    const DEMO_INPUT: &str = "initial state: #..#.#..##......###...###
-
-...## => #
-..#.. => #
-.#... => #
-.#.#. => #
-.#.## => #
-.##.. => #
-.#### => #
-#.#.# => #
-#.### => #
-##.#. => #
-##.## => #
-###.. => #
-###.# => #
-####. => #";
-
     #[test]
     #[ignore]
     fn solution() {
-        assert_solution!(3915, puzzle::Selection::new(2018, 12));
+        assert_solution!(puzzle::Selection::new(2018, 12));
     }
 
     #[test]
     fn ex1() {
-        let mut sim = parse_input(DEMO_INPUT).unwrap();
+        let checksum_after = |input: &str, generations| {
+            let mut sim = parse_input(input).unwrap();
+            for _ in 0..generations {
+                sim.next_generation();
+            }
+            sim.pot_checksum()
+        };
+
+        assert_example!(2018, 12, 1, |input| checksum_after(input, GENERATION_COUNT_1) => 325);
+    }
+
+    #[test]
+    fn unbounded_growth() {
+        // Plants drift left by one pot per generation
+        let mut sim = parse_input("initial state: #\n\n..# => #").unwrap();
+        for _ in 0..100 {
+            sim.next_generation();
+        }
+        assert_eq!(-100, sim.pot_checksum());
+        assert_eq!(1, sim.plants.len());
+
+        // Plants drift right by three pots per generation
+        let mut sim = parse_input("initial state: .#\n\n#...... => #").unwrap();
         for _ in 0..GENERATION_COUNT_1 {
             sim.next_generation();
         }
-        assert_eq!(325, sim.pot_checksum());
+        assert_eq!(61, sim.pot_checksum());
+    }
+
+    #[test]
+    fn reject_bad_rules() {
+        for input in &[
+            "initial state: #\n\n.... => #",
+            "initial state: #\n\n..#.. => #\n..# => #",
+            "initial state: #\n\n..#.. => ?",
+            "initial state: #\n\n..... => #",
+            "initial state: #\n\n",
+            "#\n\n..#.. => #",
+        ] {
+            assert!(parse_input(input).is_err(), "{}", input);
+        }
     }
 }
@@ -2,8 +2,17 @@
 
 use crate::common::puzzle;
 
-mod cart {
+/// Number of ticks between the renderings recorded with `--visualize`.
+///
+/// Part two runs for thousands of ticks, so rendering each one would record
+/// hundreds of megabytes. May be overridden with the `render_every`
+/// parameter.
+const RENDER_INTERVAL: cart::TimeTick = 100;
+
+pub mod cart {
     use crate::common::util::{Grid, IntoPoint, Pt};
+    use std::collections::{HashMap, HashSet};
+    use std::fmt;
     use std::ops::Index;
     use std::str::FromStr;
 
@@ -14,7 +23,14 @@ mod cart {
     /// Integer type used to represent ticks of time.
     pub type TimeTick = u32;
 
-    #[derive(Copy, Clone, Debug)]
+    /// The state of the active carts in a simulation, used to detect when
+    /// a simulation has entered a cycle.
+    type CartState = Vec<(Point, Direction, Turn)>;
+
+    /// Error returned when the carts in a simulation will never collide.
+    const CYCLE_ERROR: &str = "carts are moving in a cycle and will never collide";
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
     /// A way that a chart may choose to turn at an intersection.
     enum Turn {
         Left,
@@ -22,7 +38,7 @@ mod cart {
         Straight,
     }
 
-    #[derive(Copy, Clone, Debug)]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
     /// Cardinal directions in 2D space.
     enum Direction {
         North,
@@ -40,8 +56,10 @@ mod cart {
         ConnectEast,
         // "\"
         ConnectWest,
-        // "-" or "|" or an initial cart position
-        Straight,
+        // "-" or an initial cart position facing east or west
+        Horizontal,
+        // "|" or an initial cart position facing north or south
+        Vertical,
         // "+"
         Intersection,
     }
@@ -67,6 +85,9 @@ mod cart {
         track: CartTrack,
         carts: Vec<Cart>,
         tick: TimeTick,
+        render_interval: Option<TimeTick>,
+        /// The renderings of the track recorded since they were last taken.
+        frames: String,
     }
 
     impl Direction {
@@ -83,6 +104,20 @@ mod cart {
         }
     }
 
+    impl MapTile {
+        /// Returns the character used to represent this tile in a map.
+        fn as_char(self) -> char {
+            match self {
+                MapTile::Blank => ' ',
+                MapTile::ConnectEast => '/',
+                MapTile::ConnectWest => '\\',
+                MapTile::Horizontal => '-',
+                MapTile::Vertical => '|',
+                MapTile::Intersection => '+',
+            }
+        }
+    }
+
     impl Cart {
         /// Cycles this carts turning preferences for intersections according
         /// to the rules specified in the puzzle.
@@ -94,6 +129,19 @@ mod cart {
             }
         }
 
+        /// Returns the character used to represent this cart in a map.
+        fn as_char(&self) -> char {
+            if self.collided {
+                return 'X';
+            }
+            match self.facing {
+                Direction::North => '^',
+                Direction::East => '>',
+                Direction::South => 'v',
+                Direction::West => '<',
+            }
+        }
+
         /// Advances this carts position by one tile in the direction that
         /// it is facing.
         fn advance(&mut self) {
@@ -148,7 +196,8 @@ mod cart {
                         .bytes()
                         .map(|tile| match tile {
                             b' ' => Ok(MapTile::Blank),
-                            b'-' | b'|' | b'<' | b'^' | b'>' | b'v' => Ok(MapTile::Straight),
+                            b'-' | b'<' | b'>' => Ok(MapTile::Horizontal),
+                            b'|' | b'^' | b'v' => Ok(MapTile::Vertical),
                             b'+' => Ok(MapTile::Intersection),
                             b'\\' => Ok(MapTile::ConnectWest),
                             b'/' => Ok(MapTile::ConnectEast),
@@ -189,9 +238,25 @@ mod cart {
                 track,
                 carts,
                 tick: 0,
+                render_interval: None,
+                frames: String::new(),
             })
         }
 
+        /// Enables recording a rendering of this simulation's track after
+        /// every `n` ticks.
+        ///
+        /// Passing zero disables rendering.
+        pub fn render_every_n_ticks(&mut self, n: TimeTick) {
+            self.render_interval = if n == 0 { None } else { Some(n) };
+        }
+
+        /// Returns the renderings recorded since the last call, each headed
+        /// by its tick.
+        pub fn take_frames(&mut self) -> String {
+            std::mem::take(&mut self.frames)
+        }
+
         /// Advance the track simulation until a collision between two carts occurs.
         ///
         /// If multiple collisions occur during the same simulation tick, all
@@ -199,8 +264,14 @@ mod cart {
         /// occurred first (i.e. the upper-left most collision) will be returned.
         ///
         /// Solves Part 1 of the puzzle.
+        ///
+        /// An error is returned if the carts enter a cycle without colliding.
         pub fn run_until_next_collision(&mut self) -> Result<(TimeTick, Point), &'static str> {
+            let mut seen_states: HashSet<CartState> = HashSet::new();
             loop {
+                if !seen_states.insert(self.cart_state()) {
+                    return Err(CYCLE_ERROR);
+                }
                 if let Some(point) = self.run_tick()?.first() {
                     return Ok((self.tick, *point));
                 }
@@ -209,11 +280,18 @@ mod cart {
 
         /// Advances the track simulation until there is only a single cart left.
         ///
-        /// Note: this function assumes that the carts *will* eventually collide.
-        /// Bad puzzle inputs may result in an infinite loop.
+        /// An error is returned if the remaining carts enter a cycle without
+        /// colliding.
         pub fn run_until_last_cart(&mut self) -> Result<(TimeTick, Point), &'static str> {
+            let mut seen_states: HashSet<CartState> = HashSet::new();
             while self.active_carts().len() > 1 {
-                self.run_tick()?;
+                if !seen_states.insert(self.cart_state()) {
+                    return Err(CYCLE_ERROR);
+                }
+                if !self.run_tick()?.is_empty() {
+                    // States from before a collision can never recur
+                    seen_states.clear();
+                }
             }
 
             self.active_carts()
@@ -270,9 +348,28 @@ mod cart {
             self.carts
                 .sort_unstable_by_key(|&Cart { pos, .. }| (pos.y, pos.x));
 
+            if let Some(interval) = self.render_interval {
+                if self.tick.is_multiple_of(interval) {
+                    let frame = format!("Tick {}:\n{}\n", self.tick, self);
+                    self.frames.push_str(&frame);
+                }
+            }
+
             Ok(collisions)
         }
 
+        /// Returns the state of the carts in this simulation that have not
+        /// yet collided.
+        ///
+        /// Since the carts always move in the same order, the simulation
+        /// will repeat itself from any state that recurs.
+        fn cart_state(&self) -> CartState {
+            self.active_carts()
+                .into_iter()
+                .map(|cart| (cart.pos, cart.facing, cart.turn_preference))
+                .collect()
+        }
+
         /// Returns a collection of references to all of the carts in this
         /// simulation that have not yet collided.
         fn active_carts(&self) -> Vec<&Cart> {
@@ -280,6 +377,34 @@ mod cart {
         }
     }
 
+    impl fmt::Display for TrackSimulator {
+        /// Draws the track with carts (`^v<>`) at their current positions.
+        ///
+        /// Collisions are marked with an `X`. Active carts are drawn over
+        /// the sites of earlier collisions.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut carts: HashMap<Point, char> = HashMap::new();
+            for cart in &self.carts {
+                let marker = carts.entry(cart.pos).or_insert('X');
+                if !cart.collided {
+                    *marker = cart.as_char();
+                }
+            }
+
+            for (row, tiles) in self.track.path.iter().enumerate() {
+                if row > 0 {
+                    writeln!(f)?;
+                }
+                for (col, tile) in tiles.iter().enumerate() {
+                    let point = (col as PointScalar, row as PointScalar).into_pt();
+                    let c = carts.get(&point).cloned().unwrap_or_else(|| tile.as_char());
+                    write!(f, "{}", c)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
     /// Returns a sequence of `Cart` instances representing the positions of
     /// carts in the given puzzle input.
     fn find_carts(s: &str) -> Vec<Cart> {
@@ -333,17 +458,29 @@ mod cart {
 pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
     let input = puzzle::fetch_string(puzzle)?;
     let mut sim = cart::TrackSimulator::from_puzzle_input(&input)?;
+    if puzzle.visualize() {
+        sim.render_every_n_ticks(puzzle.param_or("render_every", RENDER_INTERVAL)?);
+    }
 
+    let mut frames = [String::new(), String::new()];
     solve_parts!(
         1 => {
             let (_, collision) = sim.run_until_next_collision()?;
+            frames[0] = sim.take_frames();
             format!("{},{}", collision.x, collision.y)
         },
         2 => {
             let (_, last_cart) = sim.run_until_last_cart()?;
+            frames[1] = sim.take_frames();
             format!("{},{}", last_cart.x, last_cart.y)
         },
     )
+    .map(|solution: puzzle::Solution| {
+        let recorded = (1..=2).zip(frames).filter(|(_, frames)| !frames.is_empty());
+        recorded.fold(solution, |solution, (part, frames)| {
+            solution.with_artifact(part, frames)
+        })
+    })
 }
 
 #[cfg(test)]
@@ -353,7 +490,7 @@ mod tests {
 
     #[test]
     fn solution() {
-        assert_solution!("33,69", "135,9", puzzle::Selection::new(2018, 13),)
+        assert_solution!(puzzle::Selection::new(2018, 13))
     }
 
     #[test]
@@ -387,6 +524,62 @@ mod tests {
         assert_eq!((7, 3).into_pt(), collision);
     }
 
+    #[test]
+    fn never_collide() {
+        const INPUT: &str = "/>-\\  /-<\\\n\
+                             |  |  |  |\n\
+                             \\--/  \\--/";
+        let mut sim = cart::TrackSimulator::from_puzzle_input(INPUT).unwrap();
+        assert!(sim.run_until_next_collision().is_err());
+
+        let mut sim = cart::TrackSimulator::from_puzzle_input(INPUT).unwrap();
+        assert!(sim.run_until_last_cart().is_err());
+    }
+
+    #[test]
+    fn render_track() {
+        const INPUT: &str = "/->-\\\n\
+                             |   |  /----\\\n\
+                             | /-+--+-\\  |\n\
+                             | | |  | v  |\n\
+                             \\-+-/  \\-+--/\n\
+                             \\------/ ";
+        let mut sim = cart::TrackSimulator::from_puzzle_input(INPUT).unwrap();
+        assert_eq!(INPUT, sim.to_string());
+        sim.render_every_n_ticks(2);
+
+        sim.run_tick().unwrap();
+        assert_eq!(
+            "/-->\\\n\
+             |   |  /----\\\n\
+             | /-+--+-\\  |\n\
+             | | |  | |  |\n\
+             \\-+-/  \\->--/\n\
+             \\------/ ",
+            sim.to_string()
+        );
+
+        sim.run_until_next_collision().unwrap();
+        assert_eq!(
+            "/---\\\n\
+             |   |  /----\\\n\
+             | /-+--+-\\  |\n\
+             | | |  X |  |\n\
+             \\-+-/  \\-+--/\n\
+             \\------/ ",
+            sim.to_string()
+        );
+        let frames = sim.take_frames();
+        assert!(frames.starts_with("Tick 2:\n/---v\n|   |  /----\\\n"));
+        let ticks: Vec<&str> = frames
+            .lines()
+            .filter(|line| line.starts_with("Tick"))
+            .collect();
+        assert_eq!(7, ticks.len());
+        assert_eq!(Some(&"Tick 14:"), ticks.last());
+        assert!(sim.take_frames().is_empty());
+    }
+
     #[test]
     fn ex2() {
         const INPUT: &str = "/>-<\\
@@ -396,7 +589,7 @@ mod tests {
 \\>+</ |
   |   ^
   \\<->/";
-        let (tick_count, final_cart) = cart::TrackSimulator::from_puzzle_input(&INPUT)
+        let (tick_count, final_cart) = cart::TrackSimulator::from_puzzle_input(INPUT)
             .unwrap()
             .run_until_last_cart()
             .unwrap();
@@ -0,0 +1,163 @@
+//! Solution for Advent of Code [2018 Day 20](https://adventofcode.com/2018/day/20).
+
+use crate::common::puzzle;
+use crate::common::util::search::breadth_first;
+use crate::common::util::Pt;
+use std::collections::{HashMap, HashSet};
+
+/// Point type used to represent the location of a room in the facility.
+type Room = Pt<i32>;
+
+/// Minimum number of doors that must be passed through to reach the rooms
+/// counted in Part 2.
+const FAR_ROOM_DOORS: usize = 1000;
+
+/// A map of the facility, recording the doors that connect adjacent rooms.
+struct FacilityMap {
+    doors: HashMap<Room, HashSet<Room>>,
+}
+
+impl FacilityMap {
+    /// Builds a facility map by following every route described by the
+    /// given regex, starting from the room at the origin.
+    ///
+    /// Branches are handled with a stack of the positions at which each
+    /// open group was entered, so every alternative in a group starts from
+    /// the same room.
+    fn from_regex(regex: &str) -> Result<Self, String> {
+        let regex = regex.trim();
+        let body = regex
+            .strip_prefix('^')
+            .and_then(|r| r.strip_suffix('$'))
+            .ok_or("route regex must be enclosed in '^' and '$'")?;
+
+        let mut doors: HashMap<Room, HashSet<Room>> = HashMap::new();
+        let mut stack: Vec<HashSet<Room>> = Vec::new();
+        let mut branch_ends: Vec<HashSet<Room>> = Vec::new();
+        // The set of rooms the routes described so far may currently be in.
+        let mut current: HashSet<Room> = std::iter::once(Pt::origin()).collect();
+
+        for ch in body.chars() {
+            match ch {
+                'N' | 'E' | 'S' | 'W' => {
+                    let step = match ch {
+                        'N' => Pt::n(),
+                        'E' => Pt::e(),
+                        'S' => Pt::s(),
+                        _ => Pt::w(),
+                    };
+                    current = current
+                        .into_iter()
+                        .map(|room| {
+                            let next = room + step;
+                            doors.entry(room).or_default().insert(next);
+                            doors.entry(next).or_default().insert(room);
+                            next
+                        })
+                        .collect();
+                }
+                '(' => {
+                    stack.push(current.clone());
+                    branch_ends.push(HashSet::new());
+                }
+                '|' => {
+                    let start = stack.last().ok_or("'|' outside of a group")?;
+                    let ends = branch_ends.last_mut().unwrap();
+                    ends.extend(current.drain());
+                    current = start.clone();
+                }
+                ')' => {
+                    stack.pop().ok_or("unmatched ')'")?;
+                    let mut ends = branch_ends.pop().unwrap();
+                    ends.extend(current.drain());
+                    current = ends;
+                }
+                _ => return Err(format!("unexpected character '{}' in route regex", ch)),
+            }
+        }
+
+        if !stack.is_empty() {
+            return Err("unmatched '('".to_owned());
+        }
+
+        Ok(Self { doors })
+    }
+
+    /// Returns an iterator over every room in the facility along with the
+    /// fewest number of doors that must be passed through to reach it from
+    /// the starting room.
+    fn room_distances(&self) -> impl Iterator<Item = (Room, usize)> + '_ {
+        breadth_first(Pt::origin(), move |room: &Room| {
+            self.doors.get(room).into_iter().flatten().copied()
+        })
+    }
+
+    /// Returns the fewest number of doors that must be passed through to
+    /// reach the furthest room.
+    ///
+    /// Solves Part 1.
+    fn furthest_room_doors(&self) -> usize {
+        self.room_distances()
+            .map(|(_, doors)| doors)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of rooms whose shortest path from the starting
+    /// room passes through at least `min_doors` doors.
+    ///
+    /// Solves Part 2.
+    fn count_rooms_at_least(&self, min_doors: usize) -> usize {
+        self.room_distances()
+            .filter(|&(_, doors)| doors >= min_doors)
+            .count()
+    }
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let map = FacilityMap::from_regex(&puzzle::fetch_string(puzzle)?)?;
+
+    solve_parts!(
+        1 => map.furthest_room_doors(),
+        2 => map.count_rooms_at_least(FAR_ROOM_DOORS),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let test_cases = [
+            ("^WNE$", 3),
+            ("^ENWWW(NEEE|SSE(EE|N))$", 10),
+            ("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$", 18),
+            ("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", 23),
+            (
+                "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$",
+                31,
+            ),
+        ];
+
+        for &(regex, expected) in test_cases.iter() {
+            let map = FacilityMap::from_regex(regex).unwrap();
+            assert_eq!(expected, map.furthest_room_doors(), "{}", regex);
+        }
+    }
+
+    #[test]
+    fn far_room_count() {
+        let map = FacilityMap::from_regex("^ENWWW(NEEE|SSE(EE|N))$").unwrap();
+        assert_eq!(16, map.count_rooms_at_least(0));
+        assert_eq!(4, map.count_rooms_at_least(9));
+    }
+
+    #[test]
+    fn reject_malformed_regex() {
+        assert!(FacilityMap::from_regex("WNE").is_err());
+        assert!(FacilityMap::from_regex("^(N|S$").is_err());
+        assert!(FacilityMap::from_regex("^N)$").is_err());
+        assert!(FacilityMap::from_regex("^NX$").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Solution for Advent of Code [2018 Day 22](https://adventofcode.com/2018/day/22).
+
+use crate::common::puzzle;
+use crate::common::util::search::dijkstra;
+use crate::common::util::{extract_ints, Pt};
+use std::collections::HashMap;
+
+/// Point type used to represent the location of a region in the cave.
+type Region = Pt<i32>;
+
+/// Integral type used to represent erosion levels and geologic indices.
+type Level = u64;
+
+/// Integral type used to represent the number of minutes taken to reach
+/// a region.
+type Minutes = u32;
+
+/// Minutes taken to move into an adjacent region.
+const MOVE_MINUTES: Minutes = 1;
+
+/// Minutes taken to switch to a different tool.
+const SWITCH_MINUTES: Minutes = 7;
+
+/// The type of a region in the cave.
+///
+/// The discriminant of each type is equal to its risk level.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum RegionType {
+    Rocky = 0,
+    Wet = 1,
+    Narrow = 2,
+}
+
+/// A tool that may be equipped while exploring the cave.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum Tool {
+    Neither,
+    Torch,
+    ClimbingGear,
+}
+
+impl RegionType {
+    /// Returns the tool that cannot be equipped in a region of this type.
+    fn forbidden_tool(self) -> Tool {
+        match self {
+            RegionType::Rocky => Tool::Neither,
+            RegionType::Wet => Tool::Torch,
+            RegionType::Narrow => Tool::ClimbingGear,
+        }
+    }
+
+    /// Returns `true` if the given tool may be equipped in a region of
+    /// this type.
+    fn allows(self, tool: Tool) -> bool {
+        tool != self.forbidden_tool()
+    }
+}
+
+/// A cave system, with the erosion level of each region computed as it is
+/// needed.
+struct Cave {
+    depth: Level,
+    target: Region,
+    erosion_levels: HashMap<Region, Level>,
+}
+
+impl Cave {
+    /// Creates a new cave with the given depth and target location.
+    fn new(depth: Level, target: Region) -> Self {
+        Self {
+            depth,
+            target,
+            erosion_levels: HashMap::new(),
+        }
+    }
+
+    /// Parses a cave from a description of its depth and target location.
+    fn parse(description: &str) -> Result<Self, &'static str> {
+        match extract_ints(description).collect::<Vec<i64>>()[..] {
+            [depth, x, y] if depth >= 0 && x >= 0 && y >= 0 => Ok(Self::new(
+                depth as Level,
+                Pt {
+                    x: x as i32,
+                    y: y as i32,
+                },
+            )),
+            _ => Err("cave description must contain a depth and a target coordinate"),
+        }
+    }
+
+    /// Returns the erosion level of the given region, computing and caching
+    /// it and the levels it depends on if they have not been seen before.
+    fn erosion_level(&mut self, region: Region) -> Level {
+        if let Some(&level) = self.erosion_levels.get(&region) {
+            return level;
+        }
+
+        let geologic_index = if region == Pt::origin() || region == self.target {
+            0
+        } else if region.y == 0 {
+            region.x as Level * 16807
+        } else if region.x == 0 {
+            region.y as Level * 48271
+        } else {
+            self.erosion_level(region + Pt::w()) * self.erosion_level(region + Pt::s())
+        };
+
+        let level = (geologic_index + self.depth) % 20183;
+        self.erosion_levels.insert(region, level);
+        level
+    }
+
+    /// Returns the type of the given region.
+    fn region_type(&mut self, region: Region) -> RegionType {
+        match self.erosion_level(region) % 3 {
+            0 => RegionType::Rocky,
+            1 => RegionType::Wet,
+            _ => RegionType::Narrow,
+        }
+    }
+
+    /// Returns the total risk level of the smallest rectangle containing
+    /// the cave mouth and the target.
+    ///
+    /// Solves Part 1.
+    fn total_risk(&mut self) -> u32 {
+        let target = self.target;
+        let mut risk = 0;
+        for y in 0..=target.y {
+            for x in 0..=target.x {
+                risk += self.region_type(Pt { x, y }) as u32;
+            }
+        }
+        risk
+    }
+
+    /// Returns the fewest number of minutes needed to reach the target
+    /// from the cave mouth, starting and finishing with the torch equipped.
+    ///
+    /// Solves Part 2.
+    fn fastest_rescue(&mut self) -> Option<Minutes> {
+        let target = (self.target, Tool::Torch);
+
+        dijkstra(
+            (Pt::origin(), Tool::Torch),
+            |&(region, tool): &(Region, Tool)| {
+                let mut next = Vec::with_capacity(5);
+
+                let region_type = self.region_type(region);
+                for &other in &[Tool::Neither, Tool::Torch, Tool::ClimbingGear] {
+                    if other != tool && region_type.allows(other) {
+                        next.push(((region, other), SWITCH_MINUTES));
+                    }
+                }
+
+                for adjacent in region.nb_card() {
+                    if adjacent.x >= 0 && adjacent.y >= 0 && self.region_type(adjacent).allows(tool)
+                    {
+                        next.push(((adjacent, tool), MOVE_MINUTES));
+                    }
+                }
+
+                next
+            },
+            |state| *state == target,
+        )
+        .map(|(_, minutes)| minutes)
+    }
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let mut cave = Cave::parse(&puzzle::fetch_string(puzzle)?)?;
+
+    solve_parts!(
+        1 => cave.total_risk(),
+        2 => cave.fastest_rescue().ok_or("target cannot be reached")?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::util::IntoPoint;
+
+    const EXAMPLE: &str = "depth: 510\ntarget: 10,10\n";
+
+    #[test]
+    fn erosion_levels() {
+        let mut cave = Cave::parse(EXAMPLE).unwrap();
+
+        let test_cases = [
+            ((0, 0), 510, RegionType::Rocky),
+            ((1, 0), 17317, RegionType::Wet),
+            ((0, 1), 8415, RegionType::Rocky),
+            ((1, 1), 1805, RegionType::Narrow),
+            ((10, 10), 510, RegionType::Rocky),
+        ];
+
+        for &((x, y), level, region_type) in test_cases.iter() {
+            assert_eq!(level, cave.erosion_level((x, y).into_pt()));
+            assert_eq!(region_type, cave.region_type((x, y).into_pt()));
+        }
+    }
+
+    #[test]
+    fn ex1() {
+        assert_eq!(114, Cave::parse(EXAMPLE).unwrap().total_risk());
+    }
+
+    #[test]
+    fn ex2() {
+        assert_eq!(Some(45), Cave::parse(EXAMPLE).unwrap().fastest_rescue());
+    }
+}
@@ -0,0 +1,240 @@
+//! Solution for Advent of Code [2018 Day 23](https://adventofcode.com/2018/day/23).
+
+use crate::common::puzzle;
+use crate::common::util::{extract_ints, Pt3};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Integral type used to represent coordinates and distances.
+type Coord = i64;
+
+/// Point type used to represent the position of a nanobot.
+type Point = Pt3<Coord>;
+
+/// A nanobot with a signal radius.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Nanobot {
+    pos: Point,
+    radius: Coord,
+}
+
+impl Nanobot {
+    /// Parses a nanobot from a line of the form `pos=<x,y,z>, r=radius`.
+    fn parse(line: &str) -> Result<Self, String> {
+        match extract_ints(line).collect::<Vec<Coord>>()[..] {
+            [x, y, z, radius] if radius >= 0 => Ok(Nanobot {
+                pos: Pt3::new(x, y, z),
+                radius,
+            }),
+            _ => Err(format!("invalid nanobot description: '{}'", line)),
+        }
+    }
+
+    /// Returns `true` if the given point is within this nanobot's signal
+    /// radius.
+    fn in_range(&self, point: Point) -> bool {
+        self.pos.dist_manh(point) <= self.radius
+    }
+
+    /// Returns `true` if any point of the given cube is within this
+    /// nanobot's signal radius.
+    fn reaches(&self, cube: &Cube) -> bool {
+        cube.dist_manh(self.pos) <= self.radius
+    }
+}
+
+/// An axis-aligned cube of integer points.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct Cube {
+    /// The side length of the cube. Always a power of two.
+    side: Coord,
+    /// The minimum corner of the cube.
+    min: (Coord, Coord, Coord),
+}
+
+impl Cube {
+    /// Returns the smallest cube with a power-of-two side length that
+    /// contains every given point.
+    fn bounding(points: impl Iterator<Item = Point>) -> Self {
+        let mut lo: Point = Pt3::origin();
+        let mut hi: Point = Pt3::origin();
+        for p in points {
+            lo = Pt3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z));
+            hi = Pt3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z));
+        }
+        let span = hi - lo;
+        let extent = span.x.max(span.y).max(span.z) + 1;
+
+        let mut side = 1;
+        while side < extent {
+            side *= 2;
+        }
+
+        Cube {
+            side,
+            min: (lo.x, lo.y, lo.z),
+        }
+    }
+
+    /// Returns the manhattan distance between the given point and the
+    /// closest point of this cube.
+    fn dist_manh(&self, point: Point) -> Coord {
+        let axis_dist = |p: Coord, lo: Coord| {
+            let hi = lo + self.side - 1;
+            if p < lo {
+                lo - p
+            } else if p > hi {
+                p - hi
+            } else {
+                0
+            }
+        };
+        axis_dist(point.x, self.min.0)
+            + axis_dist(point.y, self.min.1)
+            + axis_dist(point.z, self.min.2)
+    }
+
+    /// Splits this cube into its eight octants.
+    fn octants(&self) -> impl Iterator<Item = Cube> + '_ {
+        let half = self.side / 2;
+        (0..8).map(move |i| Cube {
+            side: half,
+            min: (
+                self.min.0 + (i & 1) * half,
+                self.min.1 + (i >> 1 & 1) * half,
+                self.min.2 + (i >> 2 & 1) * half,
+            ),
+        })
+    }
+}
+
+/// Returns the number of nanobots in range of the nanobot with the
+/// largest signal radius.
+///
+/// Solves Part 1.
+fn strongest_range_count(bots: &[Nanobot]) -> Option<usize> {
+    let strongest = bots.iter().max_by_key(|bot| bot.radius)?;
+    Some(
+        bots.iter()
+            .filter(|bot| strongest.in_range(bot.pos))
+            .count(),
+    )
+}
+
+/// Returns the shortest manhattan distance from the origin to a point in
+/// range of the largest number of nanobots.
+///
+/// The search repeatedly subdivides the cube containing every nanobot into
+/// octants, always exploring the cube reached by the most nanobots first.
+/// Since the number of nanobots reaching a cube is an upper bound on the
+/// number in range of any of its points, the first single point reached is
+/// optimal.
+///
+/// Ties are broken by a lower bound on the distance from the origin to any
+/// point of the cube that is in range of every nanobot reaching it. Such
+/// points are the only ones that can attain the cube's count, and the bound
+/// is exact once a cube has shrunk to a single point. Remaining ties favor
+/// smaller cubes, so that the search dives towards single points.
+///
+/// Solves Part 2.
+fn best_position_distance(bots: &[Nanobot]) -> Option<Coord> {
+    if bots.is_empty() {
+        return None;
+    }
+
+    let cube_entry = |cube: Cube| {
+        let origin_dist = cube.dist_manh(Pt3::origin());
+        let mut count = 0;
+        let mut min_dist = origin_dist;
+        for bot in bots.iter().filter(|bot| bot.reaches(&cube)) {
+            count += 1;
+            min_dist = min_dist.max(bot.pos.dist_manh(Pt3::origin()) - bot.radius);
+        }
+        (
+            count,
+            Reverse(min_dist),
+            Reverse(cube.side),
+            Reverse(origin_dist),
+            Reverse(cube),
+        )
+    };
+
+    let mut queue = BinaryHeap::new();
+    queue.push(cube_entry(Cube::bounding(bots.iter().map(|bot| bot.pos))));
+
+    while let Some((_, Reverse(dist), _, _, Reverse(cube))) = queue.pop() {
+        if cube.side == 1 {
+            return Some(dist);
+        }
+        queue.extend(cube.octants().map(cube_entry));
+    }
+    None
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let bots: Vec<Nanobot> = puzzle::fetch_lines(puzzle)?
+        .iter()
+        .map(|line| Nanobot::parse(line))
+        .collect::<Result<_, _>>()?;
+
+    solve_parts!(
+        1 => strongest_range_count(&bots).ok_or("no nanobots")?,
+        2 => best_position_distance(&bots).ok_or("no nanobots")?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_bots(input: &str) -> Vec<Nanobot> {
+        input.lines().map(|l| Nanobot::parse(l).unwrap()).collect()
+    }
+
+    #[test]
+    fn ex1() {
+        let bots = parse_bots(
+            "pos=<0,0,0>, r=4\n\
+             pos=<1,0,0>, r=1\n\
+             pos=<4,0,0>, r=3\n\
+             pos=<0,2,0>, r=1\n\
+             pos=<0,5,0>, r=3\n\
+             pos=<0,0,3>, r=1\n\
+             pos=<1,1,1>, r=1\n\
+             pos=<1,1,2>, r=1\n\
+             pos=<1,3,1>, r=1",
+        );
+        assert_eq!(Some(7), strongest_range_count(&bots));
+    }
+
+    #[test]
+    fn ex2() {
+        let bots = parse_bots(
+            "pos=<10,12,12>, r=2\n\
+             pos=<12,14,12>, r=2\n\
+             pos=<16,12,12>, r=4\n\
+             pos=<14,14,14>, r=6\n\
+             pos=<50,50,50>, r=200\n\
+             pos=<10,10,10>, r=5",
+        );
+        assert_eq!(Some(36), best_position_distance(&bots));
+    }
+
+    #[test]
+    fn cube_distance() {
+        let cube = Cube {
+            side: 4,
+            min: (0, 0, 0),
+        };
+        assert_eq!(0, cube.dist_manh(Pt3::new(3, 3, 3)));
+        assert_eq!(1, cube.dist_manh(Pt3::new(4, 3, 3)));
+        assert_eq!(6, cube.dist_manh(Pt3::new(-1, 5, 6)));
+        assert_eq!(8, cube.octants().count());
+    }
+
+    #[test]
+    fn reject_malformed_bot() {
+        assert!(Nanobot::parse("pos=<1,2>, r=3").is_err());
+        assert!(Nanobot::parse("pos=<1,2,3>, r=-1").is_err());
+    }
+}
@@ -10,6 +10,9 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
+pub mod day20;
+pub mod day22;
+pub mod day23;
 
 route_days! {
     1 => day01,
@@ -24,4 +27,17 @@ route_days! {
     11 => day11,
     12 => day12,
     13 => day13,
+    20 => day20,
+    22 => day22,
+    23 => day23,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_implemented_days() {
+        assert_routes!(2018, [1, 2, 3, 4, 5, 6, 7, 8, 10, 11, 12, 13, 20, 22, 23]);
+    }
 }
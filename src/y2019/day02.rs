@@ -0,0 +1,77 @@
+//! Solution for Advent of Code [2019 Day 02](https://adventofcode.com/2019/day/2).
+
+use crate::common::puzzle;
+use crate::common::vm::intcode::{self, Int, Machine};
+
+/// The output produced by the gravity assist program for the noun and verb
+/// that Part 2 searches for.
+const TARGET_OUTPUT: Int = 19_690_720;
+
+/// Runs the given program with the given noun and verb, returning the value
+/// left at address 0 once it halts.
+fn run_with(program: &[Int], noun: Int, verb: Int) -> Result<Int, intcode::Error> {
+    let mut machine = Machine::new(program.to_vec());
+    machine.write(1, noun);
+    machine.write(2, verb);
+    machine.run_with_input(&[])?;
+    Ok(machine.read(0))
+}
+
+/// Searches for the noun and verb that cause the given program to produce
+/// the target output, returning `100 * noun + verb` if they exist.
+///
+/// A noun and verb that cause the program to fail are not a match.
+///
+/// Solves Part 2.
+fn find_noun_verb(program: &[Int]) -> Option<Int> {
+    for noun in 0..=99 {
+        for verb in 0..=99 {
+            if run_with(program, noun, verb).ok() == Some(TARGET_OUTPUT) {
+                return Some(100 * noun + verb);
+            }
+        }
+    }
+    None
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let program = intcode::parse_program(&puzzle::fetch_string(puzzle)?)?;
+
+    solve_parts!(
+        1 => run_with(&program, 12, 2)?,
+        2 => find_noun_verb(&program).ok_or("no noun and verb produce the target output")?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ex1() {
+        let test_cases = [
+            ("1,9,10,3,2,3,11,0,99,30,40,50", 3500),
+            ("1,0,0,0,99", 2),
+            ("2,3,0,3,99", 2),
+            ("2,4,4,5,99,0", 2),
+            ("1,1,1,4,99,5,6,0,99", 30),
+        ];
+
+        for &(program, expected) in test_cases.iter() {
+            let mut machine = Machine::new(intcode::parse_program(program).unwrap());
+            machine.run_with_input(&[]).unwrap();
+            assert_eq!(expected, machine.read(0), "{}", program);
+        }
+    }
+
+    #[test]
+    fn skip_faulting_inputs() {
+        // The noun and verb are summed into the opcode of the second
+        // instruction, which is invalid for a sum of zero
+        let program = intcode::parse_program("1101,0,0,4,0,9,10,0,99,19690000,720").unwrap();
+        assert_eq!(Some(1), find_noun_verb(&program));
+
+        let program = intcode::parse_program("1101,0,0,4,0,99").unwrap();
+        assert_eq!(None, find_noun_verb(&program));
+    }
+}
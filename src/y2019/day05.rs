@@ -0,0 +1,102 @@
+//! Solution for Advent of Code [2019 Day 05](https://adventofcode.com/2019/day/5).
+
+use crate::common::puzzle;
+use crate::common::vm::intcode::{self, Int, Machine};
+
+/// The ID of the ship's air conditioner unit, used as input for Part 1.
+const AIR_CONDITIONER_ID: Int = 1;
+
+/// The ID of the ship's thermal radiator controller, used as input for
+/// Part 2.
+const THERMAL_RADIATOR_ID: Int = 5;
+
+/// Runs the diagnostic program for the system with the given ID, returning
+/// the diagnostic code it produces.
+///
+/// Every output before the diagnostic code is the result of a test, which
+/// must be `0` for the test to have passed.
+fn run_diagnostic(program: &[Int], system_id: Int) -> Result<Int, String> {
+    let output = Machine::new(program.to_vec())
+        .run_with_input(&[system_id])
+        .map_err(|err| err.to_string())?;
+
+    match output.split_last() {
+        Some((&code, tests)) => match tests.iter().position(|&result| result != 0) {
+            Some(index) => Err(format!("diagnostic test {} failed", index + 1)),
+            None => Ok(code),
+        },
+        None => Err("diagnostic program produced no output".to_owned()),
+    }
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let program = intcode::parse_program(&puzzle::fetch_string(puzzle)?)?;
+
+    solve_parts!(
+        1 => run_diagnostic(&program, AIR_CONDITIONER_ID)?,
+        2 => run_diagnostic(&program, THERMAL_RADIATOR_ID)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Larger example program from Part 2, which outputs 999 if its input
+    /// is below 8, 1000 if its input is equal to 8, and 1001 otherwise.
+    const COMPARE_TO_EIGHT: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+                                    1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+                                    999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+
+    fn run(program: &str, input: Int) -> Vec<Int> {
+        Machine::new(intcode::parse_program(program).unwrap())
+            .run_with_input(&[input])
+            .unwrap()
+    }
+
+    #[test]
+    fn ex1() {
+        let mut machine = Machine::new(intcode::parse_program("1002,4,3,4,33").unwrap());
+        machine.run_with_input(&[]).unwrap();
+        assert_eq!(99, machine.read(4));
+
+        assert_eq!(vec![17], run("3,0,4,0,99", 17));
+    }
+
+    #[test]
+    fn ex2() {
+        let comparisons = [
+            ("3,9,8,9,10,9,4,9,99,-1,8", [0, 1, 0]),
+            ("3,9,7,9,10,9,4,9,99,-1,8", [1, 0, 0]),
+            ("3,3,1108,-1,8,3,4,3,99", [0, 1, 0]),
+            ("3,3,1107,-1,8,3,4,3,99", [1, 0, 0]),
+        ];
+        for &(program, expected) in comparisons.iter() {
+            for (&input, &output) in [7, 8, 9].iter().zip(expected.iter()) {
+                assert_eq!(vec![output], run(program, input), "{}", program);
+            }
+        }
+
+        for &program in &[
+            "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+            "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+        ] {
+            assert_eq!(vec![0], run(program, 0));
+            assert_eq!(vec![1], run(program, -5));
+        }
+
+        assert_eq!(vec![999], run(COMPARE_TO_EIGHT, 7));
+        assert_eq!(vec![1000], run(COMPARE_TO_EIGHT, 8));
+        assert_eq!(vec![1001], run(COMPARE_TO_EIGHT, 9));
+    }
+
+    #[test]
+    fn failed_diagnostic() {
+        // Outputs 1 (a failed test) followed by the input
+        let program = intcode::parse_program("104,1,3,0,4,0,99").unwrap();
+        assert!(run_diagnostic(&program, 5).is_err());
+
+        let program = intcode::parse_program("104,0,3,0,4,0,99").unwrap();
+        assert_eq!(Ok(5), run_diagnostic(&program, 5));
+    }
+}
@@ -0,0 +1,73 @@
+//! Solution for Advent of Code [2019 Day 09](https://adventofcode.com/2019/day/9).
+
+use crate::common::puzzle;
+use crate::common::vm::intcode::{self, Int, Machine};
+
+/// Input that runs the BOOST program in test mode.
+const TEST_MODE: Int = 1;
+
+/// Input that runs the BOOST program in sensor boost mode.
+const SENSOR_BOOST_MODE: Int = 2;
+
+/// Runs the BOOST program in the given mode, returning the single value
+/// that it outputs.
+///
+/// In test mode, the program outputs each opcode that it found to be
+/// malfunctioning before its keycode.
+fn run_boost(program: &[Int], mode: Int) -> Result<Int, String> {
+    let output = Machine::new(program.to_vec())
+        .run_with_input(&[mode])
+        .map_err(|err| err.to_string())?;
+
+    match output[..] {
+        [value] => Ok(value),
+        [] => Err("BOOST program produced no output".to_owned()),
+        _ => Err(format!(
+            "BOOST program reported malfunctioning opcodes: {:?}",
+            &output[..output.len() - 1]
+        )),
+    }
+}
+
+pub fn solve(puzzle: &puzzle::Selection) -> puzzle::Result {
+    let program = intcode::parse_program(&puzzle::fetch_string(puzzle)?)?;
+
+    solve_parts!(
+        1 => run_boost(&program, TEST_MODE)?,
+        2 => run_boost(&program, SENSOR_BOOST_MODE)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str) -> Vec<Int> {
+        Machine::new(intcode::parse_program(program).unwrap())
+            .run_with_input(&[])
+            .unwrap()
+    }
+
+    #[test]
+    fn ex1() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        assert_eq!(intcode::parse_program(quine).unwrap(), run(quine));
+
+        let output = run("1102,34915192,34915192,7,4,7,99,0");
+        assert_eq!(16, output[0].to_string().len());
+
+        assert_eq!(vec![1125899906842624], run("104,1125899906842624,99"));
+    }
+
+    #[test]
+    fn malfunction_report() {
+        let program = intcode::parse_program("104,3,104,7,99").unwrap();
+        assert!(run_boost(&program, TEST_MODE).is_err());
+
+        let program = intcode::parse_program("3,0,4,0,99").unwrap();
+        assert_eq!(
+            Ok(SENSOR_BOOST_MODE),
+            run_boost(&program, SENSOR_BOOST_MODE)
+        );
+    }
+}
@@ -0,0 +1,19 @@
+pub mod day02;
+pub mod day05;
+pub mod day09;
+
+route_days! {
+    2 => day02,
+    5 => day05,
+    9 => day09,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_implemented_days() {
+        assert_routes!(2019, [2, 5, 9]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Runs every implemented puzzle whose input is present and checks its
+//! answers against the answer manifest.
+//!
+//! Puzzles marked as slow in the manifest are skipped. An aggregate timing
+//! report is printed at the end of the run, which can be seen with:
+//!
+//! ```bash
+//! $ cargo test --test all_days -- --nocapture
+//! ```
+
+use aoc_solutions::common::puzzle::{AnswerKey, Selection, Summary};
+use aoc_solutions::report;
+use aoc_solutions::verify;
+use std::time::Duration;
+
+/// The outcome of running a single puzzle.
+enum Outcome {
+    /// The solution's answers matched the manifest.
+    Passed,
+    /// The solution failed or produced the wrong answers.
+    Failed(String),
+    /// The puzzle was not run for the given reason.
+    Skipped(&'static str),
+}
+
+#[test]
+fn all_days() {
+    let key = AnswerKey::load().expect("failed to load answer manifest");
+
+    let mut report: Vec<(Selection, Outcome, Duration)> = Vec::new();
+    for &(year, days) in aoc_solutions::YEARS {
+        for &day in days {
+            let puzzle = Selection::new(year, day);
+            if let Some(reason) = report::skip_reason(&key, &puzzle, false) {
+                report.push((puzzle, Outcome::Skipped(reason), Duration::default()));
+                continue;
+            }
+
+            let summary = Summary::measure(puzzle, aoc_solutions::route);
+            let outcome = match verify::Outcome::check(&key, &summary) {
+                verify::Outcome::Matched => Outcome::Passed,
+                verify::Outcome::Unrecorded => Outcome::Failed("no known answers".to_owned()),
+                verify::Outcome::Mismatched(mismatch) => Outcome::Failed(mismatch),
+                verify::Outcome::Failed(e) => Outcome::Failed(e),
+                verify::Outcome::NoInput => Outcome::Skipped("no input"),
+            };
+            let duration = summary.duration();
+            report.push((Selection::new(year, day), outcome, duration));
+        }
+    }
+
+    print_report(&report);
+
+    let failures: Vec<String> = report
+        .iter()
+        .filter_map(|(puzzle, outcome, _)| match outcome {
+            Outcome::Failed(reason) => Some(format!(
+                "{} day {:02}: {}",
+                puzzle.year(),
+                puzzle.day(),
+                reason
+            )),
+            _ => None,
+        })
+        .collect();
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// Prints the outcome of each puzzle followed by aggregate statistics.
+fn print_report(report: &[(Selection, Outcome, Duration)]) {
+    for (puzzle, outcome, duration) in report {
+        let status = match outcome {
+            Outcome::Passed => "ok".to_owned(),
+            Outcome::Failed(_) => "FAILED".to_owned(),
+            Outcome::Skipped(reason) => format!("skipped ({})", reason),
+        };
+        println!(
+            "{} day {:02}: {:<18} {:>12.6}s",
+            puzzle.year(),
+            puzzle.day(),
+            status,
+            duration.as_secs_f64()
+        );
+    }
+
+    let run: Vec<_> = report
+        .iter()
+        .filter(|(_, outcome, _)| !matches!(outcome, Outcome::Skipped(_)))
+        .collect();
+    let total: Duration = run.iter().map(|(_, _, duration)| *duration).sum();
+    let passed = run
+        .iter()
+        .filter(|(_, outcome, _)| matches!(outcome, Outcome::Passed))
+        .count();
+
+    println!();
+    println!(
+        "{} run, {} passed, {} skipped",
+        run.len(),
+        passed,
+        report.len() - run.len()
+    );
+    println!("Total Elapsed: {:.6}s", total.as_secs_f64());
+    if let Some((puzzle, _, duration)) = run.iter().max_by_key(|(_, _, duration)| *duration) {
+        println!(
+            "Slowest: {} day {:02} ({:.6}s)",
+            puzzle.year(),
+            puzzle.day(),
+            duration.as_secs_f64()
+        );
+    }
+}